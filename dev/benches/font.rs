@@ -0,0 +1,24 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use rusttype::*;
+
+const WQY_MICRO_HEI: &[u8] = include_bytes!("../fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+
+fn load_and_v_metrics(c: &mut Criterion) {
+    c.bench_function("load_and_v_metrics", |b| {
+        b.iter(|| {
+            let font = Font::try_from_bytes(WQY_MICRO_HEI).unwrap();
+            font.v_metrics(Scale::uniform(16.0))
+        });
+    });
+}
+
+fn char_map_cached(c: &mut Criterion) {
+    let font = Font::try_from_bytes(WQY_MICRO_HEI).unwrap();
+    c.bench_function("char_map_cached", |b| {
+        b.iter(|| font.char_map().len());
+    });
+}
+
+criterion_group!(font_benches, load_and_v_metrics, char_map_cached);
+
+criterion_main!(font_benches);
@@ -7,10 +7,7 @@ fn main() {
         let font_path = std::env::current_dir().unwrap().join(font_path);
         let data = std::fs::read(&font_path).unwrap();
         Font::try_from_vec(data).unwrap_or_else(|| {
-            panic!(format!(
-                "error constructing a Font from data at {:?}",
-                font_path
-            ));
+            panic!("error constructing a Font from data at {:?}", font_path);
         })
     } else {
         eprintln!("No font specified ... using WenQuanYiMicroHei.ttf");
@@ -43,7 +40,7 @@ fn main() {
     let width = glyphs
         .iter()
         .rev()
-        .map(|g| g.position().x as f32 + g.unpositioned().h_metrics().advance_width)
+        .map(|g| g.position().x + g.unpositioned().h_metrics().advance_width)
         .next()
         .unwrap_or(0.0)
         .ceil() as usize;
@@ -67,7 +64,7 @@ fn main() {
                 if x >= 0 && x < width as i32 && y >= 0 && y < pixel_height as i32 {
                     let x = x as usize;
                     let y = y as usize;
-                    pixel_data[(x + y * width)] = c;
+                    pixel_data[x + y * width] = c;
                 }
             })
         }
@@ -0,0 +1,91 @@
+use once_cell::sync::Lazy;
+use rusttype::*;
+
+static DEJA_VU_MONO: Lazy<Font<'static>> = Lazy::new(|| {
+    Font::try_from_bytes(include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf") as &[u8]).unwrap()
+});
+
+/// The characters of `layout` in visual order.
+fn visual(text: &str, layout: &BidiLayout<'_>) -> String {
+    layout
+        .indices
+        .iter()
+        .map(|&i| text[i..].chars().next().unwrap())
+        .collect()
+}
+
+#[test]
+fn layout_bidi_ltr() {
+    let font = &*DEJA_VU_MONO;
+    let scale = Scale::uniform(20.0);
+    let start = point(3.0, 30.0);
+    let text = "plain (text)";
+    let layout = font.layout_bidi(text, scale, start);
+
+    // left-to-right text lays out like `layout`
+    let expected: Vec<_> = font.layout(text, scale, start).collect();
+    assert_eq!(layout.glyphs.len(), expected.len());
+    for (glyph, expected) in layout.glyphs.iter().zip(&expected) {
+        assert_eq!(glyph.id(), expected.id());
+        assert_eq!(glyph.position(), expected.position());
+    }
+    let indices: Vec<_> = text.char_indices().map(|(i, _)| i).collect();
+    assert_eq!(layout.indices, indices);
+    let advance = font.advance_widths(text, scale).last().unwrap();
+    assert!((layout.advance_width - advance).abs() < 1e-3);
+}
+
+#[test]
+fn layout_bidi_mixed() {
+    let font = &*DEJA_VU_MONO;
+    assert!(font.unsupported_chars("ابت\u{64E}").is_empty());
+    let scale = Scale::uniform(20.0);
+    let start = point(0.0, 30.0);
+
+    let text = "abc ابت def";
+    let layout = font.layout_bidi(text, scale, start);
+    assert_eq!(visual(text, &layout), "abc تبا def");
+    assert_eq!(layout.glyphs.len(), 11);
+    // glyphs advance left to right from start on the baseline
+    let w = font.glyph('a').scaled(scale).h_metrics().advance_width;
+    for (i, glyph) in layout.glyphs.iter().enumerate() {
+        assert!((glyph.position().x - i as f32 * w).abs() < 1e-3, "{}", i);
+        assert_eq!(glyph.position().y, start.y);
+    }
+    assert!((layout.advance_width - 11.0 * w).abs() < 1e-3);
+    let teh = layout.indices[4];
+    assert_eq!(&text[teh..teh + 2], "ت");
+    assert_eq!(layout.glyphs[4].id(), font.glyph('ت').id());
+
+    // a right-to-left paragraph puts the embedded English on the left
+    let text = "ابت abc";
+    let layout = font.layout_bidi(text, scale, start);
+    assert_eq!(visual(text, &layout), "abc تبا");
+
+    // numbers stay left to right within right-to-left text
+    let text = "ا 12 ب";
+    assert_eq!(
+        visual(text, &font.layout_bidi(text, scale, start)),
+        "ب 12 ا"
+    );
+}
+
+#[test]
+fn layout_bidi_mirroring_and_marks() {
+    let font = &*DEJA_VU_MONO;
+    let scale = Scale::uniform(20.0);
+    let start = point(0.0, 30.0);
+
+    // brackets are mirrored in right-to-left runs, so they still enclose
+    let text = "ا(ب)";
+    let layout = font.layout_bidi(text, scale, start);
+    assert_eq!(visual(text, &layout), ")ب(ا");
+    let ids: Vec<_> = layout.glyphs.iter().map(|g| g.id()).collect();
+    let expected: Vec<_> = "(ب)ا".chars().map(|c| font.glyph(c).id()).collect();
+    assert_eq!(ids, expected);
+
+    // combining marks stay after their base
+    let text = "اب\u{64E}ت";
+    let layout = font.layout_bidi(text, scale, start);
+    assert_eq!(visual(text, &layout), "تب\u{64E}ا");
+}
@@ -0,0 +1,180 @@
+use once_cell::sync::Lazy;
+use rusttype::*;
+use std::convert::TryInto;
+
+/// The strike size of `BITMAP_FONT`.
+const PPEM: f32 = 12.0;
+
+/// DejaVu Sans Mono with a 12ppem `EBLC`/`EBDT` strike containing only `A`, a
+/// 5x3 box outline.
+static BITMAP_FONT: Lazy<Vec<u8>> = Lazy::new(|| {
+    let data = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf");
+    let font = Font::try_from_bytes(data).unwrap();
+    let id = font.glyph('A').id().0.to_be_bytes();
+
+    let mut eblc = vec![];
+    eblc.extend([0, 2, 0, 0, 0, 0, 0, 1]); // version 2.0, 1 size
+    eblc.extend([0, 0, 0, 56, 0, 0, 0, 16, 0, 0, 0, 1, 0, 0, 0, 0]);
+    eblc.extend([0; 24]); // line metrics
+    eblc.extend(id);
+    eblc.extend(id);
+    eblc.extend([PPEM as u8, PPEM as u8, 1, 1]); // ppem, bit depth, flags
+
+    // index subtable array & a format 1 subtable of format 1 images
+    eblc.extend(id);
+    eblc.extend(id);
+    eblc.extend([0, 0, 0, 8]);
+    eblc.extend([0, 1, 0, 1, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 8]);
+
+    let mut ebdt = vec![0, 2, 0, 0];
+    ebdt.extend([3, 5, 1, 7, 7]); // height, width, bearing x & y, advance
+    ebdt.extend([0b1111_1000, 0b1000_1000, 0b1111_1000]);
+
+    with_tables(data, &[(*b"EBDT", ebdt), (*b"EBLC", eblc)])
+});
+
+/// Rebuilds the sfnt `data` with extra `tables`.
+fn with_tables(data: &[u8], tables: &[([u8; 4], Vec<u8>)]) -> Vec<u8> {
+    let u16_at = |i: usize| u16::from_be_bytes([data[i], data[i + 1]]) as usize;
+    let u32_at = |i: usize| u32::from_be_bytes(data[i..i + 4].try_into().unwrap()) as usize;
+
+    let mut all: Vec<([u8; 4], &[u8])> = (0..u16_at(4))
+        .map(|i| {
+            let record = 12 + 16 * i;
+            let (offset, len) = (u32_at(record + 8), u32_at(record + 12));
+            (
+                data[record..record + 4].try_into().unwrap(),
+                &data[offset..offset + len],
+            )
+        })
+        .collect();
+    all.extend(tables.iter().map(|(tag, table)| (*tag, &table[..])));
+    all.sort_by_key(|&(tag, _)| tag);
+
+    let mut out = data[..12].to_vec();
+    out[4..6].copy_from_slice(&(all.len() as u16).to_be_bytes());
+    let mut offset = 12 + 16 * all.len();
+    for (tag, table) in &all {
+        out.extend(tag);
+        out.extend([0; 4]); // checksum
+        out.extend((offset as u32).to_be_bytes());
+        out.extend((table.len() as u32).to_be_bytes());
+        offset += table.len().next_multiple_of(4);
+    }
+    for (_, table) in &all {
+        out.extend(*table);
+        out.resize(out.len().next_multiple_of(4), 0);
+    }
+    out
+}
+
+fn font(policy: BitmapPolicy) -> Font<'static> {
+    let mut font = Font::try_from_bytes(&BITMAP_FONT).unwrap();
+    font.set_bitmap_policy(policy);
+    font
+}
+
+fn coverage(glyph: &PositionedGlyph<'_>) -> Vec<f32> {
+    let bb = glyph.pixel_bounding_box().unwrap();
+    let mut pixels = vec![0.0; (bb.width() * bb.height()) as usize];
+    let width = bb.width() as u32;
+    glyph.draw(|x, y, v| pixels[(x + y * width) as usize] = v);
+    pixels
+}
+
+#[test]
+fn prefer_bitmap_draws_strike() {
+    let font = font(BitmapPolicy::PreferBitmap);
+    let glyph = font
+        .glyph('A')
+        .scaled(font.scale_for_pixels_per_em(PPEM))
+        .positioned(point(10.3, 20.2));
+
+    assert!(glyph.unpositioned().uses_bitmap());
+    // snapped to whole pixels, 7px above the baseline
+    assert_eq!(
+        glyph.pixel_bounding_box(),
+        Some(Rect {
+            min: point(11, 13),
+            max: point(16, 16),
+        })
+    );
+    #[rustfmt::skip]
+    assert_eq!(coverage(&glyph), [
+        1.0, 1.0, 1.0, 1.0, 1.0,
+        1.0, 0.0, 0.0, 0.0, 1.0,
+        1.0, 1.0, 1.0, 1.0, 1.0,
+    ]);
+}
+
+#[test]
+fn bitmaps_need_a_matching_strike() {
+    let font = font(BitmapPolicy::PreferBitmap);
+    let scale = font.scale_for_pixels_per_em(PPEM);
+    assert!(!font.glyph('B').scaled(scale).uses_bitmap());
+
+    let glyph = font
+        .glyph('A')
+        .scaled(font.scale_for_pixels_per_em(PPEM + 1.0));
+    assert!(!glyph.uses_bitmap());
+    let outline = glyph.positioned(point(0.0, 0.0));
+    assert!(outline.pixel_bounding_box().unwrap().height() > 3);
+}
+
+#[test]
+fn policies() {
+    let scale = font(BitmapPolicy::default()).scale_for_pixels_per_em(PPEM);
+    let uses_bitmap = |policy| font(policy).glyph('A').scaled(scale).uses_bitmap();
+
+    assert!(!uses_bitmap(BitmapPolicy::PreferOutline));
+    assert!(uses_bitmap(BitmapPolicy::PreferBitmap));
+    assert!(uses_bitmap(BitmapPolicy::BitmapBelow(12)));
+    assert!(!uses_bitmap(BitmapPolicy::BitmapBelow(11)));
+}
+
+#[test]
+fn layout_uses_bitmaps() {
+    let font = font(BitmapPolicy::PreferBitmap);
+    let scale = font.scale_for_pixels_per_em(PPEM);
+    let glyphs: Vec<_> = font.layout("AB", scale, point(0.0, 10.0)).collect();
+
+    assert!(glyphs[0].unpositioned().uses_bitmap());
+    assert_eq!(glyphs[0].pixel_bounding_box().unwrap().min, point(1, 3));
+    assert!(!glyphs[1].unpositioned().uses_bitmap());
+
+    let clone = font.clone();
+    assert_eq!(clone.bitmap_policy(), BitmapPolicy::PreferBitmap);
+}
+
+#[test]
+fn embedded_image() {
+    // ignores the policy
+    let font = font(BitmapPolicy::PreferOutline);
+    let glyph = font.glyph('A').scaled(font.scale_for_pixels_per_em(PPEM));
+    let image = glyph.embedded_image().unwrap();
+    assert_eq!(image.format, GlyphImageFormat::Mono);
+    assert_eq!((image.width, image.height, image.pixels_per_em), (5, 3, 12));
+    assert_eq!(image.offset, vector(1, -7));
+    assert_eq!(image.scale, 1.0);
+    let row: Vec<_> = (0..5).map(|x| image.coverage(x, 1).unwrap()).collect();
+    assert_eq!(row, [1.0, 0.0, 0.0, 0.0, 1.0]);
+    assert_eq!(image.coverage(5, 0), None);
+
+    // the closest strike, scaled
+    let scaled = font
+        .glyph('A')
+        .scaled(font.scale_for_pixels_per_em(2.0 * PPEM));
+    let image = scaled.embedded_image().unwrap();
+    assert_eq!((image.pixels_per_em, image.scale), (12, 2.0));
+    assert_eq!(
+        image.rect(),
+        Rect {
+            min: point(2.0, -14.0),
+            max: point(12.0, -8.0)
+        }
+    );
+
+    // outlines only
+    let scale = font.scale_for_pixels_per_em(PPEM);
+    assert!(font.glyph('B').scaled(scale).embedded_image().is_none());
+}
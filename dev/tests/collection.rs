@@ -0,0 +1,78 @@
+use rusttype::*;
+
+static DEJAVU_MONO: &[u8] = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf");
+static OPEN_SANS_ITALIC: &[u8] = include_bytes!("../fonts/opensans/OpenSans-Italic.ttf");
+
+/// A version 1.0 `ttcf` font collection of `fonts`, with their table
+/// offsets moved to their position in the collection.
+fn ttc(fonts: &[&[u8]]) -> Vec<u8> {
+    let mut data = b"ttcf".to_vec();
+    data.extend([0, 1, 0, 0]);
+    data.extend((fonts.len() as u32).to_be_bytes());
+    let mut offset = 12 + 4 * fonts.len();
+    let mut bodies = vec![];
+    for font in fonts {
+        data.extend((offset as u32).to_be_bytes());
+        let mut body = font.to_vec();
+        let tables = u16::from_be_bytes([font[4], font[5]]) as usize;
+        for record in 0..tables {
+            let at = 12 + 16 * record + 8;
+            let table_offset =
+                u32::from_be_bytes([font[at], font[at + 1], font[at + 2], font[at + 3]]);
+            body[at..at + 4].copy_from_slice(&(table_offset + offset as u32).to_be_bytes());
+        }
+        body.resize(body.len().next_multiple_of(4), 0);
+        offset += body.len();
+        bodies.push(body);
+    }
+    data.extend(bodies.concat());
+    data
+}
+
+#[test]
+fn collection_fonts() {
+    let data = ttc(&[DEJAVU_MONO, OPEN_SANS_ITALIC]);
+    assert_eq!(Font::collection_count(&data), 2);
+
+    let collection = FontCollection::from_bytes(&data).unwrap();
+    assert_eq!(collection.count(), 2);
+    let fonts: Vec<_> = collection.into_fonts().collect::<Result<_, _>>().unwrap();
+    assert_eq!(fonts.len(), 2);
+    let expected = [
+        Font::try_from_bytes(DEJAVU_MONO).unwrap(),
+        Font::try_from_bytes(OPEN_SANS_ITALIC).unwrap(),
+    ];
+    for (font, expected) in fonts.iter().zip(&expected) {
+        assert_eq!(font.glyph_count(), expected.glyph_count());
+        assert_eq!(font.units_per_em(), expected.units_per_em());
+        assert_eq!(font.glyph('a').id(), expected.glyph('a').id());
+    }
+
+    assert_eq!(
+        collection.font(2).unwrap_err(),
+        Error::IndexOutOfRange { index: 2, count: 2 }
+    );
+}
+
+#[test]
+fn collection_of_single_font() {
+    assert_eq!(Font::collection_count(DEJAVU_MONO), 1);
+    let collection = FontCollection::from_bytes(DEJAVU_MONO).unwrap();
+    let mut fonts = collection.into_fonts();
+    assert_eq!(fonts.len(), 1);
+    assert!(fonts.next().unwrap().is_ok());
+    assert_eq!(fonts.len(), 0);
+    assert!(fonts.next().is_none());
+
+    assert!(collection.font(1).is_err());
+}
+
+#[test]
+fn collection_of_non_font() {
+    assert_eq!(Font::collection_count(b"wOFF\0\0\0\0"), 0);
+    assert_eq!(Font::collection_count(&[]), 0);
+    assert_eq!(
+        FontCollection::from_bytes(b"wOFF\0\0\0\0").unwrap_err(),
+        Error::UnsupportedVersion { version: *b"wOFF" }
+    );
+}
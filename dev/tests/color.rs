@@ -0,0 +1,150 @@
+mod common;
+
+use common::{be16, rect, sfnt};
+use once_cell::sync::Lazy;
+use rusttype::*;
+
+/// A color font with 800 units per em, with `A` a `COLR` glyph of 2 layers, a
+/// 400 unit square in palette color 0 & one 200 units right in the
+/// foreground color. The 2 palettes have color 0 red & blue.
+static COLOR_FONT: Lazy<Vec<u8>> = Lazy::new(|| {
+    let be32 = |v: i32| (v as u32).to_be_bytes();
+    let glyphs = [
+        vec![],
+        rect(0, 0, 100, 100),
+        rect(0, 0, 400, 400),
+        rect(200, 0, 600, 400),
+    ];
+
+    let mut head = vec![
+        0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0x5F, 0x0F, 0x3C, 0xF5, 0, 0,
+    ];
+    head.extend(be16(800)); // units per em
+    head.extend([0; 16]); // created & modified
+    for v in [0, 0, 600, 400, 0, 8, 2, 0, 0] {
+        head.extend(be16(v)); // bbox, style, lowest ppem, direction, short loca, format
+    }
+
+    let mut hhea = vec![0, 1, 0, 0];
+    for v in [700, -100, 0, 600, 0, 0, 600, 1, 0, 0, 0, 0, 0, 0, 0, 4] {
+        hhea.extend(be16(v));
+    }
+
+    let maxp = vec![0, 0, 0x50, 0, 0, 4]; // version 0.5, 4 glyphs
+
+    // a format 6 subtable mapping 'A' to glyph 1
+    let mut cmap = vec![0, 0, 0, 1, 0, 0, 0, 3, 0, 0, 0, 12];
+    for v in [6, 12, 0, 'A' as i32, 1, 1] {
+        cmap.extend(be16(v));
+    }
+
+    let mut hmtx = vec![];
+    let (mut loca, mut glyf) = (be16(0).to_vec(), vec![]);
+    for glyph in &glyphs {
+        hmtx.extend(be16(600));
+        hmtx.extend(be16(0));
+        glyf.extend(glyph);
+        loca.extend(be16(glyf.len() as i32 / 2));
+    }
+
+    let mut colr = be16(0).to_vec(); // version 0
+    colr.extend(be16(1)); // base glyphs
+    colr.extend(be32(14)); // base glyphs offset
+    colr.extend(be32(20)); // layers offset
+    colr.extend(be16(2)); // layers
+    for v in [1, 0, 2] {
+        colr.extend(be16(v)); // glyph, first layer, layers
+    }
+    for v in [2, 0, 3, 0xFFFF] {
+        colr.extend(be16(v)); // (glyph, palette index), 0xFFFF is the foreground
+    }
+
+    let mut cpal = vec![];
+    for v in [0, 1, 2, 2] {
+        cpal.extend(be16(v)); // version, palette size, palettes, colors
+    }
+    cpal.extend(be32(16)); // colors offset
+    for v in [0, 1] {
+        cpal.extend(be16(v)); // first color of each palette
+    }
+    cpal.extend([0, 0, 255, 255, 255, 0, 0, 255]); // BGRA red & blue
+
+    sfnt(&[
+        (*b"COLR", colr),
+        (*b"CPAL", cpal),
+        (*b"cmap", cmap),
+        (*b"glyf", glyf),
+        (*b"head", head),
+        (*b"hhea", hhea),
+        (*b"hmtx", hmtx),
+        (*b"loca", loca),
+        (*b"maxp", maxp),
+    ])
+});
+
+/// The color glyph at 10px per 100 units.
+fn color_glyph(font: &Font<'static>) -> PositionedGlyph<'static> {
+    font.glyph('A')
+        .scaled(Scale::uniform(80.0))
+        .positioned(point(0.0, 0.0))
+}
+
+fn font() -> Font<'static> {
+    let mut font = Font::try_from_bytes(&COLOR_FONT).unwrap();
+    font.set_pixel_mode(PixelMode::Off);
+    font
+}
+
+#[test]
+fn color_layers() {
+    let font = font();
+    assert_eq!(font.color_palettes(), 2);
+    assert!(font.glyph('A').is_color());
+    assert!(!font.glyph(GlyphId(2)).is_color());
+
+    let glyph = color_glyph(&font);
+    let green = [0, 255, 0, 255];
+    let layers = glyph.color_layers(0, green);
+    let layers: Vec<_> = layers.iter().map(|l| (l.glyph.id(), l.color)).collect();
+    assert_eq!(
+        layers,
+        [(GlyphId(2), [255, 0, 0, 255]), (GlyphId(3), green)]
+    );
+    assert_eq!(glyph.color_layers(1, green)[0].color, [0, 0, 255, 255]);
+    assert_eq!(
+        glyph.color_pixel_bounding_box(),
+        Some(Rect {
+            min: point(0, -40),
+            max: point(60, 0)
+        })
+    );
+
+    // other glyphs are a single foreground layer
+    let dejavu = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf") as &[u8];
+    let dejavu = Font::try_from_bytes(dejavu).unwrap();
+    assert_eq!(dejavu.color_palettes(), 0);
+    let a = dejavu
+        .glyph('a')
+        .scaled(Scale::uniform(24.0))
+        .positioned(point(0.0, 0.0));
+    assert!(!a.unpositioned().unscaled().is_color());
+    let layers = a.color_layers(0, green);
+    assert_eq!(layers.len(), 1);
+    assert_eq!((layers[0].glyph.id(), layers[0].color), (a.id(), green));
+    assert_eq!(a.color_pixel_bounding_box(), a.pixel_bounding_box());
+}
+
+#[test]
+fn draw_color() {
+    let font = font();
+    let mut pixels = vec![[0.0; 4]; 60 * 40];
+    color_glyph(&font).draw_color(0, [0, 255, 0, 128], |x, y, color| {
+        pixels[(x + y * 60) as usize] = color;
+    });
+    let near = |a: [f32; 4], b: [f32; 4]| a.iter().zip(&b).all(|(a, b)| (a - b).abs() < 0.01);
+
+    // red, half transparent green over red, then half transparent green
+    assert_eq!(pixels[10 + 20 * 60], [1.0, 0.0, 0.0, 1.0]);
+    assert!(near(pixels[30 + 20 * 60], [0.5, 0.5, 0.0, 1.0]));
+    assert!(near(pixels[50 + 20 * 60], [0.0, 1.0, 0.0, 0.5]));
+}
@@ -0,0 +1,40 @@
+//! Helpers building small synthetic fonts, shared by the integration tests.
+#![allow(dead_code)]
+
+/// `v` as big endian 16 bits, signed or not.
+pub fn be16(v: i32) -> [u8; 2] {
+    (v as u16).to_be_bytes()
+}
+
+/// A simple glyph of a rectangle from `(x0, y0)` to `(x1, y1)`.
+pub fn rect(x0: i32, y0: i32, x1: i32, y1: i32) -> Vec<u8> {
+    let mut glyph = vec![];
+    for v in [1, x0, y0, x1, y1, 3, 0] {
+        glyph.extend(be16(v)); // contours, bbox, end point, no instructions
+    }
+    glyph.extend([1; 4]); // on curve points with 16 bit deltas
+    for v in [x0, 0, x1 - x0, 0, y0, y1 - y0, 0, y0 - y1] {
+        glyph.extend(be16(v));
+    }
+    glyph
+}
+
+/// Builds an sfnt from `tables`, sorted by tag.
+pub fn sfnt(tables: &[([u8; 4], Vec<u8>)]) -> Vec<u8> {
+    let mut out = vec![0, 1, 0, 0];
+    out.extend((tables.len() as u16).to_be_bytes());
+    out.extend([0; 6]); // search hints
+    let mut offset = 12 + 16 * tables.len();
+    for (tag, table) in tables {
+        out.extend(tag);
+        out.extend([0; 4]); // checksum
+        out.extend((offset as u32).to_be_bytes());
+        out.extend((table.len() as u32).to_be_bytes());
+        offset += table.len().next_multiple_of(4);
+    }
+    for (_, table) in tables {
+        out.extend(table);
+        out.resize(out.len().next_multiple_of(4), 0);
+    }
+    out
+}
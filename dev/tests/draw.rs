@@ -0,0 +1,616 @@
+use once_cell::sync::Lazy;
+use rusttype::*;
+
+static DEJA_VU_MONO: Lazy<Font<'static>> = Lazy::new(|| {
+    Font::try_from_bytes(include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf") as &[u8]).unwrap()
+});
+
+fn coverage(glyph: &PositionedGlyph<'_>, hook: Option<&mut dyn OutlineHook>) -> Vec<f32> {
+    let bb = glyph.pixel_bounding_box().unwrap();
+    let mut pixels = vec![0.0; (bb.width() * bb.height()) as usize];
+    let width = bb.width() as u32;
+    let o = |x: u32, y: u32, v: f32| pixels[(x + y * width) as usize] = v;
+    match hook {
+        Some(hook) => glyph.draw_with_hook(hook, o),
+        None => glyph.draw(o),
+    }
+    pixels
+}
+
+#[test]
+fn draw_with_noop_hook_matches_draw() {
+    struct Noop;
+    impl OutlineHook for Noop {}
+
+    let glyph = DEJA_VU_MONO
+        .glyph('g')
+        .scaled(Scale::uniform(24.0))
+        .positioned(point(0.3, 0.6));
+
+    assert_eq!(coverage(&glyph, None), coverage(&glyph, Some(&mut Noop)));
+}
+
+#[test]
+fn draw_with_hook_replacing_outline() {
+    /// Drops the glyph outline & draws a filled 2x2 square in its place.
+    struct Square;
+    impl OutlineHook for Square {
+        fn move_to(&mut self, _: &mut dyn OutlineBuilder, _: f32, _: f32) {}
+        fn line_to(&mut self, _: &mut dyn OutlineBuilder, _: f32, _: f32) {}
+        fn quad_to(&mut self, _: &mut dyn OutlineBuilder, _: f32, _: f32, _: f32, _: f32) {}
+        fn close(&mut self, _: &mut dyn OutlineBuilder) {}
+
+        fn end(&mut self, _: &PositionedGlyph<'_>, sink: &mut dyn OutlineBuilder) {
+            sink.move_to(0.0, 0.0);
+            sink.line_to(2.0, 0.0);
+            sink.line_to(2.0, 2.0);
+            sink.line_to(0.0, 2.0);
+            sink.close();
+        }
+    }
+
+    let glyph = DEJA_VU_MONO
+        .glyph('o')
+        .scaled(Scale::uniform(24.0))
+        .positioned(point(0.0, 0.0));
+    let width = glyph.pixel_bounding_box().unwrap().width() as usize;
+
+    let pixels = coverage(&glyph, Some(&mut Square));
+    let covered: Vec<_> = (0..pixels.len()).filter(|i| pixels[*i] > 0.5).collect();
+    assert_eq!(covered, vec![0, 1, width, width + 1]);
+}
+
+#[test]
+fn digit_set_tiles() {
+    let scale = Scale::uniform(20.0);
+    let font =
+        Font::try_from_bytes(include_bytes!("../fonts/opensans/OpenSans-Italic.ttf") as &[u8])
+            .unwrap();
+    let digits = DigitSet::new(&font, scale);
+
+    let advance = digits.digit_advance();
+    assert!(('0'..='9').all(|c| digits.tile(c).unwrap().advance == advance));
+    assert!(digits.tile(' ').unwrap().coverage.is_empty());
+    assert!(digits.tile('x').is_none());
+    assert_eq!(
+        digits.width("1:0x"),
+        2 * advance + digits.tile(':').unwrap().advance
+    );
+
+    // tiles match drawing the glyph centered in the tabular advance
+    let one = font.glyph('1').scaled(scale);
+    let own_advance = one.h_metrics().advance_width;
+    let one = one.positioned(point((advance as f32 - own_advance) / 2.0, 0.0));
+    let tile = digits.tile('1').unwrap();
+    let bb = one.pixel_bounding_box().unwrap();
+    assert_eq!(tile.offset, vector(bb.min.x, bb.min.y));
+    assert_eq!(tile.coverage, coverage(&one, None));
+
+    let mut drawn = Vec::new();
+    let width = digits.draw("11", |x, y, v| drawn.push((x, y, v)));
+    assert_eq!(width, 2 * advance);
+    assert_eq!(drawn.len(), 2 * tile.coverage.len());
+    let (x, y, _) = drawn[tile.coverage.len()];
+    assert_eq!((x, y), (advance + tile.offset.x, tile.offset.y));
+}
+
+#[test]
+fn draw_dilated() {
+    let glyph = DEJA_VU_MONO
+        .glyph('l')
+        .scaled(Scale::uniform(24.0))
+        .positioned(point(0.4, 0.0));
+    let bb = glyph.pixel_bounding_box().unwrap();
+    let dilated_bb = glyph.dilated_pixel_bounding_box(1.5).unwrap();
+    assert_eq!(dilated_bb.min, point(bb.min.x - 2, bb.min.y - 2));
+    assert_eq!(dilated_bb.width(), bb.width() + 4);
+
+    let mut dilated = CoverageMask::new(dilated_bb.width() as u32, dilated_bb.height() as u32);
+    let width = dilated.width;
+    glyph.draw_dilated(1.5, |x, y, v| {
+        dilated.coverage[(x + y * width) as usize] = v
+    });
+
+    // never less coverage than the original
+    let original = CoverageMask::from_glyph(&glyph);
+    original.for_each_pixel(|x, y, v| assert!(dilated.get(x as i32 + 2, y as i32 + 2) >= v));
+    let sum = |m: &CoverageMask| m.coverage.iter().sum::<f32>();
+    assert!(sum(&dilated) > sum(&original) + 10.0);
+
+    // a radius of 0 changes nothing but the padding
+    assert_eq!(original.dilate(0.0), original);
+}
+
+#[test]
+fn gaussian_blur() {
+    let glyph = DEJA_VU_MONO
+        .glyph('x')
+        .scaled(Scale::uniform(24.0))
+        .positioned(point(0.0, 0.0));
+    let mask = CoverageMask::from_glyph(&glyph);
+    let blurred = mask.gaussian_blur(1.5);
+
+    let pad = CoverageMask::blur_padding(1.5);
+    assert_eq!(pad, 5);
+    assert_eq!(blurred.width, mask.width + 2 * pad);
+    assert_eq!(blurred.height, mask.height + 2 * pad);
+
+    // blurring preserves total coverage, none is clipped
+    let sum = |m: &CoverageMask| m.coverage.iter().sum::<f32>();
+    assert!((sum(&blurred) - sum(&mask)).abs() < 0.01 * sum(&mask));
+    // & spreads it out
+    let max = |m: &CoverageMask| m.coverage.iter().cloned().fold(0.0, f32::max);
+    assert!(max(&blurred) < max(&mask));
+    assert!(blurred.get(0, 0) < 1e-3);
+
+    assert_eq!(mask.gaussian_blur(0.0), mask);
+}
+
+#[test]
+fn draw_with_effects() {
+    let options = LayoutOptions::default();
+    let scale = Scale::uniform(24.0);
+    let run = DEJA_VU_MONO.glyph_run(&SimpleShaper, "l", scale, point(0.0, 20.0), &options);
+    let glyph_bb = run.glyphs()[0].pixel_bounding_box().unwrap();
+    let draw = |effects: &TextEffects| {
+        let bb = run.effects_pixel_bounding_box(effects).unwrap();
+        let mut rgba = vec![[0.0; 4]; (bb.width() * bb.height()) as usize];
+        run.draw_with_effects(effects, |x, y, color| {
+            rgba[(x + y * bb.width() as u32) as usize] = color;
+        });
+        (bb, rgba)
+    };
+
+    // a plain fill covers just the glyph
+    let white = [255, 255, 255, 255];
+    let (bb, fill) = draw(&TextEffects::new(white));
+    assert_eq!(bb, glyph_bb);
+    let covered = |rgba: &[[f32; 4]]| rgba.iter().filter(|c| c[3] > 0.0).count();
+
+    // outlined by 2px on every side, in the outline color at the edge
+    let outlined = TextEffects::new(white).outline(2.0, [255, 0, 0, 255]);
+    let (outline_bb, outline) = draw(&outlined);
+    assert_eq!(outline_bb.min, glyph_bb.min - vector(2, 2));
+    assert_eq!(outline_bb.max, glyph_bb.max + vector(2, 2));
+    assert!(covered(&outline) > covered(&fill));
+    let middle_row = (outline_bb.height() / 2) as usize * outline_bb.width() as usize;
+    let edge = outline[middle_row..].iter().find(|c| c[3] > 0.5).unwrap();
+    assert_eq!(edge[..3], [1.0, 0.0, 0.0]);
+
+    // the shadow extends the box by its offset & is drawn under the fill
+    let shadowed = TextEffects::new(white).shadow(vector(3.0, 4.0), 0.0, [0, 0, 0, 255]);
+    let (shadow_bb, shadow) = draw(&shadowed);
+    assert_eq!(shadow_bb.min, glyph_bb.min);
+    assert_eq!(shadow_bb.max, glyph_bb.max + vector(3, 4));
+    assert!(shadow
+        .iter()
+        .any(|c| c[3] == 1.0 && c[..3] == [0.0, 0.0, 0.0]));
+    assert!(shadow
+        .iter()
+        .any(|c| c[3] == 1.0 && c[..3] == [1.0, 1.0, 1.0]));
+
+    // a blurred shadow spreads beyond the offset glyph
+    let blurred = TextEffects::new(white).shadow(vector(3.0, 4.0), 1.5, [0, 0, 0, 255]);
+    let (blur_bb, _) = draw(&blurred);
+    assert!(blur_bb.max.y > shadow_bb.max.y);
+}
+
+#[test]
+fn draw_with_gradient_fill() {
+    let options = LayoutOptions::default();
+    let scale = Scale::uniform(24.0);
+    let run = DEJA_VU_MONO.glyph_run(&SimpleShaper, "l", scale, point(0.0, 20.0), &options);
+    let bb = run.glyphs()[0].pixel_bounding_box().unwrap();
+    let draw = |effects: &TextEffects| {
+        let mut rgba = vec![[0.0; 4]; (bb.width() * bb.height()) as usize];
+        run.draw_with_effects(effects, |x, y, color| {
+            rgba[(x + y * bb.width() as u32) as usize] = color;
+        });
+        rgba
+    };
+
+    // red at the top of the glyph to blue at the bottom
+    let gradient = LinearGradient {
+        start: point(0.0, bb.min.y as f32),
+        end: point(0.0, bb.max.y as f32),
+        start_color: [255, 0, 0, 255],
+        end_color: [0, 0, 255, 255],
+    };
+    let rgba = draw(&TextEffects::new([0, 255, 0, 255]).gradient(gradient));
+    let solid: Vec<_> = rgba.iter().filter(|c| c[3] == 1.0).collect();
+    let (top, bottom) = (solid[0], solid[solid.len() - 1]);
+    assert!(top[0] > 0.9 && top[2] < 0.1);
+    assert!(bottom[2] > 0.9 && bottom[0] < 0.1);
+    assert!(solid.iter().all(|c| c[1] == 0.0));
+
+    // a fill callback is called with the glyph pixel positions
+    let effects = TextEffects::new([0, 255, 0, 255]);
+    let mut filled = vec![[0.0; 4]; rgba.len()];
+    run.draw_with_fill(
+        &effects,
+        |x, y| {
+            assert!(bb.min.x <= x && x < bb.max.x && bb.min.y <= y && y < bb.max.y);
+            gradient.color_at(point(x as f32 + 0.5, y as f32 + 0.5))
+        },
+        |x, y, color| filled[(x + y * bb.width() as u32) as usize] = color,
+    );
+    assert_eq!(filled, rgba);
+}
+
+#[test]
+fn coverage_mask_resize() {
+    let glyph = DEJA_VU_MONO
+        .glyph('W')
+        .scaled(Scale::uniform(48.0))
+        .positioned(point(0.0, 0.0));
+    let mask = CoverageMask::from_glyph(&glyph);
+    let mean = |m: &CoverageMask| m.coverage.iter().sum::<f32>() / m.coverage.len() as f32;
+
+    let half = mask.resize(mask.width / 2, mask.height / 2);
+    assert_eq!((half.width, half.height), (mask.width / 2, mask.height / 2));
+    assert!(
+        (mean(&half) - mean(&mask)).abs() < 0.05,
+        "coverage density kept"
+    );
+    assert!(half
+        .coverage
+        .iter()
+        .all(|&v| (0.0..=1.0 + 1e-4).contains(&v)));
+
+    assert_eq!(mask.resize(mask.width, mask.height), mask);
+
+    let fit = mask.resize_to_fit(16, 100);
+    assert_eq!(fit.width, 16);
+    assert!(fit.height < 100);
+
+    let solid = CoverageMask {
+        width: 3,
+        height: 2,
+        coverage: vec![1.0; 6],
+    };
+    let big = solid.resize(7, 5);
+    assert!(big.coverage.iter().all(|&v| (v - 1.0).abs() < 1e-5));
+}
+
+#[test]
+fn draw_with_backend() {
+    /// Inverts the built-in rasterizer's coverage.
+    struct Inverted;
+
+    impl RasterBackend for Inverted {
+        fn rasterize(&self, glyph: &PositionedGlyph<'_>, o: &mut dyn FnMut(u32, u32, f32)) {
+            glyph.draw(|x, y, v| o(x, y, 1.0 - v));
+        }
+    }
+
+    let glyph = DEJA_VU_MONO
+        .glyph('a')
+        .scaled(Scale::uniform(20.0))
+        .positioned(point(0.0, 0.0));
+    let plain = coverage(&glyph, None);
+
+    let mut scanline = vec![0.0; plain.len()];
+    let width = glyph.pixel_bounding_box().unwrap().width() as u32;
+    glyph.draw_with_backend(&ScanlineRasterizer, |x, y, v| {
+        scanline[(x + y * width) as usize] = v
+    });
+    assert_eq!(scanline, plain);
+
+    let mut inverted = vec![0.0; plain.len()];
+    glyph.draw_with_backend(&Inverted, |x, y, v| inverted[(x + y * width) as usize] = v);
+    assert!(inverted
+        .iter()
+        .zip(&plain)
+        .all(|(i, p)| (i + p - 1.0).abs() < 1e-6));
+
+    let space = DEJA_VU_MONO
+        .glyph(' ')
+        .scaled(Scale::uniform(20.0))
+        .positioned(point(0.0, 0.0));
+    space.draw_with_backend(&Inverted, |_, _, _| panic!("no pixels"));
+}
+
+#[test]
+fn draw_transformed() {
+    let scale = Scale::uniform(24.0);
+    let glyph = DEJA_VU_MONO.glyph('g');
+    let plain = glyph.clone().scaled(scale).positioned(point(0.0, 20.0));
+    let zoomed = glyph
+        .clone()
+        .scaled(Scale::uniform(12.0))
+        .transformed(Affine2::scale(2.0, 2.0))
+        .positioned(point(0.0, 20.0));
+
+    assert_eq!(plain.pixel_bounding_box(), zoomed.pixel_bounding_box());
+    for (a, b) in coverage(&plain, None).iter().zip(coverage(&zoomed, None)) {
+        assert!((a - b).abs() < 1e-3, "{} != {}", a, b);
+    }
+
+    // upside down, so the descender is above the baseline
+    let flipped = glyph
+        .scaled(scale)
+        .transformed(Affine2::rotate(core::f32::consts::PI))
+        .positioned(point(20.0, 20.0));
+    let (bb, plain_bb) = (
+        flipped.pixel_bounding_box().unwrap(),
+        plain.pixel_bounding_box().unwrap(),
+    );
+    assert!((bb.min.y - (40 - plain_bb.max.y)).abs() <= 1);
+    assert!((bb.max.y - (40 - plain_bb.min.y)).abs() <= 1);
+    let ink = |pixels: Vec<f32>| pixels.iter().sum::<f32>();
+    assert!((ink(coverage(&plain, None)) - ink(coverage(&flipped, None))).abs() < 1.0);
+}
+
+#[test]
+fn for_dpr_matches_physical_layout() {
+    let font = &*DEJA_VU_MONO;
+    let (scale, start) = (Scale::uniform(14.0), point(10.3, 20.0));
+    let physical: Vec<_> = font
+        .layout("HiDPI", Scale::uniform(28.0), point(20.6, 40.0))
+        .collect();
+
+    for (logical, physical) in font.layout("HiDPI", scale, start).zip(&physical) {
+        let for_dpr = logical.for_dpr(2.0);
+        assert_eq!(for_dpr.scale(), physical.scale());
+        assert!((for_dpr.position().x - physical.position().x).abs() < 1e-3);
+        assert_eq!(for_dpr.pixel_bounding_box(), physical.pixel_bounding_box());
+        assert_eq!(coverage(&for_dpr, None), coverage(physical, None));
+    }
+}
+
+#[test]
+fn raster_bounds_match_draw() {
+    let font = &*DEJA_VU_MONO;
+    let mut units = 0;
+    for c in "gjyW@|_.-☣".chars() {
+        for scale in [
+            Scale::uniform(7.3),
+            Scale::uniform(24.0),
+            Scale { x: 40.0, y: 13.0 },
+        ] {
+            for position in [
+                point(0.0, 0.0),
+                point(-3.7, 10.2),
+                point(0.5, -0.5),
+                point(12.99, 7.01),
+            ] {
+                let glyph = font.glyph(c).scaled(scale);
+                for glyph in [
+                    glyph.clone().positioned(position),
+                    glyph.transformed(Affine2::rotate(0.4)).positioned(position),
+                ] {
+                    let bounds = match glyph.raster_bounds() {
+                        Some(bounds) => bounds,
+                        None => continue,
+                    };
+                    assert_eq!(Some(bounds), glyph.pixel_bounding_box());
+                    let (width, height) = (bounds.width() as u32, bounds.height() as u32);
+
+                    let mut visits = vec![0; (width * height) as usize];
+                    glyph.draw(|x, y, _| {
+                        assert!(x < width && y < height, "{:?} draws outside", c);
+                        visits[(x + y * width) as usize] += 1;
+                    });
+                    assert!(visits.iter().all(|&v| v == 1), "{:?} misses pixels", c);
+                    units += 1;
+                }
+            }
+        }
+    }
+    assert!(units > 100);
+}
+
+/// Records outline events as their SVG path command letter & points.
+#[derive(Default)]
+struct Events(Vec<(char, Vec<Point<f32>>)>);
+
+impl OutlineBuilder for Events {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.push(('M', vec![point(x, y)]));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.push(('L', vec![point(x, y)]));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.0.push(('Q', vec![point(x1, y1), point(x, y)]));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.0
+            .push(('C', vec![point(x1, y1), point(x2, y2), point(x, y)]));
+    }
+
+    fn close(&mut self) {
+        self.0.push(('Z', vec![]));
+    }
+}
+
+#[test]
+fn build_outline_events() {
+    let glyph = DEJA_VU_MONO.glyph('@').scaled(Scale::uniform(30.0));
+    let mut events = Events::default();
+    assert!(glyph.build_outline(&mut events as &mut dyn OutlineBuilder));
+
+    // contours of a move, segments & close
+    let kinds: String = events.0.iter().map(|(kind, _)| *kind).collect();
+    assert!(kinds.starts_with('M') && kinds.ends_with('Z'));
+    for contour in kinds.split_terminator('Z') {
+        assert!(contour.starts_with('M'));
+        assert!(contour[1..].chars().all(|k| "LQC".contains(k)), "{}", kinds);
+    }
+
+    // in pixels relative to the origin, the points hull the exact bounds
+    let bb = glyph.exact_bounding_box().unwrap();
+    let points = || events.0.iter().flat_map(|(_, points)| points);
+    let min_x = points().map(|p| p.x).fold(f32::MAX, f32::min);
+    let max_y = points().map(|p| p.y).fold(f32::MIN, f32::max);
+    assert!((min_x - bb.min.x).abs() < 0.5, "{} {:?}", min_x, bb);
+    assert!((max_y - bb.max.y).abs() < 0.5, "{} {:?}", max_y, bb);
+
+    // positioned outlines are relative to the pixel bounding box
+    let positioned = glyph.clone().positioned(point(10.5, 40.0));
+    let mut positioned_events = Events::default();
+    assert!(positioned.build_outline(&mut positioned_events));
+    let min = positioned.pixel_bounding_box().unwrap().min;
+    let offset = positioned.position() - point(min.x as f32, min.y as f32);
+    assert_eq!(events.0.len(), positioned_events.0.len());
+    for ((kind, points), (p_kind, p_points)) in events.0.iter().zip(&positioned_events.0) {
+        assert_eq!(kind, p_kind);
+        for (&p, &q) in points.iter().zip(p_points) {
+            let d = p + offset - q;
+            assert!(
+                d.x.abs() < 1e-4 && d.y.abs() < 1e-4,
+                "{:?} != {:?}",
+                p + offset,
+                q
+            );
+        }
+    }
+
+    let mut empty = Events::default();
+    assert!(!DEJA_VU_MONO
+        .glyph(' ')
+        .scaled(Scale::uniform(30.0))
+        .build_outline(&mut empty));
+    assert!(empty.0.is_empty());
+}
+
+#[test]
+fn to_svg_path() {
+    let glyph = DEJA_VU_MONO
+        .glyph('S')
+        .scaled(Scale::uniform(30.0))
+        .positioned(point(10.25, 40.0));
+    let mut events = Events::default();
+    glyph
+        .unpositioned()
+        .build_outline(&mut events as &mut dyn OutlineBuilder);
+
+    let parse = |d: &str| {
+        let mut commands = vec![];
+        let mut rest = d;
+        while let Some(c) = rest.chars().next() {
+            let end = rest[1..]
+                .find(char::is_alphabetic)
+                .map_or(rest.len(), |i| i + 1);
+            let numbers: Vec<f32> = rest[1..end]
+                .split(' ')
+                .filter(|n| !n.is_empty())
+                .map(|n| n.parse().unwrap())
+                .collect();
+            commands.push((c, numbers));
+            rest = &rest[end..];
+        }
+        commands
+    };
+
+    for (options, y_sign, tolerance) in [
+        (SvgPathOptions::default(), 1.0, 0.005),
+        (
+            SvgPathOptions::default().precision(4).y_up(true),
+            -1.0,
+            5e-5,
+        ),
+        (SvgPathOptions::default().precision(0), 1.0, 0.5),
+    ] {
+        let d = glyph.to_svg_path(options);
+        assert!(
+            !d.contains("-0 ") && !d.contains(".0 ") && !d.contains('e'),
+            "{}",
+            d
+        );
+        let commands = parse(&d);
+        assert_eq!(commands.len(), events.0.len());
+        for ((c, numbers), (kind, points)) in commands.iter().zip(&events.0) {
+            assert_eq!(c, kind);
+            assert_eq!(numbers.len(), 2 * points.len());
+            for (xy, p) in numbers.chunks(2).zip(points) {
+                assert!(
+                    (xy[0] - (p.x + 10.25)).abs() <= tolerance,
+                    "{} {:?}",
+                    xy[0],
+                    p
+                );
+                assert!(
+                    (xy[1] - y_sign * (p.y + 40.0)).abs() <= tolerance,
+                    "{} {:?}",
+                    xy[1],
+                    p
+                );
+            }
+        }
+    }
+
+    let space = DEJA_VU_MONO.glyph(' ').scaled(Scale::uniform(30.0));
+    let space = space.positioned(point(0.0, 0.0));
+    assert_eq!(space.to_svg_path(SvgPathOptions::default()), "");
+}
+
+#[test]
+fn fill_shapes() {
+    let area = |mask: &CoverageMask| mask.coverage.iter().sum::<f32>();
+
+    let mut mask = CoverageMask::new(64, 40);
+    let rect = Rect {
+        min: point(2.5, 4.0),
+        max: point(60.5, 36.0),
+    };
+    mask.fill_rounded_rect(rect, 6.0);
+    let expected = 58.0 * 32.0 - (4.0 - core::f32::consts::PI) * 36.0;
+    assert!((area(&mask) - expected).abs() < 0.5, "{}", area(&mask));
+    assert!(mask.get(30, 20) > 0.999);
+    assert!((mask.get(2, 20) - 0.5).abs() < 1e-4 && (mask.get(60, 20) - 0.5).abs() < 1e-4);
+    assert_eq!(mask.get(3, 4), 0.0, "rounded corner");
+    assert_eq!(mask.get(30, 3), 0.0);
+
+    // radius clamped to a pill
+    let mut pill = CoverageMask::new(40, 20);
+    pill.fill_rounded_rect(
+        Rect {
+            min: point(0.0, 0.0),
+            max: point(40.0, 20.0),
+        },
+        100.0,
+    );
+    let expected = 20.0 * 20.0 + core::f32::consts::PI * 100.0;
+    assert!((area(&pill) - expected).abs() < 0.5, "{}", area(&pill));
+
+    let mut circle = CoverageMask::new(32, 32);
+    circle.fill_circle(point(16.0, 16.0), 10.0);
+    let expected = core::f32::consts::PI * 100.0;
+    assert!((area(&circle) - expected).abs() < 0.5, "{}", area(&circle));
+
+    // clipped to the mask, without wrapping into other rows
+    let mut clipped = CoverageMask::new(16, 16);
+    clipped.fill_circle(point(0.0, 8.0), 6.0);
+    let expected = core::f32::consts::PI * 18.0;
+    assert!(
+        (area(&clipped) - expected).abs() < 0.5,
+        "{}",
+        area(&clipped)
+    );
+    assert!((0..16).all(|y| clipped.get(15, y) == 0.0 && clipped.get(8, y) == 0.0));
+
+    let mut line = CoverageMask::new(32, 20);
+    line.stroke_line(point(5.0, 10.0), point(25.0, 10.0), 2.0);
+    assert!((area(&line) - 40.0).abs() < 1e-3);
+    assert_eq!(
+        (line.get(5, 9), line.get(24, 10), line.get(25, 10)),
+        (1.0, 1.0, 0.0)
+    );
+    let mut diagonal = CoverageMask::new(32, 32);
+    diagonal.stroke_line(point(4.0, 4.0), point(28.0, 28.0), 3.0);
+    let expected = 24.0 * 2f32.sqrt() * 3.0;
+    assert!(
+        (area(&diagonal) - expected).abs() < 0.5,
+        "{}",
+        area(&diagonal)
+    );
+
+    // composited over existing coverage
+    line.stroke_line(point(15.0, 0.0), point(15.0, 20.0), 1.0);
+    assert!(line.coverage.iter().all(|&v| v <= 1.0));
+    assert!((area(&line) - 58.0).abs() < 1e-3, "{}", area(&line));
+}
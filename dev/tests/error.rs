@@ -0,0 +1,98 @@
+mod common;
+
+use common::{be16, sfnt};
+use rusttype::*;
+
+static DEJAVU_MONO: &[u8] = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf");
+
+/// A font without glyphs of `tables` & the `head`, `hhea` & `maxp` tables,
+/// unless replaced in `tables`.
+fn font_data(mut tables: Vec<([u8; 4], Vec<u8>)>) -> Vec<u8> {
+    let mut head = vec![
+        0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0x5F, 0x0F, 0x3C, 0xF5, 0, 0,
+    ];
+    head.extend(be16(1000)); // units per em
+    head.extend([0; 16]); // created & modified
+    for v in [0, 0, 0, 0, 0, 8, 2, 0, 0] {
+        head.extend(be16(v)); // bbox, style, lowest ppem, direction, short loca, format
+    }
+    let mut hhea = vec![0, 1, 0, 0];
+    for v in [800, -200, 0, 500, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1] {
+        hhea.extend(be16(v));
+    }
+    let maxp = vec![0, 0, 0x50, 0, 0, 1]; // version 0.5, 1 glyph
+    for (tag, table) in [(*b"head", head), (*b"hhea", hhea), (*b"maxp", maxp)] {
+        if !tables.iter().any(|(t, _)| *t == tag) {
+            tables.push((tag, table));
+        }
+    }
+    tables.sort_by_key(|(tag, _)| *tag);
+    sfnt(&tables)
+}
+
+#[test]
+fn from_bytes_loads_valid_fonts() {
+    let font = Font::from_bytes(DEJAVU_MONO).unwrap();
+    assert_eq!(
+        font.glyph('a').id(),
+        Font::try_from_bytes(DEJAVU_MONO).unwrap().glyph('a').id()
+    );
+    assert!(Font::from_vec(DEJAVU_MONO.to_vec()).is_ok());
+    assert!(Font::from_bytes(&font_data(vec![])).is_ok());
+}
+
+#[test]
+fn unsupported_version() {
+    let error = Font::from_bytes(b"wOF2 and the rest").unwrap_err();
+    assert_eq!(error, Error::UnsupportedVersion { version: *b"wOF2" });
+    assert_eq!(error.to_string(), "unsupported font version 'wOF2'");
+    assert!(Font::try_from_bytes(b"wOF2 and the rest").is_none());
+}
+
+#[test]
+fn index_out_of_range() {
+    let error = Font::from_bytes_and_index(DEJAVU_MONO, 2).unwrap_err();
+    assert_eq!(error, Error::IndexOutOfRange { index: 2, count: 1 });
+    assert_eq!(
+        Font::from_vec_and_index(DEJAVU_MONO.to_vec(), 2).unwrap_err(),
+        error
+    );
+    assert_eq!(error.to_string(), "font index 2 is out of range of 1 fonts");
+}
+
+#[test]
+fn missing_table() {
+    let mut data = font_data(vec![]);
+    // rename the `maxp` table
+    let entry = data.windows(4).position(|w| w == b"maxp").unwrap();
+    data[entry..entry + 4].copy_from_slice(b"mzzz");
+    let error = Font::from_bytes(&data).unwrap_err();
+    assert_eq!(error, Error::MissingTable { tag: *b"maxp" });
+    assert_eq!(
+        error.to_string(),
+        "required 'maxp' table is missing or malformed"
+    );
+}
+
+#[test]
+fn malformed_cmap() {
+    let data = font_data(vec![(*b"cmap", vec![0, 0])]);
+    assert_eq!(Font::from_bytes(&data).unwrap_err(), Error::MalformedCmap);
+    assert_eq!(
+        Font::from_vec(data.clone()).unwrap_err(),
+        Error::MalformedCmap
+    );
+
+    // loads without mapping chars
+    let font = Font::try_from_bytes(&data).unwrap();
+    assert_eq!(font.glyph('a').id(), GlyphId(0));
+    assert!(Font::try_from_vec(data).is_some());
+}
+
+#[test]
+fn malformed() {
+    assert_eq!(Font::from_bytes(&[]).unwrap_err(), Error::Malformed);
+    // a table directory past the end of the data
+    let data = [0, 1, 0, 0, 0, 9, 0, 0, 0, 0, 0, 0];
+    assert_eq!(Font::from_bytes(&data).unwrap_err(), Error::Malformed);
+}
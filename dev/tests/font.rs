@@ -0,0 +1,1083 @@
+use once_cell::sync::Lazy;
+use rusttype::*;
+
+static DEJA_VU_MONO: Lazy<Font<'static>> = Lazy::new(|| {
+    Font::try_from_bytes(include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf") as &[u8]).unwrap()
+});
+
+#[test]
+fn chars_for_glyph() {
+    let font = &*DEJA_VU_MONO;
+
+    for c in "aZ7☣".chars() {
+        let chars = font.chars_for_glyph(font.glyph(c).id());
+        assert!(chars.contains(&c), "{:?} not in {:?}", c, chars);
+        for other in chars {
+            assert_eq!(font.glyph(other).id(), font.glyph(c).id());
+        }
+    }
+
+    assert!(!font.chars_for_glyph(GlyphId(0)).contains(&'a'));
+}
+
+#[test]
+fn char_map() {
+    let font = &*DEJA_VU_MONO;
+    let map: Vec<_> = font.char_map().collect();
+
+    assert!(map.windows(2).all(|w| w[0].0 < w[1].0));
+    assert!(map.iter().all(|&(c, id)| font.glyph(c).id() == id));
+    assert!(map.contains(&('a', font.glyph('a').id())));
+    assert!(map.iter().all(|&(_, id)| id != GlyphId(0)));
+}
+
+#[test]
+fn coverage_summary() {
+    let summary = DEJA_VU_MONO.coverage_summary();
+
+    let basic_latin = summary.get("Basic Latin").unwrap();
+    assert_eq!(basic_latin.block.first, '\0');
+    assert_eq!(basic_latin.covered, 95, "printable ascii");
+    assert!(summary.ratio("Greek and Coptic") > 0.5);
+    assert_eq!(summary.ratio("Hiragana"), 0.0);
+    assert!(summary
+        .blocks()
+        .windows(2)
+        .all(|w| w[0].block.last < w[1].block.first));
+
+    assert_eq!(UnicodeBlock::of('ж').unwrap().name, "Cyrillic");
+    assert_eq!(
+        UnicodeBlock::of('\u{10FFFF}').unwrap().name,
+        "Supplementary Private Use Area-B"
+    );
+}
+
+#[test]
+fn unsupported_chars() {
+    let font = &*DEJA_VU_MONO;
+    assert!(font.unsupported_chars("").is_empty());
+    assert!(font.unsupported_chars("Hello, ☣ Ωμέγα!").is_empty());
+
+    let text = "ok あ\u{10FFFF}é";
+    let unsupported = font.unsupported_chars(text);
+    assert_eq!(unsupported, [(3, 'あ'), (6, '\u{10FFFF}')]);
+    for (i, c) in unsupported {
+        assert_eq!(text[i..].chars().next(), Some(c));
+        assert_eq!(font.glyph(c).id(), GlyphId(0));
+    }
+}
+
+#[test]
+fn reload() {
+    let dejavu: &[u8] = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf");
+    let wqy: &[u8] = include_bytes!("../fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+
+    let mut font = Font::try_from_bytes(dejavu).unwrap();
+    let old = font.clone();
+    let mut token = font.reload_token();
+    assert_eq!(font.generation(), 0);
+
+    font.reload(wqy).unwrap();
+    assert_eq!(font.generation(), 1);
+    assert_eq!(old.generation(), 0, "clones keep the previous data");
+    assert_ne!(font.glyph_count(), old.glyph_count());
+    assert_eq!(token.generation(), 1);
+    assert!(token.take_reloaded());
+    assert!(!token.take_reloaded());
+
+    assert!(font.reload_from_vec(vec![0; 16]).is_none());
+    assert_eq!(font.generation(), 1, "unchanged by invalid data");
+    assert!(!token.take_reloaded());
+}
+
+#[test]
+fn strong_count() {
+    let dejavu: &[u8] = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf");
+    let mut font = Font::try_from_vec(dejavu.to_vec()).unwrap();
+    font.set_pixel_mode(PixelMode::Off);
+    assert_eq!(font.strong_count(), 1);
+    assert!(font.unload_hint());
+
+    let weak = font.downgrade();
+    assert_eq!(weak.strong_count(), 1, "weak fonts don't count");
+    let glyph = font.glyph('a').scaled(Scale::uniform(24.0));
+    let clone = font.clone();
+    assert_eq!(font.strong_count(), 3);
+    assert!(!font.unload_hint());
+
+    let upgraded = weak.upgrade().unwrap();
+    assert_eq!(upgraded.pixel_mode(), PixelMode::Off);
+    assert_eq!(upgraded.generation(), font.generation());
+    drop(upgraded);
+
+    // data is freed once the font, clones & glyphs are dropped
+    drop((font, clone));
+    assert_eq!(weak.strong_count(), 1);
+    assert!(weak.upgrade().is_some());
+    drop(glyph);
+    assert_eq!(weak.strong_count(), 0);
+    assert!(weak.upgrade().is_none());
+
+    // reloads replace the data
+    let mut font = Font::try_from_bytes(dejavu).unwrap();
+    let weak = font.downgrade();
+    font.reload(dejavu).unwrap();
+    assert!(weak.upgrade().is_none());
+    assert!(font.unload_hint());
+}
+
+#[test]
+fn integrity_report() {
+    let report = DEJA_VU_MONO.integrity_report();
+    assert_eq!(report.issues(), &[]);
+    assert!(report.tables().iter().all(|t| t.is_ok()));
+
+    let mut data = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf").to_vec();
+    let glyf = *report.table(*b"glyf").unwrap();
+    data[glyf.offset as usize + 20] ^= 0xff;
+    let corrupt = Font::try_from_vec(data).unwrap();
+
+    let report = corrupt.integrity_report();
+    assert!(!report.table(*b"glyf").unwrap().is_ok());
+    assert!(matches!(
+        report.issues(),
+        [
+            IntegrityIssue::TableChecksumMismatch { tag, .. },
+            IntegrityIssue::ChecksumAdjustmentMismatch { .. },
+        ] if tag == b"glyf"
+    ));
+    assert!(report.issues()[0]
+        .to_string()
+        .starts_with("'glyf' table checksum is"));
+}
+
+#[test]
+fn embedding_permissions() {
+    let permissions = DEJA_VU_MONO.embedding_permissions().unwrap();
+    assert_eq!(permissions.usage, EmbeddingUsage::Installable);
+    assert!(permissions.allows_embedding());
+    assert!(permissions.subsetting_allowed);
+    assert!(!permissions.bitmap_only);
+}
+
+#[test]
+fn classification() {
+    let open_sans: &[u8] = include_bytes!("../fonts/opensans/OpenSans-Italic.ttf");
+    let open_sans = Font::try_from_bytes(open_sans).unwrap();
+    assert_eq!(
+        open_sans.panose().unwrap().family_kind(),
+        Panose::LATIN_TEXT
+    );
+    assert_eq!(open_sans.classification(), FontClass::SansSerif);
+
+    assert_eq!(DEJA_VU_MONO.panose().unwrap().proportion(), 9);
+    assert_eq!(DEJA_VU_MONO.classification(), FontClass::Monospace);
+}
+
+#[test]
+fn visual_center_offset() {
+    let scale = Scale::uniform(32.0);
+    let font = &*DEJA_VU_MONO;
+
+    let low = font.visual_center_offset("ox", scale);
+    let high = font.visual_center_offset("\u{b0}'", scale);
+    assert!(low.y > 0.0, "{:?}", low);
+    assert!(high.y < 0.0, "{:?}", high);
+    assert!(font.visual_center_offset("gy", scale).y > low.y);
+
+    assert_eq!(font.visual_center_offset("  ", scale), vector(0.0, 0.0));
+}
+
+#[test]
+fn layout_with_language_localized_forms() {
+    let font = &*DEJA_VU_MONO;
+    let scale = Scale::uniform(20.0);
+    let ids = |options: &LayoutOptions| -> Vec<GlyphId> {
+        font.layout_with_options("бa", scale, point(0.0, 0.0), options)
+            .map(|g| g.id())
+            .collect()
+    };
+
+    let default = ids(&LayoutOptions::default());
+    assert_eq!(default, vec![font.glyph('б').id(), font.glyph('a').id()]);
+
+    let serbian = ids(&LayoutOptions::default().language(Language::from_bcp47("sr-Cyrl")));
+    assert_ne!(serbian[0], default[0], "Serbian form of б");
+    assert_eq!(serbian[1], default[1]);
+
+    // languages the font has no forms for are unchanged
+    assert_eq!(
+        ids(&LayoutOptions::default().language(Language::from_bcp47("ja"))),
+        default
+    );
+}
+
+#[test]
+fn language_from_bcp47() {
+    assert_eq!(Language::from_bcp47("ja-JP"), Some(Language::JAPANESE));
+    assert_eq!(
+        Language::from_bcp47("zh"),
+        Some(Language::CHINESE_SIMPLIFIED)
+    );
+    assert_eq!(
+        Language::from_bcp47("zh-Hant"),
+        Some(Language::CHINESE_TRADITIONAL)
+    );
+    assert_eq!(
+        Language::from_bcp47("zh_TW"),
+        Some(Language::CHINESE_TRADITIONAL)
+    );
+    assert_eq!(
+        Language::from_bcp47("zh-HK"),
+        Some(Language::CHINESE_HONG_KONG)
+    );
+    assert_eq!(Language::from_bcp47("KO"), Some(Language::KOREAN));
+    assert_eq!(Language::from_bcp47("ro"), Some(Language(*b"ROM ")));
+    assert_eq!(Language::from_bcp47("xx"), None);
+}
+
+#[test]
+fn layout_ruby() {
+    let font = Font::try_from_bytes(
+        include_bytes!("../fonts/wqy-microhei/WenQuanYiMicroHei.ttf") as &[u8],
+    )
+    .unwrap();
+    let (scale, ruby_scale) = (Scale::uniform(32.0), Scale::uniform(16.0));
+    let start = point(10.0, 50.0);
+
+    // annotation wider than the base, the base is centered below it
+    let ruby = font.layout_ruby("字", "じじじ", scale, ruby_scale, start);
+    assert_eq!(ruby.base.len(), 1);
+    assert_eq!(ruby.annotation.len(), 3);
+    let base_advance = font.glyph('字').scaled(scale).h_metrics().advance_width;
+    assert!(ruby.advance_width > base_advance);
+    let base_x = ruby.base[0].position().x;
+    assert!((base_x - start.x - (ruby.advance_width - base_advance) / 2.0).abs() < 1e-3);
+    assert!((ruby.annotation[0].position().x - start.x).abs() < 1e-3);
+
+    let base_metrics = font.v_metrics(scale);
+    let ruby_metrics = font.v_metrics(ruby_scale);
+    let annotation_y = ruby.annotation[0].position().y;
+    assert!(annotation_y < start.y - base_metrics.ascent);
+    assert!(
+        (ruby.v_metrics.ascent
+            - (base_metrics.ascent + ruby_metrics.ascent - ruby_metrics.descent))
+            .abs()
+            < 1e-3
+    );
+    assert_eq!(ruby.v_metrics.descent, base_metrics.descent);
+
+    // base wider than the annotation
+    let ruby = font.layout_ruby("漢字", "じ", scale, ruby_scale, start);
+    assert!((ruby.base[0].position().x - start.x).abs() < 1e-3);
+    assert!(ruby.annotation[0].position().x > start.x);
+
+    // no annotation, no expansion
+    let ruby = font.layout_ruby("字", "", scale, ruby_scale, start);
+    assert_eq!(ruby.v_metrics, base_metrics);
+}
+
+#[test]
+fn optical_kerning() {
+    let font = &*DEJA_VU_MONO;
+    let scale = Scale::uniform(48.0);
+    assert!(!font.has_kerning());
+
+    // the reference pairs are nearly unchanged
+    assert!(font.optical_kerning(scale, 'n', 'n').abs() < 1.0);
+    assert!(font.optical_kerning(scale, 'T', 'o') < -1.0);
+    assert!(font.optical_kerning(scale, 'A', 'V') < -1.0);
+    assert_eq!(font.optical_kerning(scale, 'a', ' '), 0.0);
+
+    let x_of = |kerning| {
+        let options = LayoutOptions::default().kerning(kerning);
+        font.layout_with_options("To", scale, point(0.0, 0.0), &options)
+            .last()
+            .unwrap()
+            .position()
+            .x
+    };
+    let advance = font.glyph('T').scaled(scale).h_metrics().advance_width;
+    assert_eq!(x_of(Kerning::Metrics), advance);
+    let optical = advance + font.optical_kerning(scale, 'T', 'o');
+    assert!((x_of(Kerning::Optical) - optical).abs() < 1e-3);
+    assert_eq!(x_of(Kerning::Auto), x_of(Kerning::Optical), "no kern table");
+
+    let open_sans =
+        Font::try_from_bytes(include_bytes!("../fonts/opensans/OpenSans-Italic.ttf") as &[u8])
+            .unwrap();
+    assert!(open_sans.has_kerning());
+}
+
+#[test]
+fn contextual_kerning_matches_layout() {
+    let font =
+        Font::try_from_bytes(include_bytes!("../fonts/opensans/OpenSans-Italic.ttf") as &[u8])
+            .unwrap();
+    let scale = Scale::uniform(32.0);
+    let text = "AVATAR Typo";
+
+    let kerning = font.contextual_kerning(scale, text.chars());
+    assert_eq!(kerning.len(), text.chars().count());
+    assert!(kerning.iter().any(|&k| k < 0.0));
+
+    let mut x = 0.0;
+    for ((glyph, c), kern) in font
+        .layout(text, scale, point(0.0, 0.0))
+        .zip(text.chars())
+        .zip(kerning)
+    {
+        assert!((glyph.position().x - x).abs() < 1e-3, "{:?}", c);
+        x += glyph.unpositioned().h_metrics().advance_width + kern;
+    }
+}
+
+#[test]
+fn layout_shaped_simple_shaper_matches_layout() {
+    let font =
+        Font::try_from_bytes(include_bytes!("../fonts/opensans/OpenSans-Italic.ttf") as &[u8])
+            .unwrap();
+    let (scale, start) = (Scale::uniform(28.0), point(5.0, 30.0));
+    let text = "AVé Typo";
+    let options = LayoutOptions::default().feature(*b"liga", 0);
+
+    let mut infos = Vec::new();
+    SimpleShaper.shape(&font, text, scale, &options, &mut infos);
+    let clusters: Vec<_> = infos.iter().map(|i| i.cluster).collect();
+    let expected: Vec<_> = text.char_indices().map(|(idx, _)| idx).collect();
+    assert_eq!(clusters, expected);
+    assert!(infos[0].advance.0 < font.glyph('A').scaled(scale).h_metrics().advance_width);
+
+    let shaped = font.layout_shaped(&SimpleShaper, text, scale, start, &options);
+    let laid_out: Vec<_> = font.layout(text, scale, start).collect();
+    assert_eq!(shaped.len(), laid_out.len());
+    for (a, b) in shaped.iter().zip(&laid_out) {
+        assert_eq!(a.id(), b.id());
+        assert!((a.position().x - b.position().x).abs() < 1e-3);
+        assert_eq!(a.position().y, b.position().y);
+    }
+}
+
+#[test]
+fn with_transform_layout() {
+    let font = &*DEJA_VU_MONO;
+    let (scale, start) = (Scale::uniform(20.0), point(3.0, 25.0));
+    let offset = vector(100.0, 50.0);
+    let zoomed = font.with_transform(Affine2::scale(2.0, 2.0).then(Affine2::translate(offset)));
+
+    let v_metrics = font.v_metrics(scale);
+    assert_eq!(zoomed.v_metrics(scale), v_metrics * 2.0);
+    assert_eq!(
+        zoomed.h_metrics('a', scale).advance_width,
+        2.0 * font.glyph('a').scaled(scale).h_metrics().advance_width
+    );
+
+    let plain: Vec<_> = font.layout("Zoom", scale, start).collect();
+    let transformed: Vec<_> = zoomed.layout("Zoom", scale, start).collect();
+    assert_eq!(plain.len(), transformed.len());
+    for (p, t) in plain.iter().zip(&transformed) {
+        let expected = point(p.position().x * 2.0, p.position().y * 2.0) + offset;
+        assert!((t.position().x - expected.x).abs() < 1e-3);
+        assert!((t.position().y - expected.y).abs() < 1e-3);
+        assert_eq!(t.unpositioned().transform(), Affine2::scale(2.0, 2.0));
+    }
+
+    // rotation & skew don't change line metrics
+    let rotated = font.with_transform(Affine2::rotate(core::f32::consts::FRAC_PI_2));
+    let rotated_metrics = rotated.v_metrics(scale);
+    assert!((rotated_metrics.ascent - v_metrics.ascent).abs() < 1e-3);
+    let glyphs: Vec<_> = rotated.layout("ab", scale, point(0.0, 0.0)).collect();
+    let advance = font.glyph('a').scaled(scale).h_metrics().advance_width;
+    assert!(glyphs[1].position().x.abs() < 1e-3);
+    assert!((glyphs[1].position().y - advance).abs() < 1e-3);
+
+    let skewed = font.with_transform(Affine2::skew_x(0.25));
+    assert!((skewed.v_metrics(scale).ascent - v_metrics.ascent).abs() < 1e-3);
+}
+
+#[test]
+fn max_glyph_dimensions_fit_all_glyphs() {
+    for font_data in [
+        include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf") as &[u8],
+        include_bytes!("../fonts/opensans/OpenSans-Italic.ttf") as &[u8],
+    ] {
+        let font = Font::try_from_bytes(font_data).unwrap();
+        for scale in [
+            Scale::uniform(9.5),
+            Scale::uniform(24.0),
+            Scale { x: 30.0, y: 12.0 },
+        ] {
+            let (width, height) = font.max_glyph_dimensions(scale);
+            let (mut max_width, mut max_height) = (0, 0);
+            for id in 0..font.glyph_count() as u16 {
+                let glyph = font.glyph(GlyphId(id)).scaled(scale);
+                for position in [point(0.0, 0.0), point(0.3, 0.7), point(0.99, 0.5)] {
+                    if let Some(bb) = glyph.clone().positioned(position).pixel_bounding_box() {
+                        max_width = max_width.max(bb.width() as u32);
+                        max_height = max_height.max(bb.height() as u32);
+                    }
+                }
+            }
+            assert!(max_width <= width, "{} > {}", max_width, width);
+            assert!(max_height <= height, "{} > {}", max_height, height);
+        }
+    }
+}
+
+#[test]
+fn advance_widths_match_layout() {
+    let font =
+        Font::try_from_bytes(include_bytes!("../fonts/opensans/OpenSans-Italic.ttf") as &[u8])
+            .unwrap();
+    let scale = Scale::uniform(32.0);
+    let text = "AVATAR Typo";
+
+    let widths: Vec<_> = font.advance_widths(text, scale).collect();
+    assert_eq!(widths.len(), text.chars().count());
+
+    let glyphs: Vec<_> = font.layout(text, scale, point(0.0, 0.0)).collect();
+    for (glyph, width) in glyphs.iter().zip(&widths) {
+        let end = glyph.position().x + glyph.unpositioned().h_metrics().advance_width;
+        assert!((end - width).abs() < 1e-3);
+    }
+    // kerned, so narrower than the sum of advances
+    let advances: f32 = glyphs
+        .iter()
+        .map(|g| g.unpositioned().h_metrics().advance_width)
+        .sum();
+    assert!(*widths.last().unwrap() < advances);
+    assert_eq!(font.advance_widths("", scale).next(), None);
+}
+
+#[test]
+fn truncate_middle() {
+    let font =
+        Font::try_from_bytes(include_bytes!("../fonts/opensans/OpenSans-Italic.ttf") as &[u8])
+            .unwrap();
+    let scale = Scale::uniform(20.0);
+    let width = |s: &str| font.advance_widths(s, scale).last().unwrap_or(0.0);
+    let text = "AVATAR WAVE Typography.txt";
+    let full = width(text);
+
+    assert_eq!(font.truncate_middle(text, scale, full), text);
+    for max_width in [full - 1.0, full / 2.0, 40.0, 25.0] {
+        let truncated = font.truncate_middle(text, scale, max_width);
+        assert!(width(&truncated) <= max_width, "{:?}", truncated);
+        assert!(truncated.starts_with('A') && truncated.ends_with('t'));
+        assert!(truncated.contains('…'));
+        assert!(!truncated.contains(" …") && !truncated.contains("… "));
+    }
+    assert_eq!(font.truncate_middle(text, scale, 1.0), "");
+}
+
+#[test]
+fn caps_tracking() {
+    let font = &*DEJA_VU_MONO;
+    let scale = Scale::uniform(20.0);
+    let em = font.scale_for_pixel_height(scale.y) * f32::from(font.units_per_em());
+    let options = LayoutOptions::default().caps_tracking(100);
+    assert_eq!(options.get_caps_tracking(), 100);
+
+    let xs = |text: &str, options: &LayoutOptions| -> Vec<f32> {
+        font.layout_with_options(text, scale, point(0.0, 0.0), options)
+            .map(|g| g.position().x)
+            .collect()
+    };
+    let plain = xs("AB1c D", &LayoutOptions::default());
+    let tracked = xs("AB1c D", &options);
+
+    // tracked between A, B & 1 only
+    let extra: Vec<_> = tracked.iter().zip(&plain).map(|(t, p)| t - p).collect();
+    let shifts = [0.0, 0.1 * em, 0.2 * em, 0.2 * em, 0.2 * em, 0.2 * em];
+    for (extra, shift) in extra.iter().zip(&shifts) {
+        assert!((extra - shift).abs() < 1e-3, "{:?}", extra);
+    }
+    assert_eq!(xs("ab", &options), xs("ab", &LayoutOptions::default()));
+}
+
+#[test]
+fn outline_hash() {
+    let font = &*DEJA_VU_MONO;
+    let scale = Scale::uniform(20.0);
+    let hash =
+        |c: char, scale: Scale, subpixel: Vector<f32>| font.glyph(c).outline_hash(scale, subpixel);
+    let a = hash('a', scale, vector(0.0, 0.0));
+
+    // stable across processes & releases
+    assert_eq!(a, 10911140334149756586);
+    assert_ne!(hash('b', scale, vector(0.0, 0.0)), a);
+    assert_ne!(hash('a', Scale::uniform(21.0), vector(0.0, 0.0)), a);
+    assert_ne!(hash('a', scale, vector(0.0, 0.5)), a);
+    assert_eq!(hash('a', scale, vector(-0.0, 0.0)), a);
+}
+
+#[test]
+fn complexity() {
+    let font = &*DEJA_VU_MONO;
+    let scale = Scale::uniform(20.0);
+    let o = font.glyph('o').scaled(scale).complexity();
+    assert_eq!(o.contours, 2);
+    assert!(o.quads > 0);
+    assert_eq!(o.cubics, 0);
+    assert_eq!(o.points, o.contours + o.lines + 2 * o.quads);
+    assert_eq!(font.glyph('l').scaled(scale).complexity().contours, 1);
+    assert_eq!(
+        font.glyph(' ').scaled(scale).complexity(),
+        GlyphComplexity::default()
+    );
+
+    let drawn = std::sync::Mutex::new(Vec::new());
+    let timed = TimedBackend::new(ScanlineRasterizer, |g, _| {
+        drawn.lock().unwrap().push(g.id())
+    });
+    let glyph = font.glyph('o').scaled(scale).positioned(point(0.0, 0.0));
+    glyph.draw_with_backend(&timed, |_, _, _| {});
+    assert_eq!(*drawn.lock().unwrap(), [glyph.id()]);
+}
+
+#[test]
+fn unscaled_advance_and_kerning() {
+    let font = include_bytes!("../fonts/opensans/OpenSans-Italic.ttf") as &[u8];
+    let font = Font::try_from_bytes(font).unwrap();
+    let scale = Scale::uniform(32.0);
+    let factor = font.scale_for_pixel_height(32.0);
+
+    let advance = font.h_advance_unscaled('A');
+    assert!(advance > 0);
+    let h_metrics = font.glyph('A').scaled(scale).h_metrics();
+    assert_eq!(h_metrics.advance_width, f32::from(advance) * factor);
+    assert_eq!(font.h_advance_unscaled(GlyphId(u16::MAX)), 0);
+
+    let kern = font.pair_kerning_unscaled('A', 'V');
+    assert!(kern < 0);
+    assert_eq!(font.pair_kerning(scale, 'A', 'V'), kern as f32 * factor);
+    assert_eq!(font.pair_kerning_unscaled('A', 'A'), 0);
+}
+
+#[test]
+fn glyph_state_round_trip() {
+    let font = &*DEJA_VU_MONO;
+    let scale = Scale { x: 20.0, y: 24.0 };
+    let skew = Affine2::skew_x(0.2);
+    let positioned = font
+        .glyph('g')
+        .scaled(scale)
+        .transformed(skew)
+        .positioned(point(3.25, 18.5));
+    assert_eq!(positioned.id(), font.glyph('g').id());
+    assert_eq!(positioned.scale(), scale);
+    assert_eq!(positioned.transform(), skew);
+    assert_eq!(positioned.position(), point(3.25, 18.5));
+    assert_eq!(positioned.unscaled().id(), positioned.id());
+    assert_eq!(positioned.unpositioned().scale(), scale);
+
+    let scaled = positioned.clone().into_unpositioned();
+    assert_eq!(scaled.transform(), skew);
+    let again = scaled.positioned(positioned.position());
+    assert_eq!(again.pixel_bounding_box(), positioned.pixel_bounding_box());
+
+    let glyph = positioned.clone().into_unscaled();
+    assert_eq!(glyph.id(), positioned.id());
+    let again = glyph
+        .scaled(positioned.scale())
+        .transformed(positioned.transform())
+        .positioned(positioned.position());
+    assert_eq!(again.pixel_bounding_box(), positioned.pixel_bounding_box());
+}
+
+#[test]
+fn layout_checkpoint_resume() {
+    let font = include_bytes!("../fonts/opensans/OpenSans-Italic.ttf") as &[u8];
+    let font = Font::try_from_bytes(font).unwrap();
+    let scale = Scale::uniform(20.0);
+    let options = LayoutOptions::default().caps_tracking(50);
+    let text = "AVA WAVE To";
+    let layout = || font.layout_with_options(text, scale, point(5.0, 20.0), &options);
+    let expected: Vec<_> = layout().map(|g| (g.id(), g.position())).collect();
+
+    for split in 0..text.chars().count() {
+        let mut iter = layout();
+        let ahead: Vec<_> = iter.by_ref().take(split).collect();
+        let checkpoint = iter.checkpoint();
+        assert_eq!(checkpoint.remaining(), &text[ahead.len()..]);
+
+        // lay out ahead & roll back
+        assert_eq!(iter.by_ref().count(), expected.len() - split);
+        iter.resume(checkpoint.clone());
+        let resumed: Vec<_> = iter.map(|g| (g.id(), g.position())).collect();
+        assert_eq!(resumed, expected[split..]);
+
+        if let Some(last) = ahead.last() {
+            let end = last.position().x - 5.0 + last.unpositioned().h_metrics().advance_width;
+            assert!((checkpoint.caret() - end).abs() < 1e-4);
+        }
+    }
+}
+
+#[test]
+fn layout_with_carets() {
+    let font = include_bytes!("../fonts/opensans/OpenSans-Italic.ttf") as &[u8];
+    let font = Font::try_from_bytes(font).unwrap();
+    let scale = Scale::uniform(18.0);
+    let text = "AVAST, Ye wrap";
+    let options = LayoutOptions::default().caps_tracking(30);
+
+    let glyphs: Vec<_> = font
+        .layout_with_options(text, scale, point(4.0, 18.0), &options)
+        .collect();
+    let mut measured = font
+        .layout_with_options(text, scale, point(4.0, 18.0), &options)
+        .with_carets();
+    let checkpoint = measured.checkpoint();
+    let pairs: Vec<_> = measured.by_ref().collect();
+
+    assert_eq!(pairs.len(), glyphs.len());
+    for (i, (glyph, caret)) in pairs.iter().enumerate() {
+        assert_eq!(glyph.id(), glyphs[i].id());
+        assert_eq!(glyph.position(), glyphs[i].position());
+        let advance = glyph.unpositioned().h_metrics().advance_width;
+        assert!((caret - (glyph.position().x - 4.0 + advance)).abs() < 1e-4);
+    }
+    // without options, carets match measuring alone
+    let carets: Vec<_> = font
+        .layout(text, scale, point(0.0, 0.0))
+        .with_carets()
+        .map(|(_, caret)| caret)
+        .collect();
+    let widths: Vec<_> = font.advance_widths(text, scale).collect();
+    assert_eq!(carets, widths);
+
+    measured.resume(checkpoint);
+    assert_eq!(measured.next().unwrap().1, pairs[0].1);
+}
+
+#[test]
+fn layout_word_spacing() {
+    let font = include_bytes!("../fonts/opensans/OpenSans-Italic.ttf") as &[u8];
+    let font = Font::try_from_bytes(font).unwrap();
+    let scale = Scale::uniform(18.0);
+    let text = "To be,\u{a0}or not";
+    let space = font.space_advance(scale);
+    assert!(space > 0.0);
+    assert_eq!(
+        space,
+        font.glyph(' ').scaled(scale).h_metrics().advance_width
+    );
+
+    let natural: Vec<_> = font.layout(text, scale, point(0.0, 0.0)).collect();
+    let stretched: Vec<_> = font
+        .layout(text, scale, point(0.0, 0.0))
+        .word_spacing(1.5, 2.0)
+        .collect();
+    let extra = 0.5 * space + 2.0;
+    let mut separators = 0;
+    for ((c, natural), stretched) in text.chars().zip(&natural).zip(&stretched) {
+        let offset = stretched.position().x - natural.position().x;
+        assert!((offset - separators as f32 * extra).abs() < 1e-3, "{:?}", c);
+        if c == ' ' || c == '\u{a0}' {
+            separators += 1;
+        }
+    }
+    assert_eq!(separators, 3);
+
+    // measuring matches
+    let widths: Vec<_> = font
+        .layout(text, scale, point(0.0, 0.0))
+        .word_spacing(1.5, 2.0)
+        .with_carets()
+        .map(|(_, caret)| caret)
+        .collect();
+    let natural_width = font.advance_widths(text, scale).last().unwrap();
+    assert!((widths.last().unwrap() - natural_width - 3.0 * extra).abs() < 1e-3);
+}
+
+#[test]
+fn caret_slope() {
+    let upright = &*DEJA_VU_MONO;
+    assert_eq!(upright.italic_angle(), 0.0);
+    assert_eq!(upright.caret_slope(), (1, 0));
+    let scale = Scale::uniform(20.0);
+    let v_metrics = upright.v_metrics(scale);
+    let [bottom, top] = upright.caret_line(scale, point(5.0, 30.0)).p;
+    assert_eq!(bottom, point(5.0, 30.0 - v_metrics.descent));
+    assert_eq!(top, point(5.0, 30.0 - v_metrics.ascent));
+
+    let font = include_bytes!("../fonts/opensans/OpenSans-Italic.ttf") as &[u8];
+    let italic = Font::try_from_bytes(font).unwrap();
+    assert_eq!(italic.italic_angle(), -12.0);
+    assert_eq!(italic.caret_slope(), (2048, 435));
+    let [bottom, top] = italic.caret_line(scale, point(5.0, 30.0)).p;
+    // leaning right by the italic angle, through the baseline position
+    let angle = (top.x - bottom.x).atan2(bottom.y - top.y).to_degrees();
+    assert!((angle - 12.0).abs() < 0.1, "{}", angle);
+    let t = (30.0 - top.y) / (bottom.y - top.y);
+    assert!((top.x + t * (bottom.x - top.x) - 5.0).abs() < 1e-4);
+
+    // wider scales lean further
+    let [bottom, top] = italic
+        .caret_line(Scale { x: 40.0, y: 20.0 }, point(5.0, 30.0))
+        .p;
+    let angle = (top.x - bottom.x).atan2(bottom.y - top.y).to_degrees();
+    assert!(angle > 20.0, "{}", angle);
+}
+
+#[test]
+fn rich_layout() {
+    let regular = &*DEJA_VU_MONO;
+    let italic = include_bytes!("../fonts/opensans/OpenSans-Italic.ttf") as &[u8];
+    let italic = Font::try_from_bytes(italic).unwrap();
+    let (small, large) = (Scale::uniform(16.0), Scale::uniform(30.0));
+    let start = point(5.0, 40.0);
+    let runs = [
+        TextRun::new("AVA ", &italic, large),
+        TextRun::new("", regular, Scale::uniform(50.0)),
+        TextRun::new("mono", regular, small).letter_spacing(2.0),
+        TextRun::new("AV", &italic, large),
+    ];
+    let layout = RichLayout::new(&runs, start);
+
+    assert_eq!(layout.glyphs.len(), 10);
+    assert_eq!(layout.runs, [0..4, 4..4, 4..8, 8..10]);
+    assert!(layout.glyphs.iter().all(|g| g.position().y == start.y));
+
+    // each run lays out as alone, from the previous run's advance
+    let first: Vec<_> = italic.layout("AVA ", large, start).collect();
+    for (glyph, expected) in layout.glyphs[..4].iter().zip(&first) {
+        assert_eq!(glyph.id(), expected.id());
+        assert_eq!(glyph.position(), expected.position());
+    }
+    let first_advance = italic.advance_widths("AVA ", large).last().unwrap();
+    let w = regular.glyph('m').scaled(small).h_metrics().advance_width;
+    for (i, glyph) in layout.glyphs[4..8].iter().enumerate() {
+        let x = start.x + first_advance + i as f32 * (w + 2.0);
+        assert!((glyph.position().x - x).abs() < 1e-3, "{}", i);
+        assert_eq!(glyph.scale(), small);
+    }
+    let mono_advance = 4.0 * (w + 2.0);
+    let second_x = start.x + first_advance + mono_advance;
+    assert!((layout.glyphs[8].position().x - second_x).abs() < 1e-3);
+    let last_advance = italic.advance_widths("AV", large).last().unwrap();
+    let advance = first_advance + mono_advance + last_advance;
+    assert!((layout.advance_width - advance).abs() < 1e-3);
+
+    // line metrics fit every run, including the empty one
+    let (big_regular, italic_large) = (
+        regular.v_metrics(Scale::uniform(50.0)),
+        italic.v_metrics(large),
+    );
+    assert_eq!(
+        layout.v_metrics.ascent,
+        big_regular.ascent.max(italic_large.ascent)
+    );
+    assert_eq!(
+        layout.v_metrics.descent,
+        big_regular.descent.min(italic_large.descent)
+    );
+
+    let empty = RichLayout::new(&[], start);
+    assert!(empty.glyphs.is_empty() && empty.advance_width == 0.0);
+    assert_eq!(empty.v_metrics.ascent, 0.0);
+}
+
+#[test]
+fn gsub_shaper_ligatures() {
+    let font =
+        Font::try_from_bytes(include_bytes!("../fonts/opensans/OpenSans-Italic.ttf") as &[u8])
+            .unwrap();
+    let (scale, start) = (Scale::uniform(28.0), point(5.0, 30.0));
+    let options = LayoutOptions::default();
+    let shape = |text: &str, options: &LayoutOptions| {
+        let mut infos = Vec::new();
+        GsubShaper.shape(&font, text, scale, options, &mut infos);
+        infos
+    };
+
+    // "fi" ligates into one glyph of the first char's cluster
+    let infos = shape("a fit", &options);
+    let clusters: Vec<_> = infos.iter().map(|i| i.cluster).collect();
+    assert_eq!(clusters, [0, 1, 2, 4]);
+    let fi = infos[2].id;
+    assert!(fi != font.glyph('f').id() && fi != GlyphId(0));
+    let fi_advance = font.glyph(fi).scaled(scale).h_metrics().advance_width;
+    assert!((infos[2].advance.0 - fi_advance).abs() < 1.0);
+    let shaped = font.layout_shaped(&GsubShaper, "a fit", scale, start, &options);
+    assert_eq!(shaped.len(), 4);
+    assert_eq!(shaped[2].id(), fi);
+
+    // without `liga`, like the simple shaper
+    let unligated = options.clone().feature(*b"liga", 0);
+    let text = "AV fit";
+    let mut simple = Vec::new();
+    SimpleShaper.shape(&font, text, scale, &unligated, &mut simple);
+    let infos = shape(text, &unligated);
+    assert_eq!(infos.len(), simple.len());
+    for (a, b) in infos.iter().zip(&simple) {
+        assert_eq!((a.id, a.cluster), (b.id, b.cluster));
+        assert!((a.advance.0 - b.advance.0).abs() < 1e-3);
+    }
+    assert!(infos[0].advance.0 < font.glyph('A').scaled(scale).h_metrics().advance_width);
+
+    // unknown features & fonts without GSUB are left alone
+    let infos = shape("fit", &options.clone().feature(*b"zzzz", 1));
+    assert_eq!(infos.len(), 2);
+    let mono = &*DEJA_VU_MONO;
+    let mut infos = Vec::new();
+    GsubShaper.shape(mono, "fit", scale, &options, &mut infos);
+    let ids: Vec<_> = infos.iter().map(|i| i.id).collect();
+    assert_eq!(
+        ids,
+        [
+            mono.glyph('f').id(),
+            mono.glyph('i').id(),
+            mono.glyph('t').id()
+        ]
+    );
+}
+
+#[test]
+fn gpos_pair_kerning() {
+    // Roboto only kerns with GPOS pair adjustments
+    let font =
+        Font::try_from_bytes(include_bytes!("../fonts/Roboto-Regular.ttf") as &[u8]).unwrap();
+    let scale = Scale::uniform(40.0);
+    let kern = font.pair_kerning(scale, 'A', 'V');
+    assert!(kern < -0.5, "{}", kern);
+    assert!(font.pair_kerning_unscaled('A', 'V') < 0);
+    assert_eq!(font.contextual_kerning(scale, "AV".chars())[0], kern);
+
+    let glyphs: Vec<_> = font.layout("AV", scale, point(0.0, 0.0)).collect();
+    let advance = font.glyph('A').scaled(scale).h_metrics().advance_width;
+    assert!((glyphs[1].position().x - (advance + kern)).abs() < 1e-3);
+
+    // so `Kerning::Auto` uses it rather than estimates
+    assert!(font.has_kerning());
+    let x_of = |kerning| {
+        let options = LayoutOptions::default().kerning(kerning);
+        font.layout_with_options("AV", scale, point(0.0, 0.0), &options)
+            .last()
+            .unwrap()
+            .position()
+            .x
+    };
+    assert_eq!(x_of(Kerning::Auto), x_of(Kerning::Metrics));
+    assert_eq!(x_of(Kerning::Auto), glyphs[1].position().x);
+
+    // the `kern` table is still preferred
+    let open_sans =
+        Font::try_from_bytes(include_bytes!("../fonts/opensans/OpenSans-Italic.ttf") as &[u8])
+            .unwrap();
+    assert!(open_sans.pair_kerning(scale, 'A', 'V') < 0.0);
+    assert_eq!(DEJA_VU_MONO.pair_kerning(scale, 'A', 'V'), 0.0);
+}
+
+#[test]
+fn gpos_mark_attachment() {
+    let font = Font::try_from_bytes(include_bytes!("../fonts/Exo2-Light.ttf") as &[u8]).unwrap();
+    let scale = Scale::uniform(40.0);
+    let start = point(3.0, 30.0);
+    let offset = font.attachment_offset(scale, 'e', '\u{301}').unwrap();
+    assert!(offset.x > 0.0);
+    assert_eq!(font.attachment_offset(scale, 'e', 'x'), None);
+    assert_eq!(font.attachment_offset(scale, '\u{301}', 'e'), None);
+
+    // marks are placed on the base, kerned & advancing as if not there
+    let text = "e\u{301}\u{308}x";
+    let glyphs: Vec<_> = font.layout(text, scale, start).collect();
+    assert_eq!(glyphs.len(), 4);
+    assert_eq!(glyphs[1].position(), start + offset);
+    let umlaut = font.attachment_offset(scale, 'e', '\u{308}').unwrap();
+    assert_eq!(glyphs[2].position(), start + umlaut);
+    let advance = font.glyph('e').scaled(scale).h_metrics().advance_width;
+    let x = start.x + advance + font.pair_kerning(scale, 'e', 'x');
+    assert!((glyphs[3].position().x - x).abs() < 1e-3);
+    assert_eq!(glyphs[3].position().y, start.y);
+
+    let widths: Vec<_> = font.advance_widths(text, scale).collect();
+    assert_eq!(widths[0], widths[1]);
+    assert_eq!(widths[1], widths[2]);
+
+    // resuming after a base still attaches
+    let mut layout = font.layout(text, scale, start);
+    layout.next();
+    let checkpoint = layout.checkpoint();
+    let rest: Vec<_> = layout.collect();
+    let mut layout = font.layout(text, scale, start);
+    layout.next();
+    layout.resume(checkpoint);
+    let resumed: Vec<_> = layout.collect();
+    for (a, b) in rest.iter().zip(&resumed) {
+        assert_eq!(a.position(), b.position());
+    }
+
+    // shaped the same way
+    let shaped = font.layout_shaped(&GsubShaper, text, scale, start, &LayoutOptions::default());
+    for (a, b) in shaped.iter().zip(&glyphs) {
+        assert_eq!(a.id(), b.id());
+        assert!((a.position().x - b.position().x).abs() < 1e-3);
+        assert!((a.position().y - b.position().y).abs() < 1e-3);
+    }
+
+    // monospace marks, with an advance, are placed over their base too
+    let mono = &*DEJA_VU_MONO;
+    let w = mono.glyph('e').scaled(scale).h_metrics().advance_width;
+    let glyphs: Vec<_> = mono.layout("e\u{301}x", scale, start).collect();
+    assert!((glyphs[1].position().x - start.x).abs() < 1.0);
+    assert!((glyphs[2].position().x - (start.x + w)).abs() < 1e-3);
+}
+
+#[test]
+fn glyph_run_offsets() {
+    let font = &*DEJA_VU_MONO;
+    let (scale, start) = (Scale::uniform(24.0), point(5.0, 30.0));
+    let options = LayoutOptions::default();
+    let text = "Héllo";
+    let run = font.glyph_run(&SimpleShaper, text, scale, start, &options);
+    let shaped = font.layout_shaped(&SimpleShaper, text, scale, start, &options);
+    assert_eq!(run.glyphs().len(), shaped.len());
+    let clusters: Vec<_> = text.char_indices().map(|(i, _)| i).collect();
+    assert_eq!(run.clusters(), &clusters[..]);
+
+    let offsets = run.glyph_offsets();
+    for (i, (offset, glyph)) in offsets.iter().zip(&shaped).enumerate() {
+        assert_eq!(
+            (offset.index, offset.order, offset.cluster),
+            (i, i, clusters[i])
+        );
+        assert_eq!(
+            offset.transform.transform_point(point(0.0, 0.0)),
+            glyph.position()
+        );
+        let grown = Affine2::scale(2.0, 2.0).then(offset.transform);
+        assert_eq!(
+            grown.transform_point(point(1.0, 0.0)),
+            glyph.position() + vector(2.0, 0.0)
+        );
+    }
+
+    // text order follows clusters, whatever the visual order
+    struct Reversed;
+    impl Shaper for Reversed {
+        fn shape(
+            &self,
+            font: &Font<'_>,
+            text: &str,
+            scale: Scale,
+            options: &LayoutOptions,
+            output: &mut Vec<GlyphInfo>,
+        ) {
+            let start = output.len();
+            SimpleShaper.shape(font, text, scale, options, output);
+            output[start..].reverse();
+            // a glyph decomposed in two, sharing a cluster
+            let last = *output.last().unwrap();
+            output.push(last);
+        }
+    }
+    let run = font.glyph_run(&Reversed, "abc", scale, start, &options);
+    let offsets = run.glyph_offsets();
+    let orders: Vec<_> = offsets.iter().map(|o| o.order).collect();
+    assert_eq!(orders, [3, 2, 0, 1]);
+    assert!(run.glyphs()[0].position().x < run.glyphs()[1].position().x);
+
+    // a ligature is one glyph
+    let open_sans =
+        Font::try_from_bytes(include_bytes!("../fonts/opensans/OpenSans-Italic.ttf") as &[u8])
+            .unwrap();
+    let run = open_sans.glyph_run(&GsubShaper, "fit", scale, start, &options);
+    let offsets = run.glyph_offsets();
+    let orders: Vec<_> = offsets.iter().map(|o| (o.order, o.cluster)).collect();
+    assert_eq!(orders, [(0, 0), (1, 2)]);
+}
+
+#[test]
+fn rustybuzz_shaper() {
+    let font =
+        Font::try_from_bytes(include_bytes!("../fonts/opensans/OpenSans-Italic.ttf") as &[u8])
+            .unwrap();
+    let (scale, start) = (Scale::uniform(28.0), point(5.0, 30.0));
+    let options = LayoutOptions::default();
+    let shape = |font: &Font<'_>, text: &str, options: &LayoutOptions| {
+        let mut infos = Vec::new();
+        RustybuzzShaper.shape(font, text, scale, options, &mut infos);
+        infos
+    };
+
+    // ligates like the GSUB shaper, kerning with GPOS
+    let infos = shape(&font, "AV fit", &options);
+    let mut gsub = Vec::new();
+    GsubShaper.shape(&font, "AV fit", scale, &options, &mut gsub);
+    assert_eq!(infos.len(), 5);
+    for (a, b) in infos.iter().zip(&gsub) {
+        assert_eq!((a.id, a.cluster), (b.id, b.cluster));
+        assert_eq!(a.advance.1, 0.0);
+    }
+    assert!(infos[0].advance.0 < font.glyph('A').scaled(scale).h_metrics().advance_width);
+    let space = font.glyph(' ').scaled(scale).h_metrics().advance_width;
+    assert!((infos[2].advance.0 - space).abs() < 1e-3);
+    let unligated = shape(&font, "fit", &options.clone().feature(*b"liga", 0));
+    assert_eq!(unligated.len(), 3);
+
+    // marks are positioned on their base without advancing
+    let exo = Font::try_from_bytes(include_bytes!("../fonts/Exo2-Light.ttf") as &[u8]).unwrap();
+    let run = exo.glyph_run(&RustybuzzShaper, "h\u{301}", scale, start, &options);
+    assert_eq!(run.clusters(), [0, 1]);
+    let offset = exo.attachment_offset(scale, 'h', '\u{301}').unwrap();
+    let mark = run.glyphs()[1].position() - (run.glyphs()[0].position() + offset);
+    assert!(mark.x.abs() < 0.05 && mark.y.abs() < 0.05, "{:?}", mark);
+
+    // Arabic is joined & output right to left
+    let mono = &*DEJA_VU_MONO;
+    let text = "بيت ab";
+    let run = mono.glyph_run(&RustybuzzShaper, text, scale, start, &options);
+    assert_eq!(run.glyphs().len(), 6);
+    let medial_yeh = run.glyphs()[run.clusters().iter().position(|&c| c == 2).unwrap()].id();
+    assert_ne!(medial_yeh, mono.glyph('ي').id());
+    let positions: Vec<_> = run.glyphs().iter().map(|g| g.position().x).collect();
+    assert!(positions.windows(2).all(|w| w[0] < w[1]), "{:?}", positions);
+}
+
+#[test]
+fn vertical_layout() {
+    let font = Font::try_from_bytes_and_index(
+        include_bytes!("../fonts/wqy-microhei/WenQuanYiMicroHei.ttf") as &[u8],
+        0,
+    )
+    .unwrap();
+    let scale = Scale::uniform(30.0);
+    let start = point(40.0, 10.0);
+
+    let han = font.glyph('中').scaled(scale);
+    let v_metrics = han.v_metrics();
+    assert!(v_metrics.advance_height > 0.0);
+    assert_eq!(v_metrics.origin.x, -han.h_metrics().advance_width / 2.0);
+    // the glyph top is the side bearing below the vertical origin
+    let bb = han.exact_bounding_box().unwrap();
+    assert!((bb.min.y + v_metrics.origin.y - v_metrics.top_side_bearing).abs() < 1e-3);
+
+    let text = "中文（字）";
+    let glyphs: Vec<_> = font.layout_vertical(text, scale, start).collect();
+    assert_eq!(glyphs.len(), 5);
+    let mut y = start.y;
+    for glyph in &glyphs {
+        let v_metrics = glyph.unpositioned().v_metrics();
+        let position = start + vector(0.0, y - start.y) + v_metrics.origin;
+        assert!((glyph.position() - position).x.abs() < 1e-3);
+        assert!((glyph.position() - position).y.abs() < 1e-3);
+        y += v_metrics.advance_height;
+    }
+    let mut layout = font.layout_vertical(text, scale, start);
+    layout.by_ref().count();
+    assert!((layout.caret() - (y - start.y)).abs() < 1e-3);
+    // without `vert` alternates in the font, glyphs are as mapped
+    for (glyph, c) in glyphs.iter().zip(text.chars()) {
+        assert_eq!(glyph.id(), font.glyph(c).id());
+    }
+
+    // fonts without vertical metrics advance by the font height
+    let mono = &*DEJA_VU_MONO;
+    let v_metrics = mono.glyph('a').scaled(scale).v_metrics();
+    let font_v_metrics = mono.v_metrics(scale);
+    let height = font_v_metrics.ascent - font_v_metrics.descent;
+    assert!((v_metrics.advance_height - height).abs() < 1e-3);
+    assert!((v_metrics.origin.y - font_v_metrics.ascent).abs() < 1e-3);
+}
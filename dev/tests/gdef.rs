@@ -0,0 +1,91 @@
+use rusttype::*;
+
+static DEJAVU_MONO: &[u8] = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf");
+static OPEN_SANS_ITALIC: &[u8] = include_bytes!("../fonts/opensans/OpenSans-Italic.ttf");
+
+/// `font` with its `tag` table replaced by `table`.
+fn with_table(font: &[u8], tag: [u8; 4], table: Vec<u8>) -> Vec<u8> {
+    let be16 = |at: usize| u16::from_be_bytes([font[at], font[at + 1]]) as usize;
+    let be32 = |at: usize| be16(at) << 16 | be16(at + 2);
+    let mut tables: Vec<([u8; 4], Vec<u8>)> = (0..be16(4))
+        .map(|i| {
+            let record = 12 + 16 * i;
+            let t = [
+                font[record],
+                font[record + 1],
+                font[record + 2],
+                font[record + 3],
+            ];
+            let (offset, len) = (be32(record + 8), be32(record + 12));
+            (t, font[offset..offset + len].to_vec())
+        })
+        .filter(|(t, _)| *t != tag)
+        .collect();
+    tables.push((tag, table));
+    tables.sort_by_key(|(t, _)| *t);
+
+    let mut out = vec![0, 1, 0, 0];
+    out.extend((tables.len() as u16).to_be_bytes());
+    out.extend([0; 6]); // search hints
+    let mut offset = 12 + 16 * tables.len();
+    for (tag, table) in &tables {
+        out.extend(tag);
+        out.extend([0; 4]); // checksum
+        out.extend((offset as u32).to_be_bytes());
+        out.extend((table.len() as u32).to_be_bytes());
+        offset += table.len().next_multiple_of(4);
+    }
+    for (_, table) in &tables {
+        out.extend(table);
+        out.resize(out.len().next_multiple_of(4), 0);
+    }
+    out
+}
+
+/// A format 2 class definition of glyph `ranges`, each first, last & class.
+fn class_def(ranges: &[(GlyphId, GlyphId, u16)]) -> Vec<u8> {
+    let mut out = vec![0, 2];
+    out.extend((ranges.len() as u16).to_be_bytes());
+    for &(first, last, class) in ranges {
+        for v in [first.0, last.0, class] {
+            out.extend(v.to_be_bytes());
+        }
+    }
+    out
+}
+
+#[test]
+fn glyph_classes() {
+    let font = Font::try_from_bytes(DEJAVU_MONO).unwrap();
+    assert!(font.has_glyph_classes());
+    assert_eq!(font.glyph_class('a'), Some(GlyphClass::Base));
+    assert_eq!(font.glyph_class('\u{301}'), Some(GlyphClass::Mark));
+    assert_eq!(font.glyph_class('\u{323}'), Some(GlyphClass::Mark));
+    assert_eq!(font.glyph_class('ﬁ'), Some(GlyphClass::Ligature));
+    let id = font.glyph('\u{301}').id();
+    assert_eq!(font.glyph_class(id), Some(GlyphClass::Mark));
+
+    // every glyph classed as a base
+    let font = Font::try_from_bytes(OPEN_SANS_ITALIC).unwrap();
+    assert_eq!(font.glyph_class('\u{301}'), Some(GlyphClass::Base));
+}
+
+#[test]
+fn mark_attachment_classes() {
+    let font = Font::try_from_bytes(DEJAVU_MONO).unwrap();
+    assert_eq!(font.mark_attachment_class('\u{301}'), 0);
+    let (above, below) = (font.glyph('\u{301}').id(), font.glyph('\u{323}').id());
+    let base = font.glyph('a').id();
+
+    // version 1.0 with only a mark attachment class definition
+    let mut gdef = vec![0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 12];
+    gdef.extend(class_def(&[(above, above, 1), (below, below, 2)]));
+    let data = with_table(DEJAVU_MONO, *b"GDEF", gdef);
+    let font = Font::try_from_bytes(&data).unwrap();
+
+    assert_eq!(font.mark_attachment_class(above), 1);
+    assert_eq!(font.mark_attachment_class('\u{323}'), 2);
+    assert_eq!(font.mark_attachment_class(base), 0);
+    assert!(!font.has_glyph_classes());
+    assert_eq!(font.glyph_class(above), None);
+}
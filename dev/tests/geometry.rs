@@ -0,0 +1,131 @@
+use rusttype::*;
+
+fn near(a: Point<f32>, b: Point<f32>) -> bool {
+    (a.x - b.x).abs() < 1e-3 && (a.y - b.y).abs() < 1e-3
+}
+
+#[test]
+fn line_intersection() {
+    let a = Line {
+        p: [point(0.0, 0.0), point(4.0, 0.0)],
+    };
+    let cross = |x0: f32, y0: f32, x1: f32, y1: f32| {
+        a.intersection(&Line {
+            p: [point(x0, y0), point(x1, y1)],
+        })
+    };
+    assert_eq!(cross(1.0, -1.0, 3.0, 1.0), Some(point(2.0, 0.0)));
+    assert_eq!(cross(4.0, -1.0, 4.0, 1.0), Some(point(4.0, 0.0)));
+    // short of the segment, parallel & collinear
+    assert_eq!(cross(5.0, -1.0, 5.0, 1.0), None);
+    assert_eq!(cross(2.0, -2.0, 2.0, -1.0), None);
+    assert_eq!(cross(0.0, 1.0, 4.0, 1.0), None);
+    assert_eq!(cross(1.0, 0.0, 3.0, 0.0), None);
+}
+
+#[test]
+fn line_nearest_point() {
+    let line = Line {
+        p: [point(0.0, 0.0), point(10.0, 0.0)],
+    };
+    assert_eq!(line.nearest_point(point(3.0, 5.0)), (0.3, point(3.0, 0.0)));
+    assert_eq!(line.nearest_point(point(-3.0, 5.0)), (0.0, point(0.0, 0.0)));
+    assert_eq!(
+        line.nearest_point(point(13.0, -5.0)),
+        (1.0, point(10.0, 0.0))
+    );
+}
+
+#[test]
+fn curve_split() {
+    let curve = Curve {
+        p: [point(0.0, 0.0), point(3.0, 6.0), point(9.0, 0.0)],
+    };
+    let (before, after) = curve.split(0.25);
+    for i in 0..=8 {
+        let t = i as f32 / 8.0;
+        assert!(near(before.at(t), curve.at(t * 0.25)));
+        assert!(near(after.at(t), curve.at(0.25 + t * 0.75)));
+    }
+
+    let cubic = Cubic {
+        p: [
+            point(0.0, 0.0),
+            point(0.0, 6.0),
+            point(9.0, 6.0),
+            point(9.0, 0.0),
+        ],
+    };
+    let (before, after) = cubic.split(0.6);
+    for i in 0..=8 {
+        let t = i as f32 / 8.0;
+        assert!(near(before.at(t), cubic.at(t * 0.6)));
+        assert!(near(after.at(t), cubic.at(0.6 + t * 0.4)));
+    }
+}
+
+#[test]
+fn curve_nearest_point() {
+    let curve = Curve {
+        p: [point(0.0, 0.0), point(5.0, 10.0), point(10.0, 0.0)],
+    };
+    // the apex
+    let (t, p) = curve.nearest_point(point(5.0, 9.0));
+    assert!((t - 0.5).abs() < 1e-3);
+    assert!(near(p, point(5.0, 5.0)));
+    assert_eq!(curve.nearest_point(point(-5.0, -5.0)).0, 0.0);
+
+    let cubic = Cubic {
+        p: [
+            point(0.0, 0.0),
+            point(0.0, 6.0),
+            point(9.0, 6.0),
+            point(9.0, 0.0),
+        ],
+    };
+    let on_curve = cubic.at(0.3);
+    let (t, p) = cubic.nearest_point(on_curve);
+    assert!((t - 0.3).abs() < 1e-3);
+    assert!(near(p, on_curve));
+}
+
+#[test]
+fn rect_conversions() {
+    let atlas: Rect<u32> = Rect {
+        min: point(64, 32),
+        max: point(96, 64),
+    };
+    let uv = atlas.to_f32() / vector(256.0, 128.0);
+    assert_eq!(
+        uv,
+        Rect {
+            min: point(0.25, 0.25),
+            max: point(0.375, 0.5),
+        }
+    );
+    assert_eq!(uv * vector(256.0, 128.0), atlas.to_f32());
+    assert_eq!(atlas.to_f64().width(), 32.0);
+    assert_eq!(atlas * 2, atlas.map(|v| v + v));
+    assert_eq!(atlas.map(i64::from).min, point(64_i64, 32));
+
+    let bounds: Rect<f32> = Rect {
+        min: point(0.5, -1.5),
+        max: point(3.25, 2.0),
+    };
+    assert_eq!(
+        bounds.round_out(),
+        Rect {
+            min: point(0, -2),
+            max: point(4, 2),
+        }
+    );
+    assert_eq!(
+        bounds.round_in(),
+        Rect {
+            min: point(1, -1),
+            max: point(3, 2),
+        }
+    );
+    assert_eq!((bounds + vector(0.5, 0.5)).min.round(), point(1, -1));
+    assert_eq!(bounds.to_f64().round_out(), bounds.round_out());
+}
@@ -0,0 +1,104 @@
+use rusttype::*;
+
+static DEJAVU_MONO: &[u8] = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf");
+
+/// Counts partially covered pixels, the blurry ones.
+fn partial_pixels(glyph: &PositionedGlyph<'_>) -> usize {
+    let mut partial = 0;
+    glyph.draw(|_, _, v| {
+        if v > 0.1 && v < 0.9 {
+            partial += 1
+        }
+    });
+    partial
+}
+
+#[test]
+fn hinting_none_matches_unhinted() {
+    let font = Font::try_from_bytes(DEJAVU_MONO).unwrap();
+    let glyph = font.glyph('g').scaled(Scale::uniform(13.0));
+    assert_eq!(glyph.hinting(), HintingMode::None);
+    let plain = glyph.clone().positioned(point(0.3, 12.0));
+    let none = glyph.hinted(HintingMode::None).positioned(point(0.3, 12.0));
+    assert_eq!(none.pixel_bounding_box(), plain.pixel_bounding_box());
+    let mut a = vec![];
+    plain.draw(|x, y, v| a.push((x, y, v)));
+    let mut b = vec![];
+    none.draw(|x, y, v| b.push((x, y, v)));
+    assert_eq!(a, b);
+}
+
+#[test]
+fn full_hinting_sharpens() {
+    let font = Font::try_from_bytes(DEJAVU_MONO).unwrap();
+    for c in "HEmn".chars() {
+        let glyph = font.glyph(c).scaled(Scale::uniform(12.0));
+        let plain = glyph.clone().positioned(point(0.0, 12.0));
+        let hinted = glyph.clone().hinted(HintingMode::Full);
+        assert_eq!(hinted.hinting(), HintingMode::Full);
+        assert_eq!(hinted.h_metrics(), glyph.h_metrics());
+
+        // stems & extremes are on whole pixels
+        let bb = hinted.exact_bounding_box().unwrap();
+        for v in [bb.min.x, bb.min.y, bb.max.x, bb.max.y].iter() {
+            assert_eq!(v.fract(), 0.0, "{:?} {:?}", c, bb);
+        }
+        let hinted = hinted.positioned(point(0.0, 12.0));
+        assert!(
+            partial_pixels(&hinted) < partial_pixels(&plain),
+            "{:?} isn't sharper",
+            c
+        );
+
+        // drawing covers the hinted bounding box
+        let bb = hinted.pixel_bounding_box().unwrap();
+        let mut max = (0, 0);
+        hinted.draw(|x, y, v| {
+            if v > 0.0 {
+                max = (max.0.max(x + 1), max.1.max(y + 1));
+            }
+        });
+        assert_eq!(max, (bb.width() as u32, bb.height() as u32));
+    }
+}
+
+#[test]
+fn light_hinting_is_vertical() {
+    let font = Font::try_from_bytes(DEJAVU_MONO).unwrap();
+    let glyph = font.glyph('x').scaled(Scale::uniform(11.0));
+    let plain = glyph.exact_bounding_box().unwrap();
+    let light = glyph.clone().hinted(HintingMode::Light);
+    let bb = light.exact_bounding_box().unwrap();
+    // the baseline & x height are on whole pixels
+    assert_eq!(bb.min.y.fract(), 0.0);
+    assert_eq!(bb.max.y.fract(), 0.0);
+    assert!((bb.min.y - plain.min.y).abs() <= 1.0);
+    // & widths mostly kept
+    assert!((bb.width() - plain.width()).abs() < 0.5);
+}
+
+#[test]
+fn hinted_transformed_and_rescaled() {
+    let font = Font::try_from_bytes(DEJAVU_MONO).unwrap();
+    let glyph = font
+        .glyph('A')
+        .scaled(Scale { x: 20.0, y: 10.0 })
+        .hinted(HintingMode::Full);
+    let bb = glyph.exact_bounding_box().unwrap();
+    let unhinted = font
+        .glyph('A')
+        .scaled(Scale { x: 20.0, y: 10.0 })
+        .exact_bounding_box()
+        .unwrap();
+    // hinted at the y scale, stretched horizontally
+    assert!((bb.width() - unhinted.width()).abs() < 2.0);
+
+    let skewed = glyph.transformed(Affine2::skew_x(0.3));
+    assert_eq!(skewed.hinting(), HintingMode::Full);
+    let skewed = skewed.positioned(point(0.0, 10.0));
+    assert!(skewed.pixel_bounding_box().unwrap().width() > bb.width() as i32);
+
+    // scaling for a device pixel ratio keeps the hinting
+    let physical = skewed.for_dpr(2.0);
+    assert_eq!(physical.unpositioned().hinting(), HintingMode::Full);
+}
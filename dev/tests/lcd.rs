@@ -0,0 +1,97 @@
+use rusttype::*;
+
+static DEJAVU_MONO: &[u8] = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf");
+
+/// The subpixel coverage of `glyph` as rows of rgb & its width.
+fn subpixel(glyph: &PositionedGlyph<'_>, layout: SubpixelLayout) -> (Vec<[f32; 3]>, usize) {
+    let bb = glyph.subpixel_pixel_bounding_box(layout).unwrap();
+    let width = bb.width() as usize;
+    let mut rgb = vec![[f32::NAN; 3]; width * bb.height() as usize];
+    glyph.draw_subpixel(layout, |x, y, v| rgb[x as usize + y as usize * width] = v);
+    (rgb, width)
+}
+
+#[test]
+fn subpixel_bounding_box() {
+    let font = Font::try_from_bytes(DEJAVU_MONO).unwrap();
+    let glyph = font
+        .glyph('a')
+        .scaled(Scale::uniform(13.0))
+        .positioned(point(2.4, 11.0));
+    let bb = glyph.pixel_bounding_box().unwrap();
+
+    let rgb = glyph
+        .subpixel_pixel_bounding_box(SubpixelLayout::Rgb)
+        .unwrap();
+    assert_eq!(rgb.min, point(bb.min.x - 1, bb.min.y));
+    assert_eq!(rgb.max, point(bb.max.x + 1, bb.max.y));
+    let vrgb = glyph
+        .subpixel_pixel_bounding_box(SubpixelLayout::VBgr)
+        .unwrap();
+    assert_eq!(vrgb.min, point(bb.min.x, bb.min.y - 1));
+    assert_eq!(vrgb.max, point(bb.max.x, bb.max.y + 1));
+
+    for &layout in [SubpixelLayout::Rgb, SubpixelLayout::VRgb].iter() {
+        let (field, _) = subpixel(&glyph, layout);
+        assert!(field.iter().flatten().all(|v| (0.0..=1.0).contains(v)));
+    }
+
+    let space = font
+        .glyph(' ')
+        .scaled(Scale::uniform(13.0))
+        .positioned(point(0.0, 0.0));
+    assert!(space
+        .subpixel_pixel_bounding_box(SubpixelLayout::Rgb)
+        .is_none());
+    space.draw_subpixel(SubpixelLayout::Rgb, |_, _, _| panic!("nothing to draw"));
+}
+
+#[test]
+fn subpixel_matches_grayscale() {
+    let font = Font::try_from_bytes(DEJAVU_MONO).unwrap();
+    let glyph = font
+        .glyph('w')
+        .scaled(Scale::uniform(16.0))
+        .positioned(point(0.3, 0.0));
+    let (rgb, width) = subpixel(&glyph, SubpixelLayout::Rgb);
+
+    // the filter preserves total coverage, 3 subpixels to a pixel
+    let mut gray = 0.0;
+    glyph.draw(|_, _, v| gray += v);
+    let total: f32 = rgb.iter().flatten().sum::<f32>() / 3.0;
+    assert!((total - gray).abs() < gray * 0.01, "{} != {}", total, gray);
+
+    // subpixels differ along the diagonal strokes
+    assert!(rgb.iter().any(|&[r, _, b]| (r - b).abs() > 0.1));
+
+    // bgr reverses the channels
+    let (bgr, _) = subpixel(&glyph, SubpixelLayout::Bgr);
+    for (&[r, g, b], &bgr) in rgb.iter().zip(&bgr) {
+        assert_eq!([b, g, r], bgr);
+    }
+
+    // coverage rises left to right across the left edge of a stroke, so the
+    // right, blue, subpixel of the first covered pixel of a row is darkest
+    let row = &rgb[width * 2..width * 3];
+    let first = row
+        .iter()
+        .position(|v| v.iter().any(|&c| c > 0.05))
+        .unwrap();
+    assert!(row[first][2] > row[first][0]);
+}
+
+#[test]
+fn subpixel_vertical() {
+    let font = Font::try_from_bytes(DEJAVU_MONO).unwrap();
+    let glyph = font
+        .glyph('o')
+        .scaled(Scale::uniform(16.0))
+        .positioned(point(0.0, 0.4));
+    let (rgb, _) = subpixel(&glyph, SubpixelLayout::VRgb);
+    let mut gray = 0.0;
+    glyph.draw(|_, _, v| gray += v);
+    let total: f32 = rgb.iter().flatten().sum::<f32>() / 3.0;
+    // curves are flattened finer at subpixel resolution, so cover a little more
+    assert!((total - gray).abs() < gray * 0.03, "{} != {}", total, gray);
+    assert!(rgb.iter().any(|&[r, _, b]| (r - b).abs() > 0.1));
+}
@@ -0,0 +1,33 @@
+use rusttype::*;
+use std::io;
+
+const DEJA_VU_MONO: &[u8] = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf");
+
+#[tokio::test]
+async fn try_from_reader_async() {
+    let font = Font::try_from_reader_async(DEJA_VU_MONO).await.unwrap();
+    assert_eq!(
+        font.glyph_count(),
+        Font::try_from_bytes(DEJA_VU_MONO).unwrap().glyph_count()
+    );
+
+    let err = Font::try_from_reader_async(&[0_u8; 12][..])
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[tokio::test]
+async fn try_from_path_async() {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/fonts/dejavu/DejaVuSansMono.ttf"
+    );
+    let font = Font::try_from_path_async(path).await.unwrap();
+    assert!(font.glyph('a').id().0 != 0);
+
+    let err = Font::try_from_path_async("does/not/exist.ttf")
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+}
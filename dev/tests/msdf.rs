@@ -0,0 +1,151 @@
+use rusttype::*;
+
+static DEJAVU_MONO: &[u8] = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf");
+
+/// The field of `glyph` as rows of rgb & its width.
+fn msdf(glyph: &PositionedGlyph<'_>, range: f32) -> (Vec<[f32; 3]>, usize) {
+    let bb = glyph.msdf_pixel_bounding_box(range).unwrap();
+    let width = bb.width() as usize;
+    let mut field = vec![[0.0; 3]; width * bb.height() as usize];
+    glyph.draw_msdf(range, |x, y, v| field[x as usize + y as usize * width] = v);
+    (field, width)
+}
+
+fn median([r, g, b]: [f32; 3]) -> f32 {
+    r.min(g).max(r.max(g).min(b))
+}
+
+/// Bilinearly samples the median of `field` at `(x, y)`, in pixels of the
+/// field, like a shader.
+fn sample(field: &[[f32; 3]], width: usize, x: f32, y: f32) -> f32 {
+    let height = field.len() / width;
+    let texel = |x: i32, y: i32| {
+        let x = x.clamp(0, width as i32 - 1) as usize;
+        let y = y.clamp(0, height as i32 - 1) as usize;
+        field[x + y * width]
+    };
+    let (x, y) = (x - 0.5, y - 0.5);
+    let (x0, y0) = (x.floor() as i32, y.floor() as i32);
+    let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+    let mut rgb = [0.0; 3];
+    for (c, v) in rgb.iter_mut().enumerate() {
+        let top = texel(x0, y0)[c] * (1.0 - fx) + texel(x0 + 1, y0)[c] * fx;
+        let bottom = texel(x0, y0 + 1)[c] * (1.0 - fx) + texel(x0 + 1, y0 + 1)[c] * fx;
+        *v = top * (1.0 - fy) + bottom * fy;
+    }
+    median(rgb)
+}
+
+#[test]
+fn msdf_bounding_box() {
+    let font = Font::try_from_bytes(DEJAVU_MONO).unwrap();
+    let glyph = font
+        .glyph('A')
+        .scaled(Scale::uniform(24.0))
+        .positioned(point(3.3, 20.0));
+    let bb = glyph.pixel_bounding_box().unwrap();
+    let msdf_bb = glyph.msdf_pixel_bounding_box(2.5).unwrap();
+    assert_eq!(msdf_bb.min, point(bb.min.x - 3, bb.min.y - 3));
+    assert_eq!(msdf_bb.max, point(bb.max.x + 3, bb.max.y + 3));
+
+    let mut calls = 0;
+    glyph.draw_msdf(2.5, |_, _, _| calls += 1);
+    assert_eq!(calls, msdf_bb.width() * msdf_bb.height());
+
+    let space = font
+        .glyph(' ')
+        .scaled(Scale::uniform(24.0))
+        .positioned(point(0.0, 0.0));
+    assert!(space.msdf_pixel_bounding_box(2.5).is_none());
+    space.draw_msdf(2.5, |_, _, _| panic!("nothing to draw"));
+}
+
+#[test]
+fn msdf_matches_coverage() {
+    let font = Font::try_from_bytes(DEJAVU_MONO).unwrap();
+    for c in "AQ&g".chars() {
+        let glyph = font
+            .glyph(c)
+            .scaled(Scale::uniform(32.0))
+            .positioned(point(0.0, 0.0));
+        let (field, width) = msdf(&glyph, 3.0);
+        let mask = CoverageMask::from_glyph(&glyph);
+        for (i, &rgb) in field.iter().enumerate() {
+            let (x, y) = ((i % width) as i32 - 3, (i / width) as i32 - 3);
+            assert_eq!(
+                median(rgb) > 0.5,
+                mask.get(x, y) >= 0.5,
+                "{:?} ({}, {})",
+                c,
+                x,
+                y
+            );
+            assert!(rgb.iter().all(|v| (0.0..=1.0).contains(v)));
+        }
+        // channels differ, where edges meet at corners
+        assert!(field.iter().any(|&[r, g, b]| r != g || g != b), "{:?}", c);
+    }
+}
+
+#[test]
+fn msdf_magnified() {
+    let font = Font::try_from_bytes(DEJAVU_MONO).unwrap();
+    let glyph = font.glyph('N');
+    let small = glyph
+        .clone()
+        .scaled(Scale::uniform(24.0))
+        .positioned(point(0.0, 0.0));
+    let large = glyph
+        .scaled(Scale::uniform(24.0 * 8.0))
+        .positioned(point(0.0, 0.0));
+    let (field, width) = msdf(&small, 2.0);
+    let small_bb = small.msdf_pixel_bounding_box(2.0).unwrap();
+    let large_bb = large.pixel_bounding_box().unwrap();
+
+    let mut mismatched = 0;
+    let mut inside = 0;
+    large.draw(|x, y, v| {
+        // the large pixel center in small pixels relative to the field
+        let fx = (large_bb.min.x as f32 + x as f32 + 0.5) / 8.0 - small_bb.min.x as f32;
+        let fy = (large_bb.min.y as f32 + y as f32 + 0.5) / 8.0 - small_bb.min.y as f32;
+        let magnified = sample(&field, width, fx, fy) > 0.5;
+        // away from anti-aliased edges
+        if !(0.01..=0.99).contains(&v) {
+            mismatched += (magnified != (v > 0.5)) as u32;
+        }
+        inside += (v > 0.5) as u32;
+    });
+    assert!(
+        mismatched * 100 < inside,
+        "{} of {} pixels mismatched",
+        mismatched,
+        inside
+    );
+}
+
+#[test]
+fn msdf_sharp_corners() {
+    // a square
+    let mut builder = StandaloneGlyph::builder(1000);
+    builder.move_to(100.0, 100.0);
+    builder.line_to(100.0, 900.0);
+    builder.line_to(900.0, 900.0);
+    builder.line_to(900.0, 100.0);
+    builder.close();
+    let icon = builder.build();
+    let glyph = icon
+        .glyph()
+        .scaled(Scale::uniform(10.0))
+        .positioned(point(0.0, 10.0));
+    let bb = glyph.msdf_pixel_bounding_box(2.0).unwrap();
+    let (field, width) = msdf(&glyph, 2.0);
+
+    // just inside & outside the top left corner, at (1, 1) in glyph pixels,
+    // where a single channel field rounds the corner off
+    let corner = (1.0 - bb.min.x as f32, 1.0 - bb.min.y as f32);
+    let at = |dx: f32, dy: f32| sample(&field, width, corner.0 + dx, corner.1 + dy);
+    assert!(at(0.15, 0.15) > 0.5);
+    assert!(at(-0.15, -0.15) < 0.5);
+    assert!(at(0.15, -0.15) < 0.5);
+    assert!(at(-0.15, 0.15) < 0.5);
+}
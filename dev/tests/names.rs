@@ -0,0 +1,88 @@
+mod common;
+
+use common::sfnt;
+use rusttype::*;
+
+static DEJAVU_MONO: &[u8] = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf");
+static OPEN_SANS_ITALIC: &[u8] = include_bytes!("../fonts/opensans/OpenSans-Italic.ttf");
+
+/// `font` with its `name` table replaced with one of `records`, each the
+/// platform, encoding, language & name ids and encoded string.
+fn with_names(font: &[u8], records: &[(u16, u16, u16, u16, &[u8])]) -> Vec<u8> {
+    let be16 = |at: usize| u16::from_be_bytes([font[at], font[at + 1]]) as usize;
+    let be32 = |at: usize| be16(at) << 16 | be16(at + 2);
+    let mut tables: Vec<([u8; 4], Vec<u8>)> = (0..be16(4))
+        .map(|i| {
+            let record = 12 + 16 * i;
+            let tag = [
+                font[record],
+                font[record + 1],
+                font[record + 2],
+                font[record + 3],
+            ];
+            let (offset, len) = (be32(record + 8), be32(record + 12));
+            (tag, font[offset..offset + len].to_vec())
+        })
+        .filter(|(tag, _)| tag != b"name")
+        .collect();
+
+    let mut name = vec![0, 0];
+    name.extend((records.len() as u16).to_be_bytes());
+    name.extend((6 + 12 * records.len() as u16).to_be_bytes());
+    let mut strings: Vec<u8> = vec![];
+    for &(platform, encoding, language, id, string) in records {
+        for v in [platform, encoding, language, id, string.len() as u16] {
+            name.extend(v.to_be_bytes());
+        }
+        name.extend((strings.len() as u16).to_be_bytes());
+        strings.extend(string);
+    }
+    name.extend(strings);
+    tables.push((*b"name", name));
+    tables.sort_by_key(|(tag, _)| *tag);
+    sfnt(&tables)
+}
+
+fn utf16(s: &str) -> Vec<u8> {
+    s.encode_utf16().flat_map(u16::to_be_bytes).collect()
+}
+
+#[test]
+fn font_names() {
+    let font = Font::try_from_bytes(OPEN_SANS_ITALIC).unwrap();
+    assert_eq!(font.family_name().as_deref(), Some("Open Sans"));
+    assert_eq!(font.style_name().as_deref(), Some("Italic"));
+    assert_eq!(font.postscript_name().as_deref(), Some("OpenSans-Italic"));
+    assert_eq!(font.name(4).as_deref(), Some("Open Sans Italic"));
+    assert_eq!(font.name(999), None);
+
+    let font = Font::try_from_bytes(DEJAVU_MONO).unwrap();
+    assert_eq!(font.family_name().as_deref(), Some("DejaVu Sans Mono"));
+}
+
+#[test]
+fn font_name_encodings() {
+    let french = utf16("Famille");
+    let ps = utf16("PS-Français");
+    let typographic = utf16("Typo ☃");
+    let data = with_names(
+        DEJAVU_MONO,
+        &[
+            (3, 1, 0x040C, 1, &french),
+            (1, 0, 0, 1, b"Caf\x8e \xd2quoted\xd3"),
+            (1, 1, 11, 2, b"\x93\xfa\x96\x7b"),
+            (3, 1, 0x040C, 6, &ps),
+            (0, 3, 0, 16, &typographic),
+        ],
+    );
+    let font = Font::try_from_bytes(&data).unwrap();
+
+    // English Mac Roman preferred over French
+    assert_eq!(font.name(1).as_deref(), Some("Café “quoted”"));
+    // typographic family preferred over the legacy family
+    assert_eq!(font.family_name().as_deref(), Some("Typo ☃"));
+    // Mac Japanese isn't decoded
+    assert_eq!(font.style_name(), None);
+    // non-English when there's no English
+    assert_eq!(font.postscript_name().as_deref(), Some("PS-Français"));
+}
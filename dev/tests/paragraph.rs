@@ -0,0 +1,516 @@
+use rusttype::*;
+
+fn font() -> Font<'static> {
+    Font::try_from_bytes(include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf") as &[u8]).unwrap()
+}
+
+/// The advance of each char of the monospace font at `scale`.
+fn advance(font: &Font<'_>, scale: Scale) -> f32 {
+    font.glyph('x').scaled(scale).h_metrics().advance_width
+}
+
+fn line_texts<'s>(paragraph: &ParagraphLayout<'_, '_, 's>) -> Vec<&'s str> {
+    paragraph
+        .lines()
+        .map(|line| paragraph.line_text(&line))
+        .collect()
+}
+
+#[test]
+fn words() {
+    let font = font();
+    let scale = Scale::uniform(20.0);
+    let w = advance(&font, scale);
+    let paragraph = font.layout_paragraph("  ab\u{a0}c  defg\n", scale, 100.0);
+
+    let words: Vec<_> = paragraph.words().collect();
+    assert_eq!(words.len(), 2);
+    assert_eq!(paragraph.word_text(&words[0]), "ab\u{a0}c");
+    assert_eq!(words[0].range, 2..7);
+    assert!((words[0].advance - 4.0 * w).abs() < 1e-3);
+    assert_eq!(paragraph.word_text(&words[1]), "defg");
+    assert!((words[1].advance - 4.0 * w).abs() < 1e-3);
+
+    let bounds = words[1].bounds.unwrap();
+    assert!(bounds.min.x >= 0.0 && bounds.max.x <= words[1].advance);
+    assert!(bounds.min.y < 0.0, "above the baseline");
+    assert_eq!(
+        font.layout_paragraph(" \n ", scale, 100.0).words().count(),
+        0
+    );
+}
+
+#[test]
+fn lines() {
+    let font = font();
+    let scale = Scale::uniform(20.0);
+    let w = advance(&font, scale);
+    let text = "aaa bb cccc dd eeeeeeeeee f";
+    let paragraph = font.layout_paragraph(text, scale, 7.5 * w);
+
+    assert_eq!(
+        line_texts(&paragraph),
+        ["aaa bb", "cccc dd", "eeeeeeeeee", "f"]
+    );
+    let lines: Vec<_> = paragraph.lines().collect();
+    assert!((lines[1].advance - 7.0 * w).abs() < 1e-3);
+    // overflowing words get their own line
+    assert!(lines[2].advance > paragraph.max_width());
+    assert!(lines[0].bounds.unwrap().max.x <= lines[0].advance);
+
+    let single = font.layout_paragraph(text, scale, f32::INFINITY);
+    assert_eq!(line_texts(&single), [text]);
+    assert_eq!(font.layout_paragraph("", scale, 10.0).lines().count(), 0);
+}
+
+#[test]
+fn newlines() {
+    let font = font();
+    let scale = Scale::uniform(20.0);
+    let paragraph = font.layout_paragraph("\nab cd\n\n\nef ", scale, 1000.0);
+    assert_eq!(line_texts(&paragraph), ["", "ab cd", "", "", "ef"]);
+
+    let lines: Vec<_> = paragraph.lines().collect();
+    assert_eq!(lines[0].range, 1..1);
+    assert_eq!(lines[2].advance, 0.0);
+    assert_eq!(lines[2].bounds, None);
+}
+
+#[test]
+fn intrinsic_widths() {
+    let font = font();
+    let scale = Scale::uniform(20.0);
+    let w = advance(&font, scale);
+    let text = "ab cdefg h\nijkl mnopqrst uv\n w";
+    let (min_content, max_content) = font.layout_paragraph(text, scale, 0.0).intrinsic_widths();
+
+    assert!((min_content - 8.0 * w).abs() < 1e-3, "mnopqrst");
+    assert!((max_content - 16.0 * w).abs() < 1e-3, "ijkl mnopqrst uv");
+
+    let widths = |max_width| {
+        let paragraph = font.layout_paragraph(text, scale, max_width);
+        let lines: Vec<_> = paragraph.lines().map(|l| l.advance).collect();
+        (lines.len(), lines.into_iter().fold(0.0, f32::max))
+    };
+    assert_eq!(widths(max_content), (3, max_content));
+    assert_eq!(widths(min_content).1, min_content);
+    assert_eq!(
+        font.layout_paragraph(" \n", scale, 0.0).intrinsic_widths(),
+        (0.0, 0.0)
+    );
+}
+
+#[test]
+fn token_breaks() {
+    let font = font();
+    let scale = Scale::uniform(20.0);
+    let w = advance(&font, scale);
+    let text = "log: https://example.com/some_path/toLongName ok";
+    let paragraph = font.layout_paragraph(text, scale, 16.5 * w);
+
+    // overflows at whitespace only
+    assert_eq!(paragraph.break_policy(), BreakPolicy::Whitespace);
+    assert_eq!(
+        line_texts(&paragraph),
+        ["log:", "https://example.com/some_path/toLongName", "ok"]
+    );
+
+    let paragraph = paragraph.with_break_policy(BreakPolicy::Tokens);
+    assert_eq!(
+        line_texts(&paragraph),
+        [
+            "log:",
+            "https://",
+            "example.com/",
+            "some_path/toLong",
+            "Name ok"
+        ]
+    );
+    assert!(paragraph.lines().all(|l| l.advance <= 16.5 * w));
+
+    let (min_content, _) = paragraph.intrinsic_widths();
+    assert!((min_content - 12.0 * w).abs() < 1e-3, "example.com/");
+    // words fitting a line aren't broken
+    let short = font
+        .layout_paragraph("a well-known fooBar", scale, 12.5 * w)
+        .with_break_policy(BreakPolicy::Tokens);
+    assert_eq!(line_texts(&short), ["a well-known", "fooBar"]);
+}
+
+#[test]
+fn positioned_lines() {
+    let font = font();
+    let scale = Scale::uniform(20.0);
+    let w = advance(&font, scale);
+    let paragraph = font.layout_paragraph("aaa bb cccc\n\nd", scale, 7.5 * w);
+    let v_metrics = font.v_metrics(scale);
+    let line_height = paragraph.line_height();
+    assert!(
+        (line_height - (v_metrics.ascent - v_metrics.descent + v_metrics.line_gap)).abs() < 1e-4
+    );
+
+    let start = point(10.0, 5.0);
+    let lines: Vec<_> = paragraph.positioned_lines(start).collect();
+    let texts: Vec<_> = lines.iter().map(|l| paragraph.line_text(&l.line)).collect();
+    assert_eq!(texts, ["aaa bb", "cccc", "", "d"]);
+    for (i, line) in lines.iter().enumerate() {
+        assert_eq!(line.line, paragraph.lines().nth(i).unwrap());
+        assert_eq!(line.v_metrics, v_metrics);
+        let baseline = start.y + v_metrics.ascent + i as f32 * line_height;
+        assert!((line.origin.y - baseline).abs() < 1e-3);
+        assert_eq!(line.origin.x, start.x);
+
+        let text = paragraph.line_text(&line.line);
+        assert_eq!(line.glyphs.len(), text.chars().count());
+        let expected: Vec<_> = font.layout(text, scale, line.origin).collect();
+        for ((glyph, expected), c) in line.glyphs.iter().zip(&expected).zip(text.chars()) {
+            assert_eq!(glyph.id(), font.glyph(c).id());
+            assert!((glyph.position().x - expected.position().x).abs() < 1e-3);
+            assert_eq!(glyph.position().y, line.origin.y);
+        }
+    }
+    assert!(lines[2].glyphs.is_empty());
+    assert_eq!(
+        font.layout_paragraph("", scale, 10.0)
+            .positioned_lines(start)
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn justification() {
+    let font = font();
+    let scale = Scale::uniform(20.0);
+    let w = advance(&font, scale);
+    let text = "aa b cc dd\nee ff gg";
+    let paragraph = font.layout_paragraph(text, scale, 8.5 * w);
+    assert_eq!(paragraph.justification(), Justification::None);
+    let ragged: Vec<_> = paragraph.positioned_lines(point(0.0, 0.0)).collect();
+
+    // Latin text has no joins to elongate
+    for justification in [Justification::Spaces, Justification::Kashida] {
+        let paragraph = paragraph.clone().with_justification(justification);
+        let lines: Vec<_> = paragraph.positioned_lines(point(0.0, 0.0)).collect();
+        let texts: Vec<_> = lines.iter().map(|l| paragraph.line_text(&l.line)).collect();
+        assert_eq!(texts, ["aa b cc", "dd", "ee ff gg"]);
+        // the spaces of the wrapped line share the slack
+        let x: Vec<_> = lines[0].glyphs.iter().map(|g| g.position().x).collect();
+        let expected = [0.0, 1.0, 2.0, 3.75, 4.75, 6.5, 7.5];
+        for (x, expected) in x.iter().zip(&expected) {
+            assert!((x - expected * w).abs() < 1e-3, "{} {}", x, expected);
+        }
+        // lines ending at a newline or the paragraph are ragged
+        for (line, ragged) in lines.iter().zip(&ragged).skip(1) {
+            let x = |l: &PositionedLine<'_>| -> Vec<_> {
+                l.glyphs.iter().map(|g| g.position().x).collect()
+            };
+            assert_eq!(x(line), x(ragged));
+        }
+        assert!(lines.iter().all(|l| l.kashidas.is_empty()));
+    }
+
+    // unshaped letters have no joins, so only stretch spaces
+    let text = "كتب الطالب درسه";
+    let scale = Scale::uniform(24.0);
+    let width = 160.0;
+    let paragraph = font
+        .layout_paragraph(text, scale, width)
+        .with_justification(Justification::Kashida);
+    let unshaped = paragraph.positioned_lines(point(0.0, 0.0)).next().unwrap();
+    assert!(unshaped.kashidas.is_empty());
+
+    // elongates the 4 joins of the pre-shaped first line with tatweels, then
+    // spaces by the remainder
+    let text = "\u{feea}\u{feb3}\u{fead}\u{fea9} \u{fe90}\u{fedf}\u{fe8e}\u{fec4}\u{fedf}\u{fe8d} \
+                \u{fe90}\u{fe98}\u{fedb}";
+    let paragraph = font
+        .layout_paragraph(text, scale, width)
+        .with_justification(Justification::Kashida);
+    let lines: Vec<_> = paragraph.positioned_lines(point(0.0, 0.0)).collect();
+    assert_eq!(lines.len(), 2);
+    let line = &lines[0];
+    let line_text = paragraph.line_text(&line.line);
+    assert_eq!(line.glyphs.len(), line_text.chars().count());
+    let tatweel = font.glyph('\u{640}').scaled(scale);
+    let tatweel_advance = tatweel.h_metrics().advance_width;
+    let slack = width - line.line.advance;
+    assert_eq!(line.kashidas.len(), (slack / tatweel_advance) as usize);
+    assert!(!line.kashidas.is_empty());
+    assert!(line.kashidas.iter().all(|g| g.id() == tatweel.id()));
+    let last = line.glyphs.last().unwrap();
+    let end = last.position().x + last.unpositioned().h_metrics().advance_width;
+    assert!((end - width).abs() < 1e-3, "{}", end);
+    assert!(lines[1].kashidas.is_empty());
+
+    // only stretching spaces
+    let paragraph = font
+        .layout_paragraph(text, scale, width)
+        .with_justification(Justification::Spaces);
+    let spaces = paragraph.positioned_lines(point(0.0, 0.0)).next().unwrap();
+    assert!(spaces.kashidas.is_empty());
+    let last = spaces.glyphs.last().unwrap();
+    let end = last.position().x + last.unpositioned().h_metrics().advance_width;
+    assert!((end - width).abs() < 1e-3, "{}", end);
+}
+
+#[test]
+fn drop_cap() {
+    let font = font();
+    let scale = Scale::uniform(20.0);
+    let w = advance(&font, scale);
+    let text = "Abc de fgh ij kl mn op";
+    let paragraph = font.layout_paragraph(text, scale, 10.0 * w);
+    let start = point(10.0, 5.0);
+    let drop_cap = paragraph.drop_cap(2, 3.0, start).unwrap();
+
+    // on the second baseline, its ink at the margin
+    let cap = &drop_cap.cap;
+    assert_eq!(cap.id(), font.glyph('A').id());
+    let bb = cap.unpositioned().exact_bounding_box().unwrap();
+    assert!((cap.position().x + bb.min.x - start.x).abs() < 1e-3);
+    let baseline = start.y + font.v_metrics(scale).ascent + paragraph.line_height();
+    assert!((cap.position().y - baseline).abs() < 1e-3);
+    assert!(bb.height() > paragraph.line_height());
+
+    let lines: Vec<_> = drop_cap.lines.clone().collect();
+    let texts: Vec<_> = lines.iter().map(|l| paragraph.line_text(&l.line)).collect();
+    let indent = bb.width() + 3.0;
+    assert!(indent > 2.0 * w && indent < 3.0 * w, "{}", indent);
+    // 7 chars beside the cap, 10 after
+    assert_eq!(texts, ["bc de", "fgh ij", "kl mn op"]);
+    for (i, line) in lines.iter().enumerate() {
+        let x = if i < 2 { start.x + indent } else { start.x };
+        assert!((line.origin.x - x).abs() < 1e-3);
+        assert!((line.glyphs[0].position().x - x).abs() < 1e-3);
+        let y = start.y + font.v_metrics(scale).ascent + i as f32 * paragraph.line_height();
+        assert!((line.origin.y - y).abs() < 1e-3);
+    }
+
+    // justified between the cap & the margin
+    let justified = paragraph.clone().with_justification(Justification::Spaces);
+    let lines: Vec<_> = justified.drop_cap(2, 3.0, start).unwrap().lines.collect();
+    for line in &lines[..2] {
+        let last = line.glyphs.last().unwrap();
+        let end = last.position().x + last.unpositioned().h_metrics().advance_width;
+        assert!((end - (start.x + 10.0 * w)).abs() < 1e-3, "{}", end);
+    }
+
+    assert!(font
+        .layout_paragraph(" \n ", scale, 10.0 * w)
+        .drop_cap(2, 3.0, start)
+        .is_none());
+}
+
+#[test]
+fn hanging_punctuation() {
+    let font = font();
+    let scale = Scale::uniform(20.0);
+    let w = advance(&font, scale);
+    let text = "\"aa bb, cc dd.\" ee\n\"ff";
+    let paragraph = font.layout_paragraph(text, scale, 5.5 * w);
+    assert!(!paragraph.hanging_punctuation());
+    assert_eq!(
+        line_texts(&paragraph),
+        ["\"aa", "bb,", "cc", "dd.\"", "ee", "\"ff"]
+    );
+    assert!(paragraph.lines().all(|l| l.hanging == (0.0, 0.0)));
+
+    let paragraph = paragraph.with_hanging_punctuation(true);
+    assert_eq!(
+        line_texts(&paragraph),
+        ["\"aa bb,", "cc dd.\"", "ee", "\"ff"]
+    );
+    let hanging: Vec<_> = paragraph.lines().map(|l| l.hanging).collect();
+    for (hanging, expected) in hanging
+        .iter()
+        .zip(&[(1.0, 1.0), (0.0, 2.0), (0.0, 0.0), (1.0, 0.0)])
+    {
+        assert!((hanging.0 - expected.0 * w).abs() < 1e-3, "{:?}", hanging);
+        assert!((hanging.1 - expected.1 * w).abs() < 1e-3, "{:?}", hanging);
+    }
+
+    // hanging into the margins, justified between them
+    let start = point(10.0, 5.0);
+    let lines: Vec<_> = paragraph
+        .clone()
+        .with_justification(Justification::Spaces)
+        .positioned_lines(start)
+        .collect();
+    let first = &lines[0];
+    assert!((first.glyphs[0].position().x - (start.x - w)).abs() < 1e-3);
+    assert!((first.glyphs[1].position().x - start.x).abs() < 1e-3);
+    let comma = first.glyphs.last().unwrap();
+    assert!((comma.position().x - (start.x + 5.5 * w)).abs() < 1e-3);
+    let period = &lines[1].glyphs[5];
+    assert!((period.position().x - (start.x + 5.5 * w)).abs() < 1e-3);
+    assert!((lines[3].glyphs[1].position().x - start.x).abs() < 1e-3);
+
+    // punctuation alone doesn't hang
+    let paragraph = font
+        .layout_paragraph("\"\" ab", scale, w)
+        .with_hanging_punctuation(true);
+    let hanging: Vec<_> = paragraph.lines().map(|l| l.hanging).collect();
+    assert_eq!(hanging, [(0.0, 0.0), (0.0, 0.0)]);
+}
+
+#[test]
+fn decorations() {
+    let font = font();
+    let scale = Scale::uniform(20.0);
+    let w = advance(&font, scale);
+    let underline = font.decoration_metrics(Decoration::Underline, scale);
+    assert!(underline.offset > 0.0 && underline.thickness > 0.0);
+    let strikethrough = font.decoration_metrics(Decoration::Strikethrough, scale);
+    assert!(strikethrough.offset < 0.0 && strikethrough.thickness > 0.0);
+
+    let text = "aaa bb cccc dd";
+    let paragraph = font.layout_paragraph(text, scale, 7.5 * w);
+    let styles = [
+        // adjacent ranges joined, the trailing space not decorated
+        (2..4, Decoration::Underline),
+        (4..5, Decoration::Underline),
+        // wrapped across lines
+        (8..13, Decoration::Strikethrough),
+    ];
+    let lines: Vec<_> = paragraph.positioned_lines(point(3.0, 0.0)).collect();
+    assert_eq!(lines.len(), 2);
+
+    let first = paragraph.decorations(&lines[0], &styles);
+    assert_eq!(first.len(), 1);
+    assert_eq!(first[0].decoration, Decoration::Underline);
+    assert_eq!(&text[first[0].range.clone()], "a b");
+    let rect = first[0].rect;
+    assert!((rect.min.x - (3.0 + 2.0 * w)).abs() < 1e-3);
+    assert!((rect.max.x - (3.0 + 5.0 * w)).abs() < 1e-3);
+    assert!((rect.min.y - (lines[0].origin.y + underline.offset)).abs() < 1e-4);
+    assert!((rect.height() - underline.thickness).abs() < 1e-4);
+
+    // the whitespace at the break isn't in a line, so isn't decorated
+    assert!(paragraph
+        .decorations(&lines[0], &[(6..13, Decoration::Strikethrough)])
+        .is_empty());
+    let second = paragraph.decorations(&lines[1], &styles);
+    assert_eq!(second.len(), 1);
+    assert_eq!(&text[second[0].range.clone()], "ccc d");
+    assert!((second[0].rect.min.x - (3.0 + w)).abs() < 1e-3);
+    assert!(second[0].rect.max.y < lines[1].origin.y);
+
+    assert!(paragraph.decorations(&lines[1], &[]).is_empty());
+}
+
+#[test]
+fn skip_ink() {
+    let font = font();
+    let scale = Scale::uniform(40.0);
+    let w = advance(&font, scale);
+    let text = "ana gyp";
+    let paragraph = font.layout_paragraph(text, scale, 1000.0);
+    let line = paragraph.positioned_lines(point(2.0, 0.0)).next().unwrap();
+    let styles = [(0..7, Decoration::Underline)];
+    let underline = paragraph.decorations(&line, &styles).remove(0);
+    let rect = underline.rect;
+
+    let parts = underline.skip_ink(&line.glyphs, 1.0);
+    assert!(parts.len() >= 4, "{:?}", parts);
+    // not cut before the descenders, whose gaps are under their glyphs
+    assert_eq!(parts[0].min, rect.min);
+    assert!(parts[0].max.x > 2.0 + 4.0 * w);
+    assert_eq!(parts.last().unwrap().max, rect.max);
+    for pair in parts.windows(2) {
+        assert!(pair[0].max.x < pair[1].min.x, "{:?}", parts);
+    }
+    for part in &parts {
+        assert!(part.width() > 0.0);
+        assert_eq!((part.min.y, part.max.y), (rect.min.y, rect.max.y));
+    }
+
+    // no rendered ink within the parts
+    for glyph in &line.glyphs[4..] {
+        let bb = glyph.pixel_bounding_box().unwrap();
+        glyph.draw(|x, y, v| {
+            let (px, py) = ((bb.min.x + x as i32) as f32, (bb.min.y + y as i32) as f32);
+            for part in &parts {
+                let inside_x = px + 1.0 > part.min.x && px < part.max.x;
+                let inside_y = py + 1.0 > part.min.y && py < part.max.y;
+                assert!(!(inside_x && inside_y) || v < 0.5, "{} {} {}", px, py, v);
+            }
+        });
+    }
+
+    // more clearance, wider gaps
+    let wide = underline.skip_ink(&line.glyphs, 3.0);
+    let total = |parts: &[Rect<f32>]| parts.iter().map(|r| r.width()).sum::<f32>();
+    assert!(total(&wide) < total(&parts));
+
+    // nothing to skip
+    let plain = paragraph.decorations(&line, &[(0..3, Decoration::Underline)]);
+    assert_eq!(plain[0].skip_ink(&line.glyphs, 1.0), [plain[0].rect]);
+    assert_eq!(underline.skip_ink(&[], 1.0), [rect]);
+}
+
+#[test]
+fn wavy_underline() {
+    let font = font();
+    let scale = Scale::uniform(40.0);
+    let text = "wavy line";
+    let paragraph = font.layout_paragraph(text, scale, 1000.0);
+    let line = paragraph.positioned_lines(point(4.0, 0.0)).next().unwrap();
+    let mut segment = paragraph
+        .decorations(&line, &[(0..9, Decoration::Underline)])
+        .remove(0);
+    // a thicker line, from pixel boundaries, to measure
+    segment.rect.min.y = 40.0;
+    segment.rect.max.y = 42.0;
+    let rect = segment.rect;
+    let thickness = rect.height();
+
+    let mut mask = CoverageMask::new(400, 60);
+    mask.fill_wavy(&segment);
+    let ink = |x: i32, y0: i32, y1: i32| (y0..y1).map(|y| mask.get(x, y)).sum::<f32>();
+    // the first crest is above the middle, the first trough below
+    let crest = (rect.min.x + 1.5 * thickness) as i32;
+    let trough = (rect.min.x + 4.5 * thickness) as i32;
+    assert!(ink(crest, 38, 41) > 1.5 && ink(crest, 41, 44) < 0.1);
+    assert!(ink(trough, 41, 44) > 1.5 && ink(trough, 38, 41) < 0.1);
+    // about a thickness of ink in each column within the line
+    for x in rect.min.x.ceil() as i32..rect.max.x.floor() as i32 {
+        let column = ink(x, 0, 60);
+        assert!(
+            column > thickness * 0.9 && column < thickness * 1.6,
+            "{} {}",
+            x,
+            column
+        );
+    }
+    // within the wave's reach
+    for y in (0..37).chain(45..60) {
+        for x in 0..400 {
+            assert_eq!(mask.get(x, y), 0.0, "{} {}", x, y);
+        }
+    }
+    for x in (0..rect.min.x as i32 - 1).chain(rect.max.x as i32 + 2..400) {
+        assert_eq!(ink(x, 0, 60), 0.0, "{}", x);
+    }
+
+    // the outline is one closed contour
+    struct Count(i32, i32);
+    impl OutlineBuilder for Count {
+        fn move_to(&mut self, _: f32, _: f32) {
+            self.0 += 1;
+        }
+        fn line_to(&mut self, _: f32, _: f32) {}
+        fn quad_to(&mut self, _: f32, _: f32, _: f32, _: f32) {}
+        fn curve_to(&mut self, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32) {}
+        fn close(&mut self) {
+            self.1 += 1;
+        }
+    }
+    let mut count = Count(0, 0);
+    segment.build_wavy_outline(&mut count);
+    assert_eq!((count.0, count.1), (1, 1));
+    segment.rect.max.x = segment.rect.min.x;
+    let mut count = Count(0, 0);
+    segment.build_wavy_outline(&mut count);
+    assert_eq!((count.0, count.1), (0, 0));
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use common::{be16, rect, sfnt};
+use once_cell::sync::Lazy;
+use rusttype::*;
+
+/// A pixel font with 800 units per em on a grid of 100 units, so 8px per em,
+/// with `A` a 3x5 & `B` a 2x7 pixel block, both 5 pixels wide.
+static PIXEL_FONT: Lazy<Vec<u8>> = Lazy::new(|| {
+    let glyphs = [vec![], rect(100, 0, 400, 500), rect(100, 0, 300, 700)];
+
+    let mut head = vec![
+        0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0x5F, 0x0F, 0x3C, 0xF5, 0, 0,
+    ];
+    head.extend(be16(800)); // units per em
+    head.extend([0; 16]); // created & modified
+    for v in [0, 0, 400, 700, 0, 8, 2, 0, 0] {
+        head.extend(be16(v)); // bbox, style, lowest ppem, direction, short loca, format
+    }
+
+    let mut hhea = vec![0, 1, 0, 0];
+    for v in [700, -100, 0, 500, 0, 100, 400, 1, 0, 0, 0, 0, 0, 0, 0, 3] {
+        hhea.extend(be16(v));
+    }
+
+    let maxp = vec![0, 0, 0x50, 0, 0, 3]; // version 0.5, 3 glyphs
+
+    // a format 6 subtable mapping 'A' & 'B' to glyphs 1 & 2
+    let mut cmap = vec![0, 0, 0, 1, 0, 0, 0, 3, 0, 0, 0, 12];
+    for v in [6, 14, 0, 'A' as i32, 2, 1, 2] {
+        cmap.extend(be16(v));
+    }
+
+    let mut hmtx = vec![];
+    let (mut loca, mut glyf) = (be16(0).to_vec(), vec![]);
+    for glyph in &glyphs {
+        hmtx.extend(be16(500));
+        hmtx.extend(be16(100));
+        glyf.extend(glyph);
+        loca.extend(be16(glyf.len() as i32 / 2));
+    }
+
+    sfnt(&[
+        (*b"cmap", cmap),
+        (*b"glyf", glyf),
+        (*b"head", head),
+        (*b"hhea", hhea),
+        (*b"hmtx", hmtx),
+        (*b"loca", loca),
+        (*b"maxp", maxp),
+    ])
+});
+
+fn font(mode: PixelMode) -> Font<'static> {
+    let mut font = Font::try_from_bytes(&PIXEL_FONT).unwrap();
+    font.set_pixel_mode(mode);
+    font
+}
+
+fn coverage(glyph: &PositionedGlyph<'_>) -> Vec<f32> {
+    let bb = glyph.pixel_bounding_box().unwrap();
+    let mut pixels = vec![0.0; (bb.width() * bb.height()) as usize];
+    let width = bb.width() as u32;
+    glyph.draw(|x, y, v| pixels[(x + y * width) as usize] = v);
+    pixels
+}
+
+#[test]
+fn detect_pixel_grid() {
+    let font = font(PixelMode::default());
+    assert_eq!(font.pixel_grid(), Some(100));
+    assert!(font.is_pixel_rendered());
+
+    let dejavu = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf") as &[u8];
+    let dejavu = Font::try_from_bytes(dejavu).unwrap();
+    assert_eq!(dejavu.pixel_grid(), None);
+    assert!(!dejavu.is_pixel_rendered());
+}
+
+#[test]
+fn pixel_rendering() {
+    let font = font(PixelMode::Auto);
+    // 2.125px per design pixel
+    let scale = Scale::uniform(17.0);
+    let glyph = font.glyph('A').scaled(scale);
+    assert_eq!(glyph.h_metrics().advance_width, 11.0);
+    assert_eq!(font.v_metrics(scale).ascent, 15.0);
+
+    let glyph = glyph.positioned(point(0.3, 10.6));
+    assert_eq!(glyph.position(), point(0.0, 11.0));
+    let pixels = coverage(&glyph);
+    assert!(pixels.iter().all(|&v| v == 0.0 || v == 1.0));
+    assert!(pixels.contains(&1.0));
+
+    let glyphs: Vec<_> = font.layout("AB", scale, point(0.4, 0.0)).collect();
+    assert_eq!(glyphs[1].position(), point(11.0, 0.0));
+}
+
+#[test]
+fn pixel_rendering_off() {
+    let font = font(PixelMode::Off);
+    assert!(!font.is_pixel_rendered());
+
+    let glyph = font
+        .glyph('A')
+        .scaled(Scale::uniform(17.0))
+        .positioned(point(0.3, 10.6));
+    assert_eq!(glyph.position(), point(0.3, 10.6));
+    assert!(coverage(&glyph).iter().any(|&v| v > 0.0 && v < 1.0));
+}
+
+#[test]
+fn pixel_scale_warning() {
+    let font = font(PixelMode::Auto);
+    assert_eq!(font.pixel_scale_warning(Scale::uniform(16.0)), None);
+    assert_eq!(font.pixel_scale_warning(Scale::uniform(24.0)), None);
+
+    let warning = font.pixel_scale_warning(Scale::uniform(17.0)).unwrap();
+    assert_eq!(warning.nearest, Scale::uniform(16.0));
+    let warning = font.pixel_scale_warning(Scale::uniform(3.0)).unwrap();
+    assert_eq!(warning.nearest, Scale::uniform(8.0));
+    assert!(warning.to_string().contains("nearest is 8x8"));
+
+    let warning = font
+        .pixel_scale_warning(Scale { x: 16.0, y: 20.0 })
+        .unwrap();
+    assert_eq!(warning.nearest, Scale { x: 16.0, y: 24.0 });
+}
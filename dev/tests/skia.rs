@@ -0,0 +1,51 @@
+use rusttype::tiny_skia::{Paint, Pixmap};
+use rusttype::*;
+
+static DEJAVU_MONO: &[u8] = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf");
+
+#[test]
+fn tiny_skia_path() {
+    let font = Font::try_from_bytes(DEJAVU_MONO).unwrap();
+    let glyph = font.glyph('w').scaled(Scale::uniform(40.0));
+    let exact = glyph.exact_bounding_box().unwrap();
+
+    let bounds = glyph.tiny_skia_path().unwrap().bounds();
+    assert!((bounds.left() - exact.min.x).abs() < 1e-4);
+    assert!((bounds.bottom() - exact.max.y).abs() < 1e-4);
+
+    let positioned = glyph.clone().positioned(point(10.5, 30.0));
+    let bounds = positioned.tiny_skia_path().unwrap().bounds();
+    assert!((bounds.left() - exact.min.x - 10.5).abs() < 1e-4);
+    assert!((bounds.top() - exact.min.y - 30.0).abs() < 1e-4);
+
+    let space = font.glyph(' ').scaled(Scale::uniform(40.0));
+    assert!(space.tiny_skia_path().is_none());
+}
+
+#[test]
+fn fill_pixmap() {
+    let font = Font::try_from_bytes(DEJAVU_MONO).unwrap();
+    let glyph = font
+        .glyph('w')
+        .scaled(Scale::uniform(40.0))
+        .positioned(point(10.5, 30.0));
+    let bb = glyph.pixel_bounding_box().unwrap();
+
+    let mut pixmap = Pixmap::new(64, 64).unwrap();
+    glyph.fill_pixmap(&mut pixmap.as_mut(), &Paint::default());
+
+    // matches rusttype's coverage, up to anti-aliasing differences
+    let mut diff = 0.0;
+    let mut total = 0.0;
+    glyph.draw(|x, y, v| {
+        let (x, y) = (x as i32 + bb.min.x, y as i32 + bb.min.y);
+        let alpha = pixmap.pixel(x as u32, y as u32).unwrap().alpha();
+        diff += (f32::from(alpha) / 255.0 - v).abs();
+        total += v;
+    });
+    assert!(total > 100.0);
+    assert!(diff < total * 0.05, "{} / {}", diff, total);
+
+    let painted = pixmap.pixels().iter().filter(|p| p.alpha() > 0).count();
+    assert!(painted <= (bb.width() * bb.height()) as usize);
+}
@@ -0,0 +1,222 @@
+use rusttype::gpu_cache::Cache;
+use rusttype::*;
+
+/// A 500 unit square from `(50, 0)` at 1000 units per em.
+fn square() -> StandaloneGlyph {
+    let mut builder = StandaloneGlyph::builder(1000).advance_width(600);
+    builder.move_to(50.0, 0.0);
+    builder.line_to(550.0, 0.0);
+    builder.line_to(550.0, 500.0);
+    builder.line_to(50.0, 500.0);
+    builder.close();
+    builder.build()
+}
+
+#[test]
+fn standalone_glyph() {
+    let glyph = square().glyph().scaled(Scale::uniform(20.0));
+    assert_eq!(
+        glyph.h_metrics(),
+        HMetrics {
+            advance_width: 12.0,
+            left_side_bearing: 1.0
+        }
+    );
+    assert_eq!(
+        glyph.exact_bounding_box(),
+        Some(Rect {
+            min: point(1.0, -10.0),
+            max: point(11.0, 0.0)
+        })
+    );
+
+    let glyph = glyph.positioned(point(5.0, 20.0));
+    let bb = glyph.pixel_bounding_box().unwrap();
+    assert_eq!((bb.min, bb.max), (point(6, 10), point(16, 20)));
+    let mut coverage = vec![];
+    glyph.draw(|_, _, v| coverage.push(v));
+    assert_eq!(coverage, vec![1.0; 100]);
+
+    // flows through the cache like font glyphs
+    let mut cache = Cache::builder().dimensions(32, 32).build();
+    cache.queue_glyph(0, glyph.clone());
+    cache.cache_queued(|_, _| {}).unwrap();
+    assert!(cache.rect_for(0, &glyph).unwrap().is_some());
+
+    let empty = StandaloneGlyph::builder(1000).build();
+    let empty = empty.glyph().scaled(Scale::uniform(20.0));
+    assert_eq!(empty.h_metrics().advance_width, 20.0);
+    assert!(empty
+        .positioned(point(0.0, 0.0))
+        .pixel_bounding_box()
+        .is_none());
+}
+
+#[test]
+fn standalone_glyphs_cached_separately() {
+    let icon = |width: f32| {
+        let mut builder = StandaloneGlyph::builder(20);
+        builder.move_to(0.0, 0.0);
+        builder.line_to(width, 0.0);
+        builder.line_to(width, 10.0);
+        builder.line_to(0.0, 10.0);
+        builder.build()
+    };
+    let (narrow, wide) = (icon(4.0), icon(18.0));
+    let glyphs = [&narrow, &wide, &narrow.clone()].map(|icon| {
+        icon.glyph()
+            .scaled(Scale::uniform(20.0))
+            .positioned(point(0.0, 20.0))
+    });
+
+    let mut cache = Cache::builder().dimensions(64, 64).build();
+    for glyph in &glyphs {
+        cache.queue_glyph(0, glyph.clone());
+    }
+    let mut uploads = 0;
+    cache.cache_queued(|_, _| uploads += 1).unwrap();
+    assert_eq!(uploads, 2);
+    let rect = |glyph| cache.rect_for(0, glyph).unwrap().unwrap();
+    assert_ne!(rect(&glyphs[0]), rect(&glyphs[1]));
+    assert_eq!(rect(&glyphs[0]), rect(&glyphs[2]), "clones share a texture");
+}
+
+#[test]
+fn standalone_glyph_curves() {
+    // a circle of radius 400 about (500, 400) from 4 cubics
+    let k = 400.0 * 0.552_284_8;
+    let mut builder = StandaloneGlyph::builder(1000);
+    builder.move_to(900.0, 400.0);
+    builder.curve_to(900.0, 400.0 + k, 500.0 + k, 800.0, 500.0, 800.0);
+    builder.curve_to(500.0 - k, 800.0, 100.0, 400.0 + k, 100.0, 400.0);
+    builder.curve_to(100.0, 400.0 - k, 500.0 - k, 0.0, 500.0, 0.0);
+    builder.curve_to(500.0 + k, 0.0, 900.0, 400.0 - k, 900.0, 400.0);
+    builder.close();
+    let circle = builder.build();
+
+    let glyph = circle.glyph().scaled(Scale::uniform(100.0));
+    assert_eq!(glyph.complexity().cubics, 4);
+    let glyph = glyph.positioned(point(0.0, 100.0));
+    let bb = glyph.pixel_bounding_box().unwrap();
+    assert_eq!((bb.width(), bb.height()), (80, 80));
+    let mut area = 0.0;
+    glyph.draw(|_, _, v| area += v);
+    let expected = std::f32::consts::PI * 40.0 * 40.0;
+    assert!((area - expected).abs() < expected * 0.005, "{}", area);
+}
+
+#[test]
+fn standalone_glyph_fractional_units() {
+    // a pixel per unit, with the left edge halfway through a pixel
+    let mut builder = StandaloneGlyph::builder(16).v_metrics(16, 0);
+    builder.move_to(0.5, 0.0);
+    builder.line_to(8.5, 0.0);
+    builder.line_to(8.5, 8.0);
+    builder.line_to(0.5, 8.0);
+    let glyph = builder.build().glyph().scaled(Scale::uniform(16.0));
+    assert_eq!(glyph.h_metrics().left_side_bearing, 0.5);
+
+    let glyph = glyph.positioned(point(0.0, 16.0));
+    assert_eq!(glyph.pixel_bounding_box().unwrap().width(), 9);
+    let mut coverage = vec![];
+    glyph.draw(|x, y, v| {
+        if y == 0 {
+            coverage.push((x, v));
+        }
+    });
+    assert_eq!(coverage[0], (0, 0.5));
+    assert_eq!(coverage[8], (8, 0.5));
+}
+
+#[test]
+fn standalone_glyph_v_metrics() {
+    // sized like the text of a font with the same v metrics
+    let font = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf") as &[u8];
+    let font = Font::try_from_bytes(font).unwrap();
+    let v = font.v_metrics_unscaled();
+    let mut builder =
+        StandaloneGlyph::builder(font.units_per_em()).v_metrics(v.ascent as i16, v.descent as i16);
+    builder.move_to(0.0, 0.0);
+    builder.line_to(0.0, 1000.0);
+    builder.line_to(1000.0, 0.0);
+    let icon = builder.build();
+
+    let scale = Scale::uniform(24.0);
+    assert_eq!(icon.v_metrics(scale), font.v_metrics(scale));
+    let factor = font.scale_for_pixel_height(24.0);
+    let bb = icon.glyph().scaled(scale).exact_bounding_box().unwrap();
+    assert!((bb.max.x - 1000.0 * factor).abs() < 1e-4);
+}
+
+#[test]
+fn standalone_glyph_empty_v_metrics() {
+    // no height to scale by, so sized like the default v metrics
+    let mut builder = StandaloneGlyph::builder(1000).v_metrics(0, 0);
+    builder.move_to(0.0, 0.0);
+    builder.line_to(0.0, 1000.0);
+    builder.line_to(1000.0, 0.0);
+    let icon = builder.build();
+
+    let scale = Scale::uniform(20.0);
+    let v = icon.v_metrics(scale);
+    assert_eq!((v.ascent, v.descent), (20.0, 0.0));
+    let glyph = icon.glyph().scaled(scale);
+    assert_eq!(glyph.h_metrics().advance_width, 20.0);
+    let bb = glyph.exact_bounding_box().unwrap();
+    assert_eq!((bb.min.y, bb.max.y), (-20.0, 0.0));
+}
+
+#[test]
+fn standalone_glyph_outline_hash() {
+    let scale = Scale::uniform(20.0);
+    let hash = |icon: &StandaloneGlyph| icon.glyph().outline_hash(scale, vector(0.0, 0.0));
+    let icon = square();
+    assert_eq!(hash(&icon), hash(&square()));
+
+    // the same outline scaled by different v metrics
+    let mut builder = StandaloneGlyph::builder(1000)
+        .advance_width(600)
+        .v_metrics(800, -400);
+    builder.move_to(50.0, 0.0);
+    builder.line_to(550.0, 0.0);
+    builder.line_to(550.0, 500.0);
+    builder.line_to(50.0, 500.0);
+    builder.close();
+    assert_ne!(hash(&builder.build()), hash(&icon));
+}
+
+#[test]
+fn bez_path_round_trip() {
+    let font = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf") as &[u8];
+    let font = Font::try_from_bytes(font).unwrap();
+    let scale = Scale::uniform(32.0);
+    let glyph = font.glyph('g');
+
+    let path = glyph.bez_path();
+    let complexity = glyph.clone().scaled(scale).complexity();
+    assert_eq!(
+        path.elements().len(),
+        complexity.points - complexity.quads + complexity.contours,
+        "an element per segment, move & close"
+    );
+    let scaled = glyph.clone().scaled(scale).bez_path();
+    let factor = f64::from(font.scale_for_pixel_height(32.0));
+    let p = path.elements()[0].end_point().unwrap();
+    let q = scaled.elements()[0].end_point().unwrap();
+    assert!((p.x * factor - q.x).abs() < 1e-4);
+    assert!((p.y * factor + q.y).abs() < 1e-4, "y down");
+
+    // back to a glyph drawn like the font glyph
+    let v = font.v_metrics_unscaled();
+    let standalone = StandaloneGlyph::builder(font.units_per_em())
+        .v_metrics(v.ascent as i16, v.descent as i16)
+        .bez_path(&path)
+        .build();
+    let draw = |glyph: Glyph<'static>| {
+        let glyph = glyph.scaled(scale).positioned(point(0.3, 32.0));
+        let mut coverage = vec![];
+        glyph.draw(|_, _, v| coverage.push(v));
+        (glyph.pixel_bounding_box(), coverage)
+    };
+    assert_eq!(draw(standalone.glyph()), draw(glyph));
+}
@@ -0,0 +1,259 @@
+use rusttype::*;
+
+const DEJA_VU_MONO: &[u8] = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf");
+const OPEN_SANS_ITALIC: &[u8] = include_bytes!("../fonts/opensans/OpenSans-Italic.ttf");
+
+fn fonts_dir() -> String {
+    concat!(env!("CARGO_MANIFEST_DIR"), "/fonts").into()
+}
+
+#[test]
+fn load_keys_from_font_tables() {
+    let mut store = FontStore::new();
+    let mono = store.load(DEJA_VU_MONO.to_vec()).unwrap();
+    let italic = store.load(OPEN_SANS_ITALIC.to_vec()).unwrap();
+    assert_eq!(store.len(), 2);
+
+    let key = store.key(italic).unwrap();
+    assert_eq!(key.family, "Open Sans");
+    assert_eq!(key.weight, Weight::NORMAL);
+    assert_eq!(key.style, Style::Italic);
+    assert_eq!(store.find(key), Some(italic));
+    assert_eq!(store.key(mono).unwrap().family, "DejaVu Sans Mono");
+
+    assert!(store.load(vec![0; 64]).is_none());
+}
+
+#[test]
+fn deduplicates_identical_data() {
+    let mut store = FontStore::new();
+    let a = store.load(DEJA_VU_MONO.to_vec()).unwrap();
+    let b = store.load(DEJA_VU_MONO.to_vec()).unwrap();
+    assert_eq!(a, b);
+    assert_eq!(store.len(), 1);
+}
+
+#[test]
+fn unload_invalidates_handles() {
+    let mut store = FontStore::new();
+    let mono = store.load(DEJA_VU_MONO.to_vec()).unwrap();
+    let key = store.key(mono).unwrap().clone();
+    let font = store.unload(mono).unwrap();
+    assert!(font.glyph('a').id().0 != 0, "unloaded font still usable");
+
+    assert!(store.is_empty());
+    assert!(store.get(mono).is_none());
+    assert!(store.find(&key).is_none());
+    assert!(store.unload(mono).is_none());
+
+    // the slot is reused, the old handle stays invalid
+    let italic = store.load(OPEN_SANS_ITALIC.to_vec()).unwrap();
+    assert_ne!(italic, mono);
+    assert!(store.get(mono).is_none());
+    assert!(store.contains(italic));
+}
+
+#[test]
+fn load_as_replaces_same_key() {
+    let key = FontKey {
+        family: "UI".into(),
+        weight: Weight::BOLD,
+        style: Style::Normal,
+    };
+    let mut store = FontStore::new();
+    let first = store.load_as(key.clone(), DEJA_VU_MONO.to_vec()).unwrap();
+    let second = store
+        .load_as(key.clone(), OPEN_SANS_ITALIC.to_vec())
+        .unwrap();
+    assert!(!store.contains(first));
+    assert_eq!(store.find(&key), Some(second));
+    assert_eq!(store.len(), 1);
+}
+
+#[test]
+fn deduplicates_by_key() {
+    let key = |family: &str| FontKey {
+        family: family.into(),
+        ..FontKey::default()
+    };
+    let mut store = FontStore::new();
+    let ui = store.load_as(key("UI"), DEJA_VU_MONO.to_vec()).unwrap();
+    let code = store.load_as(key("Code"), DEJA_VU_MONO.to_vec()).unwrap();
+    assert_ne!(ui, code);
+    assert_eq!(store.find(&key("UI")), Some(ui));
+    assert_eq!(store.find(&key("Code")), Some(code));
+    assert_eq!(store.load_as(key("UI"), DEJA_VU_MONO.to_vec()), Some(ui));
+
+    // keyed by the font's own tables
+    let mono = store.load(DEJA_VU_MONO.to_vec()).unwrap();
+    assert_ne!(mono, ui);
+    assert_eq!(store.key(mono).unwrap().family, "DejaVu Sans Mono");
+    assert_eq!(store.len(), 3);
+}
+
+#[test]
+fn load_dir() {
+    let mut store = FontStore::new();
+    // only top level files, no sub-dirs
+    let handles = store.load_dir(fonts_dir()).unwrap();
+    assert_eq!(handles.len(), 3);
+    // Exo2-Light.otf is replaced by Exo2-Light.ttf, having the same key
+    assert_eq!(store.iter().count(), 2);
+    assert!(!store.contains(handles[0]));
+
+    let families: Vec<_> = store.iter().map(|(_, key, _)| key.family.clone()).collect();
+    assert!(families.contains(&"Roboto".to_string()), "{:?}", families);
+}
+
+#[tokio::test]
+async fn load_dir_async() {
+    let mut store = FontStore::new();
+    let handles = store
+        .load_dir_async(format!("{}/dejavu", fonts_dir()))
+        .await
+        .unwrap();
+    assert_eq!(handles.len(), 1);
+    assert_eq!(store.key(handles[0]).unwrap().family, "DejaVu Sans Mono");
+
+    // already loaded fonts are deduplicated
+    let reloaded = store
+        .load_dir_async(format!("{}/dejavu", fonts_dir()))
+        .await
+        .unwrap();
+    assert_eq!(reloaded, handles);
+    assert_eq!(store.len(), 1);
+}
+
+#[test]
+fn query_loaded_fonts_css_matching() {
+    let mut store = FontStore::new();
+    // no system fonts
+    store.set_database(fontdb::Database::new());
+
+    let key = |weight, style| FontKey {
+        family: "UI".into(),
+        weight,
+        style,
+    };
+    let light = store
+        .load_as(key(Weight::LIGHT, Style::Normal), DEJA_VU_MONO.to_vec())
+        .unwrap();
+    let italic = store
+        .load_as(key(Weight::BOLD, Style::Italic), OPEN_SANS_ITALIC.to_vec())
+        .unwrap();
+
+    assert_eq!(
+        store.query("ui", Weight::NORMAL, Style::Normal),
+        Some(light)
+    );
+    assert_eq!(
+        store.query("UI", Weight::BLACK, Style::Normal),
+        Some(light),
+        "style first"
+    );
+    assert_eq!(
+        store.query("UI", Weight::THIN, Style::Oblique),
+        Some(italic)
+    );
+    assert_eq!(store.query("Other", Weight::NORMAL, Style::Normal), None);
+}
+
+#[test]
+fn query_database() {
+    let mut database = fontdb::Database::new();
+    database.load_fonts_dir(fonts_dir());
+
+    let mut store = FontStore::new();
+    store.set_database(database);
+
+    let handle = store
+        .query("Open Sans", Weight::BOLD, Style::Italic)
+        .unwrap();
+    assert_eq!(store.key(handle).unwrap().style, Style::Italic);
+    assert_eq!(store.len(), 1);
+
+    // the loaded font is reused
+    assert_eq!(
+        store.query("Open Sans", Weight::NORMAL, Style::Italic),
+        Some(handle)
+    );
+    assert_eq!(store.len(), 1);
+
+    // unloaded fonts are loaded again
+    store.unload(handle);
+    let reloaded = store
+        .query("Open Sans", Weight::BOLD, Style::Italic)
+        .unwrap();
+    assert!(store.contains(reloaded));
+    assert_ne!(reloaded, handle);
+    let handle = reloaded;
+
+    let mono = store
+        .query("DejaVu Sans Mono", Weight::NORMAL, Style::Normal)
+        .unwrap();
+    assert_ne!(mono, handle);
+    assert!(store
+        .query("Not A Font", Weight::NORMAL, Style::Normal)
+        .is_none());
+}
+
+#[test]
+fn font_query_parse() {
+    let query = FontQuery::parse("bold 14px 'Fira Sans', sans-serif").unwrap();
+    assert_eq!(query.weight, Weight::BOLD);
+    assert_eq!(query.style, Style::Normal);
+    assert_eq!(query.size, Some(14.0));
+    assert_eq!(query.line_height, None);
+    assert_eq!(
+        query.families,
+        vec![FontFamily::Name("Fira Sans".into()), FontFamily::SansSerif]
+    );
+
+    let query =
+        FontQuery::parse("oblique small-caps 300 12pt/18px  Open   Sans,MONOSPACE").unwrap();
+    assert_eq!(query.style, Style::Oblique);
+    assert_eq!(query.weight, Weight(300));
+    assert_eq!(query.size, Some(16.0));
+    assert_eq!(query.line_height, Some(LineHeight::Pixels(18.0)));
+    assert_eq!(
+        query.families,
+        vec![FontFamily::Name("Open Sans".into()), FontFamily::Monospace]
+    );
+
+    let query = FontQuery::parse("\"DejaVu Sans Mono\"").unwrap();
+    assert_eq!(query.size, None);
+    assert_eq!(query.scale(), None);
+    assert_eq!(
+        FontQuery::parse("1em serif").unwrap_err(),
+        FontQueryError::InvalidSize
+    );
+    assert_eq!(
+        FontQuery::parse("bold 14px").unwrap_err(),
+        FontQueryError::MissingFamily
+    );
+    assert_eq!(
+        FontQuery::parse("14px 'Fira").unwrap_err(),
+        FontQueryError::UnterminatedQuote
+    );
+    assert_eq!(
+        FontQuery::parse("14px a,,b").unwrap_err(),
+        FontQueryError::EmptyFamily
+    );
+}
+
+#[test]
+fn resolve_falls_back_through_families() {
+    let mut store = FontStore::new();
+    store.set_database(fontdb::Database::new());
+    let mono = store.load(DEJA_VU_MONO.to_vec()).unwrap();
+    let sans = store.load(OPEN_SANS_ITALIC.to_vec()).unwrap();
+
+    let resolve = |store: &mut FontStore, s| store.resolve(&FontQuery::parse(s).unwrap());
+    assert_eq!(
+        resolve(&mut store, "12px 'Missing', 'Open Sans'"),
+        Some(sans)
+    );
+    assert_eq!(resolve(&mut store, "12px Missing, monospace"), Some(mono));
+    assert_eq!(resolve(&mut store, "12px Missing, sans-serif"), Some(sans));
+    assert_eq!(resolve(&mut store, "12px Missing, serif"), None);
+}
@@ -0,0 +1,112 @@
+use once_cell::sync::Lazy;
+use rusttype::*;
+use std::convert::TryInto;
+
+const SVG_A: &[u8] =
+    br#"<svg xmlns="http://www.w3.org/2000/svg"><path id="glyph36" d="M0 0h500v-500z"/></svg>"#;
+const SVG_B: &[u8] =
+    br#"<svg xmlns="http://www.w3.org/2000/svg"><path id="glyph37" d="M0 0h500v-700z"/></svg>"#;
+
+/// DejaVu Sans Mono with an `SVG ` table of a plain document for `A` & a
+/// gzipped one for `B`.
+static SVG_FONT: Lazy<Vec<u8>> = Lazy::new(|| {
+    let data = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf");
+    let font = Font::try_from_bytes(data).unwrap();
+    let (a, b) = (font.glyph('A').id().0, font.glyph('B').id().0);
+
+    // a stored deflate block, checksums aren't checked
+    let mut gzip = vec![0x1F, 0x8B, 8, 0, 0, 0, 0, 0, 0, 0xFF, 1];
+    gzip.extend((SVG_B.len() as u16).to_le_bytes());
+    gzip.extend((!(SVG_B.len() as u16)).to_le_bytes());
+    gzip.extend(SVG_B);
+    gzip.extend([0; 8]);
+
+    let mut svg = vec![0, 0, 0, 0, 0, 10, 0, 0, 0, 0]; // version, list offset, reserved
+    svg.extend(2_u16.to_be_bytes());
+    let mut offset = 2 + 2 * 12;
+    for (id, doc) in [(a, SVG_A), (b, &gzip[..])] {
+        svg.extend(id.to_be_bytes());
+        svg.extend(id.to_be_bytes());
+        svg.extend((offset as u32).to_be_bytes());
+        svg.extend((doc.len() as u32).to_be_bytes());
+        offset += doc.len();
+    }
+    svg.extend(SVG_A);
+    svg.extend(&gzip);
+
+    with_tables(data, &[(*b"SVG ", svg)])
+});
+
+/// Rebuilds the sfnt `data` with extra `tables`.
+fn with_tables(data: &[u8], tables: &[([u8; 4], Vec<u8>)]) -> Vec<u8> {
+    let u16_at = |i: usize| u16::from_be_bytes([data[i], data[i + 1]]) as usize;
+    let u32_at = |i: usize| u32::from_be_bytes(data[i..i + 4].try_into().unwrap()) as usize;
+
+    let mut all: Vec<([u8; 4], &[u8])> = (0..u16_at(4))
+        .map(|i| {
+            let record = 12 + 16 * i;
+            let (offset, len) = (u32_at(record + 8), u32_at(record + 12));
+            (
+                data[record..record + 4].try_into().unwrap(),
+                &data[offset..offset + len],
+            )
+        })
+        .collect();
+    all.extend(tables.iter().map(|(tag, table)| (*tag, &table[..])));
+    all.sort_by_key(|&(tag, _)| tag);
+
+    let mut out = data[..12].to_vec();
+    out[4..6].copy_from_slice(&(all.len() as u16).to_be_bytes());
+    let mut offset = 12 + 16 * all.len();
+    for (tag, table) in &all {
+        out.extend(tag);
+        out.extend([0; 4]); // checksum
+        out.extend((offset as u32).to_be_bytes());
+        out.extend((table.len() as u32).to_be_bytes());
+        offset += table.len().next_multiple_of(4);
+    }
+    for (_, table) in &all {
+        out.extend(*table);
+        out.resize(out.len().next_multiple_of(4), 0);
+    }
+    out
+}
+
+#[test]
+fn svg_document() {
+    let font = Font::try_from_bytes(&SVG_FONT).unwrap();
+    let scale = font.scale_for_pixels_per_em(20.0);
+    let glyph = font.glyph('A').scaled(scale).positioned(point(10.0, 30.0));
+
+    let svg = glyph.svg_document().unwrap();
+    assert_eq!(svg.data, SVG_A);
+    assert_eq!(svg.glyph, glyph.id());
+    assert_eq!(svg.glyphs, glyph.id()..=glyph.id());
+    assert_eq!(svg.element_id(), "glyph36");
+    // 20px per 2048 units em
+    let p = svg.transform.transform_point(point(2048.0, -1024.0));
+    assert!(
+        (p.x - 30.0).abs() < 1e-4 && (p.y - 20.0).abs() < 1e-4,
+        "{:?}",
+        p
+    );
+
+    let v_metrics = font.v_metrics(scale);
+    let advance = glyph.unpositioned().h_metrics().advance_width;
+    assert_eq!(svg.viewport.min, point(10.0, 30.0 - v_metrics.ascent));
+    assert_eq!(
+        svg.viewport.max,
+        point(10.0 + advance, 30.0 - v_metrics.descent)
+    );
+
+    // gzipped
+    let b = font.glyph('B').scaled(scale).positioned(point(0.0, 0.0));
+    assert_eq!(b.svg_document().unwrap().data, SVG_B);
+
+    assert!(font
+        .glyph('C')
+        .scaled(scale)
+        .positioned(point(0.0, 0.0))
+        .svg_document()
+        .is_none());
+}
@@ -0,0 +1,198 @@
+use rusttype::*;
+use std::convert::TryInto;
+
+const EAC_MODIFIERS: [[i32; 8]; 16] = [
+    [-3, -6, -9, -15, 2, 5, 8, 14],
+    [-3, -7, -10, -13, 2, 6, 9, 12],
+    [-2, -5, -8, -13, 1, 4, 7, 12],
+    [-2, -4, -6, -13, 1, 3, 5, 12],
+    [-3, -6, -8, -12, 2, 5, 7, 11],
+    [-3, -7, -9, -11, 2, 6, 8, 10],
+    [-4, -7, -8, -11, 3, 6, 7, 10],
+    [-3, -5, -8, -11, 2, 4, 7, 10],
+    [-2, -6, -8, -10, 1, 5, 7, 9],
+    [-2, -5, -8, -10, 1, 4, 7, 9],
+    [-2, -4, -8, -10, 1, 3, 7, 9],
+    [-2, -5, -7, -10, 1, 4, 6, 9],
+    [-3, -4, -7, -10, 2, 3, 6, 9],
+    [-1, -2, -3, -10, 0, 1, 2, 9],
+    [-4, -6, -8, -9, 3, 5, 7, 8],
+    [-3, -5, -7, -9, 2, 4, 6, 8],
+];
+
+/// Reference BC4 decoder, returns a row major block.
+fn decode_bc4(block: &[u8]) -> [u8; 16] {
+    let (r0, r1) = (block[0] as u32, block[1] as u32);
+    let mut palette = [r0, r1, 0, 0, 0, 0, 0, 0];
+    if r0 > r1 {
+        for (i, p) in palette.iter_mut().enumerate().skip(2) {
+            *p = ((8 - i as u32) * r0 + (i as u32 - 1) * r1) / 7;
+        }
+    } else {
+        for (i, p) in palette.iter_mut().enumerate().take(6).skip(2) {
+            *p = ((6 - i as u32) * r0 + (i as u32 - 1) * r1) / 5;
+        }
+        palette[7] = 255;
+    }
+    let mut bits = [0; 8];
+    bits[..6].copy_from_slice(&block[2..8]);
+    let indices = u64::from_le_bytes(bits);
+    let mut out = [0; 16];
+    for (pixel, v) in out.iter_mut().enumerate() {
+        *v = palette[(indices >> (3 * pixel) & 7) as usize] as u8;
+    }
+    out
+}
+
+/// Reference EAC R11 decoder, returns a row major block of 11 bit values.
+fn decode_eac_r11(block: &[u8]) -> [u32; 16] {
+    let bits = u64::from_be_bytes(block.try_into().unwrap());
+    let base = (bits >> 56) as i32;
+    let multiplier = (bits >> 52 & 0xF) as i32;
+    let table = EAC_MODIFIERS[(bits >> 48 & 0xF) as usize];
+    let mut out = [0; 16];
+    for pixel in 0..16 {
+        let index = (bits >> (45 - 3 * pixel) & 7) as usize;
+        let m = if multiplier == 0 {
+            table[index]
+        } else {
+            table[index] * multiplier * 8
+        };
+        // column major pixels
+        out[(pixel % 4) * 4 + pixel / 4] = (base * 8 + 4 + m).clamp(0, 2047) as u32;
+    }
+    out
+}
+
+fn glyph_r8() -> (Vec<u8>, u32, u32) {
+    let font = Font::try_from_bytes(include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf") as &[u8])
+        .unwrap();
+    let glyph = font
+        .glyph('&')
+        .scaled(Scale::uniform(30.0))
+        .positioned(point(0.0, 0.0));
+    let mask = CoverageMask::from_glyph(&glyph);
+    (
+        texture::r8_from_coverage(&mask.coverage),
+        mask.width,
+        mask.height,
+    )
+}
+
+#[test]
+fn r8_and_rgba8() {
+    assert_eq!(
+        texture::r8_from_coverage(&[0.0, 0.25, 1.0, 2.0]),
+        [0, 64, 255, 255]
+    );
+    assert_eq!(
+        texture::rgba8_from_r8(&[0, 128], false),
+        [255, 255, 255, 0, 255, 255, 255, 128]
+    );
+    assert_eq!(
+        texture::rgba8_from_r8(&[0, 128], true),
+        [0, 0, 0, 0, 128, 128, 128, 128]
+    );
+}
+
+/// Decodes `compressed` with `decode` returning the max & mean absolute error
+/// in 8 bit units compared to `r8`.
+fn compression_error<F: Fn(&[u8]) -> [f32; 16]>(
+    r8: &[u8],
+    width: u32,
+    height: u32,
+    compressed: &[u8],
+    decode: F,
+) -> (f32, f32) {
+    let blocks_x = width.div_ceil(4) as usize;
+    let (mut max, mut sum) = (0.0_f32, 0.0);
+    for y in 0..height as usize {
+        for x in 0..width as usize {
+            let block = (y / 4) * blocks_x + x / 4;
+            let decoded = decode(&compressed[block * 8..block * 8 + 8]);
+            let error = (decoded[(y % 4) * 4 + x % 4] - r8[x + y * width as usize] as f32).abs();
+            max = max.max(error);
+            sum += error;
+        }
+    }
+    (max, sum / (width * height) as f32)
+}
+
+#[test]
+fn bc4_compression() {
+    let (r8, width, height) = glyph_r8();
+    assert!(width % 4 != 0 || height % 4 != 0, "test padding");
+
+    let bc4 = texture::bc4_from_r8(&r8, width, height);
+    assert_eq!(bc4.len(), texture::compressed_len(width, height));
+    let (max, mean) = compression_error(&r8, width, height, &bc4, |block| {
+        decode_bc4(block).map(f32::from)
+    });
+    assert!(max <= 24.0, "max error {}", max);
+    assert!(mean <= 3.0, "mean error {}", mean);
+
+    // flat blocks are exact
+    let flat = texture::bc4_from_r8(&[0; 16], 4, 4);
+    assert_eq!(decode_bc4(&flat), [0; 16]);
+    let edges: Vec<u8> = (0..16).map(|i| [0, 255, 100, 120][i % 4]).collect();
+    assert_eq!(
+        decode_bc4(&texture::bc4_from_r8(&edges, 4, 4)).to_vec(),
+        edges
+    );
+}
+
+#[test]
+fn eac_r11_compression() {
+    let (r8, width, height) = glyph_r8();
+
+    let eac = texture::eac_r11_from_r8(&r8, width, height);
+    assert_eq!(eac.len(), texture::compressed_len(width, height));
+    let (max, mean) = compression_error(&r8, width, height, &eac, |block| {
+        decode_eac_r11(block).map(|v| v as f32 * 255.0 / 2047.0)
+    });
+    assert!(max <= 40.0, "max error {}", max);
+    assert!(mean <= 4.0, "mean error {}", mean);
+
+    let flat = texture::eac_r11_from_r8(&[255; 16], 4, 4);
+    assert!(decode_eac_r11(&flat).iter().all(|&v| v >= 2040));
+    assert!(texture::eac_r11_from_r8(&[], 0, 0).is_empty());
+}
+
+#[test]
+fn mip_chain_regions_dont_bleed() {
+    // 8x4 atlas with a white 4x4 region & a black one next to it
+    let (width, height) = (8, 4);
+    let mut atlas = vec![0; width * height];
+    for y in 0..height {
+        for x in 0..4 {
+            atlas[x + y * width] = 255;
+        }
+    }
+    let regions = [
+        Rect {
+            min: point(0, 0),
+            max: point(4, 4),
+        },
+        Rect {
+            min: point(4, 0),
+            max: point(8, 4),
+        },
+    ];
+
+    let mips = texture::mip_chain(&atlas, width as u32, height as u32, &regions, 10);
+    let sizes: Vec<_> = mips.iter().map(|m| (m.width, m.height)).collect();
+    assert_eq!(sizes, [(4, 2), (2, 1), (1, 1)]);
+    assert_eq!(mips[0].data, [255, 255, 0, 0, 255, 255, 0, 0]);
+    assert_eq!(mips[1].data, [255, 0]);
+
+    // an unaligned region samples only its own pixels
+    let regions = [Rect {
+        min: point(1, 1),
+        max: point(3, 3),
+    }];
+    let mut atlas = vec![100; width * height];
+    atlas[1 + width] = 200;
+    let mips = texture::mip_chain(&atlas, width as u32, height as u32, &regions, 1);
+    assert_eq!(mips.len(), 1);
+    assert_eq!(mips[0].data, [200, 100, 0, 0, 100, 100, 0, 0]);
+}
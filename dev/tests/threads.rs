@@ -0,0 +1,76 @@
+//! `Font` & layout types are safe to share between threads & across panics.
+use once_cell::sync::Lazy;
+use rusttype::*;
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::sync::Arc;
+use std::thread;
+
+static DEJA_VU_MONO: Lazy<Font<'static>> = Lazy::new(|| {
+    Font::try_from_bytes(include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf") as &[u8]).unwrap()
+});
+
+fn assert_thread_safe<T: Send + Sync + UnwindSafe + RefUnwindSafe>() {}
+
+#[test]
+fn types_are_thread_safe() {
+    assert_thread_safe::<Font<'static>>();
+    assert_thread_safe::<Font<'_>>();
+    assert_thread_safe::<Glyph<'static>>();
+    assert_thread_safe::<ScaledGlyph<'static>>();
+    assert_thread_safe::<PositionedGlyph<'static>>();
+    assert_thread_safe::<LayoutIter<'static, 'static, 'static>>();
+    assert_thread_safe::<CharMapIter<'static>>();
+    assert_thread_safe::<ReloadToken>();
+}
+
+fn layout(font: &Font<'_>, text: &str) -> Vec<(GlyphId, Point<f32>, Vec<f32>)> {
+    font.layout(text, Scale::uniform(18.0), point(0.0, 14.0))
+        .map(|g| {
+            let mut pixels = Vec::new();
+            g.draw(|_, _, v| pixels.push(v));
+            (g.id(), g.position(), pixels)
+        })
+        .collect()
+}
+
+#[test]
+fn concurrent_layout_on_shared_font() {
+    const TEXT: &str = "The quick brown fox jumps over the lazy dog 0123456789";
+    let expected = layout(&DEJA_VU_MONO, TEXT);
+
+    // a fresh font so the lazily computed char map is raced on first use
+    let font = Arc::new(
+        Font::try_from_bytes(include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf") as &[u8])
+            .unwrap(),
+    );
+    let expected_chars = DEJA_VU_MONO.char_map().count();
+
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+            let font = Arc::clone(&font);
+            thread::spawn(move || {
+                let chars = font.char_map().count();
+                let glyphs = font.chars_for_glyph(font.glyph('a').id());
+                (chars, glyphs, layout(&font, TEXT))
+            })
+        })
+        .collect();
+
+    for t in threads {
+        let (chars, glyphs, laid_out) = t.join().unwrap();
+        assert_eq!(chars, expected_chars);
+        assert!(glyphs.contains(&'a'));
+        assert_eq!(laid_out, expected);
+    }
+}
+
+#[test]
+fn font_usable_after_panic() {
+    let font = DEJA_VU_MONO.clone();
+    let result = std::panic::catch_unwind(|| {
+        let _ = font.char_map().count();
+        panic!("request handler failed");
+    });
+    assert!(result.is_err());
+    assert_eq!(layout(&font, "ok"), layout(&DEJA_VU_MONO, "ok"));
+}
@@ -0,0 +1,226 @@
+mod common;
+
+use common::{be16, sfnt};
+use once_cell::sync::Lazy;
+use rusttype::*;
+
+/// A variable font with 800 units per em & a `wght` axis from 100 to 900,
+/// default 400, with `A` a 300x500 unit box at the default, 100 units wider
+/// with a 100 unit wider advance at 900. `AA` is kerned by `GPOS` -50 units at
+/// the default, -150 at 900.
+static VARIABLE_FONT: Lazy<Vec<u8>> = Lazy::new(|| {
+    let be32 = |v: i32| (v as u32).to_be_bytes();
+
+    let mut head = vec![
+        0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0x5F, 0x0F, 0x3C, 0xF5, 0, 0,
+    ];
+    head.extend(be16(800)); // units per em
+    head.extend([0; 16]); // created & modified
+    for v in [0, 0, 500, 500, 0, 8, 2, 0, 0] {
+        head.extend(be16(v)); // bbox, style, lowest ppem, direction, short loca, format
+    }
+
+    let mut hhea = vec![0, 1, 0, 0];
+    for v in [700, -100, 0, 500, 0, 100, 400, 1, 0, 0, 0, 0, 0, 0, 0, 2] {
+        hhea.extend(be16(v));
+    }
+
+    let maxp = vec![0, 0, 0x50, 0, 0, 2]; // version 0.5, 2 glyphs
+
+    // a format 6 subtable mapping 'A' to glyph 1
+    let mut cmap = vec![0, 0, 0, 1, 0, 0, 0, 3, 0, 0, 0, 12];
+    for v in [6, 12, 0, 'A' as i32, 1, 1] {
+        cmap.extend(be16(v));
+    }
+
+    let mut glyph = vec![];
+    for v in [1, 100, 0, 400, 500, 3, 0] {
+        glyph.extend(be16(v)); // contours, bbox, end point, no instructions
+    }
+    glyph.extend([1; 4]); // on curve points with 16 bit deltas
+    for v in [100, 0, 300, 0, 0, 500, 0, -500] {
+        glyph.extend(be16(v));
+    }
+    let mut hmtx = vec![];
+    for _ in 0..2 {
+        hmtx.extend(be16(500));
+        hmtx.extend(be16(100));
+    }
+    let mut loca = vec![];
+    for v in [0, 0, glyph.len() as i32 / 2] {
+        loca.extend(be16(v));
+    }
+
+    let mut fvar = vec![0, 1, 0, 0];
+    for v in [16, 2, 1, 20, 0, 8] {
+        fvar.extend(be16(v)); // axes offset, reserved, axis count & size, instances
+    }
+    fvar.extend(b"wght");
+    for v in [100, 400, 900] {
+        fvar.extend(be32(v << 16)); // min, default, max
+    }
+    fvar.extend([0, 0, 1, 0]); // flags, name id
+
+    // at 900 the right side & right phantom point move 100 units right
+    let mut variation = vec![];
+    for v in [1, 10, 18, 0x8000, 0x4000] {
+        variation.extend(be16(v)); // tuples, data offset, data size, embedded peak
+    }
+    variation.push(0x47); // 8 x deltas as words
+    for v in [0, 0, 100, 100, 0, 100, 0, 0] {
+        variation.extend(be16(v));
+    }
+    variation.push(0x87); // 8 zero y deltas
+    variation.push(0); // pad to 2 bytes
+    let mut gvar = vec![0, 1, 0, 0];
+    for v in [1, 0] {
+        gvar.extend(be16(v)); // axis count, shared tuples
+    }
+    gvar.extend(be32(26)); // shared tuples offset
+    gvar.extend(be16(2)); // glyph count
+    gvar.extend(be16(0)); // short offsets
+    gvar.extend(be32(26)); // variation data offset
+    for v in [0, 0, variation.len() as i32 / 2] {
+        gvar.extend(be16(v));
+    }
+    gvar.extend(variation);
+
+    // a kern feature of a class pair adjustment of every pair, the x advance
+    // varied by the delta set of the gdef item variation store
+    let mut gpos = vec![0, 1, 0, 0];
+    for v in [10, 30, 44] {
+        gpos.extend(be16(v)); // script, feature & lookup list offsets
+    }
+    gpos.extend(be16(1));
+    gpos.extend(b"DFLT");
+    for v in [8, 4, 0, 0, 0xFFFF, 1, 0] {
+        gpos.extend(be16(v)); // script & default language system of feature 0
+    }
+    gpos.extend(be16(1));
+    gpos.extend(b"kern");
+    for v in [8, 0, 1, 0] {
+        gpos.extend(be16(v)); // feature of lookup 0
+    }
+    for v in [1, 4, 2, 0, 1, 8] {
+        gpos.extend(be16(v)); // lookup list & pair adjustment lookup
+    }
+    for v in [2, 20, 0x44, 0, 26, 26, 1, 1] {
+        gpos.extend(be16(v)); // format, coverage, value formats, classes
+    }
+    for v in [-50, 30, 1, 1, 1, 2, 0, 0, 0, 0x8000] {
+        gpos.extend(be16(v)); // x advance & device, coverage, classes, device
+    }
+    let mut gdef = vec![0, 1, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    gdef.extend(be32(18)); // item variation store offset
+    gdef.extend(be16(1)); // format
+    gdef.extend(be32(12)); // region list offset
+    gdef.extend(be16(1));
+    gdef.extend(be32(22)); // variation data offset
+    for v in [1, 1, 0, 0x4000, 0x4000] {
+        gdef.extend(be16(v)); // axes, regions, a region peaking at 900
+    }
+    for v in [1, 1, 1, 0, -100] {
+        gdef.extend(be16(v)); // items, word deltas, regions, deltas
+    }
+
+    sfnt(&[
+        (*b"GDEF", gdef),
+        (*b"GPOS", gpos),
+        (*b"cmap", cmap),
+        (*b"fvar", fvar),
+        (*b"glyf", glyph),
+        (*b"gvar", gvar),
+        (*b"head", head),
+        (*b"hhea", hhea),
+        (*b"hmtx", hmtx),
+        (*b"loca", loca),
+        (*b"maxp", maxp),
+    ])
+});
+
+fn font() -> Font<'static> {
+    let mut font = Font::try_from_bytes(&VARIABLE_FONT).unwrap();
+    font.set_pixel_mode(PixelMode::Off);
+    font
+}
+
+#[test]
+fn axes() {
+    let font = font();
+    assert!(font.is_variable());
+    let axes = font.axes();
+    assert_eq!(axes.len(), 1);
+    assert_eq!(axes[0].tag, *b"wght");
+    assert_eq!(
+        (axes[0].min, axes[0].default, axes[0].max),
+        (100.0, 400.0, 900.0)
+    );
+    assert_eq!(font.variation(*b"wght"), Some(400.0));
+    assert_eq!(font.variation(*b"wdth"), None);
+
+    let dejavu = include_bytes!("../fonts/dejavu/DejaVuSansMono.ttf") as &[u8];
+    let dejavu = Font::try_from_bytes(dejavu).unwrap();
+    assert!(!dejavu.is_variable());
+    assert!(dejavu.axes().is_empty());
+}
+
+#[test]
+fn with_variations() {
+    let font = font();
+    // 12.5px per 100 units
+    let scale = Scale::uniform(100.0);
+    let width = |font: &Font<'_>| {
+        let glyph = font.glyph('A').scaled(scale);
+        let bb = glyph.exact_bounding_box().unwrap();
+        (bb.max.x - bb.min.x, glyph.h_metrics().advance_width)
+    };
+    assert_eq!(width(&font), (37.5, 62.5));
+
+    let bold = font.with_variations(&[(*b"wght", 900.0)]);
+    assert_eq!(bold.variation(*b"wght"), Some(900.0));
+    assert_eq!(width(&bold), (50.0, 75.0));
+    let glyphs: Vec<_> = bold.layout("AA", scale, point(0.0, 0.0)).collect();
+    assert_eq!(glyphs[1].position().x, 75.0 - 18.75);
+
+    // halfway, clamped & unknown axes
+    assert_eq!(
+        width(&font.with_variations(&[(*b"wght", 650.0)])),
+        (43.75, 68.75)
+    );
+    let clamped = font.with_variations(&[(*b"wght", 2000.0), (*b"wdth", 50.0)]);
+    assert_eq!(clamped.variation(*b"wght"), Some(900.0));
+    assert_eq!(width(&clamped), (50.0, 75.0));
+
+    // owned data & reloads keep variations
+    let mut owned = Font::try_from_vec(VARIABLE_FONT.clone())
+        .unwrap()
+        .with_variations(&[(*b"wght", 900.0)]);
+    owned.set_pixel_mode(PixelMode::Off);
+    assert_eq!(width(&owned), (50.0, 75.0));
+    owned.reload_from_vec(VARIABLE_FONT.clone()).unwrap();
+    assert_eq!(width(&owned), (50.0, 75.0));
+}
+
+#[test]
+fn kerning() {
+    let font = font();
+    let kerning = |font: &Font<'_>| font.pair_kerning_unscaled('A', 'A');
+    assert_eq!(kerning(&font), -50);
+    assert_eq!(kerning(&font.with_variations(&[(*b"wght", 900.0)])), -150);
+    assert_eq!(kerning(&font.with_variations(&[(*b"wght", 650.0)])), -100);
+
+    let owned = Font::try_from_vec(VARIABLE_FONT.clone()).unwrap();
+    assert_eq!(kerning(&owned.with_variations(&[(*b"wght", 900.0)])), -150);
+}
+
+#[test]
+fn unchanged_variations() {
+    let font = font();
+    let same = font.with_variations(&[(*b"wdth", 50.0)]);
+    assert_eq!(font.strong_count(), 2, "clones the font");
+    let bold = font.with_variations(&[(*b"wght", 900.0)]);
+    let same_bold = bold.with_variations(&[(*b"wght", 900.0), (*b"wdth", 50.0)]);
+    assert_eq!(bold.strong_count(), 2);
+    drop((same, same_bold));
+    assert_eq!((font.strong_count(), bold.strong_count()), (1, 1));
+}
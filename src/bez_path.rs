@@ -0,0 +1,122 @@
+//! Glyph outline conversions to & from kurbo `BezPath`s, see
+//! `ScaledGlyph::bez_path`.
+use crate::{Glyph, OutlineBuilder, ScaledGlyph, StandaloneGlyphBuilder};
+use kurbo::{BezPath, PathEl, Point};
+
+/// Collects an outline into a `BezPath`.
+struct BezPathBuilder(BezPath);
+
+impl OutlineBuilder for BezPathBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.move_to((f64::from(x), f64::from(y)));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.line_to((f64::from(x), f64::from(y)));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.0
+            .quad_to((f64::from(x1), f64::from(y1)), (f64::from(x), f64::from(y)));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.0.curve_to(
+            (f64::from(x1), f64::from(y1)),
+            (f64::from(x2), f64::from(y2)),
+            (f64::from(x), f64::from(y)),
+        );
+    }
+
+    fn close(&mut self) {
+        self.0.close_path();
+    }
+}
+
+impl Glyph<'_> {
+    /// Returns the outline of this glyph as a `BezPath` in font units, y up
+    /// with the origin on the baseline, the coordinates of the font data. Empty
+    /// for glyphs without an outline.
+    ///
+    /// Build a `StandaloneGlyph` of an edited path with
+    /// `StandaloneGlyphBuilder::bez_path`.
+    pub fn bez_path(&self) -> BezPath {
+        let mut builder = BezPathBuilder(BezPath::new());
+        self.build_unscaled_outline(&mut builder);
+        builder.0
+    }
+}
+
+impl ScaledGlyph<'_> {
+    /// Returns the outline of this glyph as a `BezPath` in pixels, y down
+    /// relative to the glyph origin, as given to `build_outline`, including
+    /// any transform.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// use kurbo::Shape;
+    ///
+    /// let path = font.glyph('a').scaled(Scale::uniform(24.0)).bez_path();
+    /// let bounds = path.bounding_box();
+    /// assert!(bounds.y0 < 0.0, "y down, so above the baseline is negative");
+    /// ```
+    pub fn bez_path(&self) -> BezPath {
+        let mut builder = BezPathBuilder(BezPath::new());
+        self.build_outline(&mut builder);
+        builder.0
+    }
+}
+
+impl StandaloneGlyphBuilder {
+    /// Appends the elements of `path`, in font units, y up, like
+    /// `Glyph::bez_path`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// use kurbo::Affine;
+    ///
+    /// // 'a' reflected horizontally
+    /// let a = font.glyph('a');
+    /// let font_units = font.scale_for_pixels_per_em(f32::from(font.units_per_em()));
+    /// let advance = a.clone().scaled(font_units).h_metrics().advance_width;
+    /// let mut path = a.bez_path();
+    /// path.apply_affine(Affine::FLIP_X.then_translate((f64::from(advance), 0.0).into()));
+    /// let mirrored = StandaloneGlyph::builder(font.units_per_em())
+    ///     .advance_width(advance as u16)
+    ///     .bez_path(&path)
+    ///     .build();
+    /// ```
+    pub fn bez_path(mut self, path: &BezPath) -> Self {
+        let f = |p: Point| (p.x as f32, p.y as f32);
+        for el in path.elements() {
+            match *el {
+                PathEl::MoveTo(p) => {
+                    let (x, y) = f(p);
+                    self.move_to(x, y);
+                }
+                PathEl::LineTo(p) => {
+                    let (x, y) = f(p);
+                    self.line_to(x, y);
+                }
+                PathEl::QuadTo(p1, p) => {
+                    let ((x1, y1), (x, y)) = (f(p1), f(p));
+                    self.quad_to(x1, y1, x, y);
+                }
+                PathEl::CurveTo(p1, p2, p) => {
+                    let ((x1, y1), (x2, y2), (x, y)) = (f(p1), f(p2), f(p));
+                    self.curve_to(x1, y1, x2, y2, x, y);
+                }
+                PathEl::ClosePath => self.close(),
+            }
+        }
+        self
+    }
+}
@@ -0,0 +1,121 @@
+//! Bidirectional text layout with the Unicode Bidirectional Algorithm, see
+//! `Font::layout_bidi`.
+use crate::{point, Font, Point, PositionedGlyph, Scale};
+use unicode_bidi::{bidi_class, BidiClass, BidiInfo};
+
+/// Mixed left-to-right & right-to-left text laid out in visual order, see
+/// `Font::layout_bidi`.
+#[derive(Clone, Debug)]
+pub struct BidiLayout<'font> {
+    /// Glyphs from left to right on the `start` baseline.
+    pub glyphs: Vec<PositionedGlyph<'font>>,
+    /// The text byte index of the character of each glyph, e.g. to map
+    /// hits & selections back to the text.
+    pub indices: Vec<usize>,
+    pub advance_width: f32,
+}
+
+/// The mirrored form of brackets & other paired characters in right-to-left
+/// runs, see Unicode's `BidiMirroring.txt`.
+fn mirrored(c: char) -> Option<char> {
+    const PAIRS: &[(char, char)] = &[
+        ('(', ')'),
+        ('<', '>'),
+        ('[', ']'),
+        ('{', '}'),
+        ('«', '»'),
+        ('‹', '›'),
+        ('⁅', '⁆'),
+        ('⁽', '⁾'),
+        ('₍', '₎'),
+        ('≤', '≥'),
+        ('⟨', '⟩'),
+        ('⟦', '⟧'),
+        ('〈', '〉'),
+        ('《', '》'),
+        ('「', '」'),
+        ('『', '』'),
+        ('【', '】'),
+    ];
+    PAIRS.iter().find_map(|&(open, close)| match c {
+        _ if c == open => Some(close),
+        _ if c == close => Some(open),
+        _ => None,
+    })
+}
+
+impl<'font> Font<'font> {
+    /// Lays out `text` in visual order with the Unicode Bidirectional
+    /// Algorithm, so mixed left-to-right & right-to-left text, e.g. Arabic
+    /// with embedded numbers or English, reads correctly. Each paragraph's
+    /// base direction comes from its first strong character.
+    ///
+    /// Right-to-left runs are reversed, keeping combining marks after their
+    /// base character, with paired brackets mirrored. Glyphs are kerned
+    /// within each run but not shaped, e.g. Arabic letters keep their
+    /// isolated forms. Like `layout`, this is a single line from `start` on
+    /// the baseline: break paragraphs into lines first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let text = "abc ابت";
+    /// let layout = font.layout_bidi(text, Scale::uniform(24.0), point(0.0, 20.0));
+    /// // the Arabic is drawn right to left, its last letter leftmost
+    /// let visual: String = layout.indices.iter().map(|&i| text[i..].chars().next().unwrap()).collect();
+    /// assert_eq!(visual, "abc تبا");
+    /// ```
+    pub fn layout_bidi(&self, text: &str, scale: Scale, start: Point<f32>) -> BidiLayout<'font> {
+        let info = BidiInfo::new(text, None);
+        let mut glyphs = Vec::new();
+        let mut indices = Vec::new();
+        let mut caret = 0.0;
+        // the visual order characters of a run & their byte indices
+        let mut run_text = String::new();
+        let mut run_indices = Vec::new();
+        for paragraph in &info.paragraphs {
+            let (levels, runs) = info.visual_runs(paragraph, paragraph.range.clone());
+            for run in runs {
+                run_text.clear();
+                run_indices.clear();
+                if levels[run.start].is_rtl() {
+                    let chars: Vec<_> = text[run.clone()].char_indices().collect();
+                    // clusters of a base & its following marks, in reverse
+                    let mut end = chars.len();
+                    while end > 0 {
+                        let mut base = end - 1;
+                        while base > 0 && bidi_class(chars[base].1) == BidiClass::NSM {
+                            base -= 1;
+                        }
+                        for &(i, c) in &chars[base..end] {
+                            run_text.push(mirrored(c).unwrap_or(c));
+                            run_indices.push(run.start + i);
+                        }
+                        end = base;
+                    }
+                } else {
+                    run_text.push_str(&text[run.clone()]);
+                    run_indices
+                        .extend(text[run.clone()].char_indices().map(|(i, _)| run.start + i));
+                }
+
+                let origin = point(start.x + caret, start.y);
+                let mut run_advance = 0.0;
+                for (glyph, after) in self.layout(&run_text, scale, origin).with_carets() {
+                    run_advance = after;
+                    glyphs.push(glyph);
+                }
+                caret += run_advance;
+                indices.extend_from_slice(&run_indices);
+            }
+        }
+        BidiLayout {
+            glyphs,
+            indices,
+            advance_width: caret,
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! Embedded bitmap glyphs, e.g. `EBDT`, `CBDT` & `sbix` strikes, see
+//! `BitmapPolicy`.
+#[cfg(all(feature = "libm-math", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{point, vector, Font, GlyphId, Point, Rect, Scale, ScaledGlyph, Vector};
+use owned_ttf_parser::{RasterGlyphImage, RasterImageFormat};
+
+/// When to draw glyphs from the embedded bitmaps of a font instead of their
+/// outlines, see `Font::set_bitmap_policy`.
+///
+/// A bitmap is only used when the font has a strike of exactly the pixels per
+/// em of the glyph scale, see `Font::scale_for_pixels_per_em`. Bitmaps are not
+/// scaled, so fall back to the outline at other scales. PNG bitmaps are not
+/// supported & colour bitmaps are drawn as their alpha coverage.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BitmapPolicy {
+    /// Always draw outlines, ignoring embedded bitmaps.
+    #[default]
+    PreferOutline,
+    /// Draw embedded bitmaps whenever a strike matches the scale.
+    PreferBitmap,
+    /// Draw embedded bitmaps at scales up to & including this many pixels per
+    /// em, outlines above it. Like FreeType, for fonts with hand tuned small
+    /// sizes.
+    BitmapBelow(u16),
+}
+
+/// The pixel format of a `GlyphImage`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GlyphImageFormat {
+    /// A PNG file, e.g. from `CBDT` & `sbix` color emoji strikes.
+    Png,
+    /// 1 bit per pixel, rows padded to whole bytes.
+    Mono,
+    /// 1 bit per pixel, rows packed without padding.
+    MonoPacked,
+    /// 2 bits per pixel grayscale coverage, rows padded to whole bytes.
+    Gray2,
+    /// 2 bits per pixel grayscale coverage, rows packed without padding.
+    Gray2Packed,
+    /// 4 bits per pixel grayscale coverage, rows padded to whole bytes.
+    Gray4,
+    /// 4 bits per pixel grayscale coverage, rows packed without padding.
+    Gray4Packed,
+    /// 1 byte per pixel grayscale coverage.
+    Gray8,
+    /// Premultiplied BGRA, 4 bytes per pixel.
+    PremulBgra32,
+}
+
+/// An embedded bitmap of a glyph, see `ScaledGlyph::embedded_image`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GlyphImage<'a> {
+    /// The image data, e.g. a PNG file or raw pixel rows from the top.
+    pub data: &'a [u8],
+    pub format: GlyphImageFormat,
+    /// Width in image pixels.
+    pub width: u16,
+    /// Height in image pixels.
+    pub height: u16,
+    /// The pixels per em of the strike the image is from.
+    pub pixels_per_em: u16,
+    /// The position of the image's top left relative to the glyph origin, in
+    /// image pixels, y down.
+    pub offset: Vector<i32>,
+    /// The factor to scale the image by to draw it at the requested glyph
+    /// scale, `1.0` for a strike of exactly the requested size.
+    pub scale: f32,
+}
+
+impl GlyphImage<'_> {
+    /// The rect covered by the image drawn at the requested glyph scale,
+    /// relative to the glyph origin.
+    pub fn rect(&self) -> Rect<f32> {
+        let min = vector(self.offset.x as f32, self.offset.y as f32) * self.scale;
+        let size = vector(f32::from(self.width), f32::from(self.height)) * self.scale;
+        Rect {
+            min: point(min.x, min.y),
+            max: point(min.x + size.x, min.y + size.y),
+        }
+    }
+
+    /// The coverage, or alpha, of image pixel `(x, y)`. `None` for PNG
+    /// images, which need decoding, & pixels outside the image.
+    pub fn coverage(&self, x: u32, y: u32) -> Option<f32> {
+        if self.format == GlyphImageFormat::Png
+            || x >= u32::from(self.width)
+            || y >= u32::from(self.height)
+        {
+            return None;
+        }
+        Some(coverage(self.data, self.format, self.width, x, y))
+    }
+}
+
+impl<'font> ScaledGlyph<'font> {
+    /// Returns the embedded bitmap of this glyph from the strike closest to
+    /// its scale, e.g. from `CBDT`, `EBDT` or `sbix` tables, preferring the
+    /// smallest strike at least as big. `None` if the font has no bitmap for
+    /// the glyph, in which case its outline should be drawn.
+    ///
+    /// Unlike drawing, see `Font::set_bitmap_policy`, this ignores the bitmap
+    /// policy & returns images of any strike & format, including PNG, for
+    /// callers drawing bitmaps themselves, e.g. scaled color emoji.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let glyph = font.glyph('a').scaled(Scale::uniform(24.0));
+    /// match glyph.embedded_image() {
+    ///     Some(image) => { /* draw image.data scaled by image.scale at image.rect() */ }
+    ///     None => glyph.positioned(point(0.0, 24.0)).draw(|x, y, v| {}),
+    /// }
+    /// ```
+    pub fn embedded_image(&self) -> Option<GlyphImage<'_>> {
+        let font = self.font()?;
+        let ppem = font.scale_for_pixel_height(self.scale().y) * f32::from(font.units_per_em());
+        let requested = ppem.round().clamp(1.0, f32::from(u16::MAX)) as u16;
+        let image = font
+            .inner()
+            .glyph_raster_image(self.id().into(), requested)?;
+        Some(GlyphImage {
+            data: image.data,
+            format: image.format.into(),
+            width: image.width,
+            height: image.height,
+            pixels_per_em: image.pixels_per_em,
+            // image offsets are y up, relative to the bottom left
+            offset: vector(
+                i32::from(image.x),
+                -i32::from(image.y) - i32::from(image.height),
+            ),
+            scale: ppem / f32::from(image.pixels_per_em.max(1)),
+        })
+    }
+}
+
+impl From<RasterImageFormat> for GlyphImageFormat {
+    fn from(format: RasterImageFormat) -> Self {
+        use RasterImageFormat::*;
+        match format {
+            PNG => GlyphImageFormat::Png,
+            BitmapMono => GlyphImageFormat::Mono,
+            BitmapMonoPacked => GlyphImageFormat::MonoPacked,
+            BitmapGray2 => GlyphImageFormat::Gray2,
+            BitmapGray2Packed => GlyphImageFormat::Gray2Packed,
+            BitmapGray4 => GlyphImageFormat::Gray4,
+            BitmapGray4Packed => GlyphImageFormat::Gray4Packed,
+            BitmapGray8 => GlyphImageFormat::Gray8,
+            BitmapPremulBgra32 => GlyphImageFormat::PremulBgra32,
+        }
+    }
+}
+
+/// Returns the bitmap of `id` that `font`'s policy selects at `scale`.
+pub(crate) fn strike<'a>(
+    font: &'a Font<'_>,
+    id: GlyphId,
+    scale: Scale,
+) -> Option<RasterGlyphImage<'a>> {
+    let max_ppem = match font.bitmap_policy() {
+        BitmapPolicy::PreferOutline => return None,
+        BitmapPolicy::PreferBitmap => u16::MAX,
+        BitmapPolicy::BitmapBelow(max) => max,
+    };
+    let upem = f32::from(font.units_per_em());
+    let ppem = (font.scale_for_pixel_height(scale.y) * upem).round();
+    let ppem_x = (font.scale_for_pixel_height(scale.x) * upem).round();
+    if ppem != ppem_x || ppem < 1.0 || ppem > f32::from(max_ppem) {
+        return None;
+    }
+
+    let image = font.inner().glyph_raster_image(id.into(), ppem as u16)?;
+    let supported = image.format != RasterImageFormat::PNG;
+    if !supported || f32::from(image.pixels_per_em) != ppem {
+        return None;
+    }
+    Some(image)
+}
+
+/// The pixel bounds of `image` drawn at `p`, snapped to the nearest pixel.
+pub(crate) fn pixel_bounds(image: &RasterGlyphImage<'_>, p: Point<f32>) -> Option<Rect<i32>> {
+    if image.width == 0 || image.height == 0 {
+        return None;
+    }
+    // image offsets are y up, relative to the bottom left
+    let min = point(
+        p.x.round() as i32 + i32::from(image.x),
+        p.y.round() as i32 - i32::from(image.y) - i32::from(image.height),
+    );
+    Some(Rect {
+        min,
+        max: point(
+            min.x + i32::from(image.width),
+            min.y + i32::from(image.height),
+        ),
+    })
+}
+
+/// Calls `o` with the coverage of each pixel of `image` in scanline order,
+/// like `PositionedGlyph::draw`.
+pub(crate) fn draw<O: FnMut(u32, u32, f32)>(image: &RasterGlyphImage<'_>, mut o: O) {
+    let width = u32::from(image.width);
+    for y in 0..u32::from(image.height) {
+        for x in 0..width {
+            o(
+                x,
+                y,
+                coverage(image.data, image.format.into(), image.width, x, y),
+            );
+        }
+    }
+}
+
+/// The coverage of pixel `(x, y)` of an image `width` pixels wide, `0.0` if
+/// missing from the data.
+fn coverage(data: &[u8], format: GlyphImageFormat, width: u16, x: u32, y: u32) -> f32 {
+    use GlyphImageFormat::*;
+
+    let width = u32::from(width);
+    let (depth, packed) = match format {
+        Mono => (1, false),
+        MonoPacked => (1, true),
+        Gray2 => (2, false),
+        Gray2Packed => (2, true),
+        Gray4 => (4, false),
+        Gray4Packed => (4, true),
+        Gray8 => (8, true),
+        PremulBgra32 => {
+            let alpha = (y * width + x) as usize * 4 + 3;
+            return data.get(alpha).map_or(0.0, |&a| f32::from(a) / 255.0);
+        }
+        Png => return 0.0,
+    };
+
+    let row_bits = if packed {
+        width * depth
+    } else {
+        (width * depth).div_ceil(8) * 8
+    };
+    let bit = y * row_bits + x * depth;
+    let byte = match data.get((bit / 8) as usize) {
+        Some(&byte) => byte,
+        None => return 0.0,
+    };
+    let max = (1 << depth) - 1;
+    let value = (u32::from(byte) >> (8 - depth - bit % 8)) & max;
+    value as f32 / max as f32
+}
@@ -0,0 +1,62 @@
+//! Italic angle & caret slope, see `Font::caret_line`.
+use crate::{point, Font, Line, Point, Scale};
+
+/// Offset of `caretSlopeRise` in the `hhea` table, followed by
+/// `caretSlopeRun`.
+const CARET_SLOPE_OFFSET: usize = 18;
+
+impl Font<'_> {
+    /// The italic angle of the font, from the `post` table, in degrees
+    /// counter-clockwise from vertical, e.g. `-12.0` for an italic leaning
+    /// right. `0.0` for upright fonts or if the font has no `post` table.
+    pub fn italic_angle(&self) -> f32 {
+        self.inner().italic_angle()
+    }
+
+    /// The slope of the caret, from the `hhea` table, as a rise & run in
+    /// font units, e.g. `(1, 0)` for an upright caret. Italic fonts
+    /// usually slope the caret to match, e.g. `(2048, 435)`. `(1, 0)` if the
+    /// font's slope is malformed.
+    pub fn caret_slope(&self) -> (i16, i16) {
+        let hhea = self
+            .raw_face()
+            .table(owned_ttf_parser::Tag::from_bytes(b"hhea"));
+        let slope = hhea
+            .and_then(|hhea| hhea.get(CARET_SLOPE_OFFSET..CARET_SLOPE_OFFSET + 4))
+            .map(|b| {
+                let rise = i16::from_be_bytes([b[0], b[1]]);
+                let run = i16::from_be_bytes([b[2], b[3]]);
+                (rise, run)
+            });
+        match slope {
+            Some((rise, run)) if rise != 0 => (rise, run),
+            _ => (1, 0),
+        }
+    }
+
+    /// The caret line at `position` on the baseline at `scale`, from the
+    /// descent to the ascent, see `v_metrics`. Sloped by `caret_slope`, so
+    /// carets in italic text lean with the glyphs, crossing the baseline at
+    /// `position`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/opensans/OpenSans-Italic.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let caret = font.caret_line(Scale::uniform(24.0), point(10.0, 30.0));
+    /// let [bottom, top] = caret.p;
+    /// assert!(top.x > bottom.x && top.y < bottom.y);
+    /// ```
+    pub fn caret_line(&self, scale: Scale, position: Point<f32>) -> Line {
+        let v_metrics = self.v_metrics(scale);
+        let (rise, run) = self.caret_slope();
+        // run per pixel of rise, scaled horizontally
+        let slope = f32::from(run) / f32::from(rise) * scale.x / scale.y;
+        let at = |height: f32| point(position.x + height * slope, position.y - height);
+        Line {
+            p: [at(v_metrics.descent), at(v_metrics.ascent)],
+        }
+    }
+}
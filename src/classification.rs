@@ -0,0 +1,102 @@
+//! Font style classification, see `Font::classification`.
+
+/// The 10 byte PANOSE classification of a font, from the OS/2 table. See
+/// `Font::panose`.
+///
+/// Each byte is a digit of a classification scheme whose meaning depends on
+/// the `family_kind`. For all digits `0` means "any" & `1` "no fit".
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Panose(pub [u8; 10]);
+
+impl Panose {
+    /// Family kind `2`, text & display Latin fonts.
+    pub const LATIN_TEXT: u8 = 2;
+    /// Family kind `3`, Latin hand written fonts.
+    pub const LATIN_HAND_WRITTEN: u8 = 3;
+    /// Family kind `4`, Latin decorative fonts.
+    pub const LATIN_DECORATIVE: u8 = 4;
+    /// Family kind `5`, Latin symbol fonts.
+    pub const LATIN_SYMBOL: u8 = 5;
+
+    /// The family kind digit, e.g. `Panose::LATIN_TEXT`.
+    #[inline]
+    pub fn family_kind(&self) -> u8 {
+        self.0[0]
+    }
+
+    /// The serif style digit of `LATIN_TEXT` fonts.
+    #[inline]
+    pub fn serif_style(&self) -> u8 {
+        self.0[1]
+    }
+
+    /// The weight digit of `LATIN_TEXT` fonts.
+    #[inline]
+    pub fn weight(&self) -> u8 {
+        self.0[2]
+    }
+
+    /// The proportion digit of `LATIN_TEXT` fonts, `9` is monospaced.
+    #[inline]
+    pub fn proportion(&self) -> u8 {
+        self.0[3]
+    }
+
+    /// Classifies from the PANOSE digits alone, `None` if they're unspecified.
+    fn classification(&self) -> Option<FontClass> {
+        match self.family_kind() {
+            Panose::LATIN_TEXT => match (self.proportion(), self.serif_style()) {
+                (9, _) => Some(FontClass::Monospace),
+                (_, 2..=10) => Some(FontClass::Serif),
+                (_, 11..=15) => Some(FontClass::SansSerif),
+                _ => None,
+            },
+            Panose::LATIN_HAND_WRITTEN => Some(FontClass::Script),
+            Panose::LATIN_DECORATIVE => Some(FontClass::Decorative),
+            Panose::LATIN_SYMBOL => Some(FontClass::Symbol),
+            _ => None,
+        }
+    }
+}
+
+/// A broad style classification of a font, useful for picking a substitute
+/// when a requested font family is missing. See `Font::classification`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FontClass {
+    /// Fonts with serifs, e.g. "Times New Roman".
+    Serif,
+    /// Fonts without serifs, e.g. "Helvetica".
+    SansSerif,
+    /// Fonts resembling hand writing or calligraphy.
+    Script,
+    /// Fonts where every glyph has the same advance width.
+    Monospace,
+    /// Ornamental display fonts.
+    Decorative,
+    /// Fonts of symbols or dingbats rather than text.
+    Symbol,
+    /// The font doesn't provide enough information to classify it.
+    Unknown,
+}
+
+impl FontClass {
+    /// Classifies a font preferring, in order, the `post` table fixed pitch
+    /// flag, the `panose` digits & the OS/2 `sFamilyClass`.
+    pub(crate) fn guess(monospaced: bool, panose: Option<Panose>, family_class: i16) -> Self {
+        if monospaced {
+            return FontClass::Monospace;
+        }
+        if let Some(class) = panose.and_then(|p| p.classification()) {
+            return class;
+        }
+        // IBM font family class, the high byte is the class
+        match family_class >> 8 {
+            1..=5 | 7 => FontClass::Serif,
+            8 => FontClass::SansSerif,
+            9 => FontClass::Decorative,
+            10 => FontClass::Script,
+            12 => FontClass::Symbol,
+            _ => FontClass::Unknown,
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Font collection (`.ttc`/`.otc`) enumeration, see `FontCollection`.
+use crate::{Error, Font};
+
+/// The fonts of font data, e.g. a font collection file, `.ttc` or `.otc`,
+/// containing multiple fonts sharing tables. A single font file is a
+/// collection of one font.
+///
+/// # Example
+///
+/// ```
+/// # use rusttype::*;
+/// # let data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+/// let collection = FontCollection::from_bytes(data).unwrap();
+/// for font in collection.into_fonts() {
+///     let font = font.unwrap();
+///     // register the font
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FontCollection<'a> {
+    data: &'a [u8],
+    count: u32,
+}
+
+impl<'a> FontCollection<'a> {
+    /// Returns the collection of `data`, an error if it's neither a font
+    /// collection nor a font.
+    pub fn from_bytes(data: &'a [u8]) -> Result<Self, Error> {
+        let count = match owned_ttf_parser::fonts_in_collection(data) {
+            Some(count) => count,
+            None => {
+                Font::from_bytes(data)?;
+                1
+            }
+        };
+        Ok(FontCollection { data, count })
+    }
+
+    /// The number of fonts in the collection.
+    #[inline]
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Loads the font at `index`, see `Font::from_bytes_and_index`.
+    pub fn font(&self, index: u32) -> Result<Font<'a>, Error> {
+        Font::from_bytes_and_index(self.data, index)
+    }
+
+    /// Returns an iterator loading each font in order.
+    #[inline]
+    pub fn into_fonts(self) -> CollectionFonts<'a> {
+        CollectionFonts {
+            collection: self,
+            index: 0,
+        }
+    }
+}
+
+/// Iterator loading the fonts of a `FontCollection`, see
+/// `FontCollection::into_fonts`.
+#[derive(Clone, Debug)]
+pub struct CollectionFonts<'a> {
+    collection: FontCollection<'a>,
+    index: u32,
+}
+
+impl<'a> Iterator for CollectionFonts<'a> {
+    type Item = Result<Font<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.collection.count {
+            return None;
+        }
+        let font = self.collection.font(self.index);
+        self.index += 1;
+        Some(font)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.collection.count - self.index) as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CollectionFonts<'_> {}
+
+impl Font<'_> {
+    /// Returns the number of fonts in font data `bytes`, e.g. of a `.ttc`
+    /// collection, for loading each with `Font::try_from_bytes_and_index`.
+    /// `1` for a single font & `0` if `bytes` isn't font data.
+    ///
+    /// See `FontCollection` to iterate over the fonts.
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// assert_eq!(Font::collection_count(data), 1);
+    /// assert_eq!(Font::collection_count(b"not a font"), 0);
+    /// ```
+    pub fn collection_count(bytes: &[u8]) -> u32 {
+        FontCollection::from_bytes(bytes).map_or(0, |c| c.count())
+    }
+}
@@ -0,0 +1,216 @@
+//! Color glyphs from `COLR` & `CPAL` tables, e.g. emoji, see
+//! `PositionedGlyph::draw_color`.
+use crate::{Font, Glyph, GlyphId, PositionedGlyph, Rect, ScaledGlyph};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use owned_ttf_parser::colr;
+
+/// A layer of a color glyph, a glyph filled with a single color, see
+/// `PositionedGlyph::color_layers`.
+#[derive(Clone, Debug)]
+pub struct ColorLayer<'font> {
+    /// The layer glyph, positioned & scaled like the color glyph.
+    pub glyph: PositionedGlyph<'font>,
+    /// Straight, non-premultiplied, sRGB RGBA.
+    pub color: [u8; 4],
+}
+
+/// Collects solid color layers painted by `colr::Table::paint`.
+struct LayerPainter {
+    outline: Option<GlyphId>,
+    clips: Vec<Option<GlyphId>>,
+    layers: Vec<(GlyphId, [u8; 4])>,
+}
+
+impl<'a> colr::Painter<'a> for LayerPainter {
+    fn outline_glyph(&mut self, glyph_id: owned_ttf_parser::GlyphId) {
+        self.outline = Some(glyph_id.into());
+    }
+
+    fn paint(&mut self, paint: colr::Paint<'a>) {
+        // COLRv1 paints fill the innermost clip glyph
+        let glyph = match self.clips.last() {
+            Some(&clip) => clip,
+            None => self.outline.take(),
+        };
+        if let (Some(glyph), colr::Paint::Solid(c)) = (glyph, paint) {
+            self.layers.push((glyph, [c.red, c.green, c.blue, c.alpha]));
+        }
+    }
+
+    fn push_clip(&mut self) {
+        self.clips.push(self.outline.take());
+    }
+
+    fn push_clip_box(&mut self, _: colr::ClipBox) {
+        self.clips.push(self.clips.last().copied().flatten());
+    }
+
+    fn pop_clip(&mut self) {
+        self.clips.pop();
+    }
+
+    fn push_layer(&mut self, _: colr::CompositeMode) {}
+    fn pop_layer(&mut self) {}
+    fn push_transform(&mut self, _: owned_ttf_parser::Transform) {}
+    fn pop_transform(&mut self) {}
+}
+
+impl Font<'_> {
+    /// The number of color palettes of the font's color glyphs, `0` for fonts
+    /// without color glyphs. See `PositionedGlyph::color_layers`.
+    pub fn color_palettes(&self) -> u16 {
+        self.layout_face()
+            .and_then(|face| face.color_palettes())
+            .map_or(0, |n| n.get())
+    }
+}
+
+impl Glyph<'_> {
+    /// Returns if this is a color glyph, made of color layers in the `COLR`
+    /// table, see `PositionedGlyph::color_layers`.
+    pub fn is_color(&self) -> bool {
+        self.font()
+            .and_then(|font| font.layout_face())
+            .is_some_and(|face| face.is_color_glyph(self.id().into()))
+    }
+}
+
+impl<'font> PositionedGlyph<'font> {
+    /// Returns the layers of a color glyph using the colors of `palette`, see
+    /// `Font::color_palettes`, painted in order, with layers in the text
+    /// color using `foreground`. Other glyphs are a single layer of
+    /// `foreground`.
+    ///
+    /// Only solid colors are supported. From `COLR` version 1 tables,
+    /// gradient filled layers are skipped & transforms & blending modes
+    /// ignored.
+    pub fn color_layers(&self, palette: u16, foreground: [u8; 4]) -> Vec<ColorLayer<'font>> {
+        let mut painter = LayerPainter {
+            outline: None,
+            clips: Vec::new(),
+            layers: Vec::new(),
+        };
+        let [red, green, blue, alpha] = foreground;
+        let foreground_color = owned_ttf_parser::RgbaColor::new(red, green, blue, alpha);
+        let painted = self.font().filter(|font| {
+            let colr = font.layout_face().and_then(|face| face.tables().colr);
+            colr.and_then(|colr| {
+                let coords = font.inner().variation_coordinates();
+                colr.paint(
+                    self.id().into(),
+                    palette,
+                    &mut painter,
+                    coords,
+                    foreground_color,
+                )
+            })
+            .is_some()
+        });
+        let font = match painted {
+            Some(font) => font,
+            None => {
+                return vec![ColorLayer {
+                    glyph: self.clone(),
+                    color: foreground,
+                }]
+            }
+        };
+
+        painter
+            .layers
+            .into_iter()
+            .map(|(id, color)| {
+                let sg = ScaledGlyph {
+                    g: font.glyph(id),
+                    ..self.sg.clone()
+                };
+                ColorLayer {
+                    glyph: sg.positioned(self.position),
+                    color,
+                }
+            })
+            .collect()
+    }
+
+    /// The pixel bounding box covering all `color_layers`, which may differ
+    /// from the `pixel_bounding_box` of a color glyph's own outline.
+    pub fn color_pixel_bounding_box(&self) -> Option<Rect<i32>> {
+        self.color_layers(0, [0; 4])
+            .iter()
+            .filter_map(|layer| layer.glyph.pixel_bounding_box())
+            .fold(None, |union: Option<Rect<i32>>, bb| {
+                Some(union.map_or(bb, |u| Rect {
+                    min: crate::point(u.min.x.min(bb.min.x), u.min.y.min(bb.min.y)),
+                    max: crate::point(u.max.x.max(bb.max.x), u.max.y.max(bb.max.y)),
+                }))
+            })
+    }
+
+    /// Rasterises the `color_layers` of this glyph composited over
+    /// transparency. For each pixel in `color_pixel_bounding_box` `o` is
+    /// called with its position relative to the box min & straight,
+    /// non-premultiplied, RGBA in the range `0.0..=1.0`, so callers can blend
+    /// it over their own output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let glyph = font.glyph('a').scaled(Scale::uniform(24.0)).positioned(point(0.0, 0.0));
+    /// let bb = glyph.color_pixel_bounding_box().unwrap();
+    /// let mut rgba = vec![[0.0; 4]; (bb.width() * bb.height()) as usize];
+    /// // a non-color glyph is drawn in the foreground color
+    /// glyph.draw_color(0, [255, 0, 0, 255], |x, y, color| {
+    ///     rgba[(x + y * bb.width() as u32) as usize] = color;
+    /// });
+    /// ```
+    pub fn draw_color<O: FnMut(u32, u32, [f32; 4])>(
+        &self,
+        palette: u16,
+        foreground: [u8; 4],
+        mut o: O,
+    ) {
+        let layers = self.color_layers(palette, foreground);
+        let bb = match self.color_pixel_bounding_box() {
+            Some(bb) => bb,
+            None => return,
+        };
+        let width = bb.width() as usize;
+        // premultiplied rgba
+        let mut pixels = vec![[0.0_f32; 4]; width * bb.height() as usize];
+        for layer in &layers {
+            let layer_bb = match layer.glyph.pixel_bounding_box() {
+                Some(bb) => bb,
+                None => continue,
+            };
+            let alpha = f32::from(layer.color[3]) / 255.0;
+            let color = [
+                f32::from(layer.color[0]) / 255.0 * alpha,
+                f32::from(layer.color[1]) / 255.0 * alpha,
+                f32::from(layer.color[2]) / 255.0 * alpha,
+                alpha,
+            ];
+            let (dx, dy) = (layer_bb.min.x - bb.min.x, layer_bb.min.y - bb.min.y);
+            layer.glyph.draw(|x, y, v| {
+                let pixel =
+                    &mut pixels[(x as i32 + dx) as usize + (y as i32 + dy) as usize * width];
+                for (p, c) in pixel.iter_mut().zip(&color) {
+                    // source over
+                    *p = c * v + *p * (1.0 - alpha * v);
+                }
+            });
+        }
+
+        for (i, [r, g, b, a]) in pixels.into_iter().enumerate() {
+            let straight = |c: f32| if a > 0.0 { (c / a).min(1.0) } else { 0.0 };
+            o(
+                (i % width) as u32,
+                (i / width) as u32,
+                [straight(r), straight(g), straight(b), a],
+            );
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Glyph complexity metrics & rasterization timing, for finding glyphs that
+//! are slow to draw, see `ScaledGlyph::complexity` & `TimedBackend`.
+use crate::{OutlineBuilder, ScaledGlyph};
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+use crate::{PositionedGlyph, RasterBackend};
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+use std::time::{Duration, Instant};
+
+/// Outline segment counts of a glyph, returned from `ScaledGlyph::complexity`.
+///
+/// Rasterization time grows with the number of segments & the pixel area, so
+/// e.g. dense CJK & decorative glyphs with many contours can be found &
+/// cached ahead of use.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GlyphComplexity {
+    /// Closed outline paths.
+    pub contours: usize,
+    /// On & off curve points, including the start of each contour.
+    pub points: usize,
+    /// Straight segments.
+    pub lines: usize,
+    /// Quadratic bézier segments, used by TrueType outlines.
+    pub quads: usize,
+    /// Cubic bézier segments, used by CFF outlines.
+    pub cubics: usize,
+}
+
+impl GlyphComplexity {
+    /// Total number of segments.
+    #[inline]
+    pub fn segments(&self) -> usize {
+        self.lines + self.quads + self.cubics
+    }
+}
+
+impl OutlineBuilder for GlyphComplexity {
+    fn move_to(&mut self, _: f32, _: f32) {
+        self.contours += 1;
+        self.points += 1;
+    }
+
+    fn line_to(&mut self, _: f32, _: f32) {
+        self.lines += 1;
+        self.points += 1;
+    }
+
+    fn quad_to(&mut self, _: f32, _: f32, _: f32, _: f32) {
+        self.quads += 1;
+        self.points += 2;
+    }
+
+    fn curve_to(&mut self, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32) {
+        self.cubics += 1;
+        self.points += 3;
+    }
+
+    fn close(&mut self) {}
+}
+
+impl ScaledGlyph<'_> {
+    /// Returns the outline segment counts of this glyph, see
+    /// `GlyphComplexity`. Glyphs without an outline, e.g. spaces, have no
+    /// segments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let complexity = font.glyph('o').scaled(Scale::uniform(24.0)).complexity();
+    /// assert_eq!(complexity.contours, 2);
+    /// ```
+    pub fn complexity(&self) -> GlyphComplexity {
+        let mut complexity = GlyphComplexity::default();
+        self.build_outline(&mut complexity);
+        complexity
+    }
+}
+
+/// A `RasterBackend` calling `report` with the time each glyph took to
+/// rasterize with `backend`, to profile `PositionedGlyph::draw_with_backend`
+/// & `gpu_cache::Cache::cache_queued_with_backend`.
+///
+/// `report` may be called concurrently from multiple threads by a
+/// multithreaded cache.
+///
+/// # Example
+///
+/// ```
+/// # use rusttype::*;
+/// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+/// # let font = Font::try_from_bytes(font_data).unwrap();
+/// let timed = TimedBackend::new(ScanlineRasterizer, |glyph, time| {
+///     if time.as_millis() > 1 {
+///         println!("slow glyph {:?}: {:?}", glyph.id(), time);
+///     }
+/// });
+/// let glyph = font.glyph('a').scaled(Scale::uniform(24.0)).positioned(point(0.0, 0.0));
+/// glyph.draw_with_backend(&timed, |_, _, _| {});
+/// ```
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+#[derive(Clone, Debug)]
+pub struct TimedBackend<B, F> {
+    backend: B,
+    report: F,
+}
+
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+impl<B, F> TimedBackend<B, F>
+where
+    B: RasterBackend,
+    F: Fn(&PositionedGlyph<'_>, Duration),
+{
+    /// Wraps `backend`, calling `report` after each glyph is rasterized.
+    pub fn new(backend: B, report: F) -> Self {
+        Self { backend, report }
+    }
+
+    /// The wrapped backend.
+    #[inline]
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+}
+
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+impl<B, F> RasterBackend for TimedBackend<B, F>
+where
+    B: RasterBackend,
+    F: Fn(&PositionedGlyph<'_>, Duration),
+{
+    fn rasterize(&self, glyph: &PositionedGlyph<'_>, o: &mut dyn FnMut(u32, u32, f32)) {
+        let start = Instant::now();
+        self.backend.rasterize(glyph, o);
+        (self.report)(glyph, start.elapsed());
+    }
+
+    fn rasterize_variant(
+        &self,
+        glyph: &PositionedGlyph<'_>,
+        variant: u32,
+        o: &mut dyn FnMut(u32, u32, f32),
+    ) {
+        let start = Instant::now();
+        self.backend.rasterize_variant(glyph, variant, o);
+        (self.report)(glyph, start.elapsed());
+    }
+}
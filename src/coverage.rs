@@ -0,0 +1,377 @@
+//! Unicode block coverage of fonts, see `Font::coverage_summary`.
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A named range of Unicode code points, as defined by the Unicode standard.
+///
+/// Blocks roughly correspond to scripts, e.g. the "Greek and Coptic" block
+/// contains the basic Greek alphabet.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UnicodeBlock {
+    /// The Unicode name of the block, e.g. `"Greek and Coptic"`.
+    pub name: &'static str,
+    /// The first code point of the block.
+    pub first: char,
+    /// The last code point of the block.
+    pub last: char,
+}
+
+impl UnicodeBlock {
+    /// All known blocks in ascending order. This covers the Basic
+    /// Multilingual Plane & commonly used supplementary blocks (emoji, CJK
+    /// extensions, private use).
+    pub fn all() -> &'static [UnicodeBlock] {
+        BLOCKS
+    }
+
+    /// Returns the block containing the code point `c`, if known.
+    pub fn of(c: char) -> Option<UnicodeBlock> {
+        let idx = BLOCKS
+            .binary_search_by(|block| {
+                if block.last < c {
+                    core::cmp::Ordering::Less
+                } else if block.first > c {
+                    core::cmp::Ordering::Greater
+                } else {
+                    core::cmp::Ordering::Equal
+                }
+            })
+            .ok()?;
+        Some(BLOCKS[idx])
+    }
+
+    /// Returns the number of code points in the block. Note that this includes
+    /// code points not (yet) assigned to characters.
+    pub fn size(&self) -> u32 {
+        self.last as u32 - self.first as u32 + 1
+    }
+
+    /// Returns `true` if `c` is in this block.
+    #[inline]
+    pub fn contains(&self, c: char) -> bool {
+        self.first <= c && c <= self.last
+    }
+}
+
+/// The number of characters of a `UnicodeBlock` that a font covers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BlockCoverage {
+    pub block: UnicodeBlock,
+    /// Number of code points in the block that map to a glyph.
+    pub covered: u32,
+}
+
+impl BlockCoverage {
+    /// Returns the ratio of covered code points in the block between `0.0` &
+    /// `1.0`.
+    ///
+    /// As blocks usually contain unassigned code points, complete support of
+    /// a block may still have a ratio lower than `1.0`.
+    pub fn ratio(&self) -> f32 {
+        self.covered as f32 / self.block.size() as f32
+    }
+}
+
+/// A summary of the Unicode blocks covered by a font's character map, see
+/// `Font::coverage_summary`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CoverageSummary {
+    blocks: Vec<BlockCoverage>,
+    uncategorized: u32,
+}
+
+impl CoverageSummary {
+    /// Builds a summary from ascending `chars`.
+    pub(crate) fn from_chars(chars: impl Iterator<Item = char>) -> Self {
+        let mut summary = CoverageSummary::default();
+        for c in chars {
+            match summary.blocks.last_mut() {
+                Some(last) if last.block.contains(c) => last.covered += 1,
+                _ => match UnicodeBlock::of(c) {
+                    Some(block) => summary.blocks.push(BlockCoverage { block, covered: 1 }),
+                    None => summary.uncategorized += 1,
+                },
+            }
+        }
+        summary
+    }
+
+    /// Coverage of each block with at least one covered character, in
+    /// ascending block order.
+    pub fn blocks(&self) -> &[BlockCoverage] {
+        &self.blocks
+    }
+
+    /// Returns the coverage of the block named `block_name`, `None` if no
+    /// characters of the block are covered.
+    pub fn get(&self, block_name: &str) -> Option<&BlockCoverage> {
+        self.blocks.iter().find(|b| b.block.name == block_name)
+    }
+
+    /// Returns the covered ratio of the block named `block_name`, see
+    /// `BlockCoverage::ratio`.
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font: Font<'static> = Font::try_from_bytes(font_data).unwrap();
+    /// let supports_greek = font.coverage_summary().ratio("Greek and Coptic") > 0.5;
+    /// ```
+    pub fn ratio(&self, block_name: &str) -> f32 {
+        self.get(block_name).map_or(0.0, BlockCoverage::ratio)
+    }
+
+    /// Number of covered characters outside of all known blocks.
+    pub fn uncategorized(&self) -> u32 {
+        self.uncategorized
+    }
+}
+
+const fn block(name: &'static str, first: char, last: char) -> UnicodeBlock {
+    UnicodeBlock { name, first, last }
+}
+
+static BLOCKS: &[UnicodeBlock] = &[
+    block("Basic Latin", '\u{0000}', '\u{007F}'),
+    block("Latin-1 Supplement", '\u{0080}', '\u{00FF}'),
+    block("Latin Extended-A", '\u{0100}', '\u{017F}'),
+    block("Latin Extended-B", '\u{0180}', '\u{024F}'),
+    block("IPA Extensions", '\u{0250}', '\u{02AF}'),
+    block("Spacing Modifier Letters", '\u{02B0}', '\u{02FF}'),
+    block("Combining Diacritical Marks", '\u{0300}', '\u{036F}'),
+    block("Greek and Coptic", '\u{0370}', '\u{03FF}'),
+    block("Cyrillic", '\u{0400}', '\u{04FF}'),
+    block("Cyrillic Supplement", '\u{0500}', '\u{052F}'),
+    block("Armenian", '\u{0530}', '\u{058F}'),
+    block("Hebrew", '\u{0590}', '\u{05FF}'),
+    block("Arabic", '\u{0600}', '\u{06FF}'),
+    block("Syriac", '\u{0700}', '\u{074F}'),
+    block("Arabic Supplement", '\u{0750}', '\u{077F}'),
+    block("Thaana", '\u{0780}', '\u{07BF}'),
+    block("NKo", '\u{07C0}', '\u{07FF}'),
+    block("Samaritan", '\u{0800}', '\u{083F}'),
+    block("Mandaic", '\u{0840}', '\u{085F}'),
+    block("Syriac Supplement", '\u{0860}', '\u{086F}'),
+    block("Arabic Extended-B", '\u{0870}', '\u{089F}'),
+    block("Arabic Extended-A", '\u{08A0}', '\u{08FF}'),
+    block("Devanagari", '\u{0900}', '\u{097F}'),
+    block("Bengali", '\u{0980}', '\u{09FF}'),
+    block("Gurmukhi", '\u{0A00}', '\u{0A7F}'),
+    block("Gujarati", '\u{0A80}', '\u{0AFF}'),
+    block("Oriya", '\u{0B00}', '\u{0B7F}'),
+    block("Tamil", '\u{0B80}', '\u{0BFF}'),
+    block("Telugu", '\u{0C00}', '\u{0C7F}'),
+    block("Kannada", '\u{0C80}', '\u{0CFF}'),
+    block("Malayalam", '\u{0D00}', '\u{0D7F}'),
+    block("Sinhala", '\u{0D80}', '\u{0DFF}'),
+    block("Thai", '\u{0E00}', '\u{0E7F}'),
+    block("Lao", '\u{0E80}', '\u{0EFF}'),
+    block("Tibetan", '\u{0F00}', '\u{0FFF}'),
+    block("Myanmar", '\u{1000}', '\u{109F}'),
+    block("Georgian", '\u{10A0}', '\u{10FF}'),
+    block("Hangul Jamo", '\u{1100}', '\u{11FF}'),
+    block("Ethiopic", '\u{1200}', '\u{137F}'),
+    block("Ethiopic Supplement", '\u{1380}', '\u{139F}'),
+    block("Cherokee", '\u{13A0}', '\u{13FF}'),
+    block(
+        "Unified Canadian Aboriginal Syllabics",
+        '\u{1400}',
+        '\u{167F}',
+    ),
+    block("Ogham", '\u{1680}', '\u{169F}'),
+    block("Runic", '\u{16A0}', '\u{16FF}'),
+    block("Tagalog", '\u{1700}', '\u{171F}'),
+    block("Hanunoo", '\u{1720}', '\u{173F}'),
+    block("Buhid", '\u{1740}', '\u{175F}'),
+    block("Tagbanwa", '\u{1760}', '\u{177F}'),
+    block("Khmer", '\u{1780}', '\u{17FF}'),
+    block("Mongolian", '\u{1800}', '\u{18AF}'),
+    block(
+        "Unified Canadian Aboriginal Syllabics Extended",
+        '\u{18B0}',
+        '\u{18FF}',
+    ),
+    block("Limbu", '\u{1900}', '\u{194F}'),
+    block("Tai Le", '\u{1950}', '\u{197F}'),
+    block("New Tai Lue", '\u{1980}', '\u{19DF}'),
+    block("Khmer Symbols", '\u{19E0}', '\u{19FF}'),
+    block("Buginese", '\u{1A00}', '\u{1A1F}'),
+    block("Tai Tham", '\u{1A20}', '\u{1AAF}'),
+    block(
+        "Combining Diacritical Marks Extended",
+        '\u{1AB0}',
+        '\u{1AFF}',
+    ),
+    block("Balinese", '\u{1B00}', '\u{1B7F}'),
+    block("Sundanese", '\u{1B80}', '\u{1BBF}'),
+    block("Batak", '\u{1BC0}', '\u{1BFF}'),
+    block("Lepcha", '\u{1C00}', '\u{1C4F}'),
+    block("Ol Chiki", '\u{1C50}', '\u{1C7F}'),
+    block("Cyrillic Extended-C", '\u{1C80}', '\u{1C8F}'),
+    block("Georgian Extended", '\u{1C90}', '\u{1CBF}'),
+    block("Sundanese Supplement", '\u{1CC0}', '\u{1CCF}'),
+    block("Vedic Extensions", '\u{1CD0}', '\u{1CFF}'),
+    block("Phonetic Extensions", '\u{1D00}', '\u{1D7F}'),
+    block("Phonetic Extensions Supplement", '\u{1D80}', '\u{1DBF}'),
+    block(
+        "Combining Diacritical Marks Supplement",
+        '\u{1DC0}',
+        '\u{1DFF}',
+    ),
+    block("Latin Extended Additional", '\u{1E00}', '\u{1EFF}'),
+    block("Greek Extended", '\u{1F00}', '\u{1FFF}'),
+    block("General Punctuation", '\u{2000}', '\u{206F}'),
+    block("Superscripts and Subscripts", '\u{2070}', '\u{209F}'),
+    block("Currency Symbols", '\u{20A0}', '\u{20CF}'),
+    block(
+        "Combining Diacritical Marks for Symbols",
+        '\u{20D0}',
+        '\u{20FF}',
+    ),
+    block("Letterlike Symbols", '\u{2100}', '\u{214F}'),
+    block("Number Forms", '\u{2150}', '\u{218F}'),
+    block("Arrows", '\u{2190}', '\u{21FF}'),
+    block("Mathematical Operators", '\u{2200}', '\u{22FF}'),
+    block("Miscellaneous Technical", '\u{2300}', '\u{23FF}'),
+    block("Control Pictures", '\u{2400}', '\u{243F}'),
+    block("Optical Character Recognition", '\u{2440}', '\u{245F}'),
+    block("Enclosed Alphanumerics", '\u{2460}', '\u{24FF}'),
+    block("Box Drawing", '\u{2500}', '\u{257F}'),
+    block("Block Elements", '\u{2580}', '\u{259F}'),
+    block("Geometric Shapes", '\u{25A0}', '\u{25FF}'),
+    block("Miscellaneous Symbols", '\u{2600}', '\u{26FF}'),
+    block("Dingbats", '\u{2700}', '\u{27BF}'),
+    block(
+        "Miscellaneous Mathematical Symbols-A",
+        '\u{27C0}',
+        '\u{27EF}',
+    ),
+    block("Supplemental Arrows-A", '\u{27F0}', '\u{27FF}'),
+    block("Braille Patterns", '\u{2800}', '\u{28FF}'),
+    block("Supplemental Arrows-B", '\u{2900}', '\u{297F}'),
+    block(
+        "Miscellaneous Mathematical Symbols-B",
+        '\u{2980}',
+        '\u{29FF}',
+    ),
+    block(
+        "Supplemental Mathematical Operators",
+        '\u{2A00}',
+        '\u{2AFF}',
+    ),
+    block("Miscellaneous Symbols and Arrows", '\u{2B00}', '\u{2BFF}'),
+    block("Glagolitic", '\u{2C00}', '\u{2C5F}'),
+    block("Latin Extended-C", '\u{2C60}', '\u{2C7F}'),
+    block("Coptic", '\u{2C80}', '\u{2CFF}'),
+    block("Georgian Supplement", '\u{2D00}', '\u{2D2F}'),
+    block("Tifinagh", '\u{2D30}', '\u{2D7F}'),
+    block("Ethiopic Extended", '\u{2D80}', '\u{2DDF}'),
+    block("Cyrillic Extended-A", '\u{2DE0}', '\u{2DFF}'),
+    block("Supplemental Punctuation", '\u{2E00}', '\u{2E7F}'),
+    block("CJK Radicals Supplement", '\u{2E80}', '\u{2EFF}'),
+    block("Kangxi Radicals", '\u{2F00}', '\u{2FDF}'),
+    block("Ideographic Description Characters", '\u{2FF0}', '\u{2FFF}'),
+    block("CJK Symbols and Punctuation", '\u{3000}', '\u{303F}'),
+    block("Hiragana", '\u{3040}', '\u{309F}'),
+    block("Katakana", '\u{30A0}', '\u{30FF}'),
+    block("Bopomofo", '\u{3100}', '\u{312F}'),
+    block("Hangul Compatibility Jamo", '\u{3130}', '\u{318F}'),
+    block("Kanbun", '\u{3190}', '\u{319F}'),
+    block("Bopomofo Extended", '\u{31A0}', '\u{31BF}'),
+    block("CJK Strokes", '\u{31C0}', '\u{31EF}'),
+    block("Katakana Phonetic Extensions", '\u{31F0}', '\u{31FF}'),
+    block("Enclosed CJK Letters and Months", '\u{3200}', '\u{32FF}'),
+    block("CJK Compatibility", '\u{3300}', '\u{33FF}'),
+    block("CJK Unified Ideographs Extension A", '\u{3400}', '\u{4DBF}'),
+    block("Yijing Hexagram Symbols", '\u{4DC0}', '\u{4DFF}'),
+    block("CJK Unified Ideographs", '\u{4E00}', '\u{9FFF}'),
+    block("Yi Syllables", '\u{A000}', '\u{A48F}'),
+    block("Yi Radicals", '\u{A490}', '\u{A4CF}'),
+    block("Lisu", '\u{A4D0}', '\u{A4FF}'),
+    block("Vai", '\u{A500}', '\u{A63F}'),
+    block("Cyrillic Extended-B", '\u{A640}', '\u{A69F}'),
+    block("Bamum", '\u{A6A0}', '\u{A6FF}'),
+    block("Modifier Tone Letters", '\u{A700}', '\u{A71F}'),
+    block("Latin Extended-D", '\u{A720}', '\u{A7FF}'),
+    block("Syloti Nagri", '\u{A800}', '\u{A82F}'),
+    block("Common Indic Number Forms", '\u{A830}', '\u{A83F}'),
+    block("Phags-pa", '\u{A840}', '\u{A87F}'),
+    block("Saurashtra", '\u{A880}', '\u{A8DF}'),
+    block("Devanagari Extended", '\u{A8E0}', '\u{A8FF}'),
+    block("Kayah Li", '\u{A900}', '\u{A92F}'),
+    block("Rejang", '\u{A930}', '\u{A95F}'),
+    block("Hangul Jamo Extended-A", '\u{A960}', '\u{A97F}'),
+    block("Javanese", '\u{A980}', '\u{A9DF}'),
+    block("Myanmar Extended-B", '\u{A9E0}', '\u{A9FF}'),
+    block("Cham", '\u{AA00}', '\u{AA5F}'),
+    block("Myanmar Extended-A", '\u{AA60}', '\u{AA7F}'),
+    block("Tai Viet", '\u{AA80}', '\u{AADF}'),
+    block("Meetei Mayek Extensions", '\u{AAE0}', '\u{AAFF}'),
+    block("Ethiopic Extended-A", '\u{AB00}', '\u{AB2F}'),
+    block("Latin Extended-E", '\u{AB30}', '\u{AB6F}'),
+    block("Cherokee Supplement", '\u{AB70}', '\u{ABBF}'),
+    block("Meetei Mayek", '\u{ABC0}', '\u{ABFF}'),
+    block("Hangul Syllables", '\u{AC00}', '\u{D7AF}'),
+    block("Hangul Jamo Extended-B", '\u{D7B0}', '\u{D7FF}'),
+    block("Private Use Area", '\u{E000}', '\u{F8FF}'),
+    block("CJK Compatibility Ideographs", '\u{F900}', '\u{FAFF}'),
+    block("Alphabetic Presentation Forms", '\u{FB00}', '\u{FB4F}'),
+    block("Arabic Presentation Forms-A", '\u{FB50}', '\u{FDFF}'),
+    block("Variation Selectors", '\u{FE00}', '\u{FE0F}'),
+    block("Vertical Forms", '\u{FE10}', '\u{FE1F}'),
+    block("Combining Half Marks", '\u{FE20}', '\u{FE2F}'),
+    block("CJK Compatibility Forms", '\u{FE30}', '\u{FE4F}'),
+    block("Small Form Variants", '\u{FE50}', '\u{FE6F}'),
+    block("Arabic Presentation Forms-B", '\u{FE70}', '\u{FEFF}'),
+    block("Halfwidth and Fullwidth Forms", '\u{FF00}', '\u{FFEF}'),
+    block("Specials", '\u{FFF0}', '\u{FFFF}'),
+    block(
+        "Mathematical Alphanumeric Symbols",
+        '\u{1D400}',
+        '\u{1D7FF}',
+    ),
+    block("Mahjong Tiles", '\u{1F000}', '\u{1F02F}'),
+    block("Domino Tiles", '\u{1F030}', '\u{1F09F}'),
+    block("Playing Cards", '\u{1F0A0}', '\u{1F0FF}'),
+    block("Enclosed Alphanumeric Supplement", '\u{1F100}', '\u{1F1FF}'),
+    block("Enclosed Ideographic Supplement", '\u{1F200}', '\u{1F2FF}'),
+    block(
+        "Miscellaneous Symbols and Pictographs",
+        '\u{1F300}',
+        '\u{1F5FF}',
+    ),
+    block("Emoticons", '\u{1F600}', '\u{1F64F}'),
+    block("Ornamental Dingbats", '\u{1F650}', '\u{1F67F}'),
+    block("Transport and Map Symbols", '\u{1F680}', '\u{1F6FF}'),
+    block("Alchemical Symbols", '\u{1F700}', '\u{1F77F}'),
+    block("Geometric Shapes Extended", '\u{1F780}', '\u{1F7FF}'),
+    block("Supplemental Arrows-C", '\u{1F800}', '\u{1F8FF}'),
+    block(
+        "Supplemental Symbols and Pictographs",
+        '\u{1F900}',
+        '\u{1F9FF}',
+    ),
+    block(
+        "Symbols and Pictographs Extended-A",
+        '\u{1FA70}',
+        '\u{1FAFF}',
+    ),
+    block(
+        "CJK Unified Ideographs Extension B",
+        '\u{20000}',
+        '\u{2A6DF}',
+    ),
+    block(
+        "CJK Compatibility Ideographs Supplement",
+        '\u{2F800}',
+        '\u{2FA1F}',
+    ),
+    block("Tags", '\u{E0000}', '\u{E007F}'),
+    block("Variation Selectors Supplement", '\u{E0100}', '\u{E01EF}'),
+    block("Supplementary Private Use Area-A", '\u{F0000}', '\u{FFFFF}'),
+    block(
+        "Supplementary Private Use Area-B",
+        '\u{100000}',
+        '\u{10FFFF}',
+    ),
+];
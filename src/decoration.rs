@@ -0,0 +1,450 @@
+//! Underline & strikethrough geometry, see `ParagraphLayout::decorations`.
+#[cfg(all(feature = "libm-math", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::outliner::OutlineTranslator;
+use crate::{
+    point, vector, CoverageMask, Font, OutlineBuilder, ParagraphLayout, Point, PositionedGlyph,
+    PositionedLine, Rect, Scale,
+};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// A line drawn along text, see `Font::decoration_metrics`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Decoration {
+    Underline,
+    Strikethrough,
+}
+
+/// The placement of a `Decoration` relative to the baseline, in pixels.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DecorationMetrics {
+    /// The distance of the top of the line below the baseline, negative above
+    /// it, e.g. for strikethroughs.
+    pub offset: f32,
+    pub thickness: f32,
+}
+
+/// A decorated run of a line, see `ParagraphLayout::decorations`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecorationSegment {
+    pub decoration: Decoration,
+    /// Byte range of the decorated text.
+    pub range: Range<usize>,
+    /// The area to fill, in pixels.
+    pub rect: Rect<f32>,
+}
+
+impl Font<'_> {
+    /// The placement of `decoration` at `scale`, from the `post` table for
+    /// underlines & the OS/2 table for strikethroughs. Fonts without them,
+    /// or with a zero thickness, get a line a 14th of an em thick a 10th of
+    /// an em below the baseline, or centered on half the x height.
+    pub fn decoration_metrics(&self, decoration: Decoration, scale: Scale) -> DecorationMetrics {
+        let face = self.inner();
+        let metrics = match decoration {
+            Decoration::Underline => face.underline_metrics(),
+            Decoration::Strikethrough => face.strikeout_metrics(),
+        };
+        let units_per_em = f32::from(self.units_per_em());
+        let (position, thickness) = match metrics {
+            Some(m) if m.thickness > 0 => (f32::from(m.position), f32::from(m.thickness)),
+            _ => {
+                let thickness = units_per_em / 14.0;
+                let position = match decoration {
+                    Decoration::Underline => -units_per_em / 10.0,
+                    Decoration::Strikethrough => {
+                        let x_height = face.x_height().map_or(units_per_em / 2.0, f32::from);
+                        (x_height + thickness) / 2.0
+                    }
+                };
+                (position, thickness)
+            }
+        };
+        let factor = self.scale_for_pixel_height(scale.y);
+        DecorationMetrics {
+            offset: -position * factor,
+            thickness: thickness * factor,
+        }
+    }
+}
+
+impl ParagraphLayout<'_, '_, '_> {
+    /// Returns the decoration geometry of `line`, from this paragraph's
+    /// `positioned_lines`, for the text byte ranges in `styles`, e.g. links
+    /// or misspellings.
+    ///
+    /// Consecutive glyphs decorated the same way, even by adjacent or
+    /// overlapping ranges, form one segment spanning from the first glyph's
+    /// origin to the last's advance. Whitespace at either end of a segment
+    /// isn't decorated, so lines don't stick out past words. Segments are in
+    /// `styles` decoration order, then left to right.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let text = "see the docs for more";
+    /// let paragraph = font.layout_paragraph(text, Scale::uniform(24.0), 1000.0);
+    /// // a link underlined
+    /// let styles = [(4..12, Decoration::Underline)];
+    /// for line in paragraph.positioned_lines(point(0.0, 0.0)) {
+    ///     for segment in paragraph.decorations(&line, &styles) {
+    ///         assert_eq!(&text[segment.range], "the docs");
+    ///         // fill segment.rect
+    ///     }
+    /// }
+    /// ```
+    pub fn decorations(
+        &self,
+        line: &PositionedLine<'_>,
+        styles: &[(Range<usize>, Decoration)],
+    ) -> Vec<DecorationSegment> {
+        let start = line.line.range.start;
+        let text = &self.text()[line.line.range.clone()];
+        let glyphs = &line.glyphs;
+        // byte index, whether whitespace & x extent of each glyph
+        let chars: Vec<_> = text
+            .char_indices()
+            .zip(glyphs)
+            .enumerate()
+            .map(|(i, ((index, c), glyph))| {
+                let x0 = glyph.position().x;
+                let x1 = match glyphs.get(i + 1) {
+                    Some(next) => next.position().x,
+                    None => x0 + glyph.unpositioned().h_metrics().advance_width,
+                };
+                (start + index, c.is_whitespace(), x0..x1)
+            })
+            .collect();
+        let text_end = line.line.range.end;
+
+        let mut decorations: Vec<Decoration> = Vec::new();
+        for (_, decoration) in styles {
+            if !decorations.contains(decoration) {
+                decorations.push(*decoration);
+            }
+        }
+
+        let mut segments = Vec::new();
+        for decoration in decorations {
+            let metrics = self.font().decoration_metrics(decoration, self.scale());
+            let mut push = |run: Range<usize>| {
+                let run = trim(&chars, run);
+                if run.is_empty() {
+                    return;
+                }
+                let y = line.origin.y + metrics.offset;
+                let end = chars.get(run.end).map_or(text_end, |c| c.0);
+                segments.push(DecorationSegment {
+                    decoration,
+                    range: chars[run.start].0..end,
+                    rect: Rect {
+                        min: point(chars[run.start].2.start, y),
+                        max: point(chars[run.end - 1].2.end, y + metrics.thickness),
+                    },
+                });
+            };
+
+            let mut run_start = None;
+            for (i, (index, ..)) in chars.iter().enumerate() {
+                let styled = styles
+                    .iter()
+                    .any(|(range, d)| *d == decoration && range.contains(index));
+                match (styled, run_start) {
+                    (true, None) => run_start = Some(i),
+                    (false, Some(s)) => {
+                        push(s..i);
+                        run_start = None;
+                    }
+                    _ => {}
+                }
+            }
+            if let Some(s) = run_start {
+                push(s..chars.len());
+            }
+        }
+        segments
+    }
+}
+
+impl DecorationSegment {
+    /// Returns the parts of this segment's `rect` clear of the ink of
+    /// `glyphs`, e.g. the line they were decorated on, so underlines skip
+    /// descenders like CSS `text-decoration-skip-ink: auto`.
+    ///
+    /// Ink is found from the glyph outlines: wherever an outline comes within
+    /// `clearance` pixels of the rect, the rect is cut with a gap, so lines end
+    /// that far from the glyph. Parts are left to right, & the whole rect if
+    /// nothing crosses it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let text = "gap";
+    /// let paragraph = font.layout_paragraph(text, Scale::uniform(24.0), 1000.0);
+    /// let line = paragraph.positioned_lines(point(0.0, 0.0)).next().unwrap();
+    /// let underline = &paragraph.decorations(&line, &[(0..3, Decoration::Underline)])[0];
+    /// // cut around the descenders of 'g' & 'p'
+    /// let parts = underline.skip_ink(&line.glyphs, 1.0);
+    /// assert!(parts.len() > 1);
+    /// ```
+    pub fn skip_ink(&self, glyphs: &[PositionedGlyph<'_>], clearance: f32) -> Vec<Rect<f32>> {
+        let Rect { min, max } = self.rect;
+        let clearance = clearance.max(0.0);
+        let band = (min.y - clearance, max.y + clearance);
+        let mut ink: Vec<(f32, f32)> = Vec::new();
+        for glyph in glyphs {
+            let bounds = match glyph.pixel_bounding_box() {
+                Some(bb) => bb,
+                None => continue,
+            };
+            let (x0, x1) = (bounds.min.x as f32, bounds.max.x as f32);
+            let (y0, y1) = (bounds.min.y as f32, bounds.max.y as f32);
+            let outside_x = x1 < min.x - clearance || x0 > max.x + clearance;
+            if outside_x || y1 < band.0 || y0 > band.1 {
+                continue;
+            }
+            let mut edges = Edges::default();
+            glyph
+                .unpositioned()
+                .build_outline(&mut OutlineTranslator::new(&mut edges, glyph.position()));
+            edges.ink(band, &mut ink);
+        }
+        ink.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut parts = Vec::new();
+        let mut x = min.x;
+        for (x0, x1) in ink {
+            let (x0, x1) = (x0 - clearance, x1 + clearance);
+            if x0 > x {
+                parts.push(Rect {
+                    min: point(x, min.y),
+                    max: point(x0.min(max.x), max.y),
+                });
+            }
+            x = x.max(x1);
+            if x >= max.x {
+                break;
+            }
+        }
+        if x < max.x {
+            parts.push(Rect {
+                min: point(x, min.y),
+                max,
+            });
+        }
+        parts
+    }
+}
+
+/// Peak heights of wavy lines from their center, in thicknesses.
+const WAVE_AMPLITUDE: f32 = 1.0;
+/// Wavelengths of wavy lines, in thicknesses.
+const WAVE_LENGTH: f32 = 6.0;
+/// Lines per wavelength of wavy lines.
+const WAVE_LINES: u32 = 16;
+
+impl DecorationSegment {
+    /// Builds the outline of this segment drawn as a wavy line, e.g. for
+    /// spelling & grammar markers: a sine wave along the middle of `rect`,
+    /// `rect` high, so as thick as the font's underline. Waves are 6
+    /// thicknesses long, reaching a thickness above & below the middle,
+    /// starting upwards at the left end & cut at the right.
+    ///
+    /// See `CoverageMask::fill_wavy` to rasterize it.
+    pub fn build_wavy_outline(&self, builder: &mut (impl OutlineBuilder + ?Sized)) {
+        let Rect { min, max } = self.rect;
+        let thickness = self.rect.height();
+        if self.rect.width() <= 0.0 || thickness <= 0.0 {
+            return;
+        }
+        let (amplitude, length) = (thickness * WAVE_AMPLITUDE, thickness * WAVE_LENGTH);
+        let k = 2.0 * core::f32::consts::PI / length;
+        let middle = (min.y + max.y) / 2.0;
+        // points on the wave & their normals, y down so waves start upwards
+        let step = length / WAVE_LINES as f32;
+        let count = (self.rect.width() / step).ceil() as u32;
+        let wave = (0..=count).map(|i| {
+            let x = (min.x + i as f32 * step).min(max.x);
+            let phase = k * (x - min.x);
+            let slope = -amplitude * k * phase.cos();
+            let normal = vector(-slope, 1.0) * (0.5 / (1.0 + slope * slope).sqrt());
+            (
+                point(x, middle - amplitude * phase.sin()),
+                normal * thickness,
+            )
+        });
+
+        let mut first = true;
+        for (p, normal) in wave.clone() {
+            let top = p - normal;
+            if first {
+                builder.move_to(top.x, top.y);
+                first = false;
+            } else {
+                builder.line_to(top.x, top.y);
+            }
+        }
+        let bottom: Vec<_> = wave.map(|(p, normal)| p + normal).collect();
+        for p in bottom.iter().rev() {
+            builder.line_to(p.x, p.y);
+        }
+        builder.close();
+    }
+}
+
+impl CoverageMask {
+    /// Fills `segment` as a wavy line, see
+    /// `DecorationSegment::build_wavy_outline`. Composited over the existing
+    /// coverage & clipped to the mask.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let text = "mispelled";
+    /// let paragraph = font.layout_paragraph(text, Scale::uniform(24.0), 1000.0);
+    /// let line = paragraph.positioned_lines(point(0.0, 0.0)).next().unwrap();
+    /// let mut mask = CoverageMask::new(200, 40);
+    /// for segment in paragraph.decorations(&line, &[(0..9, Decoration::Underline)]) {
+    ///     mask.fill_wavy(&segment);
+    /// }
+    /// ```
+    pub fn fill_wavy(&mut self, segment: &DecorationSegment) {
+        let Rect { min, max } = segment.rect;
+        let thickness = segment.rect.height();
+        let reach = thickness * (WAVE_AMPLITUDE + 0.5);
+        let middle = (min.y + max.y) / 2.0;
+        // the ends are cut along the normals, so may lean out
+        let bounds = Rect {
+            min: point(min.x - thickness / 2.0, middle - reach),
+            max: point(max.x + thickness / 2.0, middle + reach),
+        };
+        self.fill(bounds, |b| segment.build_wavy_outline(b));
+    }
+}
+
+/// Curves are flattened into this many lines to find ink, plenty at text
+/// sizes.
+const CURVE_LINES: u32 = 8;
+
+/// The edges of an outline, flattened into lines.
+#[derive(Default)]
+struct Edges {
+    lines: Vec<(Point<f32>, Point<f32>)>,
+    start: Point<f32>,
+    last: Point<f32>,
+}
+
+impl Edges {
+    /// Adds the x extents of the ink of the outline within the `band` of y
+    /// values to `ink`: the edges crossing the band & the filled spans across
+    /// it, by the nonzero rule.
+    fn ink(&self, band: (f32, f32), ink: &mut Vec<(f32, f32)>) {
+        let (top, bottom) = band;
+        for &(a, b) in &self.lines {
+            let (lo, hi) = if a.y <= b.y { (a, b) } else { (b, a) };
+            if hi.y < top || lo.y > bottom {
+                continue;
+            }
+            let x_at = |y: f32| {
+                if hi.y == lo.y {
+                    lo.x
+                } else {
+                    lo.x + (hi.x - lo.x) * ((y - lo.y) / (hi.y - lo.y)).clamp(0.0, 1.0)
+                }
+            };
+            let (xa, xb) = (x_at(top.max(lo.y)), x_at(bottom.min(hi.y)));
+            ink.push((xa.min(xb), xa.max(xb)));
+        }
+
+        // spans of ink covering the band without edges in it, e.g. stems
+        let mut crossings: Vec<(f32, i32)> = Vec::new();
+        for &y in &[top, (top + bottom) / 2.0, bottom] {
+            crossings.clear();
+            for &(a, b) in &self.lines {
+                let (lo, hi, winding) = if a.y < b.y { (a, b, 1) } else { (b, a, -1) };
+                if lo.y <= y && y < hi.y {
+                    let x = lo.x + (hi.x - lo.x) * (y - lo.y) / (hi.y - lo.y);
+                    crossings.push((x, winding));
+                }
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let mut winding = 0;
+            for pair in crossings.windows(2) {
+                winding += pair[0].1;
+                if winding != 0 {
+                    ink.push((pair[0].0, pair[1].0));
+                }
+            }
+        }
+    }
+}
+
+impl OutlineBuilder for Edges {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.start = point(x, y);
+        self.last = self.start;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let p = point(x, y);
+        self.lines.push((self.last, p));
+        self.last = p;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (p0, p1, p2) = (self.last, point(x1, y1), point(x, y));
+        for i in 1..=CURVE_LINES {
+            let t = i as f32 / CURVE_LINES as f32;
+            let u = 1.0 - t;
+            let p = point(
+                u * u * p0.x + 2.0 * u * t * p1.x + t * t * p2.x,
+                u * u * p0.y + 2.0 * u * t * p1.y + t * t * p2.y,
+            );
+            self.line_to(p.x, p.y);
+        }
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (p0, p1, p2, p3) = (self.last, point(x1, y1), point(x2, y2), point(x, y));
+        for i in 1..=CURVE_LINES {
+            let t = i as f32 / CURVE_LINES as f32;
+            let u = 1.0 - t;
+            let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+            let p = point(
+                a * p0.x + b * p1.x + c * p2.x + d * p3.x,
+                a * p0.y + b * p1.y + c * p2.y + d * p3.y,
+            );
+            self.line_to(p.x, p.y);
+        }
+    }
+
+    fn close(&mut self) {
+        if self.last != self.start {
+            let start = self.start;
+            self.line_to(start.x, start.y);
+        }
+    }
+}
+
+/// `run` of `chars` without whitespace at either end.
+fn trim(chars: &[(usize, bool, Range<f32>)], run: Range<usize>) -> Range<usize> {
+    let start = (run.start..run.end)
+        .find(|&i| !chars[i].1)
+        .unwrap_or(run.end);
+    let end = (start..run.end)
+        .rev()
+        .find(|&i| !chars[i].1)
+        .map_or(start, |i| i + 1);
+    start..end
+}
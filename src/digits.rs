@@ -0,0 +1,156 @@
+//! Pre-rasterized digits for fast numeric displays, see `DigitSet`.
+#[cfg(all(feature = "libm-math", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{point, vector, Font, Scale, Vector};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// A pre-rasterized glyph of a `DigitSet`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DigitTile {
+    /// Offset from the pen position, on the baseline, to the top left pixel of
+    /// the tile.
+    pub offset: Vector<i32>,
+    pub width: u32,
+    pub height: u32,
+    /// Coverage values in the range `0.0..=1.0`, row major.
+    pub coverage: Vec<f32>,
+    /// Whole pixels to advance the pen by after this glyph.
+    pub advance: i32,
+}
+
+/// Digits `0`-`9` & separators rasterized once at a scale, for FPS counters,
+/// scores & similar that change every frame.
+///
+/// Digits are given the same whole pixel advance, the widest digit advance
+/// rounded, & are centered within it. So numbers don't jitter as they change
+/// & every tile can be reused at any whole pixel position. Separators keep
+/// their own advance, rounded.
+///
+/// # Example
+///
+/// ```
+/// # use rusttype::{DigitSet, Font, Scale};
+/// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+/// # let font = Font::try_from_bytes(font_data).unwrap();
+/// let digits = DigitSet::new(&font, Scale::uniform(16.0));
+///
+/// let (width, height) = (100, 20);
+/// let mut pixels = vec![0.0; width * height];
+/// // each frame
+/// let baseline = 15;
+/// digits.draw("60.0", |x, y, v| {
+///     let (x, y) = (x as usize, (baseline + y) as usize);
+///     if x < width && y < height {
+///         pixels[x + y * width] = v;
+///     }
+/// });
+/// ```
+#[derive(Clone, Debug)]
+pub struct DigitSet {
+    /// Tiles sorted by char.
+    tiles: Vec<(char, DigitTile)>,
+    digit_advance: i32,
+}
+
+impl DigitSet {
+    /// Separators rasterized by `DigitSet::new` as well as the digits.
+    pub const DEFAULT_SEPARATORS: &'static str = " .,:-+%";
+
+    /// Rasterizes the digits & `DEFAULT_SEPARATORS` of `font` at `scale`.
+    pub fn new(font: &Font<'_>, scale: Scale) -> Self {
+        Self::with_separators(font, scale, Self::DEFAULT_SEPARATORS)
+    }
+
+    /// Rasterizes the digits & the chars of `separators` of `font` at `scale`.
+    pub fn with_separators(font: &Font<'_>, scale: Scale, separators: &str) -> Self {
+        let digit_advance = ('0'..='9')
+            .map(|c| font.glyph(c).scaled(scale).h_metrics().advance_width)
+            .fold(0.0_f32, f32::max)
+            .round();
+
+        let mut tiles: Vec<_> = ('0'..='9')
+            .map(|c| (c, Some(digit_advance)))
+            .chain(separators.chars().map(|c| (c, None)))
+            .map(|(c, tabular_advance)| {
+                let glyph = font.glyph(c).scaled(scale);
+                let own_advance = glyph.h_metrics().advance_width;
+                let advance = tabular_advance.unwrap_or_else(|| own_advance.round());
+                let glyph = glyph.positioned(point((advance - own_advance) / 2.0, 0.0));
+
+                let tile = match glyph.pixel_bounding_box() {
+                    Some(bb) => {
+                        let (width, height) = (bb.width() as u32, bb.height() as u32);
+                        let mut coverage = vec![0.0; (width * height) as usize];
+                        glyph.draw(|x, y, v| coverage[(x + y * width) as usize] = v);
+                        DigitTile {
+                            offset: vector(bb.min.x, bb.min.y),
+                            width,
+                            height,
+                            coverage,
+                            advance: advance as i32,
+                        }
+                    }
+                    None => DigitTile {
+                        offset: vector(0, 0),
+                        width: 0,
+                        height: 0,
+                        coverage: Vec::new(),
+                        advance: advance as i32,
+                    },
+                };
+                (c, tile)
+            })
+            .collect();
+        tiles.sort_by_key(|(c, _)| *c);
+        tiles.dedup_by_key(|(c, _)| *c);
+
+        Self {
+            tiles,
+            digit_advance: digit_advance as i32,
+        }
+    }
+
+    /// The whole pixel advance shared by all digits.
+    #[inline]
+    pub fn digit_advance(&self) -> i32 {
+        self.digit_advance
+    }
+
+    /// Returns the tile of `c`, if rasterized.
+    pub fn tile(&self, c: char) -> Option<&DigitTile> {
+        let idx = self.tiles.binary_search_by_key(&c, |(c, _)| *c).ok()?;
+        Some(&self.tiles[idx].1)
+    }
+
+    /// Returns the whole pixel width of `s`. Chars without a tile are
+    /// skipped.
+    pub fn width(&self, s: &str) -> i32 {
+        s.chars()
+            .filter_map(|c| self.tile(c))
+            .map(|t| t.advance)
+            .sum()
+    }
+
+    /// Draws `s` with the pen starting at `(0, 0)` on the baseline, calling
+    /// `o` with the pixel position & coverage of every pixel of every tile.
+    /// Positions left of or above the start are negative. Chars without a
+    /// tile are skipped.
+    ///
+    /// Returns the whole pixel width of `s`.
+    pub fn draw<O: FnMut(i32, i32, f32)>(&self, s: &str, mut o: O) -> i32 {
+        let mut pen = 0;
+        for tile in s.chars().filter_map(|c| self.tile(c)) {
+            if tile.width > 0 {
+                let rows = tile.coverage.chunks_exact(tile.width as usize);
+                for (y, row) in rows.enumerate() {
+                    for (x, &v) in row.iter().enumerate() {
+                        o(pen + tile.offset.x + x as i32, tile.offset.y + y as i32, v);
+                    }
+                }
+            }
+            pen += tile.advance;
+        }
+        pen
+    }
+}
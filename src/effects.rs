@@ -0,0 +1,335 @@
+//! Shadow, outline & gradient text effects, see `GlyphRun::draw_with_effects`.
+#[cfg(all(feature = "libm-math", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{point, vector, CoverageMask, GlyphRun, Point, Rect, Vector};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// A shadow drawn under the text & its outline, see `TextEffects::shadow`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Shadow {
+    /// Offset of the shadow from the text, rounded to whole pixels.
+    pub offset: Vector<f32>,
+    /// Standard deviation of the gaussian blur in pixels, `0.0` for a hard
+    /// shadow. Blurring needs the `blur` feature, otherwise shadows are hard.
+    pub blur: f32,
+    pub color: [u8; 4],
+}
+
+/// A stroke around the outside of the text drawn under the fill, see
+/// `TextEffects::outline`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Outline {
+    /// Stroke width in pixels.
+    pub width: f32,
+    pub color: [u8; 4],
+}
+
+/// A linear gradient from `start_color` at `start` to `end_color` at `end`,
+/// in pixel coordinates, extended with the end colors beyond them. See
+/// `TextEffects::gradient`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LinearGradient {
+    pub start: Point<f32>,
+    pub end: Point<f32>,
+    pub start_color: [u8; 4],
+    pub end_color: [u8; 4],
+}
+
+impl LinearGradient {
+    /// Returns the color of the gradient at `p`, interpolated in straight
+    /// alpha rgba.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// let gradient = LinearGradient {
+    ///     start: point(0.0, 0.0),
+    ///     end: point(0.0, 10.0),
+    ///     start_color: [255, 0, 0, 255],
+    ///     end_color: [0, 0, 255, 255],
+    /// };
+    /// assert_eq!(gradient.color_at(point(3.0, 5.0)), [128, 0, 128, 255]);
+    /// assert_eq!(gradient.color_at(point(0.0, 20.0)), [0, 0, 255, 255]);
+    /// ```
+    pub fn color_at(&self, p: Point<f32>) -> [u8; 4] {
+        let direction = self.end - self.start;
+        let length_sq = direction.x * direction.x + direction.y * direction.y;
+        let t = match length_sq > 0.0 {
+            true => {
+                let offset = p - self.start;
+                ((offset.x * direction.x + offset.y * direction.y) / length_sq).clamp(0.0, 1.0)
+            }
+            false => 0.0,
+        };
+        let mut color = [0; 4];
+        for ((c, &from), &to) in color.iter_mut().zip(&self.start_color).zip(&self.end_color) {
+            *c = (f32::from(from) + (f32::from(to) - f32::from(from)) * t).round() as u8;
+        }
+        color
+    }
+}
+
+/// The fill color & effects of a `GlyphRun` drawn with
+/// `GlyphRun::draw_with_effects`. Colors are straight alpha rgba.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TextEffects {
+    pub fill: [u8; 4],
+    /// Fills the text with a gradient instead of the `fill` color.
+    pub gradient: Option<LinearGradient>,
+    pub outline: Option<Outline>,
+    pub shadow: Option<Shadow>,
+}
+
+impl TextEffects {
+    /// Text filled with `fill`, without effects.
+    pub fn new(fill: [u8; 4]) -> Self {
+        Self {
+            fill,
+            gradient: None,
+            outline: None,
+            shadow: None,
+        }
+    }
+
+    /// Fills the text with `gradient` instead of the `fill` color, e.g. for
+    /// headlines.
+    pub fn gradient(mut self, gradient: LinearGradient) -> Self {
+        self.gradient = Some(gradient);
+        self
+    }
+
+    /// Strokes the text `width` pixels outwards in `color`.
+    pub fn outline(mut self, width: f32, color: [u8; 4]) -> Self {
+        self.outline = Some(Outline { width, color });
+        self
+    }
+
+    /// Draws a shadow of the text offset by `offset`, blurred by `blur` &
+    /// in `color`.
+    pub fn shadow(mut self, offset: Vector<f32>, blur: f32, color: [u8; 4]) -> Self {
+        self.shadow = Some(Shadow {
+            offset,
+            blur,
+            color,
+        });
+        self
+    }
+}
+
+/// A coverage mask drawn in one color, with its top left pixel position.
+struct Layer {
+    mask: CoverageMask,
+    origin: Point<i32>,
+    color: [u8; 4],
+}
+
+impl Layer {
+    fn rect(&self) -> Rect<i32> {
+        Rect {
+            min: self.origin,
+            max: self.origin + vector(self.mask.width as i32, self.mask.height as i32),
+        }
+    }
+}
+
+impl GlyphRun<'_> {
+    /// Returns the pixel bounding box of the run drawn with `effects`,
+    /// including the outline & shadow. `None` if no glyph has a shape.
+    pub fn effects_pixel_bounding_box(&self, effects: &TextEffects) -> Option<Rect<i32>> {
+        self.effect_layers(effects)
+            .iter()
+            .map(Layer::rect)
+            .reduce(union)
+    }
+
+    /// Draws the run with `effects` in one call: the shadow, under the
+    /// outline, under the fill, composited source over.
+    ///
+    /// Calls `o` with every pixel of the `effects_pixel_bounding_box`,
+    /// relative to its top left, & its straight alpha rgba color in the range
+    /// `0.0..=1.0`, like `PositionedGlyph::draw_color`. Glyphs are drawn by
+    /// coverage, so color glyphs are filled in the `fill` color, or
+    /// `gradient`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let options = LayoutOptions::default();
+    /// let run = font.glyph_run(&SimpleShaper, "Hello", Scale::uniform(24.0), point(0.0, 20.0), &options);
+    /// let effects = TextEffects::new([255, 255, 255, 255])
+    ///     .outline(1.5, [0, 0, 0, 255])
+    ///     .shadow(vector(2.0, 2.0), 1.0, [0, 0, 0, 128]);
+    ///
+    /// let bb = run.effects_pixel_bounding_box(&effects).unwrap();
+    /// let mut rgba = vec![[0.0; 4]; (bb.width() * bb.height()) as usize];
+    /// run.draw_with_effects(&effects, |x, y, color| {
+    ///     rgba[(x + y * bb.width() as u32) as usize] = color;
+    /// });
+    /// ```
+    pub fn draw_with_effects<O: FnMut(u32, u32, [f32; 4])>(&self, effects: &TextEffects, o: O) {
+        match effects.gradient {
+            // sampled at pixel centres
+            Some(gradient) => self.draw_with_fill(
+                effects,
+                |x, y| gradient.color_at(point(x as f32 + 0.5, y as f32 + 0.5)),
+                o,
+            ),
+            None => self.draw_with_fill(effects, |_, _| effects.fill, o),
+        }
+    }
+
+    /// Draws the run with `effects` like `draw_with_effects`, filling the
+    /// text with the straight alpha rgba color returned from `fill` for each
+    /// pixel, e.g. for patterns or gradients other than `LinearGradient`.
+    /// `fill` is called with the pixel position in the coordinates of the
+    /// glyph positions, & only for pixels covered by the text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let options = LayoutOptions::default();
+    /// let run = font.glyph_run(&SimpleShaper, "Hello", Scale::uniform(24.0), point(0.0, 20.0), &options);
+    /// let effects = TextEffects::new([255, 255, 255, 255]).outline(1.0, [0, 0, 0, 255]);
+    /// let bb = run.effects_pixel_bounding_box(&effects).unwrap();
+    /// let mut rgba = vec![[0.0; 4]; (bb.width() * bb.height()) as usize];
+    /// // stripes every other pixel row
+    /// let stripes = |_, y: i32| if y % 2 == 0 { [255, 0, 0, 255] } else { [255, 255, 0, 255] };
+    /// run.draw_with_fill(&effects, stripes, |x, y, color| {
+    ///     rgba[(x + y * bb.width() as u32) as usize] = color;
+    /// });
+    /// ```
+    pub fn draw_with_fill<F, O>(&self, effects: &TextEffects, mut fill: F, mut o: O)
+    where
+        F: FnMut(i32, i32) -> [u8; 4],
+        O: FnMut(u32, u32, [f32; 4]),
+    {
+        let layers = self.effect_layers(effects);
+        let bb = match layers.iter().map(Layer::rect).reduce(union) {
+            Some(bb) => bb,
+            None => return,
+        };
+        let width = bb.width() as usize;
+        // premultiplied rgba
+        let mut pixels = vec![[0.0_f32; 4]; width * bb.height() as usize];
+        for (index, layer) in layers.iter().enumerate() {
+            // the fill is the top layer
+            let is_fill = index == layers.len() - 1;
+            let offset = layer.origin - bb.min;
+            layer.mask.for_each_pixel(|x, y, v| {
+                if v == 0.0 {
+                    return;
+                }
+                let color = match is_fill {
+                    true => fill(x as i32 + layer.origin.x, y as i32 + layer.origin.y),
+                    false => layer.color,
+                };
+                let alpha = f32::from(color[3]) / 255.0;
+                let color = [
+                    f32::from(color[0]) / 255.0 * alpha,
+                    f32::from(color[1]) / 255.0 * alpha,
+                    f32::from(color[2]) / 255.0 * alpha,
+                    alpha,
+                ];
+                let pixel = &mut pixels
+                    [(x as i32 + offset.x) as usize + (y as i32 + offset.y) as usize * width];
+                for (p, c) in pixel.iter_mut().zip(&color) {
+                    // source over
+                    *p = c * v + *p * (1.0 - alpha * v);
+                }
+            });
+        }
+
+        for (i, [r, g, b, a]) in pixels.into_iter().enumerate() {
+            let straight = |c: f32| if a > 0.0 { (c / a).min(1.0) } else { 0.0 };
+            o(
+                (i % width) as u32,
+                (i / width) as u32,
+                [straight(r), straight(g), straight(b), a],
+            );
+        }
+    }
+
+    /// The layers of the run drawn with `effects`, bottom first.
+    fn effect_layers(&self, effects: &TextEffects) -> Vec<Layer> {
+        let fill = match self.fill_layer(effects.fill) {
+            Some(fill) => fill,
+            None => return Vec::new(),
+        };
+        let mut layers = Vec::with_capacity(3);
+        let outline = effects.outline.filter(|o| o.width > 0.0).map(|outline| {
+            let pad = outline.width.ceil() as i32;
+            Layer {
+                mask: fill.mask.dilate(outline.width),
+                origin: fill.origin - vector(pad, pad),
+                color: outline.color,
+            }
+        });
+        if let Some(shadow) = effects.shadow {
+            // the shadow of the whole shape, outline included
+            let shape = outline.as_ref().unwrap_or(&fill);
+            let offset = vector(
+                shadow.offset.x.round() as i32,
+                shadow.offset.y.round() as i32,
+            );
+            #[cfg(feature = "blur")]
+            let (mask, pad) = (
+                shape.mask.gaussian_blur(shadow.blur),
+                CoverageMask::blur_padding(shadow.blur) as i32,
+            );
+            #[cfg(not(feature = "blur"))]
+            let (mask, pad) = (shape.mask.clone(), 0);
+            layers.push(Layer {
+                mask,
+                origin: shape.origin + offset - vector(pad, pad),
+                color: shadow.color,
+            });
+        }
+        layers.extend(outline);
+        layers.push(fill);
+        layers
+    }
+
+    /// The coverage of all glyphs of the run, overlapping glyphs summed.
+    fn fill_layer(&self, color: [u8; 4]) -> Option<Layer> {
+        let bb = self
+            .glyphs()
+            .iter()
+            .filter_map(|g| g.pixel_bounding_box())
+            .reduce(union)?;
+        let mut mask = CoverageMask::new(bb.width() as u32, bb.height() as u32);
+        let width = mask.width as i32;
+        for glyph in self.glyphs() {
+            let glyph_bb = match glyph.pixel_bounding_box() {
+                Some(bb) => bb,
+                None => continue,
+            };
+            let offset = glyph_bb.min - bb.min;
+            glyph.draw(|x, y, v| {
+                let index = (x as i32 + offset.x) + (y as i32 + offset.y) * width;
+                let coverage = &mut mask.coverage[index as usize];
+                *coverage = (*coverage + v).min(1.0);
+            });
+        }
+        Some(Layer {
+            mask,
+            origin: bb.min,
+            color,
+        })
+    }
+}
+
+/// The smallest rect containing `a` & `b`.
+fn union(a: Rect<i32>, b: Rect<i32>) -> Rect<i32> {
+    Rect {
+        min: point(a.min.x.min(b.min.x), a.min.y.min(b.min.y)),
+        max: point(a.max.x.max(b.max.x), a.max.y.max(b.max.y)),
+    }
+}
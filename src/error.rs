@@ -0,0 +1,78 @@
+//! Font loading errors, see `Font::from_bytes`.
+use crate::integrity::DisplayTag;
+use crate::Font;
+use core::fmt;
+use owned_ttf_parser::FaceParsingError;
+
+/// Why font data couldn't be loaded, see `Font::from_bytes`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Error {
+    /// The data doesn't start with a TrueType, OpenType or font collection
+    /// version, e.g. it isn't a font file or is a compressed WOFF file.
+    UnsupportedVersion { version: [u8; 4] },
+    /// The font collection `index` is past the `count` fonts of the data.
+    IndexOutOfRange { index: u32, count: u32 },
+    /// A table required to use the font is missing or malformed.
+    MissingTable { tag: [u8; 4] },
+    /// The `cmap` table is present but has no readable subtable, so chars
+    /// can't be mapped to glyphs.
+    MalformedCmap,
+    /// The data is truncated or its table directory is out of bounds.
+    Malformed,
+}
+
+impl Error {
+    /// The error of parsing `data` at `index` failing with `error`.
+    pub(crate) fn from_parsing(error: FaceParsingError, data: &[u8], index: u32) -> Self {
+        match error {
+            FaceParsingError::UnknownMagic => match data.get(..4) {
+                Some(&[a, b, c, d]) => Error::UnsupportedVersion {
+                    version: [a, b, c, d],
+                },
+                _ => Error::Malformed,
+            },
+            FaceParsingError::FaceIndexOutOfBounds => Error::IndexOutOfRange {
+                index,
+                count: owned_ttf_parser::fonts_in_collection(data).unwrap_or(1),
+            },
+            FaceParsingError::NoHeadTable => Error::MissingTable { tag: *b"head" },
+            FaceParsingError::NoHheaTable => Error::MissingTable { tag: *b"hhea" },
+            FaceParsingError::NoMaxpTable => Error::MissingTable { tag: *b"maxp" },
+            FaceParsingError::MalformedFont => Error::Malformed,
+        }
+    }
+
+    /// Checks tables parsing the face doesn't require, failing `from_bytes` &
+    /// the like but not `try_from_bytes`.
+    pub(crate) fn check(font: &Font<'_>) -> Result<(), Self> {
+        let cmap = owned_ttf_parser::Tag::from_bytes(b"cmap");
+        if font.raw_face().table(cmap).is_some() && font.inner().tables().cmap.is_none() {
+            return Err(Error::MalformedCmap);
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Error::UnsupportedVersion { version } => {
+                write!(f, "unsupported font version '{}'", DisplayTag(version))
+            }
+            Error::IndexOutOfRange { index, count } => {
+                write!(f, "font index {} is out of range of {} fonts", index, count)
+            }
+            Error::MissingTable { tag } => write!(
+                f,
+                "required '{}' table is missing or malformed",
+                DisplayTag(tag)
+            ),
+            Error::MalformedCmap => write!(f, "'cmap' table is malformed"),
+            Error::Malformed => write!(f, "font data is malformed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
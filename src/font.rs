@@ -1,11 +1,21 @@
-use crate::{Glyph, GlyphIter, IntoGlyphId, LayoutIter, Point, Scale, VMetrics};
+use crate::integrity::IntegrityReport;
+#[cfg(all(feature = "libm-math", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::once::OnceCache;
+use crate::optical::OpticalKerning;
+use crate::{
+    point, vector, AdvanceWidths, BitmapPolicy, CharMapIter, CoverageSummary, EmbeddingPermissions,
+    EmbeddingUsage, Error, FontClass, Glyph, GlyphId, GlyphIter, IntoGlyphId, Kerning, LayoutIter,
+    LayoutOptions, Panose, PixelMode, Point, Rect, Scale, VMetrics, Vector,
+};
 #[cfg(not(feature = "has-atomics"))]
-use alloc::rc::Rc as Arc;
+use alloc::rc::{Rc as Arc, Weak};
 #[cfg(feature = "has-atomics")]
-use alloc::sync::Arc;
+use alloc::sync::{Arc, Weak};
 #[cfg(not(feature = "std"))]
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
 use core::fmt;
+use yoke::{Yoke, Yokeable};
 
 /// A single font. This may or may not own the font data.
 ///
@@ -14,6 +24,32 @@ use core::fmt;
 /// cases ie both dynamically loaded owned data and for referenced compile time
 /// font data.
 ///
+/// # Load cost
+/// Constructing a font parses the table directory & the headers of the
+/// tables used for metrics & outlines, without reading glyph data, so is
+/// cheap enough to do for every font in a font list. The `GSUB`, `GPOS` &
+/// `COLR` tables are parsed when used, e.g. laying out text or drawing
+/// color glyphs. Derived data, like the full character map, is computed on
+/// first use & shared by all clones.
+///
+/// # Thread safety
+/// With the default `has-atomics` (or `std`) feature `Font`, glyphs & the
+/// layout iterators are `Send`, `Sync`, `UnwindSafe` & `RefUnwindSafe`. A
+/// single font can be used to lay out & draw from many threads at once, lazily
+/// computed data is initialised atomically. No thread pool is needed or used.
+///
+/// # Memory
+/// Clones share the parsed font & its lazily computed data, which are freed
+/// when the last clone is dropped. Glyphs, e.g. `Glyph`, `ScaledGlyph` &
+/// `PositionedGlyph`, hold a clone so keep the data alive too, while layout
+/// iterators borrow the font. Owned font data, from `try_from_vec`, is freed
+/// with it. Borrowed data is never freed by the font.
+///
+/// `strong_count` & `unload_hint` report whether dropping a font releases its
+/// data, `downgrade` returns a `WeakFont` referring to the data without
+/// keeping it alive. Reloads replace the data, the previous data is freed
+/// once clones made before the reload are dropped.
+///
 /// # Example
 ///
 /// ```
@@ -28,9 +64,343 @@ use core::fmt;
 /// # }
 /// ```
 #[derive(Clone)]
-pub enum Font<'a> {
-    Ref(Arc<owned_ttf_parser::Font<'a>>),
-    Owned(Arc<owned_ttf_parser::OwnedFont>),
+pub struct Font<'a> {
+    inner: Arc<FontInner<'a>>,
+    /// Shared by all clones & reloads, see `Font::reload`.
+    generations: Arc<Generations>,
+    bitmap_policy: BitmapPolicy,
+    pixel_mode: PixelMode,
+}
+
+// Keep the documented thread safety guarantees.
+#[cfg(feature = "has-atomics")]
+const _: fn() = || {
+    use core::panic::{RefUnwindSafe, UnwindSafe};
+    fn assert_thread_safe<T: Send + Sync + UnwindSafe + RefUnwindSafe>() {}
+    assert_thread_safe::<Font<'static>>();
+};
+
+/// Font data shared by all clones of a `Font`.
+///
+/// Tables other than `GSUB`, `GPOS` & `COLR` are parsed when constructing,
+/// see `Font`. Derived data that is expensive to compute is built lazily on
+/// first use & cached here.
+struct FontInner<'a> {
+    face: FontFace<'a>,
+    /// Font collection index the face was parsed from.
+    index: u32,
+    /// See `Font::generation`.
+    generation: u32,
+    /// All char -> glyph mappings sorted by char, see `Font::char_map`.
+    char_map: OnceCache<Vec<(char, GlyphId)>>,
+    /// All glyph -> char mappings sorted by glyph, see `Font::chars_for_glyph`.
+    glyph_chars: OnceCache<Vec<(GlyphId, char)>>,
+    /// See `Font::pixel_grid`.
+    pixel_grid: OnceCache<Option<u16>>,
+    /// See `Font::gpos_lookups`.
+    gpos: OnceCache<crate::gpos::GposLookups>,
+    /// Axis values applied to `face`, see `Font::with_variations`.
+    variations: Vec<([u8; 4], f32)>,
+    /// See `ScaledGlyph::hinted`.
+    #[cfg(feature = "hinting")]
+    hinting: crate::hinting::HintingCache,
+}
+
+// Only ever stored behind the `Arc` so the variant size difference is moot.
+#[allow(clippy::large_enum_variant)]
+enum FontFace<'a> {
+    Ref(ParsedFace<'a>),
+    /// Parsed from owned data, which may be shared with other faces, e.g. the
+    /// other fonts of a collection.
+    Shared(Yoke<ParsedFace<'static>, Arc<Vec<u8>>>),
+}
+
+/// A face & its table directory, which the face doesn't keep when parsed
+/// from a subset of its tables.
+#[derive(Clone, Yokeable)]
+struct ParsedFace<'a> {
+    face: owned_ttf_parser::Face<'a>,
+    raw: owned_ttf_parser::RawFace<'a>,
+}
+
+impl<'a> ParsedFace<'a> {
+    /// Parses the face without the tables parsed on first use, see `Font`.
+    fn parse(data: &'a [u8], index: u32) -> Result<Self, owned_ttf_parser::FaceParsingError> {
+        let raw = owned_ttf_parser::RawFace::parse(data, index)?;
+        let face = owned_ttf_parser::Face::from_raw_tables(raw_tables(raw, false))?;
+        Ok(ParsedFace { face, raw })
+    }
+}
+
+impl<'a> FontFace<'a> {
+    fn parse(data: &'a [u8], index: u32) -> Result<Self, owned_ttf_parser::FaceParsingError> {
+        ParsedFace::parse(data, index).map(FontFace::Ref)
+    }
+
+    #[inline]
+    fn get(&self) -> &ParsedFace<'_> {
+        match self {
+            FontFace::Ref(f) => f,
+            FontFace::Shared(f) => f.get(),
+        }
+    }
+}
+
+impl FontFace<'static> {
+    fn shared(data: Arc<Vec<u8>>, index: u32) -> Result<Self, owned_ttf_parser::FaceParsingError> {
+        let face = Yoke::try_attach_to_cart(data, |data| ParsedFace::parse(data, index))?;
+        Ok(FontFace::Shared(face))
+    }
+}
+
+/// Collects the tables of `raw` to parse, including `GSUB`, `GPOS` & `COLR`
+/// only if `layout`.
+fn raw_tables(
+    raw: owned_ttf_parser::RawFace<'_>,
+    layout: bool,
+) -> owned_ttf_parser::RawFaceTables<'_> {
+    let mut tables = owned_ttf_parser::RawFaceTables::default();
+    for record in raw.table_records {
+        let start = record.offset as usize;
+        let data = start
+            .checked_add(record.length as usize)
+            .and_then(|end| raw.data.get(start..end));
+        let data = match data {
+            Some(data) => data,
+            None => continue,
+        };
+        let table = match &record.tag.to_bytes() {
+            b"COLR" | b"GPOS" | b"GSUB" if !layout => continue,
+            b"COLR" => &mut tables.colr,
+            b"GPOS" => &mut tables.gpos,
+            b"GSUB" => &mut tables.gsub,
+            b"head" => {
+                tables.head = data;
+                continue;
+            }
+            b"hhea" => {
+                tables.hhea = data;
+                continue;
+            }
+            b"maxp" => {
+                tables.maxp = data;
+                continue;
+            }
+            b"bdat" => &mut tables.bdat,
+            b"bloc" => &mut tables.bloc,
+            b"CBDT" => &mut tables.cbdt,
+            b"CBLC" => &mut tables.cblc,
+            b"CFF " => &mut tables.cff,
+            b"CFF2" => &mut tables.cff2,
+            b"CPAL" => &mut tables.cpal,
+            b"EBDT" => &mut tables.ebdt,
+            b"EBLC" => &mut tables.eblc,
+            b"GDEF" => &mut tables.gdef,
+            b"MATH" => &mut tables.math,
+            b"HVAR" => &mut tables.hvar,
+            b"MVAR" => &mut tables.mvar,
+            b"OS/2" => &mut tables.os2,
+            b"SVG " => &mut tables.svg,
+            b"VORG" => &mut tables.vorg,
+            b"VVAR" => &mut tables.vvar,
+            b"ankr" => &mut tables.ankr,
+            b"avar" => &mut tables.avar,
+            b"cmap" => &mut tables.cmap,
+            b"feat" => &mut tables.feat,
+            b"fvar" => &mut tables.fvar,
+            b"glyf" => &mut tables.glyf,
+            b"gvar" => &mut tables.gvar,
+            b"hmtx" => &mut tables.hmtx,
+            b"kern" => &mut tables.kern,
+            b"kerx" => &mut tables.kerx,
+            b"loca" => &mut tables.loca,
+            b"morx" => &mut tables.morx,
+            b"name" => &mut tables.name,
+            b"post" => &mut tables.post,
+            b"sbix" => &mut tables.sbix,
+            b"STAT" => &mut tables.stat,
+            b"trak" => &mut tables.trak,
+            b"vhea" => &mut tables.vhea,
+            b"vmtx" => &mut tables.vmtx,
+            _ => continue,
+        };
+        *table = Some(data);
+    }
+    tables
+}
+
+impl<'a> FontInner<'a> {
+    fn new(
+        mut face: FontFace<'a>,
+        index: u32,
+        generation: u32,
+        variations: Vec<([u8; 4], f32)>,
+    ) -> Arc<Self> {
+        for &(tag, value) in &variations {
+            let tag = owned_ttf_parser::Tag::from_bytes(&tag);
+            match &mut face {
+                FontFace::Ref(f) => {
+                    f.face.set_variation(tag, value);
+                }
+                FontFace::Shared(f) => f.with_mut(move |f| {
+                    f.face.set_variation(tag, value);
+                }),
+            };
+        }
+        Arc::new(FontInner {
+            face,
+            index,
+            generation,
+            char_map: OnceCache::new(),
+            glyph_chars: OnceCache::new(),
+            pixel_grid: OnceCache::new(),
+            gpos: OnceCache::new(),
+            variations,
+            #[cfg(feature = "hinting")]
+            hinting: Default::default(),
+        })
+    }
+}
+
+/// Generation counter shared by a font & all of its reloads.
+#[derive(Default)]
+struct Generations {
+    #[cfg(feature = "has-atomics")]
+    latest: core::sync::atomic::AtomicU32,
+    #[cfg(not(feature = "has-atomics"))]
+    latest: core::cell::Cell<u32>,
+}
+
+impl Generations {
+    #[inline]
+    fn latest(&self) -> u32 {
+        #[cfg(feature = "has-atomics")]
+        {
+            self.latest.load(core::sync::atomic::Ordering::Acquire)
+        }
+        #[cfg(not(feature = "has-atomics"))]
+        {
+            self.latest.get()
+        }
+    }
+
+    /// Allocates a new, unique, generation returning it.
+    #[inline]
+    fn bump(&self) -> u32 {
+        #[cfg(feature = "has-atomics")]
+        {
+            1 + self
+                .latest
+                .fetch_add(1, core::sync::atomic::Ordering::AcqRel)
+        }
+        #[cfg(not(feature = "has-atomics"))]
+        {
+            let next = self.latest.get() + 1;
+            self.latest.set(next);
+            next
+        }
+    }
+}
+
+/// Observes reloads of a font, see `Font::reload_token`.
+///
+/// Caches of data derived from a font, e.g. rasterized glyphs, can hold a
+/// token to find out when they should invalidate their entries.
+///
+/// # Example
+///
+/// ```
+/// # use rusttype::Font;
+/// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+/// let mut font = Font::try_from_bytes(font_data).unwrap();
+/// let mut token = font.reload_token();
+/// assert!(!token.take_reloaded());
+///
+/// font.reload(font_data).unwrap();
+/// assert!(token.take_reloaded());
+/// assert!(!token.take_reloaded());
+/// ```
+#[derive(Clone)]
+pub struct ReloadToken {
+    generations: Arc<Generations>,
+    seen: u32,
+}
+
+impl ReloadToken {
+    /// The generation of the most recent reload of the font.
+    #[inline]
+    pub fn generation(&self) -> u32 {
+        self.generations.latest()
+    }
+
+    /// Returns `true` if the font has been reloaded since this token was
+    /// created or last returned `true`.
+    pub fn take_reloaded(&mut self) -> bool {
+        let latest = self.generations.latest();
+        let reloaded = latest != self.seen;
+        self.seen = latest;
+        reloaded
+    }
+}
+
+impl fmt::Debug for ReloadToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReloadToken")
+            .field("generation", &self.generation())
+            .field("seen", &self.seen)
+            .finish()
+    }
+}
+
+/// A handle to a font that doesn't keep its data alive, see `Font::downgrade`.
+///
+/// # Example
+///
+/// ```
+/// # use rusttype::Font;
+/// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+/// let font = Font::try_from_vec(font_data.to_vec()).unwrap();
+/// let weak = font.downgrade();
+/// assert!(weak.upgrade().is_some());
+///
+/// drop(font);
+/// // the font data has been freed
+/// assert!(weak.upgrade().is_none());
+/// ```
+#[derive(Clone)]
+pub struct WeakFont<'a> {
+    inner: Weak<FontInner<'a>>,
+    generations: Arc<Generations>,
+    bitmap_policy: BitmapPolicy,
+    pixel_mode: PixelMode,
+}
+
+impl<'a> WeakFont<'a> {
+    /// Returns the font if its data is still alive. The font has the bitmap
+    /// policy & pixel mode of the font this was downgraded from.
+    pub fn upgrade(&self) -> Option<Font<'a>> {
+        Some(Font {
+            inner: self.inner.upgrade()?,
+            generations: Arc::clone(&self.generations),
+            bitmap_policy: self.bitmap_policy,
+            pixel_mode: self.pixel_mode,
+        })
+    }
+
+    /// The number of fonts keeping the data alive, `0` once it's freed. See
+    /// `Font::strong_count`.
+    #[inline]
+    pub fn strong_count(&self) -> usize {
+        self.inner.strong_count()
+    }
+}
+
+impl fmt::Debug for WeakFont<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakFont")
+            .field("strong_count", &self.strong_count())
+            .finish()
+    }
 }
 
 impl fmt::Debug for Font<'_> {
@@ -42,49 +412,354 @@ impl fmt::Debug for Font<'_> {
 impl Font<'_> {
     /// Creates a Font from byte-slice data.
     ///
-    /// Returns `None` for invalid data.
+    /// Returns `None` for invalid data, see `from_bytes` for why. Unlike
+    /// `from_bytes` fonts with a malformed `cmap` table load, mapping every
+    /// char to glyph 0.
     pub fn try_from_bytes(bytes: &[u8]) -> Option<Font<'_>> {
         Self::try_from_bytes_and_index(bytes, 0)
     }
 
     /// Creates a Font from byte-slice data & a font collection `index`.
     ///
-    /// Returns `None` for invalid data.
+    /// Returns `None` for invalid data, see `try_from_bytes`.
     pub fn try_from_bytes_and_index(bytes: &[u8], index: u32) -> Option<Font<'_>> {
-        let inner = Arc::new(owned_ttf_parser::Font::from_data(bytes, index)?);
-        Some(Font::Ref(inner))
+        let face = FontFace::parse(bytes, index).ok()?;
+        Some(Font::from_face(face, index))
     }
 
     /// Creates a Font from owned font data.
     ///
-    /// Returns `None` for invalid data.
+    /// Returns `None` for invalid data, see `try_from_bytes`.
     pub fn try_from_vec(data: Vec<u8>) -> Option<Font<'static>> {
         Self::try_from_vec_and_index(data, 0)
     }
 
     /// Creates a Font from owned font data & a font collection `index`.
     ///
-    /// Returns `None` for invalid data.
+    /// Returns `None` for invalid data, see `try_from_bytes`.
     pub fn try_from_vec_and_index(data: Vec<u8>, index: u32) -> Option<Font<'static>> {
-        let inner = Arc::new(owned_ttf_parser::OwnedFont::from_vec(data, index)?);
-        Some(Font::Owned(inner))
+        Self::from_shared(Arc::new(data), index).ok()
+    }
+
+    /// Creates a Font from byte-slice data, like `try_from_bytes`, returning
+    /// why invalid data couldn't be loaded. Fonts with a malformed `cmap`
+    /// table fail with `Error::MalformedCmap`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// let error = Font::from_bytes(b"wOFF\0\0\0\0").unwrap_err();
+    /// assert_eq!(error, Error::UnsupportedVersion { version: *b"wOFF" });
+    /// assert_eq!(error.to_string(), "unsupported font version 'wOFF'");
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Font<'_>, Error> {
+        Self::from_bytes_and_index(bytes, 0)
+    }
+
+    /// Creates a Font from byte-slice data & a font collection `index`, see
+    /// `from_bytes`.
+    pub fn from_bytes_and_index(bytes: &[u8], index: u32) -> Result<Font<'_>, Error> {
+        let face =
+            FontFace::parse(bytes, index).map_err(|e| Error::from_parsing(e, bytes, index))?;
+        let font = Font::from_face(face, index);
+        Error::check(&font)?;
+        Ok(font)
+    }
+
+    /// Creates a Font from owned font data, see `from_bytes`.
+    pub fn from_vec(data: Vec<u8>) -> Result<Font<'static>, Error> {
+        Self::from_vec_and_index(data, 0)
+    }
+
+    /// Creates a Font from owned font data & a font collection `index`, see
+    /// `from_bytes`.
+    pub fn from_vec_and_index(data: Vec<u8>, index: u32) -> Result<Font<'static>, Error> {
+        let font = Self::from_shared(Arc::new(data), index)?;
+        Error::check(&font)?;
+        Ok(font)
+    }
+
+    /// Creates a Font from owned font data shared with other fonts, e.g. to
+    /// load every font of a collection without copying the data.
+    pub(crate) fn from_shared(data: Arc<Vec<u8>>, index: u32) -> Result<Font<'static>, Error> {
+        let face = FontFace::shared(Arc::clone(&data), index)
+            .map_err(|e| Error::from_parsing(e, &data, index))?;
+        Ok(Font::from_face(face, index))
     }
 }
 
 impl<'font> Font<'font> {
+    fn from_face(face: FontFace<'font>, index: u32) -> Self {
+        Font {
+            inner: FontInner::new(face, index, 0, Vec::new()),
+            generations: Arc::default(),
+            bitmap_policy: BitmapPolicy::default(),
+            pixel_mode: PixelMode::default(),
+        }
+    }
+
+    /// Replaces the font data with `bytes`, parsed using the same font
+    /// collection index as the current data, e.g. after the font file has been
+    /// edited.
+    ///
+    /// The font keeps its identity, but is given a new `generation`. Clones
+    /// made before the reload keep the previous data, a `ReloadToken` can be
+    /// used to find out when caches need invalidating.
+    ///
+    /// Returns `None` & leaves the font unchanged for invalid data.
+    pub fn reload(&mut self, bytes: &'font [u8]) -> Option<()> {
+        let face = FontFace::parse(bytes, self.inner.index).ok()?;
+        self.reload_face(face);
+        Some(())
+    }
+
+    /// Replaces the font data with owned `data`, see `reload`.
+    ///
+    /// Returns `None` & leaves the font unchanged for invalid data.
+    pub fn reload_from_vec(&mut self, data: Vec<u8>) -> Option<()> {
+        let face = FontFace::shared(Arc::new(data), self.inner.index).ok()?;
+        self.reload_face(face);
+        Some(())
+    }
+
+    fn reload_face(&mut self, face: FontFace<'font>) {
+        let generation = self.generations.bump();
+        let variations = self.inner.variations.clone();
+        self.inner = FontInner::new(face, self.inner.index, generation, variations);
+    }
+
+    /// Returns a new font of the same data with `variations` applied, see
+    /// `Font::with_variations`.
+    pub(crate) fn with_applied_variations(&self, variations: Vec<([u8; 4], f32)>) -> Self {
+        let face = match &self.inner.face {
+            FontFace::Ref(f) => FontFace::Ref(f.clone()),
+            FontFace::Shared(f) => FontFace::Shared(f.clone()),
+        };
+        Font {
+            inner: FontInner::new(face, self.inner.index, 0, variations),
+            generations: Arc::default(),
+            ..self.clone()
+        }
+    }
+
+    /// Axis values applied by `with_variations`.
     #[inline]
-    pub(crate) fn inner(&self) -> &owned_ttf_parser::Font<'_> {
-        use owned_ttf_parser::AsFontRef;
-        match self {
-            Self::Ref(f) => f,
-            Self::Owned(f) => f.as_font(),
+    pub(crate) fn applied_variations(&self) -> &[([u8; 4], f32)] {
+        &self.inner.variations
+    }
+
+    /// The generation of this font's data. Starts at `0` & is given a new,
+    /// unique, value by each `reload`.
+    ///
+    /// Caches can include this in their keys so glyphs from reloaded fonts do
+    /// not match stale entries.
+    #[inline]
+    pub fn generation(&self) -> u32 {
+        self.inner.generation
+    }
+
+    /// The number of fonts sharing this font's data, including this one,
+    /// clones held by glyphs & clones made before a reload of the same data.
+    /// Doesn't include `WeakFont`s.
+    ///
+    /// Fonts made by `reload` & `with_variations` aren't counted, though
+    /// instances made by `with_variations` share the font data.
+    #[inline]
+    pub fn strong_count(&self) -> usize {
+        Arc::strong_count(&self.inner)
+    }
+
+    /// Returns `true` if dropping this font frees its data, as no other
+    /// fonts or glyphs share it. See `strong_count`.
+    ///
+    /// With owned data this frees the font file, unless shared by
+    /// `with_variations` instances or other fonts of a collection. Borrowed
+    /// data is only released for reuse by its owner.
+    #[inline]
+    pub fn unload_hint(&self) -> bool {
+        self.strong_count() == 1
+    }
+
+    /// Returns a `WeakFont` referring to this font's data without keeping it
+    /// alive, e.g. for font registries that shouldn't stop unused fonts from
+    /// being freed.
+    pub fn downgrade(&self) -> WeakFont<'font> {
+        WeakFont {
+            inner: Arc::downgrade(&self.inner),
+            generations: Arc::clone(&self.generations),
+            bitmap_policy: self.bitmap_policy,
+            pixel_mode: self.pixel_mode,
+        }
+    }
+
+    /// Returns a token to observe reloads of this font, or any of its clones.
+    pub fn reload_token(&self) -> ReloadToken {
+        ReloadToken {
+            generations: Arc::clone(&self.generations),
+            seen: self.generations.latest(),
+        }
+    }
+
+    /// Sets when glyphs are drawn from embedded bitmaps instead of outlines,
+    /// honoured by glyphs, layouts & the gpu cache using this font. Clones made
+    /// afterwards keep the policy.
+    ///
+    /// The default `BitmapPolicy::PreferOutline` ignores embedded bitmaps.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// let mut font = Font::try_from_bytes(font_data).unwrap();
+    /// // hand tuned bitmaps for small sizes, outlines for large ones
+    /// font.set_bitmap_policy(BitmapPolicy::BitmapBelow(16));
+    /// assert_eq!(font.bitmap_policy(), BitmapPolicy::BitmapBelow(16));
+    /// ```
+    pub fn set_bitmap_policy(&mut self, policy: BitmapPolicy) {
+        self.bitmap_policy = policy;
+    }
+
+    /// The policy set by `set_bitmap_policy`.
+    #[inline]
+    pub fn bitmap_policy(&self) -> BitmapPolicy {
+        self.bitmap_policy
+    }
+
+    /// Sets when this font is rendered as a pixel font, with whole pixel
+    /// metrics & positions and without anti-aliasing. Clones made afterwards
+    /// keep the mode.
+    ///
+    /// By default, `PixelMode::Auto`, fonts with a `pixel_grid` are rendered
+    /// as pixel fonts. See also `pixel_scale_warning`.
+    pub fn set_pixel_mode(&mut self, mode: PixelMode) {
+        self.pixel_mode = mode;
+    }
+
+    /// The mode set by `set_pixel_mode`.
+    #[inline]
+    pub fn pixel_mode(&self) -> PixelMode {
+        self.pixel_mode
+    }
+
+    /// The design pixel size, in font units, of a pixel font. `None` unless
+    /// every printable ASCII glyph outline is made of straight lines on a
+    /// common grid of at most 64 pixels per em.
+    ///
+    /// The grid is detected on first use & shared by all clones.
+    pub fn pixel_grid(&self) -> Option<u16> {
+        *self
+            .inner
+            .pixel_grid
+            .get_or_init(|| crate::pixel::detect_grid(self))
+    }
+
+    /// The `GPOS` lookups `layout` applies, resolved on first use & shared
+    /// by all clones.
+    pub(crate) fn gpos_lookups(&self) -> &crate::gpos::GposLookups {
+        self.inner
+            .gpos
+            .get_or_init(|| crate::gpos::GposLookups::new(self))
+    }
+
+    /// Returns if glyphs are rendered as a pixel font, see `set_pixel_mode`.
+    #[inline]
+    pub fn is_pixel_rendered(&self) -> bool {
+        match self.pixel_mode {
+            PixelMode::Auto => self.pixel_grid().is_some(),
+            PixelMode::On => true,
+            PixelMode::Off => false,
+        }
+    }
+
+    /// The face, without the `GSUB`, `GPOS` & `COLR` tables, see
+    /// `layout_face`.
+    #[inline]
+    pub(crate) fn inner(&self) -> &owned_ttf_parser::Face<'_> {
+        &self.inner.face.get().face
+    }
+
+    /// The face with only the `GSUB`, `GPOS` & `COLR` tables, which
+    /// constructing doesn't parse, see `Font`. `None` if it has none of them.
+    ///
+    /// This only reads the table headers, but is done on every use rather
+    /// than cached, as the tables borrow the font data.
+    pub(crate) fn layout_face(&self) -> Option<owned_ttf_parser::Face<'_>> {
+        let raw = self.raw_face();
+        let table = |tag: &[u8; 4]| raw.table(owned_ttf_parser::Tag::from_bytes(tag));
+        let tables = owned_ttf_parser::RawFaceTables {
+            head: table(b"head")?,
+            hhea: table(b"hhea")?,
+            maxp: table(b"maxp")?,
+            colr: table(b"COLR"),
+            cpal: table(b"CPAL"),
+            gpos: table(b"GPOS"),
+            gsub: table(b"GSUB"),
+            ..owned_ttf_parser::RawFaceTables::default()
+        };
+        if tables.colr.is_none() && tables.gpos.is_none() && tables.gsub.is_none() {
+            return None;
         }
+        owned_ttf_parser::Face::from_raw_tables(tables).ok()
+    }
+
+    /// The face with all its tables & the font's variations, for shaping.
+    #[cfg(feature = "shaping")]
+    pub(crate) fn full_face(&self) -> owned_ttf_parser::Face<'_> {
+        let face = owned_ttf_parser::Face::from_raw_tables(raw_tables(*self.raw_face(), true));
+        let mut face = face.unwrap_or_else(|_| self.inner().clone());
+        for &(tag, value) in self.inner.variations.iter() {
+            face.set_variation(owned_ttf_parser::Tag::from_bytes(&tag), value);
+        }
+        face
+    }
+
+    /// The table directory of the face.
+    #[inline]
+    pub(crate) fn raw_face(&self) -> &owned_ttf_parser::RawFace<'_> {
+        &self.inner.face.get().raw
+    }
+
+    /// The whole font file data, including any other fonts of a collection.
+    #[cfg(any(feature = "std", feature = "hinting"))]
+    #[inline]
+    pub(crate) fn data(&self) -> &[u8] {
+        self.raw_face().data
+    }
+
+    /// The collection index of the face in `data`.
+    #[cfg(feature = "hinting")]
+    #[inline]
+    pub(crate) fn index(&self) -> u32 {
+        self.inner.index
+    }
+
+    /// The axis values applied by `with_variations`.
+    #[cfg(feature = "hinting")]
+    #[inline]
+    pub(crate) fn variations(&self) -> &[([u8; 4], f32)] {
+        &self.inner.variations
+    }
+
+    #[cfg(feature = "hinting")]
+    #[inline]
+    pub(crate) fn hinting_cache(&self) -> &crate::hinting::HintingCache {
+        &self.inner.hinting
     }
 
     /// The "vertical metrics" for this font at a given scale. These metrics are
     /// shared by all of the glyphs in the font. See `VMetrics` for more detail.
     pub fn v_metrics(&self, scale: Scale) -> VMetrics {
-        self.v_metrics_unscaled() * self.scale_for_pixel_height(scale.y)
+        let v_metrics = self.v_metrics_unscaled() * self.scale_for_pixel_height(scale.y);
+        if self.is_pixel_rendered() {
+            return VMetrics {
+                ascent: v_metrics.ascent.round(),
+                descent: v_metrics.descent.round(),
+                line_gap: v_metrics.line_gap.round(),
+            };
+        }
+        v_metrics
     }
 
     /// Get the unscaled VMetrics for this font, shared by all glyphs.
@@ -98,11 +773,108 @@ impl<'font> Font<'font> {
         }
     }
 
+    /// The advance width of a glyph in font units, see `units_per_em`, for
+    /// exact integer text measurement, e.g. in PDF text space. `0` for glyphs
+    /// the font doesn't have.
+    ///
+    /// Unlike `HMetrics` this isn't rounded for pixel fonts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// // PDF glyph widths are in thousandths of an em
+    /// let width = u32::from(font.h_advance_unscaled('a')) * 1000 / u32::from(font.units_per_em());
+    /// assert_eq!(width, 602);
+    /// ```
+    pub fn h_advance_unscaled<C: IntoGlyphId>(&self, id: C) -> u16 {
+        let id = id.into_glyph_id(self);
+        self.inner().glyph_hor_advance(id.into()).unwrap_or(0)
+    }
+
+    /// Returns the embedding permissions of the font, from the OS/2 `fsType`
+    /// field, e.g. so document export can refuse to embed restricted fonts.
+    ///
+    /// Returns `None` if the font has no OS/2 table or the value is malformed.
+    pub fn embedding_permissions(&self) -> Option<EmbeddingPermissions> {
+        use owned_ttf_parser::Permissions;
+        let font = self.inner();
+        let usage = match font.permissions()? {
+            Permissions::Installable => EmbeddingUsage::Installable,
+            Permissions::Restricted => EmbeddingUsage::Restricted,
+            Permissions::PreviewAndPrint => EmbeddingUsage::PreviewAndPrint,
+            Permissions::Editable => EmbeddingUsage::Editable,
+        };
+        Some(EmbeddingPermissions {
+            usage,
+            subsetting_allowed: font.is_subsetting_allowed(),
+            bitmap_only: !font.is_outline_embedding_allowed(),
+        })
+    }
+
+    /// Returns the PANOSE classification of the font, from the OS/2 table.
+    ///
+    /// Returns `None` if the font has no OS/2 table.
+    pub fn panose(&self) -> Option<Panose> {
+        let os2 = self.os2_table()?;
+        let mut panose = [0; 10];
+        panose.copy_from_slice(os2.get(32..42)?);
+        Some(Panose(panose))
+    }
+
+    /// Guesses a broad style classification of the font, e.g. to pick a
+    /// substitute of the same kind when a requested font family is missing.
+    ///
+    /// Uses the `post` table fixed pitch flag, the PANOSE digits & the OS/2
+    /// family class, in that order.
+    pub fn classification(&self) -> FontClass {
+        let family_class = self
+            .os2_table()
+            .and_then(|os2| os2.get(30..32))
+            .map_or(0, |b| i16::from_be_bytes([b[0], b[1]]));
+        FontClass::guess(self.inner().is_monospaced(), self.panose(), family_class)
+    }
+
+    fn os2_table(&self) -> Option<&[u8]> {
+        let tag = owned_ttf_parser::Tag::from_bytes(b"OS/2");
+        self.raw_face().table(tag)
+    }
+
     /// Returns the units per EM square of this font
     pub fn units_per_em(&self) -> u16 {
-        self.inner()
-            .units_per_em()
-            .expect("Invalid font units_per_em")
+        self.inner().units_per_em()
+    }
+
+    /// The maximum pixel width & height of any glyph's `pixel_bounding_box` at
+    /// `scale` & any position, from the font's global bounding box. For sizing
+    /// fixed atlas slots or terminal cells without rasterizing every glyph.
+    ///
+    /// Includes a pixel for subpixel positioning, so glyphs positioned at whole
+    /// pixels may be a pixel smaller.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let scale = Scale::uniform(16.0);
+    /// let (width, height) = font.max_glyph_dimensions(scale);
+    ///
+    /// let bb = font.glyph('W').scaled(scale).positioned(point(0.5, 0.5)).pixel_bounding_box().unwrap();
+    /// assert!(bb.width() as u32 <= width && bb.height() as u32 <= height);
+    /// ```
+    pub fn max_glyph_dimensions(&self, scale: Scale) -> (u32, u32) {
+        let bbox = self.inner().global_bounding_box();
+        let width = f32::from(bbox.x_max) - f32::from(bbox.x_min);
+        let height = f32::from(bbox.y_max) - f32::from(bbox.y_min);
+        let pixels = |units: f32, scale: f32| (units.max(0.0) * scale).ceil() as u32 + 1;
+        (
+            pixels(width, self.kerning_factor(scale)),
+            pixels(height, self.scale_for_pixel_height(scale.y)),
+        )
     }
 
     /// The number of glyphs present in this font. Glyph identifiers for this
@@ -125,12 +897,134 @@ impl<'font> Font<'font> {
         let gid = id.into_glyph_id(self);
         assert!((gid.0 as usize) < self.glyph_count());
         // font clone either a reference clone, or arc clone
-        Glyph {
-            font: self.clone(),
-            id: gid,
+        Glyph::new(self.clone(), gid)
+    }
+
+    /// Returns an iterator over all the character to glyph mappings of this
+    /// font, in ascending character order. Characters mapped to the ".notdef"
+    /// glyph are omitted.
+    ///
+    /// This is much cheaper than probing all of Unicode with `glyph`.
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font: Font<'static> = Font::try_from_bytes(font_data).unwrap();
+    /// let greek_glyphs = font
+    ///     .char_map()
+    ///     .filter(|(c, _)| ('\u{0370}'..='\u{03FF}').contains(c))
+    ///     .count();
+    /// ```
+    pub fn char_map(&self) -> CharMapIter<'_> {
+        CharMapIter {
+            inner: self.char_map_entries().iter(),
         }
     }
 
+    fn char_map_entries(&self) -> &[(char, GlyphId)] {
+        self.inner.char_map.get_or_init(|| {
+            let inner = self.inner();
+            let mut chars = Vec::new();
+            if let Some(cmap) = inner.tables().cmap {
+                for subtable in cmap.subtables.into_iter().filter(|st| st.is_unicode()) {
+                    subtable.codepoints(|cp| chars.extend(core::char::from_u32(cp)));
+                }
+            }
+            chars.sort_unstable();
+            chars.dedup();
+            chars
+                .into_iter()
+                .filter_map(|c| match inner.glyph_index(c) {
+                    Some(id) if id.0 != 0 => Some((c, id.into())),
+                    _ => None,
+                })
+                .collect()
+        })
+    }
+
+    /// Validates the font file's table checksums, the `head` table's whole file
+    /// `checksumAdjustment` & the table directory, e.g. to reject corrupt
+    /// third-party fonts early.
+    ///
+    /// A font that successfully constructs may still have issues, renderers
+    /// often tolerate them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::Font;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// let font = Font::try_from_bytes(font_data).unwrap();
+    /// let report = font.integrity_report();
+    /// for issue in report.issues() {
+    ///     eprintln!("font issue: {}", issue);
+    /// }
+    /// assert!(report.table(*b"glyf").unwrap().is_ok());
+    /// ```
+    pub fn integrity_report(&self) -> IntegrityReport {
+        IntegrityReport::check(self.raw_face())
+    }
+
+    /// Returns a summary of the Unicode blocks covered by this font's character
+    /// map, e.g. to decide whether a font supports a script before falling
+    /// back to another font.
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font: Font<'static> = Font::try_from_bytes(font_data).unwrap();
+    /// for coverage in font.coverage_summary().blocks() {
+    ///     println!("{}: {:.0}%", coverage.block.name, coverage.ratio() * 100.0);
+    /// }
+    /// ```
+    pub fn coverage_summary(&self) -> CoverageSummary {
+        CoverageSummary::from_chars(self.char_map().map(|(c, _)| c))
+    }
+
+    /// Returns the byte index & char of each char of `text` this font has no
+    /// glyph for, so would be drawn as the ".notdef" glyph, often a box or
+    /// "tofu". E.g. to validate text before publishing, or pick a fallback
+    /// font. Control chars, like `'\n'`, are included if unmapped, as layouts
+    /// don't handle them.
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font: Font<'static> = Font::try_from_bytes(font_data).unwrap();
+    /// assert_eq!(font.unsupported_chars("a 字 b"), [(2, '字')]);
+    /// ```
+    pub fn unsupported_chars(&self, text: &str) -> Vec<(usize, char)> {
+        text.char_indices()
+            .filter(|&(_, c)| c.into_glyph_id(self).0 == 0)
+            .collect()
+    }
+
+    /// Returns the Unicode code points that map to the glyph `id`, the reverse
+    /// of the mapping used by `glyph`.
+    ///
+    /// A glyph may be mapped to by multiple code points, or by none at all (e.g.
+    /// ".notdef" or glyphs only reachable by shaping). The returned code
+    /// points are sorted and unique.
+    ///
+    /// The reverse mapping is built on first use, subsequent calls are cheap.
+    pub fn chars_for_glyph(&self, id: GlyphId) -> Vec<char> {
+        let glyph_chars = self.inner.glyph_chars.get_or_init(|| {
+            let mut glyph_chars: Vec<_> = self
+                .char_map_entries()
+                .iter()
+                .map(|&(c, id)| (id, c))
+                .collect();
+            glyph_chars.sort_unstable();
+            glyph_chars
+        });
+        let start = glyph_chars.partition_point(|&(gid, _)| gid < id);
+        glyph_chars[start..]
+            .iter()
+            .take_while(|&&(gid, _)| gid == id)
+            .map(|&(_, c)| c)
+            .collect()
+    }
+
     /// A convenience function.
     ///
     /// Returns an iterator that produces the glyphs corresponding to the code
@@ -159,8 +1053,11 @@ impl<'font> Font<'font> {
     /// [unicode-normalization](http://crates.io/crates/unicode-normalization)
     /// is perfect for this purpose.
     ///
-    /// Calling this function is equivalent to a longer sequence of operations
-    /// involving `glyphs_for`, e.g.
+    /// Combining marks following a base glyph are placed on it with the font's
+    /// `GPOS` mark attachment, see `attachment_offset`, without advancing.
+    ///
+    /// Otherwise, calling this function is equivalent to a longer sequence of
+    /// operations involving `glyphs_for`, e.g.
     ///
     /// ```no_run
     /// # use rusttype::*;
@@ -196,39 +1093,328 @@ impl<'font> Font<'font> {
         scale: Scale,
         start: Point<f32>,
     ) -> LayoutIter<'a, 'font, 's> {
-        LayoutIter {
+        let mut layout = LayoutIter {
             font: self,
             chars: s.chars(),
             caret: 0.0,
             scale,
             start,
             last_glyph: None,
+            last_base: None,
+            localized_forms: Default::default(),
+            optical_kerning: None,
+            contextual_kerning: None,
+            glyph_index: 0,
+            caps_tracking: 0.0,
+            last_caps: false,
+            word_spacing: (1.0, 0.0),
+        };
+        layout.contextual_kerning = self.layout_contextual_kerning(&layout);
+        layout
+    }
+
+    /// Returns an iterator over the caret position after each char of `s`,
+    /// relative to its start, as laid out by `layout` including kerning.
+    ///
+    /// The last position is the width of `s`. As no glyphs are positioned,
+    /// this is cheaper than `layout` for measuring, e.g. aligning columns,
+    /// placing a cursor or checking where to truncate text.
+    ///
+    /// Kerning with a char is applied to its own position, so a position
+    /// doesn't include the kerning to the following char.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let scale = Scale::uniform(24.0);
+    /// // the number of chars of "Hello World" fitting 60px
+    /// let fit = font
+    ///     .advance_widths("Hello World", scale)
+    ///     .take_while(|&x| x <= 60.0)
+    ///     .count();
+    /// assert_eq!(fit, 4);
+    /// ```
+    pub fn advance_widths<'a, 's>(
+        &'a self,
+        s: &'s str,
+        scale: Scale,
+    ) -> AdvanceWidths<'a, 'font, 's> {
+        AdvanceWidths {
+            layout: self.layout(s, scale, point(0.0, 0.0)),
+        }
+    }
+
+    /// The advance width of the space glyph at `scale`, in pixels, e.g. to
+    /// compute a `LayoutIter::word_spacing` multiplier fitting spaces to a
+    /// grid. The `.notdef` glyph's advance if the font has no space.
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let scale = Scale::uniform(24.0);
+    /// // spaces 10px wide
+    /// let multiplier = 10.0 / font.space_advance(scale);
+    /// let layout = font.layout("a b", scale, point(0.0, 0.0)).word_spacing(multiplier, 0.0);
+    /// ```
+    pub fn space_advance(&self, scale: Scale) -> f32 {
+        self.glyph(' ').scaled(scale).h_metrics().advance_width
+    }
+
+    /// Lays out text like `layout`, using `options`, e.g. to select language
+    /// specific glyph forms.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let options = LayoutOptions::default().language(Language::from_bcp47("sr"));
+    /// let glyphs: Vec<_> = font
+    ///     .layout_with_options("б", Scale::uniform(24.0), point(0.0, 0.0), &options)
+    ///     .collect();
+    /// ```
+    pub fn layout_with_options<'a, 's>(
+        &'a self,
+        s: &'s str,
+        scale: Scale,
+        start: Point<f32>,
+        options: &LayoutOptions,
+    ) -> LayoutIter<'a, 'font, 's> {
+        let mut layout = self.layout(s, scale, start);
+        if let Some(language) = options.get_language() {
+            layout.localized_forms = crate::language::LocalizedForms::new(self, language);
+        }
+        let optical = match options.get_kerning() {
+            Kerning::Metrics => false,
+            Kerning::Optical => true,
+            Kerning::Auto => !self.has_kerning(),
+        };
+        let em = self.kerning_factor(scale) * f32::from(self.units_per_em());
+        layout.caps_tracking = f32::from(options.get_caps_tracking()) / 1000.0 * em;
+        if optical {
+            layout.optical_kerning = Some(OpticalKerning::new(self));
+            layout.contextual_kerning = None;
+        } else if !layout.localized_forms.is_empty() {
+            layout.contextual_kerning = self.layout_contextual_kerning(&layout);
+        }
+        layout
+    }
+
+    /// Returns the offset from the geometric center of the laid out text `s` to
+    /// the center of its ink, i.e. the union of the glyph shapes.
+    ///
+    /// The geometric center is the middle of the line box, spanning the text
+    /// advance horizontally & the font ascent to descent vertically. Centering
+    /// a label geometrically can look off, e.g. "ox" sits low as it has no
+    /// ascenders. Subtracting this offset from the label position centers it
+    /// optically instead.
+    ///
+    /// Returns a zero vector for text without ink, e.g. whitespace.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::{Font, Scale};
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let offset = font.visual_center_offset("ox", Scale::uniform(24.0));
+    /// // no ascenders, so the ink is below the line box center
+    /// assert!(offset.y > 0.0);
+    /// ```
+    pub fn visual_center_offset(&self, s: &str, scale: Scale) -> Vector<f32> {
+        let v_metrics = self.v_metrics(scale);
+        let mut advance = 0.0_f32;
+        let mut ink: Option<Rect<f32>> = None;
+
+        for glyph in self.layout(s, scale, point(0.0, 0.0)) {
+            let position = glyph.position();
+            let unpositioned = glyph.unpositioned();
+            advance = advance.max(position.x + unpositioned.h_metrics().advance_width);
+
+            if let Some(bb) = unpositioned.exact_bounding_box() {
+                let offset = vector(position.x, position.y);
+                let bb = Rect {
+                    min: bb.min + offset,
+                    max: bb.max + offset,
+                };
+                ink = Some(match ink {
+                    Some(ink) => Rect {
+                        min: point(ink.min.x.min(bb.min.x), ink.min.y.min(bb.min.y)),
+                        max: point(ink.max.x.max(bb.max.x), ink.max.y.max(bb.max.y)),
+                    },
+                    None => bb,
+                });
+            }
+        }
+
+        match ink {
+            Some(ink) => {
+                // y down, the baseline at 0
+                let geometric = point(advance / 2.0, -(v_metrics.ascent + v_metrics.descent) / 2.0);
+                let ink_center =
+                    point((ink.min.x + ink.max.x) / 2.0, (ink.min.y + ink.max.y) / 2.0);
+                ink_center - geometric
+            }
+            None => vector(0.0, 0.0),
         }
     }
 
     /// Returns additional kerning to apply as well as that given by HMetrics
-    /// for a particular pair of glyphs.
+    /// for a particular pair of glyphs, from the `kern` table, or else the
+    /// `GPOS` table's `kern` feature pair adjustments.
+    ///
+    /// State machine `kern` subtables are evaluated for the pair alone, see
+    /// `contextual_kerning` for kerning in context.
     pub fn pair_kerning<A, B>(&self, scale: Scale, first: A, second: B) -> f32
     where
         A: IntoGlyphId,
         B: IntoGlyphId,
     {
-        let first_id = first.into_glyph_id(self).into();
-        let second_id = second.into_glyph_id(self).into();
+        self.kerning_factor(scale) * self.pair_kerning_unscaled(first, second) as f32
+    }
 
-        let factor = {
-            let hscale = self.scale_for_pixel_height(scale.y);
-            hscale * (scale.x / scale.y)
+    /// Returns the kerning of a pair of glyphs in font units, see
+    /// `pair_kerning`, to add to the `h_advance_unscaled` of `first`.
+    pub fn pair_kerning_unscaled<A, B>(&self, first: A, second: B) -> i32
+    where
+        A: IntoGlyphId,
+        B: IntoGlyphId,
+    {
+        let first_id = first.into_glyph_id(self);
+        let second_id = second.into_glyph_id(self);
+
+        let mut kern = i32::from(self.pairwise_kerning(first_id, second_id));
+        if crate::kern::has_state_machine(self) {
+            kern += crate::kern::state_machine_kerning(self, &[first_id, second_id])[0];
+        }
+        kern
+    }
+
+    /// Returns the kerning of `glyphs` in order, to add to the advance of
+    /// each glyph, including contextual kerning of the `kern` table
+    /// state machine, format 1, subtables.
+    ///
+    /// Unlike pairs, state machines can kern glyphs depending on those
+    /// around them, e.g. a pair only at the start of a word. `layout` applies
+    /// this kerning, so is only needed for custom layout.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::{Font, Scale};
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/opensans/OpenSans-Italic.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let scale = Scale::uniform(24.0);
+    /// let kerning = font.contextual_kerning(scale, "AVA".chars());
+    /// assert_eq!(kerning[0], font.pair_kerning(scale, 'A', 'V'));
+    /// assert_eq!(kerning[2], 0.0, "nothing follows the last glyph");
+    /// ```
+    pub fn contextual_kerning<I>(&self, scale: Scale, glyphs: I) -> Vec<f32>
+    where
+        I: IntoIterator,
+        I::Item: IntoGlyphId,
+    {
+        let glyphs: Vec<GlyphId> = glyphs.into_iter().map(|g| g.into_glyph_id(self)).collect();
+        let mut kerning: Vec<i32> = if crate::kern::has_state_machine(self) {
+            crate::kern::state_machine_kerning(self, &glyphs)
+        } else {
+            vec![0; glyphs.len()]
         };
-        let kern = self
-            .inner()
-            .kerning_subtables()
-            .filter(|st| st.is_horizontal() && !st.is_variable())
-            .filter_map(|st| st.glyphs_kerning(first_id, second_id))
-            .next()
-            .unwrap_or(0);
+        for (kern, pair) in kerning.iter_mut().zip(glyphs.windows(2)) {
+            *kern += i32::from(self.pairwise_kerning(pair[0], pair[1]));
+        }
+        let factor = self.kerning_factor(scale);
+        kerning
+            .into_iter()
+            .map(|kern| factor * kern as f32)
+            .collect()
+    }
 
-        factor * f32::from(kern)
+    /// Returns the kerning of the first `kern` subtable with an entry for the
+    /// pair, or else of the `GPOS` pair adjustments, in font units.
+    pub(crate) fn pairwise_kerning(&self, first: GlyphId, second: GlyphId) -> i16 {
+        self.inner()
+            .tables()
+            .kern
+            .into_iter()
+            .flat_map(|kern| kern.subtables)
+            .filter(|st| st.horizontal && !st.variable)
+            .find_map(|st| st.glyphs_kerning(first.into(), second.into()))
+            .or_else(|| self.gpos_pair_kerning(first, second))
+            .unwrap_or(0)
+    }
+
+    /// Returns the state machine kerning in pixels of the glyphs `layout`
+    /// will yield, `None` if the font has none.
+    fn layout_contextual_kerning(&self, layout: &LayoutIter<'_, '_, '_>) -> Option<Vec<f32>> {
+        if !crate::kern::has_state_machine(self) {
+            return None;
+        }
+        let glyphs: Vec<GlyphId> = layout
+            .chars
+            .clone()
+            .map(|c| layout.localized_forms.apply(self, self.glyph(c).id()))
+            .collect();
+        let factor = self.kerning_factor(layout.scale);
+        let kerning = crate::kern::state_machine_kerning(self, &glyphs);
+        Some(
+            kerning
+                .into_iter()
+                .map(|kern| factor * kern as f32)
+                .collect(),
+        )
+    }
+
+    /// Returns an estimate of the kerning for a pair of glyphs from their
+    /// shapes, like the "optical" kerning of DTP software. For fonts without
+    /// kerning data, see `Kerning::Auto`.
+    ///
+    /// The ink gap between the glyphs is measured in horizontal bands & the
+    /// pair moved so its mean gap matches that of `nn` & `oo`. Adjustments
+    /// are limited to a tenth of an em & never move the glyphs closer than a
+    /// quarter of that reference gap. Returns `0.0` for fonts with neither
+    /// `n` nor `o` & pairs without ink at a common height, e.g. spaces.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::{Font, Scale};
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let scale = Scale::uniform(48.0);
+    /// assert!(font.optical_kerning(scale, 'T', 'o') < font.optical_kerning(scale, 'n', 'n'));
+    /// ```
+    pub fn optical_kerning<A, B>(&self, scale: Scale, first: A, second: B) -> f32
+    where
+        A: IntoGlyphId,
+        B: IntoGlyphId,
+    {
+        let first_id = first.into_glyph_id(self);
+        let second_id = second.into_glyph_id(self);
+        let kern = OpticalKerning::new(self).kerning(self, first_id, second_id);
+        self.kerning_factor(scale) * kern
+    }
+
+    /// Returns if the font has `kern` table data or `GPOS` pair adjustments
+    /// used by `pair_kerning`.
+    pub fn has_kerning(&self) -> bool {
+        let kern = self.inner().tables().kern.is_some_and(|kern| {
+            kern.subtables
+                .into_iter()
+                .any(|st| st.horizontal && !st.variable)
+        });
+        kern || self.gpos_lookups().has_pairs()
+    }
+
+    /// Converts font unit kerning to pixels at `scale`.
+    pub(crate) fn kerning_factor(&self, scale: Scale) -> f32 {
+        let hscale = self.scale_for_pixel_height(scale.y);
+        hscale * (scale.x / scale.y)
     }
 
     /// Computes a scale factor to produce a font whose "height" is 'pixels'
@@ -243,4 +1429,12 @@ impl<'font> Font<'font> {
         let fheight = f32::from(inner.ascender()) - f32::from(inner.descender());
         height / fheight
     }
+
+    /// The uniform `Scale` with `ppem` pixels per em, the size bitmap strikes
+    /// & other tools, e.g. CSS font sizes, are specified in.
+    pub fn scale_for_pixels_per_em(&self, ppem: f32) -> Scale {
+        let inner = self.inner();
+        let fheight = f32::from(inner.ascender()) - f32::from(inner.descender());
+        Scale::uniform(ppem * fheight / f32::from(self.units_per_em()))
+    }
 }
@@ -0,0 +1,64 @@
+//! Glyph classes of the `GDEF` table, see `Font::glyph_class`.
+use crate::{Font, IntoGlyphId};
+
+/// The kind of a glyph, from the font's `GDEF` table, see `Font::glyph_class`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GlyphClass {
+    /// A single char glyph that marks can attach to, e.g. `a`.
+    Base,
+    /// A glyph of multiple chars, e.g. `ﬁ`, a caret can be placed inside.
+    Ligature,
+    /// A combining glyph attaching to the previous base, e.g. an acute accent,
+    /// with no advance of its own. Cursors & selections shouldn't stop between
+    /// a mark & its base.
+    Mark,
+    /// A part of a glyph composed by shaping, e.g. of an Arabic ligature.
+    Component,
+}
+
+impl<'font> Font<'font> {
+    /// Returns if this font's `GDEF` table classifies glyphs, see
+    /// `glyph_class`.
+    pub fn has_glyph_classes(&self) -> bool {
+        self.inner()
+            .tables()
+            .gdef
+            .is_some_and(|gdef| gdef.has_glyph_classes())
+    }
+
+    /// The class of glyph `id`, e.g. to tell combining marks from base glyphs
+    /// when positioning a cursor or shaping. `None` if the font doesn't
+    /// classify the glyph.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// assert_eq!(font.glyph_class('a'), Some(GlyphClass::Base));
+    /// assert_eq!(font.glyph_class('\u{301}'), Some(GlyphClass::Mark));
+    /// ```
+    pub fn glyph_class<C: IntoGlyphId>(&self, id: C) -> Option<GlyphClass> {
+        use owned_ttf_parser::gdef;
+        let id = id.into_glyph_id(self);
+        let class = self.inner().tables().gdef?.glyph_class(id.into())?;
+        Some(match class {
+            gdef::GlyphClass::Base => GlyphClass::Base,
+            gdef::GlyphClass::Ligature => GlyphClass::Ligature,
+            gdef::GlyphClass::Mark => GlyphClass::Mark,
+            gdef::GlyphClass::Component => GlyphClass::Component,
+        })
+    }
+
+    /// The mark attachment class of glyph `id`, grouping marks for shaping
+    /// lookups that only apply to some marks, e.g. above or below marks. `0`
+    /// if the glyph has none, e.g. isn't a mark.
+    pub fn mark_attachment_class<C: IntoGlyphId>(&self, id: C) -> u16 {
+        let id = id.into_glyph_id(self);
+        self.inner()
+            .tables()
+            .gdef
+            .map_or(0, |gdef| gdef.glyph_mark_attachment_class(id.into()))
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(all(feature = "libm-math", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
 use core::ops;
 
 /// A point in 2-dimensional space, with each dimension of type `N`.
@@ -156,3 +158,471 @@ impl<N: ops::Sub<Output = N> + Copy> Rect<N> {
         self.max.y - self.min.y
     }
 }
+
+impl<N> Point<N> {
+    /// Converts each coordinate with `f`, e.g. `p.map(i64::from)`.
+    #[inline]
+    pub fn map<M>(self, mut f: impl FnMut(N) -> M) -> Point<M> {
+        point(f(self.x), f(self.y))
+    }
+}
+
+impl<N> Vector<N> {
+    /// Converts each component with `f`, e.g. `v.map(i64::from)`.
+    #[inline]
+    pub fn map<M>(self, mut f: impl FnMut(N) -> M) -> Vector<M> {
+        vector(f(self.x), f(self.y))
+    }
+}
+
+impl<N> Rect<N> {
+    /// Converts each corner coordinate with `f`, e.g. `r.map(i64::from)`.
+    #[inline]
+    pub fn map<M>(self, mut f: impl FnMut(N) -> M) -> Rect<M> {
+        Rect {
+            min: self.min.map(&mut f),
+            max: self.max.map(&mut f),
+        }
+    }
+}
+
+impl<N: ops::Add<Output = N> + Copy> ops::Add<Vector<N>> for Rect<N> {
+    type Output = Rect<N>;
+    fn add(self, rhs: Vector<N>) -> Rect<N> {
+        Rect {
+            min: self.min + rhs,
+            max: self.max + rhs,
+        }
+    }
+}
+
+impl<N: ops::Sub<Output = N> + Copy> ops::Sub<Vector<N>> for Rect<N> {
+    type Output = Rect<N>;
+    fn sub(self, rhs: Vector<N>) -> Rect<N> {
+        Rect {
+            min: self.min - rhs,
+            max: self.max - rhs,
+        }
+    }
+}
+
+/// Conversions to floating point & scaling about the origin, by a scalar or
+/// per axis by a vector, e.g. dividing a texture rect by the texture size for
+/// uv coordinates.
+macro_rules! impl_numeric {
+    ($($n:ty),*) => {$(
+        impl Point<$n> {
+            /// Converts to `f32` coordinates, rounding to the nearest `f32`
+            /// for integers too large to represent exactly.
+            #[inline]
+            pub fn to_f32(self) -> Point<f32> {
+                self.map(|v| v as f32)
+            }
+
+            /// Converts to `f64` coordinates.
+            #[inline]
+            pub fn to_f64(self) -> Point<f64> {
+                self.map(|v| v as f64)
+            }
+        }
+
+        impl Vector<$n> {
+            /// Converts to `f32` components, rounding to the nearest `f32`
+            /// for integers too large to represent exactly.
+            #[inline]
+            pub fn to_f32(self) -> Vector<f32> {
+                self.map(|v| v as f32)
+            }
+
+            /// Converts to `f64` components.
+            #[inline]
+            pub fn to_f64(self) -> Vector<f64> {
+                self.map(|v| v as f64)
+            }
+        }
+
+        impl Rect<$n> {
+            /// Converts to `f32` coordinates, rounding to the nearest `f32`
+            /// for integers too large to represent exactly.
+            #[inline]
+            pub fn to_f32(self) -> Rect<f32> {
+                self.map(|v| v as f32)
+            }
+
+            /// Converts to `f64` coordinates.
+            #[inline]
+            pub fn to_f64(self) -> Rect<f64> {
+                self.map(|v| v as f64)
+            }
+        }
+
+        impl ops::Mul<$n> for Rect<$n> {
+            type Output = Rect<$n>;
+            fn mul(self, rhs: $n) -> Rect<$n> {
+                self.map(|v| v * rhs)
+            }
+        }
+
+        impl ops::Div<$n> for Rect<$n> {
+            type Output = Rect<$n>;
+            fn div(self, rhs: $n) -> Rect<$n> {
+                self.map(|v| v / rhs)
+            }
+        }
+
+        impl ops::Mul<Vector<$n>> for Rect<$n> {
+            type Output = Rect<$n>;
+            fn mul(self, rhs: Vector<$n>) -> Rect<$n> {
+                Rect {
+                    min: point(self.min.x * rhs.x, self.min.y * rhs.y),
+                    max: point(self.max.x * rhs.x, self.max.y * rhs.y),
+                }
+            }
+        }
+
+        impl ops::Div<Vector<$n>> for Rect<$n> {
+            type Output = Rect<$n>;
+            fn div(self, rhs: Vector<$n>) -> Rect<$n> {
+                Rect {
+                    min: point(self.min.x / rhs.x, self.min.y / rhs.y),
+                    max: point(self.max.x / rhs.x, self.max.y / rhs.y),
+                }
+            }
+        }
+    )*};
+}
+
+impl_numeric!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64);
+
+/// Rounding of floating point geometry to whole pixels.
+macro_rules! impl_rounding {
+    ($($n:ty),*) => {$(
+        impl Point<$n> {
+            /// Rounds to the nearest whole pixel.
+            #[inline]
+            pub fn round(self) -> Point<i32> {
+                self.map(|v| v.round() as i32)
+            }
+        }
+
+        impl Rect<$n> {
+            /// The smallest whole pixel rect containing this rect, rounding
+            /// `min` down & `max` up, e.g. the pixels a shape touches.
+            #[inline]
+            pub fn round_out(self) -> Rect<i32> {
+                Rect {
+                    min: self.min.map(|v| v.floor() as i32),
+                    max: self.max.map(|v| v.ceil() as i32),
+                }
+            }
+
+            /// The largest whole pixel rect inside this rect, rounding `min`
+            /// up & `max` down, e.g. the pixels a shape fully covers. Empty,
+            /// with `min` past `max`, if no whole pixel fits.
+            #[inline]
+            pub fn round_in(self) -> Rect<i32> {
+                Rect {
+                    min: self.min.map(|v| v.ceil() as i32),
+                    max: self.max.map(|v| v.floor() as i32),
+                }
+            }
+        }
+    )*};
+}
+
+impl_rounding!(f32, f64);
+
+/// A 2-dimensional affine transform in pixel space, where y points down,
+/// mapping `(x, y)` to
+/// `(xx * x + xy * y + translation.x, yx * x + yy * y + translation.y)`.
+///
+/// ```
+/// # use rusttype::*;
+/// let zoom = Affine2::scale(2.0, 2.0).then(Affine2::translate(vector(10.0, 0.0)));
+/// assert_eq!(zoom.transform_point(point(1.0, 1.0)), point(12.0, 2.0));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Affine2 {
+    pub xx: f32,
+    pub yx: f32,
+    pub xy: f32,
+    pub yy: f32,
+    pub translation: Vector<f32>,
+}
+
+impl Default for Affine2 {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Affine2 {
+    /// The transform leaving everything unchanged.
+    pub const IDENTITY: Affine2 = Affine2 {
+        xx: 1.0,
+        yx: 0.0,
+        xy: 0.0,
+        yy: 1.0,
+        translation: Vector { x: 0.0, y: 0.0 },
+    };
+
+    /// Scales by `x` horizontally & `y` vertically about the origin.
+    pub fn scale(x: f32, y: f32) -> Self {
+        Self {
+            xx: x,
+            yy: y,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Rotates clockwise, as y points down, by `radians` about the origin.
+    pub fn rotate(radians: f32) -> Self {
+        let (sin, cos) = (radians.sin(), radians.cos());
+        Self {
+            xx: cos,
+            yx: sin,
+            xy: -sin,
+            yy: cos,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Skews horizontally, shifting points right by `factor` times their
+    /// height above the origin, e.g. `0.2` for a synthetic italic.
+    pub fn skew_x(factor: f32) -> Self {
+        Self {
+            xy: -factor,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Translates by `v`.
+    pub fn translate(v: Vector<f32>) -> Self {
+        Self {
+            translation: v,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Returns the transform applying `self` followed by `next`.
+    pub fn then(self, next: Affine2) -> Self {
+        Self {
+            xx: next.xx * self.xx + next.xy * self.yx,
+            yx: next.yx * self.xx + next.yy * self.yx,
+            xy: next.xx * self.xy + next.xy * self.yy,
+            yy: next.yx * self.xy + next.yy * self.yy,
+            translation: next.transform_vector(self.translation) + next.translation,
+        }
+    }
+
+    #[inline]
+    pub fn transform_point(&self, p: Point<f32>) -> Point<f32> {
+        point(
+            self.xx * p.x + self.xy * p.y + self.translation.x,
+            self.yx * p.x + self.yy * p.y + self.translation.y,
+        )
+    }
+
+    /// Transforms `v`, ignoring the translation.
+    #[inline]
+    pub fn transform_vector(&self, v: Vector<f32>) -> Vector<f32> {
+        vector(self.xx * v.x + self.xy * v.y, self.yx * v.x + self.yy * v.y)
+    }
+
+    /// The transform without its translation.
+    #[inline]
+    pub fn linear(&self) -> Self {
+        Self {
+            translation: vector(0.0, 0.0),
+            ..*self
+        }
+    }
+
+    #[inline]
+    pub fn determinant(&self) -> f32 {
+        self.xx * self.yy - self.xy * self.yx
+    }
+
+    #[inline]
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+}
+
+#[inline]
+fn lerp(t: f32, p0: Point<f32>, p1: Point<f32>) -> Point<f32> {
+    p0 + (p1 - p0) * t
+}
+
+#[inline]
+fn distance_squared(a: Point<f32>, b: Point<f32>) -> f32 {
+    let d = b - a;
+    d.x * d.x + d.y * d.y
+}
+
+/// Returns the `t` in `0..=1` nearest to `p` of the curve `at`, by sampling
+/// then narrowing down around the nearest sample.
+fn nearest_t(at: impl Fn(f32) -> Point<f32>, p: Point<f32>) -> f32 {
+    const SAMPLES: u32 = 16;
+    let distance = |t: f32| distance_squared(at(t), p);
+    let mut best = 0.0;
+    for i in 1..=SAMPLES {
+        let t = i as f32 / SAMPLES as f32;
+        if distance(t) < distance(best) {
+            best = t;
+        }
+    }
+
+    let step = 1.0 / SAMPLES as f32;
+    let (mut lo, mut hi) = ((best - step).max(0.0), (best + step).min(1.0));
+    for _ in 0..32 {
+        let (a, b) = (lo + (hi - lo) / 3.0, hi - (hi - lo) / 3.0);
+        if distance(a) < distance(b) {
+            hi = b;
+        } else {
+            lo = a;
+        }
+    }
+    // the ends, where the nearest point often is, exactly
+    [lo, (lo + hi) / 2.0, hi].iter().fold(best, |best, &t| {
+        if distance(t) < distance(best) {
+            t
+        } else {
+            best
+        }
+    })
+}
+
+/// A straight line segment from `p[0]` to `p[1]`, e.g. of a glyph outline.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Line {
+    pub p: [Point<f32>; 2],
+}
+
+impl Line {
+    /// The point a fraction `t` along the line.
+    #[inline]
+    pub fn at(&self, t: f32) -> Point<f32> {
+        lerp(t, self.p[0], self.p[1])
+    }
+
+    /// Returns the point where this segment crosses `other`, `None` if they
+    /// don't or are parallel.
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// let a = Line { p: [point(0.0, 0.0), point(2.0, 2.0)] };
+    /// let b = Line { p: [point(0.0, 2.0), point(2.0, 0.0)] };
+    /// assert_eq!(a.intersection(&b), Some(point(1.0, 1.0)));
+    /// ```
+    pub fn intersection(&self, other: &Line) -> Option<Point<f32>> {
+        let d0 = self.p[1] - self.p[0];
+        let d1 = other.p[1] - other.p[0];
+        let denominator = d0.x * d1.y - d0.y * d1.x;
+        if denominator == 0.0 {
+            return None;
+        }
+        let between = other.p[0] - self.p[0];
+        let t = (between.x * d1.y - between.y * d1.x) / denominator;
+        let u = (between.x * d0.y - between.y * d0.x) / denominator;
+        if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+            Some(self.at(t))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the `t` & point on the line nearest to `p`.
+    pub fn nearest_point(&self, p: Point<f32>) -> (f32, Point<f32>) {
+        let d = self.p[1] - self.p[0];
+        let length_squared = d.x * d.x + d.y * d.y;
+        if length_squared == 0.0 {
+            return (0.0, self.p[0]);
+        }
+        let v = p - self.p[0];
+        let t = ((v.x * d.x + v.y * d.y) / length_squared).clamp(0.0, 1.0);
+        (t, self.at(t))
+    }
+}
+
+/// A quadratic Bézier curve from `p[0]` to `p[2]` with the control point
+/// `p[1]`, e.g. of a TrueType glyph outline.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Curve {
+    pub p: [Point<f32>; 3],
+}
+
+impl Curve {
+    /// The point on the curve at `t` in `0..=1`.
+    #[inline]
+    pub fn at(&self, t: f32) -> Point<f32> {
+        lerp(
+            t,
+            lerp(t, self.p[0], self.p[1]),
+            lerp(t, self.p[1], self.p[2]),
+        )
+    }
+
+    /// Splits the curve at `t` into the curves before & after `t`.
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// let curve = Curve { p: [point(0.0, 0.0), point(1.0, 2.0), point(2.0, 0.0)] };
+    /// let (before, after) = curve.split(0.5);
+    /// assert_eq!(before.p[2], curve.at(0.5));
+    /// assert_eq!(after.p[0], curve.at(0.5));
+    /// ```
+    pub fn split(&self, t: f32) -> (Curve, Curve) {
+        let [p0, p1, p2] = self.p;
+        let (a, b) = (lerp(t, p0, p1), lerp(t, p1, p2));
+        let mid = lerp(t, a, b);
+        (Curve { p: [p0, a, mid] }, Curve { p: [mid, b, p2] })
+    }
+
+    /// Returns the `t` & point on the curve nearest to `p`, to within a
+    /// small fraction of a pixel for glyph sized curves.
+    pub fn nearest_point(&self, p: Point<f32>) -> (f32, Point<f32>) {
+        let t = nearest_t(|t| self.at(t), p);
+        (t, self.at(t))
+    }
+}
+
+/// A cubic Bézier curve from `p[0]` to `p[3]` with the control points `p[1]`
+/// & `p[2]`, e.g. of a CFF glyph outline.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Cubic {
+    pub p: [Point<f32>; 4],
+}
+
+impl Cubic {
+    /// The point on the curve at `t` in `0..=1`.
+    pub fn at(&self, t: f32) -> Point<f32> {
+        let [p0, p1, p2, p3] = self.p;
+        let (a, b, c) = (lerp(t, p0, p1), lerp(t, p1, p2), lerp(t, p2, p3));
+        lerp(t, lerp(t, a, b), lerp(t, b, c))
+    }
+
+    /// Splits the curve at `t` into the curves before & after `t`.
+    pub fn split(&self, t: f32) -> (Cubic, Cubic) {
+        let [p0, p1, p2, p3] = self.p;
+        let (a, b, c) = (lerp(t, p0, p1), lerp(t, p1, p2), lerp(t, p2, p3));
+        let (ab, bc) = (lerp(t, a, b), lerp(t, b, c));
+        let mid = lerp(t, ab, bc);
+        (
+            Cubic {
+                p: [p0, a, ab, mid],
+            },
+            Cubic {
+                p: [mid, bc, c, p3],
+            },
+        )
+    }
+
+    /// Returns the `t` & point on the curve nearest to `p`, see
+    /// `Curve::nearest_point`.
+    pub fn nearest_point(&self, p: Point<f32>) -> (f32, Point<f32>) {
+        let t = nearest_t(|t| self.at(t), p);
+        (t, self.at(t))
+    }
+}
@@ -0,0 +1,206 @@
+//! OpenType `GPOS` pair kerning & mark to base attachment, see
+//! `Font::pair_kerning` & `Font::attachment_offset`.
+#[cfg(all(feature = "libm-math", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{vector, Font, GlyphId, IntoGlyphId, Scale, Vector};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use owned_ttf_parser::gpos::{Device, MarkToBaseAdjustment, PairAdjustment, PositioningSubtable};
+use owned_ttf_parser::Tag;
+
+/// The `kern` & `mark` feature lookups of a font, resolved once per font.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct GposLookups {
+    kern: Vec<u16>,
+    mark: Vec<u16>,
+    /// Whether a `kern` lookup has pair adjustment subtables.
+    pairs: bool,
+}
+
+impl GposLookups {
+    /// Finds the lookups of the default language system of every script.
+    pub(crate) fn new(font: &Font<'_>) -> Self {
+        let mut lookups = Self::default();
+        let gpos = match font.layout_face().and_then(|face| face.tables().gpos) {
+            Some(gpos) => gpos,
+            None => return lookups,
+        };
+        let (kern, mark) = (Tag::from_bytes(b"kern"), Tag::from_bytes(b"mark"));
+        for script in gpos.scripts {
+            let language_system = match script.default_language {
+                Some(language_system) => language_system,
+                None => continue,
+            };
+            for feature_index in language_system.feature_indices {
+                let feature = match gpos.features.get(feature_index) {
+                    Some(feature) => feature,
+                    None => continue,
+                };
+                if feature.tag == kern {
+                    lookups.kern.extend(feature.lookup_indices);
+                } else if feature.tag == mark {
+                    lookups.mark.extend(feature.lookup_indices);
+                }
+            }
+        }
+        for lookups in [&mut lookups.kern, &mut lookups.mark] {
+            lookups.sort_unstable();
+            lookups.dedup();
+        }
+        lookups.pairs = lookups.kern.iter().any(|&index| {
+            gpos.lookups.get(index).is_some_and(|lookup| {
+                lookup
+                    .subtables
+                    .into_iter::<PositioningSubtable<'_>>()
+                    .any(|subtable| matches!(subtable, PositioningSubtable::Pair(_)))
+            })
+        });
+        lookups
+    }
+
+    /// Returns if the font kerns pairs with `GPOS` pair adjustments.
+    #[inline]
+    pub(crate) fn has_pairs(&self) -> bool {
+        self.pairs
+    }
+
+    /// Returns if the font attaches marks to bases.
+    #[inline]
+    pub(crate) fn has_marks(&self) -> bool {
+        !self.mark.is_empty()
+    }
+}
+
+impl Font<'_> {
+    /// Returns the `GPOS` pair adjustment of the advance of `first` followed
+    /// by `second`, in font units, `None` if no pair adjustment subtable has
+    /// an entry for the pair.
+    pub(crate) fn gpos_pair_kerning(&self, first: GlyphId, second: GlyphId) -> Option<i16> {
+        let gpos = self.layout_face()?.tables().gpos?;
+        let (first, second) = (first.into(), second.into());
+        let mut kerning = None;
+        for &index in &self.gpos_lookups().kern {
+            let lookup = match gpos.lookups.get(index) {
+                Some(lookup) => lookup,
+                None => continue,
+            };
+            let adjustment = lookup
+                .subtables
+                .into_iter::<PositioningSubtable<'_>>()
+                .find_map(|subtable| {
+                    let pair = match subtable {
+                        PositioningSubtable::Pair(pair) => pair,
+                        _ => return None,
+                    };
+                    let coverage_index = pair.coverage().get(first)?;
+                    let records = match pair {
+                        PairAdjustment::Format1 { sets, .. } => {
+                            sets.get(coverage_index)?.get(second)
+                        }
+                        PairAdjustment::Format2 {
+                            classes, matrix, ..
+                        } => matrix.get((classes.0.get(first), classes.1.get(second))),
+                    };
+                    records.map(|(record, _)| {
+                        let delta = self.variation_delta(record.x_advance_device);
+                        record.x_advance.saturating_add(delta)
+                    })
+                });
+            if let Some(adjustment) = adjustment {
+                kerning = Some(kerning.unwrap_or(0_i16).saturating_add(adjustment));
+            }
+        }
+        kerning
+    }
+
+    /// Returns the change of a `GPOS` value of `device` at this font's axis
+    /// values, in font units, `0` for fonts without variations.
+    fn variation_delta(&self, device: Option<Device<'_>>) -> i16 {
+        let face = self.inner();
+        match (device, face.tables().gdef) {
+            (Some(Device::Variation(device)), Some(gdef)) => gdef
+                .glyph_variation_delta(
+                    device.outer_index,
+                    device.inner_index,
+                    face.variation_coordinates(),
+                )
+                .map_or(0, |delta| delta.round() as i16),
+            _ => 0,
+        }
+    }
+
+    /// Returns the offset of the origin of `mark` attached to `base` from the
+    /// origin of `base`, in pixels at `scale`, y down, from the font's `GPOS`
+    /// mark to base attachment, e.g. for an accent above a letter. `None` if
+    /// the font doesn't attach `mark` to `base`.
+    ///
+    /// `layout` & `GsubShaper` position marks following a base this way,
+    /// without advancing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/Exo2-Light.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let scale = Scale::uniform(24.0);
+    /// // a combining acute accent on an 'e'
+    /// let offset = font.attachment_offset(scale, 'e', '\u{301}').unwrap();
+    /// let glyphs: Vec<_> = font.layout("e\u{301}", scale, point(0.0, 20.0)).collect();
+    /// assert_eq!(glyphs[1].position(), glyphs[0].position() + offset);
+    /// ```
+    pub fn attachment_offset<B, M>(&self, scale: Scale, base: B, mark: M) -> Option<Vector<f32>>
+    where
+        B: IntoGlyphId,
+        M: IntoGlyphId,
+    {
+        let base = base.into_glyph_id(self);
+        let mark = mark.into_glyph_id(self);
+        let (x, y) = self.attachment_offset_unscaled(base, mark)?;
+        let y_factor = self.scale_for_pixel_height(scale.y);
+        Some(vector(
+            self.kerning_factor(scale) * f32::from(x),
+            -y_factor * f32::from(y),
+        ))
+    }
+
+    /// `attachment_offset` in font units, y up.
+    fn attachment_offset_unscaled(&self, base: GlyphId, mark: GlyphId) -> Option<(i16, i16)> {
+        let gpos = self.layout_face()?.tables().gpos?;
+        let (base, mark) = (base.into(), mark.into());
+        for &index in &self.gpos_lookups().mark {
+            let lookup = match gpos.lookups.get(index) {
+                Some(lookup) => lookup,
+                None => continue,
+            };
+            for subtable in lookup.subtables.into_iter::<PositioningSubtable<'_>>() {
+                let attachment = match subtable {
+                    PositioningSubtable::MarkToBase(attachment) => attachment,
+                    _ => continue,
+                };
+                let offset = mark_to_base(&attachment, base, mark);
+                if offset.is_some() {
+                    return offset;
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Returns the offset of `mark` from `base` by the anchors of `subtable`, in
+/// font units, y up.
+fn mark_to_base(
+    subtable: &MarkToBaseAdjustment<'_>,
+    base: owned_ttf_parser::GlyphId,
+    mark: owned_ttf_parser::GlyphId,
+) -> Option<(i16, i16)> {
+    let mark_index = subtable.mark_coverage.get(mark)?;
+    let base_index = subtable.base_coverage.get(base)?;
+    let (class, mark_anchor) = subtable.marks.get(mark_index)?;
+    let base_anchor = subtable.anchors.get(base_index, class)?;
+    Some((
+        base_anchor.x.saturating_sub(mark_anchor.x),
+        base_anchor.y.saturating_sub(mark_anchor.y),
+    ))
+}
@@ -34,6 +34,9 @@
 //! to get the UV coordinates in the cache texture for each glyph. For a
 //! concrete use case see the `gpu_cache` example.
 //!
+//! A cache may also pack glyphs into several texture pages, e.g. the layers
+//! of a texture array, see `CacheBuilder::pages`.
+//!
 //! Cache dimensions are immutable. If you need to change the dimensions of the
 //! cache texture (e.g. due to high cache pressure), rebuild a new `Cache`.
 //! Either from scratch or with `CacheBuilder::rebuild`.
@@ -68,13 +71,20 @@
 //! # Ok(())
 //! # }
 //! ```
-use crate::{point, vector, GlyphId, Point, PositionedGlyph, Rect, Vector};
+use crate::{
+    point, vector, Font, GlyphId, Point, PositionedGlyph, RasterBackend, Rect, Scale,
+    ScanlineRasterizer, SubpixelStrategy, Vector,
+};
 use linked_hash_map::LinkedHashMap;
 use rustc_hash::{FxHashMap, FxHasher};
 use std::collections::{HashMap, HashSet};
 use std::error;
 use std::fmt;
 use std::hash::BuildHasherDefault;
+use std::io;
+use std::path::Path;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
 
 type FxBuildHasher = BuildHasherDefault<FxHasher>;
 
@@ -84,6 +94,14 @@ type FxBuildHasher = BuildHasherDefault<FxHasher>;
 pub type TextureCoords = (Rect<f32>, Rect<i32>);
 type FontId = usize;
 
+/// Glyphs cached per `cache_queued` call by `Cache::warm_cache`, between
+/// progress reports.
+const WARM_BATCH: usize = 32;
+
+/// Glyphs cached between checks of a `CacheBudget::Time`.
+#[cfg(not(target_arch = "wasm32"))]
+const BUDGET_BATCH: usize = 8;
+
 /// Indicates where a glyph texture is stored in the cache
 /// (row position, glyph index in row)
 type TextureRowGlyphIndex = (u32, u32);
@@ -93,7 +111,15 @@ type TextureRowGlyphIndex = (u32, u32);
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 struct LossyGlyphInfo {
     font_id: FontId,
+    /// `Font::generation` so reloaded fonts don't match stale textures
+    font_generation: u32,
+    /// User discriminator of visually distinct variants, see
+    /// `Cache::queue_glyph_variant`
+    variant: u32,
     glyph_id: GlyphId,
+    /// `StandaloneGlyph` outline hash, as standalone glyphs share ids, `0`
+    /// for font glyphs
+    standalone_key: u64,
     /// x & y scales divided by `scale_tolerance` & rounded
     scale_over_tolerance: (u32, u32),
     /// Normalised subpixel positions divided by `position_tolerance` & rounded
@@ -101,6 +127,12 @@ struct LossyGlyphInfo {
     /// `u16` is enough as subpixel position `[-0.5, 0.5]` converted to `[0, 1]`
     ///  divided by the min `position_tolerance` (`0.001`) is small.
     offset_over_tolerance: (u16, u16),
+    /// Bits of the `ScaledGlyph::transform` matrix, so transformed glyphs
+    /// don't match untransformed textures
+    transform: [u32; 4],
+    /// `ScaledGlyph::hinting`, as hinting changes the rasterized outline
+    #[cfg(feature = "hinting")]
+    hinting: crate::HintingMode,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -173,20 +205,24 @@ struct GlyphTexInfo {
     glyph_info: LossyGlyphInfo,
     /// Actual (lossless) normalised subpixel offset of rasterized glyph
     offset: Vector<f32>,
+    /// Actual (lossless) scale of rasterized glyph
+    scale: Scale,
     tex_coords: Rect<u32>,
+    /// Copy of the uploaded pixels excluding padding, see `Cache::pixels_for`
+    pixels: Vec<u8>,
 }
 
 trait PaddingAware {
-    fn unpadded(self) -> Self;
+    fn unpadded(self, padding: u32) -> Self;
 }
 
 impl PaddingAware for Rect<u32> {
-    /// A padded texture has 1 extra pixel on all sides
-    fn unpadded(mut self) -> Self {
-        self.min.x += 1;
-        self.min.y += 1;
-        self.max.x -= 1;
-        self.max.y -= 1;
+    /// A padded texture has `padding` extra pixels on all sides
+    fn unpadded(mut self, padding: u32) -> Self {
+        self.min.x += padding;
+        self.min.y += padding;
+        self.max.x -= padding;
+        self.max.y -= padding;
         self
     }
 }
@@ -198,16 +234,27 @@ pub struct Cache<'font> {
     position_tolerance: f32,
     width: u32,
     height: u32,
+    pages: u32,
+    /// Dimensions the cache may grow to, see `CacheBuilder::max_dimensions`
+    max_dimensions: (u32, u32),
+    sdf: Option<SdfSettings>,
+    subpixel: Option<SubpixelStrategy>,
+    /// Rows by their top in the pages stacked vertically
     rows: LinkedHashMap<u32, Row, FxBuildHasher>,
     /// Mapping of row gaps bottom -> top
     space_start_for_end: FxHashMap<u32, u32>,
     /// Mapping of row gaps top -> bottom
     space_end_for_start: FxHashMap<u32, u32>,
-    queue: Vec<(FontId, PositionedGlyph<'font>)>,
+    queue: Vec<(FontId, u32, GlyphPriority, PositionedGlyph<'font>)>,
     all_glyphs: FxHashMap<LossyGlyphInfo, TextureRowGlyphIndex>,
-    pad_glyphs: bool,
+    /// Transparent texels around each glyph, see `CacheBuilder::padding`
+    padding: u32,
     align_4x4: bool,
     multithread: bool,
+    deterministic: bool,
+    retain_pixels: bool,
+    /// Uploaded regions since the last `take_dirty_rects`
+    dirty_rects: Vec<Rect<u32>>,
 }
 
 /// Builder & rebuilder for `Cache`.
@@ -226,6 +273,10 @@ pub struct Cache<'font> {
 ///     .pad_glyphs(true)
 ///     .align_4x4(false)
 ///     .multithread(true)
+///     .deterministic(false)
+///     .retain_pixels(false)
+///     .pages(1)
+///     .max_dimensions(256, 256)
 ///     .build();
 ///
 /// // Create a cache with all default values, except with a dimension of 1024x1024
@@ -236,9 +287,15 @@ pub struct CacheBuilder {
     dimensions: (u32, u32),
     scale_tolerance: f32,
     position_tolerance: f32,
-    pad_glyphs: bool,
+    padding: u32,
     align_4x4: bool,
     multithread: bool,
+    deterministic: bool,
+    retain_pixels: bool,
+    pages: u32,
+    max_dimensions: Option<(u32, u32)>,
+    sdf: Option<SdfSettings>,
+    subpixel: Option<SubpixelStrategy>,
 }
 
 impl Default for CacheBuilder {
@@ -247,9 +304,15 @@ impl Default for CacheBuilder {
             dimensions: (256, 256),
             scale_tolerance: 0.1,
             position_tolerance: 0.1,
-            pad_glyphs: true,
+            padding: 1,
             align_4x4: false,
             multithread: true,
+            deterministic: false,
+            retain_pixels: false,
+            pages: 1,
+            max_dimensions: None,
+            sdf: None,
+            subpixel: None,
         }
     }
 }
@@ -331,6 +394,7 @@ impl CacheBuilder {
     }
     /// Pack glyphs in texture with a padding of a single zero alpha pixel to
     /// avoid bleeding from interpolated shader texture lookups near edges.
+    /// Equivalent to `padding(1)`, or `padding(0)` if `false`.
     ///
     /// If glyphs are never transformed this may be set to `false` to slightly
     /// improve the glyph packing.
@@ -341,8 +405,27 @@ impl CacheBuilder {
     /// # use rusttype::gpu_cache::Cache;
     /// let cache = Cache::builder().pad_glyphs(true).build();
     /// ```
-    pub fn pad_glyphs(mut self, pad_glyphs: bool) -> Self {
-        self.pad_glyphs = pad_glyphs;
+    pub fn pad_glyphs(self, pad_glyphs: bool) -> Self {
+        self.padding(pad_glyphs as u32)
+    }
+
+    /// Pack glyphs in texture with a border of `padding` zero alpha texels,
+    /// so neighbouring glyphs don't bleed into each other when sampled with
+    /// bilinear filtering or from mipmaps. Texture coordinates returned by
+    /// `rect_for` exclude the padding. Defaults to `1`, see `pad_glyphs`.
+    ///
+    /// Each mipmap level halves the border, so sampling down to level `n`
+    /// needs a padding of `2^n` texels.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::gpu_cache::Cache;
+    /// // mipmaps down to level 2
+    /// let cache = Cache::builder().padding(4).build();
+    /// ```
+    pub fn padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
         self
     }
     /// Align glyphs in texture to 4x4 texel boundaries.
@@ -379,9 +462,134 @@ impl CacheBuilder {
         self.multithread = multithread;
         self
     }
+    /// Make packing, eviction & upload order depend only on the queued
+    /// glyphs, for screenshot based tests.
+    ///
+    /// Glyphs are packed in queue order among glyphs of the same height, new
+    /// rows take the topmost free space & multithreaded rasterization uploads
+    /// in packing order, instead of as each glyph is ready. So results don't
+    /// vary with thread timing or hash map iteration order, which may change
+    /// between dependency versions. The cache uses no random state, so there
+    /// is no seed to fix.
+    ///
+    /// # Example (set to default value)
+    ///
+    /// ```
+    /// # use rusttype::gpu_cache::Cache;
+    /// let cache = Cache::builder().deterministic(false).build();
+    /// ```
+    pub fn deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+    /// Keep a cpu copy of uploaded glyph pixels, see `Cache::pixels_for`.
+    ///
+    /// Off by default, as the copy about doubles glyph memory use & the
+    /// pixels are usually only needed on the GPU. `Cache::retains_pixels`
+    /// reports the active mode.
+    ///
+    /// # Example (set to default value)
+    ///
+    /// ```
+    /// # use rusttype::gpu_cache::Cache;
+    /// let cache = Cache::builder().retain_pixels(false).build();
+    /// ```
+    pub fn retain_pixels(mut self, retain_pixels: bool) -> Self {
+        self.retain_pixels = retain_pixels;
+        self
+    }
+
+    /// Number of texture pages of `dimensions`, e.g. layers of a texture
+    /// array or separate textures, the cache packs glyphs into. Glyphs are
+    /// cached in any page with room before rows are evicted, so more pages
+    /// hold more glyphs without one huge texture.
+    ///
+    /// Use `Cache::cache_queued_paged` to upload to the right page &
+    /// `Cache::page_rect_for` to find the page of each glyph.
+    ///
+    /// # Example (set to default value)
+    ///
+    /// ```
+    /// # use rusttype::gpu_cache::Cache;
+    /// let cache = Cache::builder().pages(1).build();
+    /// ```
+    pub fn pages(mut self, pages: u32) -> Self {
+        self.pages = pages;
+        self
+    }
+
+    /// Maximum dimensions the cache grows to when a queue doesn't fit, by
+    /// doubling the `dimensions` up to `max_width` & `max_height`. Defaults
+    /// to the `dimensions`, i.e. no growth.
+    ///
+    /// When the cache grows `cache_queued` returns `CachedBy::Resizing`, so
+    /// reallocate the GPU texture with the new `Cache::dimensions`. The queue
+    /// is rasterized & uploaded again into the grown texture, as with
+    /// `CachedBy::Reordering`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::gpu_cache::Cache;
+    /// let cache = Cache::builder()
+    ///     .dimensions(256, 256)
+    ///     .max_dimensions(2048, 2048)
+    ///     .build();
+    /// ```
+    pub fn max_dimensions(mut self, max_width: u32, max_height: u32) -> Self {
+        self.max_dimensions = Some((max_width, max_height));
+        self
+    }
+
+    /// Caches signed distance fields of glyphs instead of coverage, so one
+    /// cached field serves a glyph at any scale. Fields are generated at a
+    /// pixel height of `scale`, with distances up to `spread` pixels either
+    /// side of the outline at that scale. Defaults to coverage.
+    ///
+    /// Glyphs matching at the generation scale share a field whatever their
+    /// queued scale & subpixel position. Field values are `128` on the
+    /// outline, rising to `255` inside & falling to `0` outside, see
+    /// `Cache::sdf_rect_for` for the screen rect & scaled spread to render
+    /// each glyph with.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::gpu_cache::Cache;
+    /// let cache = Cache::builder().dimensions(512, 512).sdf(4.0, 48.0).build();
+    /// ```
+    pub fn sdf(mut self, spread: f32, scale: f32) -> Self {
+        self.sdf = Some(SdfSettings { spread, scale });
+        self
+    }
+
+    /// Bins the positions of queued glyphs with `strategy`, so glyphs are
+    /// cached & drawn at the same subpixel positions as glyphs snapped with
+    /// `SubpixelStrategy::snap_glyph`. Defaults to unbinned positions,
+    /// matched within `position_tolerance`. See also `Cache::set_subpixel`.
+    ///
+    /// Glyphs read with `rect_for` are binned the same way, so the screen
+    /// rect of an unsnapped glyph is where its binned rasterization goes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::{gpu_cache::Cache, SubpixelStrategy};
+    /// let cache = Cache::builder()
+    ///     .subpixel(SubpixelStrategy::HorizontalThirds)
+    ///     .build();
+    /// ```
+    pub fn subpixel(mut self, strategy: SubpixelStrategy) -> Self {
+        self.subpixel = Some(strategy);
+        self
+    }
 
     fn validated(self) -> Self {
         assert!(self.scale_tolerance >= 0.0);
+        assert!(self.pages > 0);
+        if let Some(sdf) = self.sdf {
+            assert!(sdf.spread > 0.0 && sdf.scale > 0.0);
+        }
         assert!(self.position_tolerance >= 0.0);
         let scale_tolerance = self.scale_tolerance.max(0.001);
         let position_tolerance = self.position_tolerance.max(0.001);
@@ -402,7 +610,7 @@ impl CacheBuilder {
     /// # Panics
     ///
     /// `scale_tolerance` or `position_tolerance` are less than or equal to
-    /// zero.
+    /// zero, `pages` is zero, or the `sdf` spread or scale isn't positive.
     ///
     /// # Example
     ///
@@ -415,33 +623,40 @@ impl CacheBuilder {
             dimensions: (width, height),
             scale_tolerance,
             position_tolerance,
-            pad_glyphs,
+            padding,
             align_4x4,
             multithread,
+            deterministic,
+            retain_pixels,
+            pages,
+            max_dimensions,
+            sdf,
+            subpixel,
         } = self.validated();
 
-        Cache {
+        let mut cache = Cache {
             scale_tolerance,
             position_tolerance,
             width,
             height,
+            pages,
+            max_dimensions: max_dimensions.unwrap_or((width, height)),
+            sdf,
+            subpixel,
             rows: LinkedHashMap::default(),
-            space_start_for_end: {
-                let mut m = HashMap::default();
-                m.insert(height, 0);
-                m
-            },
-            space_end_for_start: {
-                let mut m = HashMap::default();
-                m.insert(0, height);
-                m
-            },
+            space_start_for_end: HashMap::default(),
+            space_end_for_start: HashMap::default(),
             queue: Vec::new(),
             all_glyphs: HashMap::default(),
-            pad_glyphs,
+            padding,
             align_4x4,
             multithread,
-        }
+            deterministic,
+            retain_pixels,
+            dirty_rects: Vec::new(),
+        };
+        cache.clear();
+        cache
     }
 
     /// Rebuilds a `Cache` with new attributes. All cached glyphs are cleared,
@@ -450,7 +665,7 @@ impl CacheBuilder {
     /// # Panics
     ///
     /// `scale_tolerance` or `position_tolerance` are less than or equal to
-    /// zero.
+    /// zero, `pages` is zero, or the `sdf` spread or scale isn't positive.
     ///
     /// # Example
     ///
@@ -465,19 +680,32 @@ impl CacheBuilder {
             dimensions: (width, height),
             scale_tolerance,
             position_tolerance,
-            pad_glyphs,
+            padding,
             align_4x4,
             multithread,
+            deterministic,
+            retain_pixels,
+            pages,
+            max_dimensions,
+            sdf,
+            subpixel,
         } = self.validated();
 
         cache.width = width;
         cache.height = height;
+        cache.pages = pages;
+        cache.max_dimensions = max_dimensions.unwrap_or((width, height));
+        cache.sdf = sdf;
+        cache.subpixel = subpixel;
         cache.scale_tolerance = scale_tolerance;
         cache.position_tolerance = position_tolerance;
-        cache.pad_glyphs = pad_glyphs;
+        cache.padding = padding;
         cache.align_4x4 = align_4x4;
         cache.multithread = multithread;
+        cache.deterministic = deterministic;
+        cache.retain_pixels = retain_pixels;
         cache.clear();
+        cache.dirty_rects.clear();
     }
 }
 
@@ -530,6 +758,183 @@ pub enum CachedBy {
     /// Fit the glyph queue by re-ordering all glyph texture positions.
     /// Previous texture positions are no longer valid.
     Reordering,
+    /// Fit the glyph queue by growing the cache `dimensions`, see
+    /// `CacheBuilder::max_dimensions`. The texture must be reallocated &
+    /// previous texture positions are no longer valid.
+    Resizing,
+}
+
+/// How much of the glyph queue `Cache::cache_queued_with_budget` caches.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CacheBudget {
+    /// Cache at most this many new glyphs.
+    Glyphs(usize),
+    /// Cache new glyphs in small batches until this much time has passed.
+    /// The first batch is always cached, so the queue makes progress.
+    #[cfg(not(target_arch = "wasm32"))]
+    Time(Duration),
+}
+
+/// Priority of a queued glyph, see `Cache::queue_glyph_with_priority`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum GlyphPriority {
+    /// Needed now, e.g. on screen. Queued by `Cache::queue_glyph`.
+    #[default]
+    Visible,
+    /// Likely needed soon. Cached after visible glyphs & only if there's room
+    /// left, otherwise skipped, so never fails or reorders the cache.
+    /// Textures used only by prefetched glyphs are evicted before those of
+    /// visible glyphs. Cached last within a `CacheBudget`.
+    Prefetch,
+}
+
+/// Returned from `Cache::cache_queued_with_budget`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CacheProgress {
+    /// How the glyphs were cached, `Reordering` if any batch reordered.
+    pub cached_by: CachedBy,
+    /// Queued glyphs newly cached by this call.
+    pub cached: usize,
+    /// Queued glyphs not cached as the budget ran out, to be queued again.
+    pub remaining: usize,
+}
+
+impl CacheProgress {
+    /// Returns if all queued glyphs are cached.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+/// Signed distance field settings of a cache, see `CacheBuilder::sdf`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SdfSettings {
+    /// Distance in generation pixels either side of the outline the field
+    /// covers.
+    pub spread: f32,
+    /// Pixel height fields are generated at.
+    pub scale: f32,
+}
+
+impl SdfSettings {
+    /// The glyph cached for `glyph`, at the generation scale with the same
+    /// aspect ratio & transform, at the origin.
+    fn generation_glyph<'font>(&self, glyph: &PositionedGlyph<'font>) -> PositionedGlyph<'font> {
+        let scaled = glyph.unpositioned();
+        let scale = scaled.scale();
+        scaled
+            .unscaled()
+            .clone()
+            .scaled(Scale {
+                x: self.scale * scale.x / scale.y,
+                y: self.scale,
+            })
+            .transformed(scaled.transform())
+            .positioned(point(0.0, 0.0))
+    }
+
+    /// Pixels a field extends the glyph's pixel bounding box on every side.
+    #[inline]
+    fn padding(&self) -> u32 {
+        self.spread.ceil() as u32
+    }
+}
+
+/// Where to draw a cached signed distance field, returned from
+/// `Cache::sdf_rect_for`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SdfRect {
+    /// Texture page of the field, see `CacheBuilder::pages`.
+    pub page: u32,
+    /// Texture coordinates of the field within its page.
+    pub uv_rect: Rect<f32>,
+    /// Pixel coordinates to draw the field at, the glyph's box & spread
+    /// scaled from the generation scale to the glyph's.
+    pub screen_rect: Rect<f32>,
+    /// The nominal scale the field was generated at.
+    pub generation_scale: Scale,
+    /// The spread scaled to the glyph, the screen distance from the outline
+    /// at which field values reach `0` or `255`, e.g. to anti-alias edges.
+    pub spread: f32,
+}
+
+/// Rasterizes signed distance fields with the coverage of `backend`, of the
+/// glyph's pixel bounding box padded by the spread.
+struct SdfRasterizer<'b, B: ?Sized> {
+    backend: &'b B,
+    sdf: SdfSettings,
+}
+
+impl<B: RasterBackend + ?Sized> RasterBackend for SdfRasterizer<'_, B> {
+    fn rasterize(&self, glyph: &PositionedGlyph<'_>, o: &mut dyn FnMut(u32, u32, f32)) {
+        self.rasterize_variant(glyph, 0, o)
+    }
+
+    fn rasterize_variant(
+        &self,
+        glyph: &PositionedGlyph<'_>,
+        variant: u32,
+        o: &mut dyn FnMut(u32, u32, f32),
+    ) {
+        let bb = glyph.pixel_bounding_box().unwrap();
+        let pad = self.sdf.padding() as usize;
+        let width = bb.width() as usize + 2 * pad;
+        let height = bb.height() as usize + 2 * pad;
+        let mut coverage = vec![0.0; width * height];
+        self.backend
+            .rasterize_variant(glyph, variant, &mut |x, y, v| {
+                coverage[x as usize + pad + (y as usize + pad) * width] = v;
+            });
+        let field = signed_distance_field(&coverage, width, self.sdf.spread);
+        for (i, v) in field.into_iter().enumerate() {
+            o((i % width) as u32, (i / width) as u32, v);
+        }
+    }
+}
+
+/// Returns the signed distance field of `coverage`, `width` pixels wide,
+/// with values `0.5` on the edge, inside `>= 0.5`, reaching `0.0` & `1.0`
+/// `spread` pixels from the edge.
+///
+/// Coverage is taken as `0.5` plus the distance inside the edge near edges,
+/// so the distance of each pixel is its own `c - 0.5` on the edge, or to the
+/// edge just before the nearest pixel on the other side of the edge.
+fn signed_distance_field(coverage: &[f32], width: usize, spread: f32) -> Vec<f32> {
+    let height = coverage.len() / width;
+    let radius = spread.ceil() as isize + 1;
+    let mut field = Vec::with_capacity(coverage.len());
+    for y in 0..height as isize {
+        for x in 0..width as isize {
+            let c = coverage[x as usize + y as usize * width];
+            let inside = c >= 0.5;
+            let mut distance = match c > 0.0 && c < 1.0 {
+                true => (c - 0.5).abs().min(spread),
+                false => spread,
+            };
+            for dy in -radius..=radius {
+                let ny = y + dy;
+                if ny < 0 || ny >= height as isize {
+                    continue;
+                }
+                for dx in -radius..=radius {
+                    let nx = x + dx;
+                    if nx < 0 || nx >= width as isize {
+                        continue;
+                    }
+                    let other = coverage[nx as usize + ny as usize * width];
+                    if (other >= 0.5) == inside {
+                        continue;
+                    }
+                    let length = ((dx * dx + dy * dy) as f32).sqrt();
+                    distance = distance.min(length - (other - 0.5).abs());
+                }
+            }
+            let signed = if inside { distance } else { -distance };
+            field.push((0.5 + signed / (2.0 * spread)).clamp(0.0, 1.0));
+        }
+    }
+    field
 }
 
 fn normalised_offset_from_position(position: Point<f32>) -> Vector<f32> {
@@ -564,18 +969,186 @@ impl<'font> Cache<'font> {
         self.position_tolerance
     }
 
+    /// Changes the scale & subpixel position tolerances, see
+    /// `CacheBuilder::scale_tolerance` & `CacheBuilder::position_tolerance`,
+    /// without clearing the cache, e.g. loosening them during fast scrolling &
+    /// tightening them again for static text.
+    ///
+    /// Cached glyphs are re-keyed by the exact scale & position they were
+    /// rasterized at, so remain valid for glyphs within the new tolerances.
+    /// When loosening merges glyphs cached separately only one stays
+    /// reachable, the others are invalidated & their texture space reclaimed
+    /// once their rows are evicted. Tightening invalidates nothing, but later
+    /// queues may cache more glyphs as fewer match.
+    ///
+    /// # Panics
+    ///
+    /// `scale_tolerance` or `position_tolerance` are less than zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::gpu_cache::Cache;
+    /// # let mut cache = Cache::builder().build();
+    /// # let scrolling = true;
+    /// if scrolling {
+    ///     cache.set_tolerances(0.5, 0.5);
+    /// } else {
+    ///     cache.set_tolerances(0.1, 0.1);
+    /// }
+    /// ```
+    pub fn set_tolerances(&mut self, scale_tolerance: f32, position_tolerance: f32) {
+        let CacheBuilder {
+            scale_tolerance,
+            position_tolerance,
+            ..
+        } = self
+            .to_builder()
+            .scale_tolerance(scale_tolerance)
+            .position_tolerance(position_tolerance)
+            .validated();
+        if scale_tolerance == self.scale_tolerance && position_tolerance == self.position_tolerance
+        {
+            return;
+        }
+        self.scale_tolerance = scale_tolerance;
+        self.position_tolerance = position_tolerance;
+
+        let reachable = core::mem::take(&mut self.all_glyphs);
+        let mut rows = core::mem::take(&mut self.rows);
+        // most recently used rows first, so their glyphs stay reachable
+        for (top, row) in rows.iter_mut().rev() {
+            for (index, g) in row.glyphs.iter_mut().enumerate() {
+                let location = (*top, index as u32);
+                if reachable.get(&g.glyph_info) != Some(&location) {
+                    // e.g. invalidated by `invalidate_font`
+                    continue;
+                }
+                let (scale_over_tolerance, offset_over_tolerance) =
+                    self.over_tolerance(g.scale, g.offset);
+                g.glyph_info.scale_over_tolerance = scale_over_tolerance;
+                g.glyph_info.offset_over_tolerance = offset_over_tolerance;
+                self.all_glyphs.entry(g.glyph_info).or_insert(location);
+            }
+        }
+        self.rows = rows;
+    }
+
     /// Returns the cache texture dimensions assumed by the cache. For proper
-    /// operation this should match the dimensions of the used GPU texture.
+    /// operation this should match the dimensions of the used GPU texture,
+    /// so check after `CachedBy::Resizing`.
     pub fn dimensions(&self) -> (u32, u32) {
         (self.width, self.height)
     }
 
+    /// Returns if a cpu copy of uploaded glyph pixels is kept, see
+    /// `CacheBuilder::retain_pixels`.
+    pub fn retains_pixels(&self) -> bool {
+        self.retain_pixels
+    }
+
+    /// Returns the number of texture pages of `dimensions`, see
+    /// `CacheBuilder::pages`.
+    pub fn pages(&self) -> u32 {
+        self.pages
+    }
+
+    /// Returns the signed distance field settings, `None` if the cache holds
+    /// coverage, see `CacheBuilder::sdf`.
+    pub fn sdf(&self) -> Option<SdfSettings> {
+        self.sdf
+    }
+
+    /// Returns the subpixel strategy binning glyph positions, see
+    /// `CacheBuilder::subpixel`.
+    pub fn subpixel(&self) -> Option<SubpixelStrategy> {
+        self.subpixel
+    }
+
+    /// Changes the subpixel strategy, see `CacheBuilder::subpixel`, e.g. as
+    /// text starts or stops scrolling. Cached glyphs are kept, glyphs at
+    /// the new bins are cached as they're queued.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::{gpu_cache::Cache, vector, SubpixelStrategy, SubpixelTarget};
+    /// # let mut cache = Cache::builder().build();
+    /// let target = SubpixelTarget {
+    ///     scroll_velocity: vector(0.0, 1.5),
+    ///     ..SubpixelTarget::default()
+    /// };
+    /// cache.set_subpixel(Some(SubpixelStrategy::select(target)));
+    /// assert_eq!(cache.subpixel(), Some(SubpixelStrategy::Thirds));
+    /// ```
+    pub fn set_subpixel(&mut self, strategy: Option<SubpixelStrategy>) {
+        self.subpixel = strategy;
+    }
+
     /// Queue a glyph for caching by the next call to `cache_queued`. `font_id`
     /// is used to disambiguate glyphs from different fonts. The user should
     /// ensure that `font_id` is unique to the font the glyph is from.
     pub fn queue_glyph(&mut self, font_id: usize, glyph: PositionedGlyph<'font>) {
+        self.queue_glyph_variant(font_id, 0, glyph)
+    }
+
+    /// Queue a glyph for caching like `queue_glyph`, as the `variant` of the
+    /// glyph. Variants are cached separately, so visually distinct versions
+    /// of the same glyph, e.g. a synthetic bold, a colour palette index or SDF
+    /// instead of coverage data, don't collide. `queue_glyph` queues variant
+    /// `0`.
+    ///
+    /// The variant is passed to `RasterBackend::rasterize_variant`. See
+    /// `rect_for_variant`.
+    pub fn queue_glyph_variant(
+        &mut self,
+        font_id: usize,
+        variant: u32,
+        glyph: PositionedGlyph<'font>,
+    ) {
+        self.queue_glyph_with_priority(font_id, variant, glyph, GlyphPriority::Visible)
+    }
+
+    /// Queue a glyph `variant` for caching like `queue_glyph_variant`, with a
+    /// `priority`, e.g. to prefetch glyphs likely needed soon like the next
+    /// page of a list. See `GlyphPriority`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rusttype::gpu_cache::{Cache, GlyphPriority};
+    /// # use rusttype::{Font, point, Scale};
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font: Font<'static> = Font::try_from_bytes(font_data).unwrap();
+    /// # fn update_gpu_texture(_: rusttype::Rect<u32>, _: &[u8]) {};
+    /// let mut cache = Cache::builder().build();
+    /// let scale = Scale::uniform(24.0);
+    /// for glyph in font.layout("on screen", scale, point(0.0, 24.0)) {
+    ///     cache.queue_glyph(0, glyph);
+    /// }
+    /// for glyph in font.layout("next page", scale, point(0.0, 48.0)) {
+    ///     cache.queue_glyph_with_priority(0, 0, glyph, GlyphPriority::Prefetch);
+    /// }
+    /// cache.cache_queued(update_gpu_texture)?;
+    /// # Ok::<(), rusttype::gpu_cache::CacheWriteErr>(())
+    /// ```
+    pub fn queue_glyph_with_priority(
+        &mut self,
+        font_id: usize,
+        variant: u32,
+        glyph: PositionedGlyph<'font>,
+        priority: GlyphPriority,
+    ) {
+        let glyph = match self.subpixel {
+            Some(strategy) => strategy.snap_glyph(glyph),
+            None => glyph,
+        };
+        let glyph = match self.sdf {
+            Some(sdf) => sdf.generation_glyph(&glyph),
+            None => glyph,
+        };
         if glyph.pixel_bounding_box().is_some() {
-            self.queue.push((font_id, glyph));
+            self.queue.push((font_id, variant, priority, glyph));
         }
     }
 
@@ -583,47 +1156,224 @@ impl<'font> Cache<'font> {
     pub fn clear(&mut self) {
         self.rows.clear();
         self.space_end_for_start.clear();
-        self.space_end_for_start.insert(0, self.height);
         self.space_start_for_end.clear();
-        self.space_start_for_end.insert(self.height, 0);
+        // a gap per page, rows can't span pages
+        for page in 0..self.pages {
+            let (start, end) = (page * self.height, (page + 1) * self.height);
+            self.space_end_for_start.insert(start, end);
+            self.space_start_for_end.insert(end, start);
+        }
         self.all_glyphs.clear();
     }
 
+    /// Removes all cached glyphs of the font `font_id`, e.g. after it has been
+    /// reloaded. Their texture space is reclaimed once their rows are evicted.
+    ///
+    /// Glyphs from a reloaded font never match textures cached before the
+    /// reload, see `Font::generation`, so this isn't required for correctness
+    /// but stops stale textures from occupying rows until evicted.
+    pub fn invalidate_font(&mut self, font_id: usize) {
+        self.all_glyphs.retain(|info, _| info.font_id != font_id);
+    }
+
     /// Clears the glyph queue.
     pub fn clear_queue(&mut self) {
         self.queue.clear();
     }
 
+    /// Texture regions uploaded since the last `take_dirty_rects`, with
+    /// regions uploaded next to each other in a row merged, so may cover
+    /// unused row space.
+    ///
+    /// Glyphs queued with different variants, e.g. toggling a synthetic bold,
+    /// are cached separately, so only newly needed variants are uploaded &
+    /// dirty. Renderers keeping their own copy of the texture can upload just
+    /// these regions once per frame, instead of in the `cache_queued`
+    /// `uploader`. With multiple `pages` the regions are of the pages as one
+    /// texture, see `cache_queued_paged`.
+    pub fn dirty_rects(&self) -> &[Rect<u32>] {
+        &self.dirty_rects
+    }
+
+    /// Returns & clears the `dirty_rects`, e.g. once per frame.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::{Font, gpu_cache::Cache, point, Scale};
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font: Font<'static> = Font::try_from_bytes(font_data).unwrap();
+    /// # fn update_gpu_texture(_: rusttype::Rect<u32>) {};
+    /// let mut cache = Cache::builder().build();
+    /// // a texture copy kept on the cpu
+    /// let mut pixels = vec![0; 256 * 256];
+    /// for glyph in font.layout("Hello", Scale::uniform(24.0), point(0.0, 24.0)) {
+    ///     cache.queue_glyph(0, glyph);
+    /// }
+    /// cache.cache_queued(|rect, data| {
+    ///     let width = rect.width() as usize;
+    ///     for (y, line) in data.chunks(width).enumerate() {
+    ///         let start = (rect.min.y as usize + y) * 256 + rect.min.x as usize;
+    ///         pixels[start..start + width].copy_from_slice(line);
+    ///     }
+    /// })?;
+    ///
+    /// for rect in cache.take_dirty_rects() {
+    ///     update_gpu_texture(rect);
+    /// }
+    /// assert!(cache.dirty_rects().is_empty());
+    /// # Ok::<(), rusttype::gpu_cache::CacheWriteErr>(())
+    /// ```
+    pub fn take_dirty_rects(&mut self) -> Vec<Rect<u32>> {
+        std::mem::take(&mut self.dirty_rects)
+    }
+
+    /// Caches the glyphs of `charset` from `font` at `scale` ahead of use,
+    /// e.g. during a loading screen, to avoid rasterizing on first use.
+    ///
+    /// Glyphs are cached at whole pixel positions, in batches, calling
+    /// `progress` with the number of glyphs cached so far & the total after
+    /// each. Previously queued glyphs are left queued. The cache should be big
+    /// enough to fit the whole charset, otherwise later batches may evict
+    /// earlier ones.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::{Font, gpu_cache::Cache, Scale};
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font: Font<'static> = Font::try_from_bytes(font_data).unwrap();
+    /// # fn update_gpu_texture(_: rusttype::Rect<u32>, _: &[u8]) {};
+    /// let mut cache = Cache::builder().dimensions(512, 512).build();
+    /// cache.warm_cache(
+    ///     0,
+    ///     &font,
+    ///     Scale::uniform(18.0),
+    ///     ' '..='~',
+    ///     |region, data| update_gpu_texture(region, data),
+    ///     |cached, total| println!("loading glyphs {}/{}", cached, total),
+    /// )?;
+    /// # Ok::<(), rusttype::gpu_cache::CacheWriteErr>(())
+    /// ```
+    pub fn warm_cache<I, F, P>(
+        &mut self,
+        font_id: usize,
+        font: &Font<'font>,
+        scale: Scale,
+        charset: I,
+        mut uploader: F,
+        mut progress: P,
+    ) -> Result<CachedBy, CacheWriteErr>
+    where
+        I: IntoIterator<Item = char>,
+        F: FnMut(Rect<u32>, &[u8]),
+        P: FnMut(usize, usize),
+    {
+        let mut ids = HashSet::with_hasher(FxBuildHasher::default());
+        let glyphs: Vec<_> = charset
+            .into_iter()
+            .map(|c| font.glyph(c))
+            .filter(|g| ids.insert(g.id()))
+            .map(|g| g.scaled(scale).positioned(point(0.0, 0.0)))
+            .filter(|g| g.pixel_bounding_box().is_some())
+            .collect();
+
+        let queued = std::mem::take(&mut self.queue);
+        let mut cached_by = CachedBy::Adding;
+        let mut result = Ok(());
+        for (index, batch) in glyphs.chunks(WARM_BATCH).enumerate() {
+            for glyph in batch {
+                self.queue_glyph(font_id, glyph.clone());
+            }
+            match self.cache_queued(&mut uploader) {
+                Ok(by) => cached_by = cached_by.max(by),
+                Err(err) => {
+                    result = Err(err);
+                    break;
+                }
+            }
+            progress(index * WARM_BATCH + batch.len(), glyphs.len());
+        }
+        self.queue = queued;
+        result.map(|_| cached_by)
+    }
+
     /// Returns a `CacheBuilder` with this cache's attributes.
     pub fn to_builder(&self) -> CacheBuilder {
         CacheBuilder {
             dimensions: (self.width, self.height),
             position_tolerance: self.position_tolerance,
             scale_tolerance: self.scale_tolerance,
-            pad_glyphs: self.pad_glyphs,
+            padding: self.padding,
             align_4x4: self.align_4x4,
             multithread: self.multithread,
+            deterministic: self.deterministic,
+            retain_pixels: self.retain_pixels,
+            pages: self.pages,
+            max_dimensions: Some(self.max_dimensions),
+            sdf: self.sdf,
+            subpixel: self.subpixel,
         }
     }
 
     /// Returns glyph info with accuracy according to the set tolerances.
-    fn lossy_info_for(&self, font_id: FontId, glyph: &PositionedGlyph<'font>) -> LossyGlyphInfo {
-        let scale = glyph.scale();
-        let offset = normalised_offset_from_position(glyph.position());
+    fn lossy_info_for(
+        &self,
+        font_id: FontId,
+        variant: u32,
+        glyph: &PositionedGlyph<'font>,
+    ) -> LossyGlyphInfo {
+        // fields are keyed by the generation glyph, whatever the glyph scale
+        let generation_glyph;
+        let glyph = match self.sdf {
+            Some(sdf) => {
+                generation_glyph = sdf.generation_glyph(glyph);
+                &generation_glyph
+            }
+            None => glyph,
+        };
+        let offset = normalised_offset_from_position(self.snapped_position(glyph));
+        let transform = glyph.unpositioned().transform();
+        let (scale_over_tolerance, offset_over_tolerance) =
+            self.over_tolerance(glyph.scale(), offset);
 
         LossyGlyphInfo {
             font_id,
+            font_generation: glyph.font().map_or(0, Font::generation),
+            variant,
             glyph_id: glyph.id(),
-            scale_over_tolerance: (
+            standalone_key: glyph.unscaled().standalone_key(),
+            scale_over_tolerance,
+            offset_over_tolerance,
+            transform: [transform.xx, transform.yx, transform.xy, transform.yy].map(f32::to_bits),
+            #[cfg(feature = "hinting")]
+            hinting: glyph.unpositioned().hinting(),
+        }
+    }
+
+    /// Returns the position of `glyph` binned by the subpixel strategy, see
+    /// `CacheBuilder::subpixel`.
+    fn snapped_position(&self, glyph: &PositionedGlyph<'_>) -> Point<f32> {
+        match self.subpixel {
+            Some(strategy) => strategy.snap(glyph.position()),
+            None => glyph.position(),
+        }
+    }
+
+    /// Returns `scale` & the normalised `offset` divided by the tolerances &
+    /// rounded, see `LossyGlyphInfo`.
+    fn over_tolerance(&self, scale: Scale, offset: Vector<f32>) -> ((u32, u32), (u16, u16)) {
+        (
+            (
                 (scale.x / self.scale_tolerance + 0.5) as u32,
                 (scale.y / self.scale_tolerance + 0.5) as u32,
             ),
             // convert [-0.5, 0.5] -> [0, 1] then divide
-            offset_over_tolerance: (
+            (
                 ((offset.x + 0.5) / self.position_tolerance + 0.5) as u16,
                 ((offset.y + 0.5) / self.position_tolerance + 0.5) as u16,
             ),
-        }
+        )
     }
 
     /// Caches the queued glyphs. If this is unsuccessful, the queue is
@@ -642,57 +1392,240 @@ impl<'font> Cache<'font> {
     /// previously cached glyph textures.
     pub fn cache_queued<F: FnMut(Rect<u32>, &[u8])>(
         &mut self,
-        mut uploader: F,
+        uploader: F,
     ) -> Result<CachedBy, CacheWriteErr> {
-        let mut queue_success = true;
-        let from_empty = self.all_glyphs.is_empty();
-
-        {
-            let (mut in_use_rows, mut uncached_glyphs) = {
-                let mut in_use_rows =
-                    HashSet::with_capacity_and_hasher(self.rows.len(), FxBuildHasher::default());
-                let mut uncached_glyphs = Vec::with_capacity(self.queue.len());
+        self.cache_queued_with_backend(&ScanlineRasterizer, uploader)
+    }
 
-                // divide glyphs into texture rows where a matching glyph texture
-                // already exists & glyphs where new textures must be cached
-                for (font_id, ref glyph) in &self.queue {
-                    let glyph_info = self.lossy_info_for(*font_id, glyph);
-                    if let Some((row, ..)) = self.all_glyphs.get(&glyph_info) {
-                        in_use_rows.insert(*row);
-                    } else {
-                        uncached_glyphs.push((glyph, glyph_info));
-                    }
+    /// Caches the queued glyphs like `cache_queued`, but only as many new
+    /// glyphs as `budget` allows, so a sudden wall of new glyphs, e.g. after a
+    /// language switch, is cached over several frames instead of causing one
+    /// long frame.
+    ///
+    /// Queued glyphs already cached are always kept. The queue is cleared when
+    /// successful, like `cache_queued`, so queue the glyphs again next frame
+    /// while `CacheProgress::remaining` glyphs haven't been cached. Until then
+    /// `rect_for` returns `Err` for them, so draw them later or with a
+    /// fallback.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rusttype::gpu_cache::{Cache, CacheBudget};
+    /// # use rusttype::{Font, point, Scale};
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font: Font<'static> = Font::try_from_bytes(font_data).unwrap();
+    /// # fn update_gpu_texture(_: rusttype::Rect<u32>, _: &[u8]) {};
+    /// let mut cache = Cache::builder().dimensions(512, 512).build();
+    /// for glyph in font.layout("lots of new text", Scale::uniform(24.0), point(0.0, 24.0)) {
+    ///     cache.queue_glyph(0, glyph);
+    /// }
+    /// let progress = cache.cache_queued_with_budget(CacheBudget::Glyphs(4), update_gpu_texture)?;
+    /// assert_eq!(progress.cached, 4);
+    /// assert!(!progress.is_complete());
+    /// # Ok::<(), rusttype::gpu_cache::CacheWriteErr>(())
+    /// ```
+    pub fn cache_queued_with_budget<F: FnMut(Rect<u32>, &[u8])>(
+        &mut self,
+        budget: CacheBudget,
+        mut uploader: F,
+    ) -> Result<CacheProgress, CacheWriteErr> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let start = Instant::now();
+
+        // split glyphs matching a cached texture from distinct new glyphs
+        let queue = self.queue.clone();
+        let mut new_infos = HashSet::with_hasher(FxBuildHasher::default());
+        let (mut cached, mut uncached) = (Vec::new(), Vec::new());
+        for queued in &queue {
+            let (font_id, variant, _, ref glyph) = *queued;
+            let info = self.lossy_info_for(font_id, variant, glyph);
+            if self.all_glyphs.contains_key(&info) {
+                cached.push(queued.clone());
+            } else if new_infos.insert(info) {
+                uncached.push(queued.clone());
+            }
+        }
+        // visible glyphs first, keeping the queue order otherwise
+        uncached.sort_by_key(|&(_, _, priority, _)| priority);
+
+        let mut cached_by = CachedBy::Adding;
+        let mut taken = 0;
+        loop {
+            let next = match budget {
+                CacheBudget::Glyphs(max) => max,
+                #[cfg(not(target_arch = "wasm32"))]
+                CacheBudget::Time(_) => taken + BUDGET_BATCH,
+            }
+            .min(uncached.len());
+            self.queue = cached.iter().chain(&uncached[..next]).cloned().collect();
+            match self.cache_queued(&mut uploader) {
+                Ok(by) => cached_by = cached_by.max(by),
+                Err(err) => {
+                    self.queue = queue;
+                    return Err(err);
                 }
+            }
+            taken = next;
 
-                (in_use_rows, uncached_glyphs)
+            let out_of_budget = match budget {
+                CacheBudget::Glyphs(_) => true,
+                #[cfg(not(target_arch = "wasm32"))]
+                CacheBudget::Time(limit) => start.elapsed() >= limit,
             };
-
-            for row in &in_use_rows {
-                self.rows.get_refresh(row);
+            if out_of_budget || taken == uncached.len() {
+                break;
             }
+        }
 
-            // tallest first gives better packing
-            // can use 'sort_unstable' as order of equal elements is unimportant
-            uncached_glyphs
-                .sort_unstable_by_key(|(glyph, ..)| -glyph.pixel_bounding_box().unwrap().height());
+        Ok(CacheProgress {
+            cached_by,
+            cached: taken,
+            remaining: uncached.len() - taken,
+        })
+    }
 
-            self.all_glyphs.reserve(uncached_glyphs.len());
-            let mut draw_and_upload = Vec::with_capacity(uncached_glyphs.len());
+    /// Caches the queued glyphs like `cache_queued`, rasterizing them with
+    /// `backend`. See `RasterBackend`.
+    ///
+    /// With `multithread` enabled `backend` is used by multiple threads at
+    /// once.
+    pub fn cache_queued_with_backend<B, F>(
+        &mut self,
+        backend: &B,
+        mut uploader: F,
+    ) -> Result<CachedBy, CacheWriteErr>
+    where
+        B: RasterBackend + Sync + ?Sized,
+        F: FnMut(Rect<u32>, &[u8]),
+    {
+        let height = self.height;
+        self.cache_queued_paged_with_backend(backend, |page, rect, data| {
+            uploader(stacked(rect, page, height), data)
+        })
+    }
 
-            'per_glyph: for (glyph, glyph_info) in uncached_glyphs {
-                // glyph may match a texture cached by a previous iteration
-                if self.all_glyphs.contains_key(&glyph_info) {
+    /// Caches the queued glyphs like `cache_queued`, calling `uploader` with
+    /// the texture page of each upload, from `0`, & the region within that
+    /// page. See `CacheBuilder::pages`.
+    ///
+    /// Without paged uploads the pages act as one texture of `pages` times
+    /// the height, page `n` starting at `n * height`, for the `cache_queued`
+    /// uploads, `rect_for`, `dirty_rects` & `debug_overlay`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::{Font, gpu_cache::Cache, point, Scale};
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font: Font<'static> = Font::try_from_bytes(font_data).unwrap();
+    /// # fn update_texture_layer(_: u32, _: rusttype::Rect<u32>, _: &[u8]) {};
+    /// let mut cache = Cache::builder().dimensions(64, 64).pages(4).build();
+    /// for glyph in font.layout("Hello paged world", Scale::uniform(24.0), point(0.0, 24.0)) {
+    ///     cache.queue_glyph(0, glyph);
+    /// }
+    /// cache.cache_queued_paged(|page, rect, data| update_texture_layer(page, rect, data))?;
+    /// # Ok::<(), rusttype::gpu_cache::CacheWriteErr>(())
+    /// ```
+    pub fn cache_queued_paged<F: FnMut(u32, Rect<u32>, &[u8])>(
+        &mut self,
+        uploader: F,
+    ) -> Result<CachedBy, CacheWriteErr> {
+        self.cache_queued_paged_with_backend(&ScanlineRasterizer, uploader)
+    }
+
+    /// Caches the queued glyphs like `cache_queued_paged`, rasterizing them
+    /// with `backend`, see `cache_queued_with_backend`.
+    pub fn cache_queued_paged_with_backend<B, F>(
+        &mut self,
+        backend: &B,
+        uploader: F,
+    ) -> Result<CachedBy, CacheWriteErr>
+    where
+        B: RasterBackend + Sync + ?Sized,
+        F: FnMut(u32, Rect<u32>, &[u8]),
+    {
+        match self.sdf {
+            Some(sdf) => self.cache_queued_packed(&SdfRasterizer { backend, sdf }, uploader),
+            None => self.cache_queued_packed(backend, uploader),
+        }
+    }
+
+    fn cache_queued_packed<B, F>(
+        &mut self,
+        backend: &B,
+        mut uploader: F,
+    ) -> Result<CachedBy, CacheWriteErr>
+    where
+        B: RasterBackend + Sync + ?Sized,
+        F: FnMut(u32, Rect<u32>, &[u8]),
+    {
+        let mut queue_success = true;
+        let mut grow = false;
+        let from_empty = self.all_glyphs.is_empty();
+
+        {
+            let (mut in_use_rows, mut uncached_glyphs) = {
+                let mut in_use_rows =
+                    HashSet::with_capacity_and_hasher(self.rows.len(), FxBuildHasher::default());
+                let mut uncached_glyphs = Vec::with_capacity(self.queue.len());
+
+                // divide glyphs into texture rows where a matching glyph texture
+                // already exists & glyphs where new textures must be cached
+                let (mut visible_rows, mut prefetch_rows) = (Vec::new(), Vec::new());
+                for (font_id, variant, priority, ref glyph) in &self.queue {
+                    let glyph_info = self.lossy_info_for(*font_id, *variant, glyph);
+                    if let Some((row, ..)) = self.all_glyphs.get(&glyph_info) {
+                        // only rows of visible glyphs are protected from eviction
+                        match priority {
+                            GlyphPriority::Visible if in_use_rows.insert(*row) => {
+                                visible_rows.push(*row)
+                            }
+                            GlyphPriority::Visible => {}
+                            GlyphPriority::Prefetch => prefetch_rows.push(*row),
+                        }
+                    } else {
+                        uncached_glyphs.push((glyph, glyph_info, *priority));
+                    }
+                }
+
+                // visible rows refreshed last, in queue order, so evicted last
+                for row in prefetch_rows.iter().chain(&visible_rows) {
+                    self.rows.get_refresh(row);
+                }
+                (in_use_rows, uncached_glyphs)
+            };
+
+            // visible glyphs first, tallest first gives better packing
+            let packing_order =
+                |(glyph, _, priority): &(&PositionedGlyph<'_>, LossyGlyphInfo, GlyphPriority)| {
+                    (*priority, -glyph.pixel_bounding_box().unwrap().height())
+                };
+            if self.deterministic {
+                uncached_glyphs.sort_by_key(packing_order);
+            } else {
+                // can use 'sort_unstable' as order of equal elements is unimportant
+                uncached_glyphs.sort_unstable_by_key(packing_order);
+            }
+
+            self.all_glyphs.reserve(uncached_glyphs.len());
+            let mut draw_and_upload = Vec::with_capacity(uncached_glyphs.len());
+
+            'per_glyph: for (glyph, glyph_info, priority) in uncached_glyphs {
+                // prefetched glyphs are cached if there's room, without
+                // failing or reordering the cache
+                let prefetch = priority == GlyphPriority::Prefetch;
+                // glyph may match a texture cached by a previous iteration
+                if self.all_glyphs.contains_key(&glyph_info) {
                     continue;
                 }
 
                 // Not cached, so add it:
                 let (unaligned_width, unaligned_height) = {
                     let bb = glyph.pixel_bounding_box().unwrap();
-                    if self.pad_glyphs {
-                        (bb.width() as u32 + 2, bb.height() as u32 + 2)
-                    } else {
-                        (bb.width() as u32, bb.height() as u32)
-                    }
+                    // padded on every side, & fields extend by the spread
+                    let pad = 2 * (self.sdf.map_or(0, |sdf| sdf.padding()) + self.padding);
+                    (bb.width() as u32 + pad, bb.height() as u32 + pad)
                 };
                 let (aligned_width, aligned_height) = if self.align_4x4 {
                     // align to the next 4x4 texel boundary
@@ -701,6 +1634,9 @@ impl<'font> Cache<'font> {
                     (unaligned_width, unaligned_height)
                 };
                 if aligned_width >= self.width || aligned_height >= self.height {
+                    if prefetch {
+                        continue;
+                    }
                     return Result::Err(CacheWriteErr::GlyphTooLarge);
                 }
                 // find row to put the glyph in, most used rows first
@@ -714,14 +1650,17 @@ impl<'font> Cache<'font> {
                 }
 
                 if row_top.is_none() {
-                    let mut gap = None;
                     // See if there is space for a new row
-                    for (start, end) in &self.space_end_for_start {
-                        if end - start >= aligned_height {
-                            gap = Some((*start, *end));
-                            break;
-                        }
-                    }
+                    let mut fitting_gaps = self
+                        .space_end_for_start
+                        .iter()
+                        .filter(|&(start, end)| end - start >= aligned_height)
+                        .map(|(&start, &end)| (start, end));
+                    let mut gap = if self.deterministic {
+                        fitting_gaps.min()
+                    } else {
+                        fitting_gaps.next()
+                    };
                     if gap.is_none() {
                         // Remove old rows until room is available
                         while !self.rows.is_empty() {
@@ -730,18 +1669,29 @@ impl<'font> Cache<'font> {
                                 // Remove row
                                 let (top, row) = self.rows.pop_front().unwrap();
 
-                                for g in row.glyphs {
-                                    self.all_glyphs.remove(&g.glyph_info);
+                                for (index, g) in row.glyphs.iter().enumerate() {
+                                    // the key may point elsewhere, e.g. after
+                                    // glyphs merged in `set_tolerances`
+                                    let location = (top, index as u32);
+                                    if self.all_glyphs.get(&g.glyph_info) == Some(&location) {
+                                        self.all_glyphs.remove(&g.glyph_info);
+                                    }
                                 }
 
                                 let (mut new_start, mut new_end) = (top, top + row.height);
                                 // Update the free space maps
                                 // Combine with neighbouring free space if possible
-                                if let Some(end) = self.space_end_for_start.remove(&new_end) {
-                                    new_end = end;
+                                // but not across pages
+                                if new_end % self.height != 0 {
+                                    if let Some(end) = self.space_end_for_start.remove(&new_end) {
+                                        new_end = end;
+                                    }
                                 }
-                                if let Some(start) = self.space_start_for_end.remove(&new_start) {
-                                    new_start = start;
+                                if new_start % self.height != 0 {
+                                    if let Some(start) = self.space_start_for_end.remove(&new_start)
+                                    {
+                                        new_start = start;
+                                    }
                                 }
                                 self.space_start_for_end.insert(new_end, new_start);
                                 self.space_end_for_start.insert(new_start, new_end);
@@ -752,11 +1702,20 @@ impl<'font> Cache<'font> {
                                 }
                             }
                             // all rows left are in use
+                            else if prefetch {
+                                continue 'per_glyph;
+                            }
                             // try a clean insert of all needed glyphs
                             // if that doesn't work, fail
                             else if from_empty {
                                 // already trying a clean insert, don't do it again
-                                return Err(CacheWriteErr::NoRoomForWholeQueue);
+                                if self.grown_dimensions().is_none() {
+                                    return Err(CacheWriteErr::NoRoomForWholeQueue);
+                                }
+                                // but a larger texture may fit
+                                grow = true;
+                                queue_success = false;
+                                break 'per_glyph;
                             } else {
                                 // signal that a retry is needed
                                 queue_success = false;
@@ -797,13 +1756,15 @@ impl<'font> Cache<'font> {
                     max: point(row.width + unaligned_width, row_top + unaligned_height),
                 };
 
-                draw_and_upload.push((aligned_tex_coords, glyph));
+                draw_and_upload.push((aligned_tex_coords, glyph, glyph_info));
 
                 // add the glyph to the row
                 row.glyphs.push(GlyphTexInfo {
                     glyph_info,
                     offset: normalised_offset_from_position(glyph.position()),
+                    scale: glyph.scale(),
                     tex_coords: unaligned_tex_coords,
+                    pixels: Vec::new(),
                 });
                 row.width += aligned_width;
                 in_use_rows.insert(row_top);
@@ -813,6 +1774,23 @@ impl<'font> Cache<'font> {
             }
 
             if queue_success {
+                for &(tex_coords, ..) in &draw_and_upload {
+                    mark_dirty(&mut self.dirty_rects, tex_coords);
+                }
+                let (padding, retain_pixels) = (self.padding, self.retain_pixels);
+                let (rows, all_glyphs, height) = (&mut self.rows, &self.all_glyphs, self.height);
+                // uploads & keeps a copy of the pixels, see `pixels_for`
+                let mut upload = |glyph_info, tex_coords, pixels: ByteArray2d| {
+                    let (page, page_coords) = paged(tex_coords, height);
+                    uploader(page, page_coords, pixels.as_slice());
+                    if !retain_pixels {
+                        return;
+                    }
+                    let (row, index) = all_glyphs[&glyph_info];
+                    let info = &mut rows.get_mut(&row).unwrap().glyphs[index as usize];
+                    info.pixels = glyph_pixels(&pixels, info.tex_coords, padding);
+                };
+
                 #[cfg(not(target_arch = "wasm32"))]
                 {
                     let glyph_count = draw_and_upload.len();
@@ -827,9 +1805,18 @@ impl<'font> Cache<'font> {
 
                         let rasterize_queue = crossbeam_deque::Injector::new();
                         let (to_main, from_stealers) = mpsc::channel();
-                        let pad_glyphs = self.pad_glyphs;
+                        let deterministic = self.deterministic;
+                        // deterministic uploads are held back to upload in packing order
+                        let mut in_order = Vec::new();
+                        let mut upload_ready = |index, glyph_info, tex_coords, pixels| {
+                            if deterministic {
+                                in_order.push((index, glyph_info, tex_coords, pixels));
+                            } else {
+                                upload(glyph_info, tex_coords, pixels);
+                            }
+                        };
 
-                        for el in draw_and_upload {
+                        for el in draw_and_upload.into_iter().enumerate() {
                             rasterize_queue.push(el);
                         }
                         crossbeam_utils::thread::scope(|scope| {
@@ -838,9 +1825,20 @@ impl<'font> Cache<'font> {
                                 let to_main = to_main.clone();
                                 scope.spawn(move |_| loop {
                                     match rasterize_queue.steal() {
-                                        Steal::Success((tex_coords, glyph)) => {
-                                            let pixels = draw_glyph(tex_coords, glyph, pad_glyphs);
-                                            to_main.send((tex_coords, pixels)).unwrap();
+                                        Steal::Success((
+                                            index,
+                                            (tex_coords, glyph, glyph_info),
+                                        )) => {
+                                            let pixels = draw_glyph(
+                                                backend,
+                                                tex_coords,
+                                                glyph,
+                                                glyph_info.variant,
+                                                padding,
+                                            );
+                                            to_main
+                                                .send((index, glyph_info, tex_coords, pixels))
+                                                .unwrap();
                                         }
                                         Steal::Empty => break,
                                         Steal::Retry => {}
@@ -852,9 +1850,15 @@ impl<'font> Cache<'font> {
                             let mut workers_finished = false;
                             loop {
                                 match rasterize_queue.steal() {
-                                    Steal::Success((tex_coords, glyph)) => {
-                                        let pixels = draw_glyph(tex_coords, glyph, pad_glyphs);
-                                        uploader(tex_coords, pixels.as_slice());
+                                    Steal::Success((index, (tex_coords, glyph, glyph_info))) => {
+                                        let pixels = draw_glyph(
+                                            backend,
+                                            tex_coords,
+                                            glyph,
+                                            glyph_info.variant,
+                                            padding,
+                                        );
+                                        upload_ready(index, glyph_info, tex_coords, pixels);
                                     }
                                     Steal::Empty if workers_finished => break,
                                     Steal::Empty | Steal::Retry => {}
@@ -862,8 +1866,8 @@ impl<'font> Cache<'font> {
 
                                 while !workers_finished {
                                     match from_stealers.try_recv() {
-                                        Ok((tex_coords, pixels)) => {
-                                            uploader(tex_coords, pixels.as_slice())
+                                        Ok((index, glyph_info, tex_coords, pixels)) => {
+                                            upload_ready(index, glyph_info, tex_coords, pixels)
                                         }
                                         Err(TryRecvError::Disconnected) => workers_finished = true,
                                         Err(TryRecvError::Empty) => break,
@@ -872,19 +1876,26 @@ impl<'font> Cache<'font> {
                             }
                         })
                         .unwrap();
+
+                        in_order.sort_unstable_by_key(|&(index, ..)| index);
+                        for (_, glyph_info, tex_coords, pixels) in in_order {
+                            upload(glyph_info, tex_coords, pixels);
+                        }
                     } else {
                         // single thread rasterization
-                        for (tex_coords, glyph) in draw_and_upload {
-                            let pixels = draw_glyph(tex_coords, glyph, self.pad_glyphs);
-                            uploader(tex_coords, pixels.as_slice());
+                        for (tex_coords, glyph, glyph_info) in draw_and_upload {
+                            let pixels =
+                                draw_glyph(backend, tex_coords, glyph, glyph_info.variant, padding);
+                            upload(glyph_info, tex_coords, pixels);
                         }
                     }
                 }
                 #[cfg(target_arch = "wasm32")]
                 {
-                    for (tex_coords, glyph) in draw_and_upload {
-                        let pixels = draw_glyph(tex_coords, glyph, self.pad_glyphs);
-                        uploader(tex_coords, pixels.as_slice());
+                    for (tex_coords, glyph, glyph_info) in draw_and_upload {
+                        let pixels =
+                            draw_glyph(backend, tex_coords, glyph, glyph_info.variant, padding);
+                        upload(glyph_info, tex_coords, pixels);
                     }
                 }
             }
@@ -893,13 +1904,32 @@ impl<'font> Cache<'font> {
         if queue_success {
             self.queue.clear();
             Ok(CachedBy::Adding)
+        } else if grow {
+            let (width, height) = self.grown_dimensions().unwrap();
+            self.width = width;
+            self.height = height;
+            // the texture is reallocated so earlier uploads are gone
+            self.dirty_rects.clear();
+            self.clear();
+            self.cache_queued_packed(backend, uploader)
+                .map(|_| CachedBy::Resizing)
         } else {
             // clear the cache then try again with optimal packing
             self.clear();
-            self.cache_queued(uploader).map(|_| CachedBy::Reordering)
+            self.cache_queued_packed(backend, uploader)
+                .map(|by| by.max(CachedBy::Reordering))
         }
     }
 
+    /// Returns the doubled `dimensions` within the `max_dimensions`, `None`
+    /// if the cache can't grow.
+    fn grown_dimensions(&self) -> Option<(u32, u32)> {
+        let (max_width, max_height) = self.max_dimensions;
+        let width = (self.width * 2).min(max_width).max(self.width);
+        let height = (self.height * 2).min(max_height).max(self.height);
+        Some((width, height)).filter(|&grown| grown != (self.width, self.height))
+    }
+
     /// Retrieves the (floating point) texture coordinates of the quad for a
     /// glyph in the cache, as well as the pixel-space (integer) coordinates
     /// that this region should be drawn at. These pixel-space coordinates
@@ -914,40 +1944,96 @@ impl<'font> Cache<'font> {
     ///
     /// Ensure that `font_id` matches the `font_id` that was passed to
     /// `queue_glyph` with this `glyph`.
+    ///
+    /// With multiple `pages` the texture coordinates are of the pages as one
+    /// texture, see `page_rect_for` for coordinates within the glyph's page.
     pub fn rect_for(
         &self,
         font_id: usize,
         glyph: &PositionedGlyph,
     ) -> Result<Option<TextureCoords>, CacheReadErr> {
+        self.rect_for_variant(font_id, 0, glyph)
+    }
+
+    /// Retrieves the texture coordinates of a glyph `variant` queued with
+    /// `queue_glyph_variant`, like `rect_for`.
+    pub fn rect_for_variant(
+        &self,
+        font_id: usize,
+        variant: u32,
+        glyph: &PositionedGlyph,
+    ) -> Result<Option<TextureCoords>, CacheReadErr> {
+        let pages = self.pages as f32;
+        let coords = self.page_rect_for_variant(font_id, variant, glyph)?;
+        Ok(coords.map(|(page, (uv_rect, bb))| {
+            let page = page as f32;
+            let uv_rect = Rect {
+                min: point(uv_rect.min.x, (page + uv_rect.min.y) / pages),
+                max: point(uv_rect.max.x, (page + uv_rect.max.y) / pages),
+            };
+            (uv_rect, bb)
+        }))
+    }
+
+    /// Retrieves the texture page of a cached glyph, from `0`, & its
+    /// texture coordinates within that page, like `rect_for`. See
+    /// `CacheBuilder::pages`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::{Font, gpu_cache::Cache, point, Scale};
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font: Font<'static> = Font::try_from_bytes(font_data).unwrap();
+    /// let glyph = font.glyph('a').scaled(Scale::uniform(24.0)).positioned(point(0.0, 0.0));
+    /// let mut cache = Cache::builder().pages(2).build();
+    /// cache.queue_glyph(0, glyph.clone());
+    /// cache.cache_queued_paged(|_, _, _| {})?;
+    ///
+    /// let (page, (uv_rect, screen_rect)) = cache.page_rect_for(0, &glyph).unwrap().unwrap();
+    /// assert!(page < cache.pages());
+    /// # Ok::<(), rusttype::gpu_cache::CacheWriteErr>(())
+    /// ```
+    pub fn page_rect_for(
+        &self,
+        font_id: usize,
+        glyph: &PositionedGlyph,
+    ) -> Result<Option<(u32, TextureCoords)>, CacheReadErr> {
+        self.page_rect_for_variant(font_id, 0, glyph)
+    }
+
+    /// Retrieves the texture page & coordinates of a glyph `variant` queued
+    /// with `queue_glyph_variant`, like `page_rect_for`.
+    pub fn page_rect_for_variant(
+        &self,
+        font_id: usize,
+        variant: u32,
+        glyph: &PositionedGlyph,
+    ) -> Result<Option<(u32, TextureCoords)>, CacheReadErr> {
+        if self.sdf.is_some() {
+            // the nearest pixels, see `sdf_rect_for` for the exact rect
+            let rect = self.sdf_rect_for_variant(font_id, variant, glyph)?;
+            return Ok(rect.map(|rect| {
+                let screen_rect = rect.screen_rect.map(|v| v.round() as i32);
+                (rect.page, (rect.uv_rect, screen_rect))
+            }));
+        }
         if glyph.pixel_bounding_box().is_none() {
             return Ok(None);
         }
 
         let (row, index) = self
             .all_glyphs
-            .get(&self.lossy_info_for(font_id, glyph))
+            .get(&self.lossy_info_for(font_id, variant, glyph))
             .ok_or(CacheReadErr::GlyphNotCached)?;
 
-        let (tex_width, tex_height) = (self.width as f32, self.height as f32);
-
         let GlyphTexInfo {
-            tex_coords: mut tex_rect,
+            tex_coords: tex_rect,
             offset: tex_offset,
             ..
-        } = self.rows[&row].glyphs[*index as usize];
-        if self.pad_glyphs {
-            tex_rect = tex_rect.unpadded();
-        }
-        let uv_rect = Rect {
-            min: point(
-                tex_rect.min.x as f32 / tex_width,
-                tex_rect.min.y as f32 / tex_height,
-            ),
-            max: point(
-                tex_rect.max.x as f32 / tex_width,
-                tex_rect.max.y as f32 / tex_height,
-            ),
-        };
+        } = self.rows[row].glyphs[*index as usize];
+        let (page, tex_rect) = paged(tex_rect.unpadded(self.padding), self.height);
+        let uv_rect = tex_rect.to_f32() / vector(self.width, self.height).to_f32();
 
         let local_bb = glyph
             .unpositioned()
@@ -955,34 +2041,393 @@ impl<'font> Cache<'font> {
             .positioned(point(0.0, 0.0) + tex_offset)
             .pixel_bounding_box()
             .unwrap();
-        let min_from_origin =
-            point(local_bb.min.x as f32, local_bb.min.y as f32) - (point(0.0, 0.0) + tex_offset);
-        let ideal_min = min_from_origin + glyph.position();
-        let min = point(ideal_min.x.round() as i32, ideal_min.y.round() as i32);
+        let min_from_origin = local_bb.min.to_f32() - (point(0.0, 0.0) + tex_offset);
+        let ideal_min = min_from_origin + self.snapped_position(glyph);
+        let min = ideal_min.round();
         let bb_offset = min - local_bb.min;
         let bb = Rect {
             min,
             max: local_bb.max + bb_offset,
         };
-        Ok(Some((uv_rect, bb)))
+        Ok(Some((page, (uv_rect, bb))))
+    }
+
+    /// Retrieves where to draw the cached signed distance field of a glyph
+    /// queued at any scale, see `CacheBuilder::sdf`. Includes the nominal
+    /// scale the field was generated at & the spread scaled to the glyph.
+    ///
+    /// Like `rect_for` a successful result is `Some` if the glyph isn't an
+    /// empty glyph. Also `Ok(None)` for caches without `sdf`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::{Font, gpu_cache::Cache, point, Scale};
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font: Font<'static> = Font::try_from_bytes(font_data).unwrap();
+    /// let mut cache = Cache::builder().sdf(4.0, 32.0).build();
+    /// let small = font.glyph('a').scaled(Scale::uniform(16.0)).positioned(point(0.0, 20.0));
+    /// let large = font.glyph('a').scaled(Scale::uniform(64.0)).positioned(point(0.0, 80.0));
+    /// cache.queue_glyph(0, small.clone());
+    /// cache.queue_glyph(0, large.clone());
+    /// let mut uploads = 0;
+    /// cache.cache_queued(|_, _| uploads += 1)?;
+    /// // one field for both sizes
+    /// assert_eq!(uploads, 1);
+    ///
+    /// let rect = cache.sdf_rect_for(0, &large).unwrap().unwrap();
+    /// assert_eq!(rect.generation_scale, Scale::uniform(32.0));
+    /// assert_eq!(rect.spread, 8.0);
+    /// # Ok::<(), rusttype::gpu_cache::CacheWriteErr>(())
+    /// ```
+    pub fn sdf_rect_for(
+        &self,
+        font_id: usize,
+        glyph: &PositionedGlyph,
+    ) -> Result<Option<SdfRect>, CacheReadErr> {
+        self.sdf_rect_for_variant(font_id, 0, glyph)
+    }
+
+    /// Retrieves where to draw the signed distance field of a glyph
+    /// `variant` queued with `queue_glyph_variant`, like `sdf_rect_for`.
+    pub fn sdf_rect_for_variant(
+        &self,
+        font_id: usize,
+        variant: u32,
+        glyph: &PositionedGlyph,
+    ) -> Result<Option<SdfRect>, CacheReadErr> {
+        let sdf = match self.sdf {
+            Some(sdf) => sdf,
+            None => return Ok(None),
+        };
+        let generation_bb = match sdf.generation_glyph(glyph).pixel_bounding_box() {
+            Some(bb) if glyph.pixel_bounding_box().is_some() => bb,
+            _ => return Ok(None),
+        };
+
+        let (row, index) = self
+            .all_glyphs
+            .get(&self.lossy_info_for(font_id, variant, glyph))
+            .ok_or(CacheReadErr::GlyphNotCached)?;
+        let info = &self.rows[row].glyphs[*index as usize];
+        let tex_rect = info.tex_coords.unpadded(self.padding);
+        let (page, tex_rect) = paged(tex_rect, self.height);
+        let uv_rect = tex_rect.to_f32() / vector(self.width, self.height).to_f32();
+
+        // generation pixels to the glyph's, the same on both axes
+        let factor = glyph.scale().y / sdf.scale;
+        let pad = vector(1.0, 1.0) * sdf.padding() as f32;
+        let field = generation_bb.map(|v| v as f32);
+        let (position, origin) = (self.snapped_position(glyph), Point::default());
+        let screen_rect = Rect {
+            min: position + (field.min - pad - origin) * factor,
+            max: position + (field.max + pad - origin) * factor,
+        };
+        Ok(Some(SdfRect {
+            page,
+            uv_rect,
+            screen_rect,
+            generation_scale: info.scale,
+            spread: sdf.spread * factor,
+        }))
+    }
+
+    /// Returns the texture pixel rect & a copy of the pixels uploaded there
+    /// for a cached glyph, row major with a stride of the rect width &
+    /// excluding padding. So hybrid CPU/GPU renderers & screenshot tools can
+    /// reuse rasterized glyphs.
+    ///
+    /// Returns `None` for uncached & empty glyphs & if the cache doesn't
+    /// `retains_pixels`. Like `rect_for` the pixels may be of a glyph within
+    /// the cache tolerances, drawn at a slightly different scale or subpixel
+    /// position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::{Font, gpu_cache::Cache, point, Scale};
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font: Font<'static> = Font::try_from_bytes(font_data).unwrap();
+    /// let glyph = font.glyph('a').scaled(Scale::uniform(24.0)).positioned(point(0.0, 0.0));
+    /// let mut cache = Cache::builder().retain_pixels(true).build();
+    /// cache.queue_glyph(0, glyph.clone());
+    /// cache.cache_queued(|_, _| {})?;
+    ///
+    /// let (rect, pixels) = cache.pixels_for(0, &glyph).unwrap();
+    /// assert_eq!(pixels.len(), (rect.width() * rect.height()) as usize);
+    /// # Ok::<(), rusttype::gpu_cache::CacheWriteErr>(())
+    /// ```
+    pub fn pixels_for(
+        &self,
+        font_id: usize,
+        glyph: &PositionedGlyph,
+    ) -> Option<(Rect<u32>, &[u8])> {
+        self.pixels_for_variant(font_id, 0, glyph)
+    }
+
+    /// Returns the pixels of a glyph `variant` queued with
+    /// `queue_glyph_variant`, like `pixels_for`.
+    pub fn pixels_for_variant(
+        &self,
+        font_id: usize,
+        variant: u32,
+        glyph: &PositionedGlyph,
+    ) -> Option<(Rect<u32>, &[u8])> {
+        if !self.retain_pixels {
+            return None;
+        }
+        glyph.pixel_bounding_box()?;
+        let (row, index) = self
+            .all_glyphs
+            .get(&self.lossy_info_for(font_id, variant, glyph))?;
+        let info = &self.rows[row].glyphs[*index as usize];
+        let tex_rect = info.tex_coords.unpadded(self.padding);
+        Some((tex_rect, &info.pixels))
+    }
+
+    /// Returns the texture rows & cached glyph rects with their keys, to draw
+    /// over the texture when diagnosing packing, evictions or distorted
+    /// glyphs, of the pages as one texture. See also `debug_dump_png`.
+    pub fn debug_overlay(&self) -> CacheOverlay {
+        let mut overlay = CacheOverlay {
+            rows: Vec::with_capacity(self.rows.len()),
+            glyphs: Vec::with_capacity(self.all_glyphs.len()),
+        };
+        // rows iterate least recently used first
+        for (index, (&top, row)) in self.rows.iter().enumerate() {
+            let age = self.rows.len() - 1 - index;
+            overlay.rows.push(OverlayRow {
+                rect: Rect {
+                    min: point(0, top),
+                    max: point(self.width, top + row.height),
+                },
+                used_width: row.width,
+                age,
+            });
+            for info in &row.glyphs {
+                let LossyGlyphInfo {
+                    font_id,
+                    variant,
+                    glyph_id,
+                    ..
+                } = info.glyph_info;
+                overlay.glyphs.push(OverlayGlyph {
+                    font_id,
+                    variant,
+                    glyph_id,
+                    scale: info.scale,
+                    offset: info.offset,
+                    rect: info.tex_coords.unpadded(self.padding),
+                    padded_rect: info.tex_coords,
+                    row_age: age,
+                });
+            }
+        }
+        overlay
+    }
+
+    /// Writes a PNG image of the `debug_overlay` the size of the texture to
+    /// `path`. Glyphs are drawn green in the most recently used row, fading
+    /// to red in the next row to be evicted, with padding darker. Unused row
+    /// space is grey & space outside rows black. Glyph shapes are only drawn
+    /// if the cache `retains_pixels`.
+    pub fn debug_dump_png<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        std::fs::write(path, self.debug_png())
     }
+
+    fn debug_png(&self) -> Vec<u8> {
+        let image_height = self.height * self.pages;
+        let (width, height) = (self.width as usize, image_height as usize);
+        let mut rgb = vec![0; width * height * 3];
+        let mut fill = |rect: Rect<u32>, color: [u8; 3]| {
+            for y in rect.min.y as usize..rect.max.y as usize {
+                let start = (y * width + rect.min.x as usize) * 3;
+                let end = (y * width + rect.max.x as usize) * 3;
+                for pixel in rgb[start..end].chunks_exact_mut(3) {
+                    pixel.copy_from_slice(&color);
+                }
+            }
+        };
+
+        let overlay = self.debug_overlay();
+        for row in &overlay.rows {
+            fill(row.rect, [64, 64, 64]);
+        }
+        let oldest = overlay.rows.len().saturating_sub(1).max(1);
+        for glyph in &overlay.glyphs {
+            let red = (255 * glyph.row_age / oldest) as u8;
+            fill(glyph.padded_rect, [red / 2, (255 - red) / 2, 32]);
+            fill(glyph.rect, [red, 255 - red, 64]);
+        }
+        // shade glyph shapes by coverage, from a quarter to full brightness
+        for info in self.rows.values().flat_map(|row| &row.glyphs) {
+            let rect = info.tex_coords.unpadded(self.padding);
+            let rect_width = rect.width() as usize;
+            for (y, line) in info.pixels.chunks_exact(rect_width).enumerate() {
+                let start = ((rect.min.y as usize + y) * width + rect.min.x as usize) * 3;
+                let pixels = rgb[start..start + rect_width * 3].chunks_exact_mut(3);
+                for (pixel, &v) in pixels.zip(line) {
+                    for c in pixel {
+                        *c = (u32::from(*c) * (255 + 3 * u32::from(v)) / 1020) as u8;
+                    }
+                }
+            }
+        }
+
+        let mut scanlines = Vec::with_capacity(height * (1 + width * 3));
+        for line in rgb.chunks_exact(width * 3) {
+            scanlines.push(0); // no filter
+            scanlines.extend_from_slice(line);
+        }
+        let mut ihdr = Vec::with_capacity(13);
+        ihdr.extend_from_slice(&self.width.to_be_bytes());
+        ihdr.extend_from_slice(&image_height.to_be_bytes());
+        ihdr.extend_from_slice(&[8, 2, 0, 0, 0]); // 8 bit rgb, no interlace
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut png, b"IHDR", &ihdr);
+        png_chunk(&mut png, b"IDAT", &zlib_stored(&scanlines));
+        png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+}
+
+/// Texture rows & cached glyphs, returned from `Cache::debug_overlay`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CacheOverlay {
+    /// Rows from the least to the most recently used.
+    pub rows: Vec<OverlayRow>,
+    /// Cached glyphs by row.
+    pub glyphs: Vec<OverlayGlyph>,
+}
+
+/// A texture row of a `CacheOverlay`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OverlayRow {
+    /// Texture pixels of the row, the full texture width.
+    pub rect: Rect<u32>,
+    /// Pixel width in use by glyphs from the left.
+    pub used_width: u32,
+    /// Number of rows used more recently, so the row with the highest age is
+    /// evicted next.
+    pub age: usize,
+}
+
+/// A cached glyph texture of a `CacheOverlay`, with its cache key.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OverlayGlyph {
+    /// `font_id` the glyph was queued with.
+    pub font_id: usize,
+    /// Variant the glyph was queued with, see `Cache::queue_glyph_variant`.
+    pub variant: u32,
+    pub glyph_id: GlyphId,
+    /// Exact scale the glyph was drawn at, matching queued glyphs within the
+    /// scale tolerance.
+    pub scale: Scale,
+    /// Exact normalised subpixel offset the glyph was drawn at, matching
+    /// queued glyphs within the position tolerance.
+    pub offset: Vector<f32>,
+    /// Texture pixels of the glyph, as returned from `Cache::rect_for`.
+    pub rect: Rect<u32>,
+    /// `rect` including padding, if the cache pads glyphs.
+    pub padded_rect: Rect<u32>,
+    /// `OverlayRow::age` of the glyph's row.
+    pub row_age: usize,
+}
+
+/// Returns the texture page of `rect` in the pages stacked vertically, & the
+/// rect within that page.
+fn paged(rect: Rect<u32>, height: u32) -> (u32, Rect<u32>) {
+    let page = rect.min.y / height;
+    (page, rect - vector(0, page * height))
+}
+
+/// Returns `rect` of texture `page` in the pages stacked vertically.
+fn stacked(rect: Rect<u32>, page: u32, height: u32) -> Rect<u32> {
+    rect + vector(0, page * height)
+}
+
+/// Adds `rect` to `dirty_rects`, merged with a rect it continues in a row.
+fn mark_dirty(dirty_rects: &mut Vec<Rect<u32>>, rect: Rect<u32>) {
+    let next_to = dirty_rects
+        .iter_mut()
+        .find(|dirty| dirty.min.y == rect.min.y && dirty.max.x == rect.min.x);
+    match next_to {
+        Some(dirty) => {
+            dirty.max.x = rect.max.x;
+            dirty.max.y = dirty.max.y.max(rect.max.y);
+        }
+        None => dirty_rects.push(rect),
+    }
+}
+
+/// Appends a PNG chunk with its length & CRC.
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = png[start..].iter().fold(!0_u32, |mut crc, &byte| {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+        crc
+    });
+    png.extend_from_slice(&(!crc).to_be_bytes());
+}
+
+/// Uncompressed zlib data, fine for debug images.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() + data.len() / 0xFFFF * 5 + 11);
+    out.extend_from_slice(&[0x78, 0x01]);
+    let mut blocks = data.chunks(0xFFFF).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(blocks.peek().is_none() as u8);
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    let (a, b) = data.iter().fold((1_u32, 0_u32), |(a, b), &byte| {
+        let a = (a + u32::from(byte)) % 65521;
+        (a, (b + a) % 65521)
+    });
+    out.extend_from_slice(&((b << 16) | a).to_be_bytes());
+    out
+}
+
+/// Returns the pixels of `tex_coords` within drawn `pixels`, excluding padding.
+fn glyph_pixels(pixels: &ByteArray2d, tex_coords: Rect<u32>, padding: u32) -> Vec<u8> {
+    let pad = padding as usize;
+    let tex_coords = tex_coords.unpadded(padding);
+    let width = tex_coords.width() as usize;
+    let mut out = Vec::with_capacity(width * tex_coords.height() as usize);
+    for y in pad..pad + tex_coords.height() as usize {
+        let start = pixels.get_vec_index(y, pad);
+        out.extend_from_slice(&pixels.as_slice()[start..start + width]);
+    }
+    out
 }
 
 #[inline]
-fn draw_glyph(tex_coords: Rect<u32>, glyph: &PositionedGlyph<'_>, pad_glyphs: bool) -> ByteArray2d {
+fn draw_glyph<B: RasterBackend + ?Sized>(
+    backend: &B,
+    tex_coords: Rect<u32>,
+    glyph: &PositionedGlyph<'_>,
+    variant: u32,
+    padding: u32,
+) -> ByteArray2d {
     let mut pixels = ByteArray2d::zeros(tex_coords.height() as usize, tex_coords.width() as usize);
-    if pad_glyphs {
-        glyph.draw(|x, y, v| {
-            let v = (v * 255.0).round() as u8;
-            // `+ 1` accounts for top/left glyph padding
-            pixels[(y as usize + 1, x as usize + 1)] = v;
-        });
-    } else {
-        glyph.draw(|x, y, v| {
-            let v = (v * 255.0).round() as u8;
-            pixels[(y as usize, x as usize)] = v;
-        });
-    }
+    let pad = padding as usize;
+    backend.rasterize_variant(glyph, variant, &mut |x, y, v| {
+        let v = (v * 255.0).round() as u8;
+        // `+ pad` accounts for top/left glyph padding
+        pixels[(y as usize + pad, x as usize + pad)] = v;
+    });
     pixels
 }
 
@@ -1053,6 +2498,500 @@ mod test {
         cache.rect_for(0, &large_right).unwrap();
     }
 
+    #[test]
+    fn reloaded_font_glyphs_not_cached() {
+        let font_data = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+        let mut font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let glyph = font.glyph('l').scaled(Scale::uniform(10.0));
+        let before = glyph.positioned(point(0.0, 0.0));
+
+        let mut cache = Cache::builder().dimensions(32, 32).build();
+        cache.queue_glyph(0, before.clone());
+        cache.cache_queued(|_, _| {}).unwrap();
+
+        font.reload(font_data).unwrap();
+        let after = font
+            .glyph('l')
+            .scaled(Scale::uniform(10.0))
+            .positioned(point(0.0, 0.0));
+        assert_eq!(cache.rect_for(0, &after), Err(CacheReadErr::GlyphNotCached));
+        cache.rect_for(0, &before).unwrap();
+
+        cache.invalidate_font(0);
+        assert_eq!(
+            cache.rect_for(0, &before),
+            Err(CacheReadErr::GlyphNotCached)
+        );
+    }
+
+    #[test]
+    fn cache_queued_with_backend() {
+        /// Fully covers every pixel.
+        struct Solid;
+        impl RasterBackend for Solid {
+            fn rasterize(&self, glyph: &PositionedGlyph<'_>, o: &mut dyn FnMut(u32, u32, f32)) {
+                let bb = glyph.pixel_bounding_box().unwrap();
+                for y in 0..bb.height() as u32 {
+                    for x in 0..bb.width() as u32 {
+                        o(x, y, 1.0);
+                    }
+                }
+            }
+        }
+
+        let font_data = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let mut cache = Cache::builder()
+            .dimensions(64, 64)
+            .multithread(true)
+            .build();
+        for c in "abc".chars() {
+            let glyph = font.glyph(c).scaled(Scale::uniform(12.0));
+            cache.queue_glyph(0, glyph.positioned(point(0.0, 0.0)));
+        }
+
+        let mut uploads = 0;
+        cache
+            .cache_queued_with_backend(&Solid, |rect, data| {
+                uploads += 1;
+                assert_eq!(data.len(), (rect.width() * rect.height()) as usize);
+                // padded by a transparent pixel
+                let width = rect.width() as usize;
+                assert_eq!(data[0], 0);
+                assert_eq!(data[width + 1], 255);
+            })
+            .unwrap();
+        assert_eq!(uploads, 3);
+    }
+
+    #[test]
+    fn glyph_variants_cached_separately() {
+        /// Rasterizes variant `1` fully covered.
+        struct Variants;
+        impl RasterBackend for Variants {
+            fn rasterize(&self, glyph: &PositionedGlyph<'_>, o: &mut dyn FnMut(u32, u32, f32)) {
+                glyph.draw(o)
+            }
+
+            fn rasterize_variant(
+                &self,
+                glyph: &PositionedGlyph<'_>,
+                variant: u32,
+                o: &mut dyn FnMut(u32, u32, f32),
+            ) {
+                match variant {
+                    1 => glyph.draw(|x, y, _| o(x, y, 1.0)),
+                    _ => self.rasterize(glyph, o),
+                }
+            }
+        }
+
+        let font_data = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let glyph = font
+            .glyph('a')
+            .scaled(Scale::uniform(12.0))
+            .positioned(point(0.0, 0.0));
+
+        let mut cache = Cache::builder().dimensions(64, 64).build();
+        cache.queue_glyph(0, glyph.clone());
+        cache.queue_glyph_variant(0, 1, glyph.clone());
+        let mut uploads = Vec::new();
+        cache
+            .cache_queued_with_backend(&Variants, |rect, data| uploads.push((rect, data.to_vec())))
+            .unwrap();
+        assert_eq!(uploads.len(), 2);
+        assert_ne!(uploads[0].1, uploads[1].1);
+
+        let (plain, _) = cache.rect_for(0, &glyph).unwrap().unwrap();
+        let (variant, _) = cache.rect_for_variant(0, 1, &glyph).unwrap().unwrap();
+        assert_ne!(plain, variant);
+        assert_eq!(
+            cache.rect_for_variant(0, 2, &glyph),
+            Err(CacheReadErr::GlyphNotCached)
+        );
+    }
+
+    #[test]
+    fn transformed_glyphs_cached_separately() {
+        let font_data = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let glyph = font.glyph('a').scaled(Scale::uniform(12.0));
+        let plain = glyph.clone().positioned(point(0.0, 0.0));
+        let skewed = glyph
+            .transformed(crate::Affine2::skew_x(0.3))
+            .positioned(point(0.0, 0.0));
+
+        let mut cache = Cache::builder().dimensions(64, 64).build();
+        cache.queue_glyph(0, plain.clone());
+        cache.queue_glyph(0, skewed.clone());
+        cache.cache_queued(|_, _| {}).unwrap();
+
+        let (plain_rect, _) = cache.rect_for(0, &plain).unwrap().unwrap();
+        let (skewed_rect, _) = cache.rect_for(0, &skewed).unwrap().unwrap();
+        assert_ne!(plain_rect, skewed_rect);
+    }
+
+    #[cfg(feature = "hinting")]
+    #[test]
+    fn hinted_glyphs_cached_separately() {
+        let font_data = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let glyph = font.glyph('a').scaled(Scale::uniform(12.0));
+        let plain = glyph.clone().positioned(point(0.0, 0.0));
+        let hinted = glyph
+            .hinted(crate::HintingMode::Full)
+            .positioned(point(0.0, 0.0));
+
+        let mut cache = Cache::builder().dimensions(64, 64).build();
+        cache.queue_glyph(0, plain.clone());
+        cache.queue_glyph(0, hinted.clone());
+        cache.cache_queued(|_, _| {}).unwrap();
+
+        let (plain_rect, _) = cache.rect_for(0, &plain).unwrap().unwrap();
+        let (hinted_rect, _) = cache.rect_for(0, &hinted).unwrap().unwrap();
+        assert_ne!(plain_rect, hinted_rect);
+    }
+
+    #[test]
+    fn set_tolerances() {
+        let font_data = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let glyph = |scale: f32, x: f32| {
+            font.glyph('a')
+                .scaled(Scale::uniform(scale))
+                .positioned(point(x, 0.0))
+        };
+
+        let mut cache = Cache::builder().dimensions(128, 128).build();
+        for g in [glyph(20.0, 0.0), glyph(20.2, 0.0), glyph(20.0, 0.3)] {
+            cache.queue_glyph(0, g);
+        }
+        cache.cache_queued(|_, _| {}).unwrap();
+        assert_eq!(cache.all_glyphs.len(), 3);
+
+        // loosened, the glyphs merge into one
+        cache.set_tolerances(1.0, 1.0);
+        assert_eq!(cache.scale_tolerance(), 1.0);
+        assert_eq!(cache.all_glyphs.len(), 1);
+        assert!(cache.rect_for(0, &glyph(20.4, 0.2)).unwrap().is_some());
+
+        // tightened, the reachable glyph matches by its exact scale & offset
+        cache.set_tolerances(0.1, 0.1);
+        assert_eq!(cache.all_glyphs.len(), 1);
+        let reachable = [glyph(20.0, 0.0), glyph(20.2, 0.0), glyph(20.0, 0.3)]
+            .iter()
+            .filter(|g| cache.rect_for(0, g).is_ok())
+            .count();
+        assert_eq!(reachable, 1);
+        assert_eq!(
+            cache.rect_for(0, &glyph(20.4, 0.2)),
+            Err(CacheReadErr::GlyphNotCached)
+        );
+
+        // invalidated glyphs stay invalidated
+        cache.invalidate_font(0);
+        cache.set_tolerances(1.0, 1.0);
+        assert!(cache.all_glyphs.is_empty());
+    }
+
+    #[test]
+    fn evict_after_set_tolerances() {
+        let font_data = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let glyph = |c: char, scale: f32| {
+            font.glyph(c)
+                .scaled(Scale::uniform(scale))
+                .positioned(point(0.0, 0.0))
+        };
+
+        // one glyph per row & room for 2 rows, so the `a`s are in different
+        // rows & `e` only fits by evicting one
+        let mut cache = Cache::builder().dimensions(16, 28).build();
+        for g in [glyph('a', 20.0), glyph('a', 20.2)] {
+            cache.queue_glyph(0, g);
+            cache.cache_queued(|_, _| {}).unwrap();
+        }
+        assert_eq!(cache.rows.len(), 2);
+
+        // loosened, the glyphs merge into the most recent row's
+        cache.set_tolerances(1.0, 1.0);
+        assert_eq!(cache.all_glyphs.len(), 1);
+
+        // evicting the older row keeps the merged glyph
+        cache.queue_glyph(0, glyph('a', 20.0));
+        cache.queue_glyph(0, glyph('e', 20.0));
+        cache.cache_queued(|_, _| {}).unwrap();
+        assert_eq!(cache.rows.len(), 2);
+        assert!(cache.rect_for(0, &glyph('a', 20.0)).unwrap().is_some());
+        assert!(cache.rect_for(0, &glyph('e', 20.0)).unwrap().is_some());
+    }
+
+    #[test]
+    fn cache_queued_with_budget() {
+        let font_data = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let glyphs: Vec<_> = font
+            .layout("abcdefghij", Scale::uniform(16.0), point(0.0, 16.0))
+            .collect();
+        let mut cache = Cache::builder().dimensions(256, 256).build();
+        let queue_all = |cache: &mut Cache<'static>| {
+            for g in &glyphs {
+                cache.queue_glyph(0, g.clone());
+            }
+        };
+
+        queue_all(&mut cache);
+        let mut uploads = 0;
+        let progress = cache
+            .cache_queued_with_budget(CacheBudget::Glyphs(4), |_, _| uploads += 1)
+            .unwrap();
+        assert_eq!((progress.cached, progress.remaining), (4, 6));
+        assert_eq!(uploads, 4);
+        assert!(cache.queue.is_empty());
+        let cached = glyphs.iter().filter(|g| cache.rect_for(0, g).is_ok());
+        assert_eq!(cached.count(), 4);
+
+        // cached glyphs aren't counted against the budget
+        queue_all(&mut cache);
+        let progress = cache
+            .cache_queued_with_budget(CacheBudget::Glyphs(100), |_, _| uploads += 1)
+            .unwrap();
+        assert_eq!((progress.cached, progress.remaining), (6, 0));
+        assert!(progress.is_complete());
+        assert_eq!(uploads, 10);
+        assert!(glyphs.iter().all(|g| cache.rect_for(0, g).is_ok()));
+
+        // time budgets cache at least a batch
+        cache.clear();
+        queue_all(&mut cache);
+        let progress = cache
+            .cache_queued_with_budget(CacheBudget::Time(Duration::ZERO), |_, _| {})
+            .unwrap();
+        assert_eq!((progress.cached, progress.remaining), (BUDGET_BATCH, 2));
+    }
+
+    #[test]
+    fn glyph_priorities() {
+        let font_data = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let scale = Scale::uniform(16.0);
+        let visible: Vec<_> = font.layout("abcd", scale, point(0.0, 16.0)).collect();
+        let prefetch: Vec<_> = font.layout("efghij", scale, point(0.0, 16.0)).collect();
+        let queue_all = |cache: &mut Cache<'static>| {
+            for g in &prefetch {
+                cache.queue_glyph_with_priority(0, 0, g.clone(), GlyphPriority::Prefetch);
+            }
+            for g in &visible {
+                cache.queue_glyph(0, g.clone());
+            }
+        };
+
+        // budgets take visible glyphs first
+        let mut cache = Cache::builder().dimensions(256, 256).build();
+        queue_all(&mut cache);
+        cache
+            .cache_queued_with_budget(CacheBudget::Glyphs(4), |_, _| {})
+            .unwrap();
+        assert!(visible.iter().all(|g| cache.rect_for(0, g).is_ok()));
+        assert!(prefetch.iter().all(|g| cache.rect_for(0, g).is_err()));
+
+        // prefetched glyphs without room are skipped
+        let mut cache = Cache::builder()
+            .dimensions(40, 20)
+            .pad_glyphs(false)
+            .build();
+        queue_all(&mut cache);
+        cache.cache_queued(|_, _| {}).unwrap();
+        assert!(visible.iter().all(|g| cache.rect_for(0, g).is_ok()));
+        assert!(prefetch.iter().any(|g| cache.rect_for(0, g).is_err()));
+
+        // & their rows are evicted for visible glyphs
+        for g in &prefetch {
+            cache.queue_glyph(0, g.clone());
+        }
+        cache.cache_queued(|_, _| {}).unwrap();
+        assert!(prefetch.iter().all(|g| cache.rect_for(0, g).is_ok()));
+    }
+
+    #[test]
+    fn debug_overlay() {
+        let font_data = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let glyphs: Vec<_> = font
+            .layout("ab", Scale::uniform(16.0), point(0.0, 16.0))
+            .collect();
+        let mut cache = Cache::builder().dimensions(64, 32).build();
+        for g in &glyphs {
+            cache.queue_glyph(0, g.clone());
+        }
+        cache.cache_queued(|_, _| {}).unwrap();
+
+        let overlay = cache.debug_overlay();
+        assert_eq!(overlay.glyphs.len(), 2);
+        assert_eq!(overlay.rows[overlay.rows.len() - 1].age, 0);
+        for g in &glyphs {
+            let overlaid = overlay
+                .glyphs
+                .iter()
+                .find(|o| o.glyph_id == g.id())
+                .unwrap();
+            let (uv, _) = cache.rect_for(0, g).unwrap().unwrap();
+            assert_eq!(uv, overlaid.rect.to_f32() / vector(64.0, 32.0));
+            assert_eq!(overlaid.rect, overlaid.padded_rect.unpadded(1));
+        }
+
+        let png = cache.debug_png();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert!(png.ends_with(&[0xAE, 0x42, 0x60, 0x82])); // IEND crc
+
+        // 64x32 rgb pixels with filter bytes, stored uncompressed
+        assert!(png.len() > 32 * (1 + 64 * 3));
+    }
+
+    #[test]
+    fn deterministic() {
+        let font_data = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let glyphs: Vec<_> = font
+            .layout(
+                "the quick brown fox",
+                Scale::uniform(16.0),
+                point(0.0, 16.0),
+            )
+            .collect();
+        let uploads = |multithread: bool| {
+            let mut cache = Cache::builder()
+                .dimensions(64, 64)
+                .multithread(multithread)
+                .deterministic(true)
+                .build();
+            let mut uploads = Vec::new();
+            for words in glyphs.chunks(6) {
+                for g in words {
+                    cache.queue_glyph(0, g.clone());
+                }
+                cache
+                    .cache_queued(|rect, data| uploads.push((rect, data.to_vec())))
+                    .unwrap();
+            }
+            uploads
+        };
+
+        let expected = uploads(false);
+        for _ in 0..4 {
+            assert_eq!(uploads(true), expected);
+        }
+    }
+
+    #[test]
+    fn dirty_rects() {
+        let font_data = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let glyphs: Vec<_> = font
+            .layout("abc", Scale::uniform(16.0), point(0.0, 16.0))
+            .collect();
+        let mut cache = Cache::builder().dimensions(256, 256).build();
+        let mut uploads = Vec::new();
+        for g in &glyphs {
+            cache.queue_glyph(0, g.clone());
+        }
+        cache.cache_queued(|rect, _| uploads.push(rect)).unwrap();
+
+        // uploads next to each other in the row are merged
+        let dirty = cache.take_dirty_rects();
+        assert_eq!(dirty.len(), 1);
+        assert_eq!(dirty[0].min, uploads[0].min);
+        assert_eq!(dirty[0].max.x, uploads[2].max.x);
+        assert!(cache.dirty_rects().is_empty());
+
+        // a new variant of 'b' is the only upload
+        for (i, g) in glyphs.iter().enumerate() {
+            cache.queue_glyph_variant(0, (i == 1) as u32, g.clone());
+        }
+        uploads.clear();
+        cache.cache_queued(|rect, _| uploads.push(rect)).unwrap();
+        assert_eq!(uploads.len(), 1);
+        assert_eq!(cache.dirty_rects(), &uploads[..]);
+    }
+
+    #[test]
+    fn pixels_for() {
+        let font_data = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let glyph = font
+            .glyph('a')
+            .scaled(Scale::uniform(16.0))
+            .positioned(point(0.3, 0.0));
+        let bb = glyph.pixel_bounding_box().unwrap();
+        let mut expected = vec![0; (bb.width() * bb.height()) as usize];
+        glyph.draw(|x, y, v| {
+            expected[(x + y * bb.width() as u32) as usize] = (v * 255.0).round() as u8
+        });
+
+        for &pad_glyphs in &[true, false] {
+            let mut cache = Cache::builder()
+                .dimensions(64, 64)
+                .pad_glyphs(pad_glyphs)
+                .retain_pixels(true)
+                .build();
+            assert!(cache.retains_pixels());
+            assert_eq!(cache.pixels_for(0, &glyph), None);
+            cache.queue_glyph(0, glyph.clone());
+            cache.cache_queued(|_, _| {}).unwrap();
+
+            let (rect, pixels) = cache.pixels_for(0, &glyph).unwrap();
+            let (uv, _) = cache.rect_for(0, &glyph).unwrap().unwrap();
+            assert_eq!(uv, rect.to_f32() / 64.0);
+            assert_eq!(pixels, &expected[..]);
+            assert_eq!(cache.pixels_for_variant(0, 1, &glyph), None);
+        }
+
+        // not retained by default
+        let mut cache = Cache::builder().build();
+        assert!(!cache.retains_pixels());
+        cache.queue_glyph(0, glyph.clone());
+        cache.cache_queued(|_, _| {}).unwrap();
+        assert!(cache.rect_for(0, &glyph).unwrap().is_some());
+        assert_eq!(cache.pixels_for(0, &glyph), None);
+    }
+
+    #[test]
+    fn warm_cache() {
+        let font_data = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let scale = Scale::uniform(16.0);
+        let queued = font.glyph('!').scaled(scale).positioned(point(0.3, 0.0));
+
+        let mut cache = Cache::builder().dimensions(256, 256).build();
+        cache.queue_glyph(0, queued);
+        let mut reports = Vec::new();
+        let mut uploads = 0;
+        cache
+            .warm_cache(
+                0,
+                &font,
+                scale,
+                "abc"
+                    .chars()
+                    .chain('a'..='z')
+                    .chain('A'..='Z')
+                    .chain(' '..=' '),
+                |_, _| uploads += 1,
+                |cached, total| reports.push((cached, total)),
+            )
+            .unwrap();
+
+        // duplicates & blank glyphs are skipped
+        assert_eq!(uploads, 52);
+        assert_eq!(reports, [(32, 52), (52, 52)]);
+        assert_eq!(cache.queue.len(), 1, "queued glyphs are left queued");
+        for c in "abcXYZ".chars() {
+            let glyph = font.glyph(c).scaled(scale).positioned(point(10.0, 20.0));
+            assert!(cache.rect_for(0, &glyph).unwrap().is_some());
+        }
+    }
+
     #[test]
     fn lossy_info() {
         let font_data = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
@@ -1078,15 +3017,15 @@ mod test {
             .position_tolerance(0.5)
             .build();
 
-        let small_info = cache.lossy_info_for(0, &small_pos);
+        let small_info = cache.lossy_info_for(0, 0, &small_pos);
 
-        assert_eq!(small_info, cache.lossy_info_for(0, &match_1));
-        assert_eq!(small_info, cache.lossy_info_for(0, &match_2));
-        assert_eq!(small_info, cache.lossy_info_for(0, &match_3));
+        assert_eq!(small_info, cache.lossy_info_for(0, 0, &match_1));
+        assert_eq!(small_info, cache.lossy_info_for(0, 0, &match_2));
+        assert_eq!(small_info, cache.lossy_info_for(0, 0, &match_3));
 
-        assert_ne!(small_info, cache.lossy_info_for(0, &miss_1));
-        assert_ne!(small_info, cache.lossy_info_for(0, &miss_2));
-        assert_ne!(small_info, cache.lossy_info_for(0, &miss_3));
+        assert_ne!(small_info, cache.lossy_info_for(0, 0, &miss_1));
+        assert_ne!(small_info, cache.lossy_info_for(0, 0, &miss_2));
+        assert_ne!(small_info, cache.lossy_info_for(0, 0, &miss_3));
     }
 
     #[test]
@@ -1095,9 +3034,18 @@ mod test {
             dimensions: (32, 64),
             scale_tolerance: 0.2,
             position_tolerance: 0.3,
-            pad_glyphs: false,
+            padding: 0,
             align_4x4: false,
             multithread: false,
+            deterministic: false,
+            retain_pixels: true,
+            pages: 3,
+            max_dimensions: Some((64, 128)),
+            sdf: Some(SdfSettings {
+                spread: 2.0,
+                scale: 24.0,
+            }),
+            subpixel: Some(SubpixelStrategy::Thirds),
         }
         .build();
 
@@ -1106,9 +3054,14 @@ mod test {
         assert_eq!(to_builder.dimensions, (32, 64));
         assert_relative_eq!(to_builder.scale_tolerance, 0.2);
         assert_relative_eq!(to_builder.position_tolerance, 0.3);
-        assert_eq!(to_builder.pad_glyphs, false);
-        assert_eq!(to_builder.align_4x4, false);
-        assert_eq!(to_builder.multithread, false);
+        assert_eq!(to_builder.padding, 0);
+        assert!(!to_builder.align_4x4);
+        assert!(!to_builder.multithread);
+        assert!(to_builder.retain_pixels);
+        assert_eq!(to_builder.pages, 3);
+        assert_eq!(to_builder.max_dimensions, Some((64, 128)));
+        assert_eq!(to_builder.sdf, cache.sdf());
+        assert_eq!(to_builder.subpixel, Some(SubpixelStrategy::Thirds));
     }
 
     #[test]
@@ -1154,9 +3107,9 @@ mod test {
         assert_eq!(cache.height, 128);
         assert_relative_eq!(cache.scale_tolerance, 0.05);
         assert_relative_eq!(cache.position_tolerance, 0.15);
-        assert_eq!(cache.pad_glyphs, true);
-        assert_eq!(cache.align_4x4, false);
-        assert_eq!(cache.multithread, false);
+        assert_eq!(cache.padding, 1);
+        assert!(!cache.align_4x4);
+        assert!(!cache.multithread);
 
         assert!(
             cache.all_glyphs.is_empty(),
@@ -1189,6 +3142,253 @@ mod test {
         assert_eq!(cache.cache_queued(|_, _| {}), Ok(CachedBy::Reordering));
     }
 
+    #[test]
+    fn max_dimensions() {
+        let font_data = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let glyphs: Vec<_> = font
+            .layout("ABCDEFGHIJK", Scale::uniform(16.0), point(0.0, 0.0))
+            .collect();
+
+        // too many glyphs for the size of `return_cache_by_reordering`
+        let mut cache = Cache::builder().dimensions(36, 27).build();
+        for glyph in &glyphs {
+            cache.queue_glyph(0, glyph.clone());
+        }
+        assert_eq!(
+            cache.cache_queued(|_, _| {}),
+            Err(CacheWriteErr::NoRoomForWholeQueue)
+        );
+
+        let mut cache = Cache::builder()
+            .dimensions(36, 27)
+            .max_dimensions(100, 54)
+            .build();
+        let mut uploads = 0;
+        for glyph in &glyphs {
+            cache.queue_glyph(0, glyph.clone());
+        }
+        let cached_by = cache.cache_queued(|rect, _| {
+            assert!(rect.max.x <= 72 && rect.max.y <= 54);
+            uploads += 1;
+        });
+        assert_eq!(cached_by, Ok(CachedBy::Resizing));
+        assert_eq!(cache.dimensions(), (72, 54));
+        assert_eq!(uploads, glyphs.len());
+        for glyph in &glyphs {
+            assert!(cache.rect_for(0, glyph).unwrap().is_some());
+        }
+
+        // fits from now on
+        for glyph in &glyphs {
+            cache.queue_glyph(0, glyph.clone());
+        }
+        assert_eq!(cache.cache_queued(|_, _| {}), Ok(CachedBy::Adding));
+        assert_eq!(cache.to_builder().build().dimensions(), (72, 54));
+    }
+
+    #[test]
+    fn sdf() {
+        let font_data = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let glyph = |size, x| {
+            font.glyph('l')
+                .scaled(Scale::uniform(size))
+                .positioned(point(x, 60.0))
+        };
+        let (small, large) = (glyph(16.0, 0.3), glyph(48.0, 10.7));
+
+        let mut cache = Cache::builder().sdf(4.0, 32.0).build();
+        cache.queue_glyph(0, small.clone());
+        cache.queue_glyph(0, large.clone());
+        let mut uploads = Vec::new();
+        cache
+            .cache_queued(|rect, data| uploads.push((rect, data.to_vec())))
+            .unwrap();
+        assert_eq!(uploads.len(), 1);
+
+        // the field of the generation glyph padded by the spread & padding
+        let generation_bb = glyph(32.0, 0.0).pixel_bounding_box().unwrap();
+        let (rect, data) = &uploads[0];
+        assert_eq!(rect.width() as i32, generation_bb.width() + 8 + 2);
+        assert_eq!(rect.height() as i32, generation_bb.height() + 8 + 2);
+        let width = rect.width() as usize;
+        let at = |x: usize, y: usize| data[x + y * width];
+        // padding, far outside & on the stem
+        assert_eq!(at(0, 0), 0);
+        assert_eq!(at(1, 1), 0);
+        let middle = rect.height() as usize / 2;
+        let row: Vec<_> = (0..width).map(|x| at(x, middle)).collect();
+        // rising into the stem 32 a pixel, a 4 pixel spread, & falling again
+        let peak = (0..width).max_by_key(|&x| row[x]).unwrap();
+        assert!(row[peak] > 160);
+        assert!(row[..peak].windows(2).all(|w| w[0] <= w[1]));
+        assert!(row[peak..].windows(2).all(|w| w[0] >= w[1]));
+        assert!((0..width).any(|x| (100..156).contains(&row[x])));
+
+        let sdf = cache.sdf_rect_for(0, &large).unwrap().unwrap();
+        assert_eq!(sdf.generation_scale, Scale::uniform(32.0));
+        assert_relative_eq!(sdf.spread, 6.0);
+        // the glyph's box padded by the scaled spread
+        let bb = large.pixel_bounding_box().unwrap();
+        assert!((sdf.screen_rect.min.x - (bb.min.x as f32 - 6.0)).abs() < 2.0);
+        assert!((sdf.screen_rect.max.y - (bb.max.y as f32 + 6.0)).abs() < 2.0);
+        let (uv_rect, screen_rect) = cache.rect_for(0, &large).unwrap().unwrap();
+        assert_eq!(uv_rect, sdf.uv_rect);
+        assert_eq!(screen_rect.min.x, sdf.screen_rect.min.x.round() as i32);
+
+        let small_sdf = cache.sdf_rect_for(0, &small).unwrap().unwrap();
+        assert_eq!(small_sdf.uv_rect, sdf.uv_rect);
+        assert_relative_eq!(small_sdf.spread, 2.0);
+
+        // coverage caches have no fields
+        let mut coverage = Cache::builder().build();
+        coverage.queue_glyph(0, large.clone());
+        coverage.cache_queued(|_, _| {}).unwrap();
+        assert_eq!(coverage.sdf_rect_for(0, &large), Ok(None));
+    }
+
+    #[test]
+    fn padding() {
+        let font_data = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let mut cache = Cache::builder()
+            .dimensions(128, 128)
+            .padding(3)
+            .multithread(false)
+            .retain_pixels(true)
+            .build();
+        assert_eq!(cache.to_builder().padding, 3);
+        let glyphs: Vec<_> = "ABC"
+            .chars()
+            .map(|c| {
+                font.glyph(c)
+                    .scaled(Scale::uniform(20.0))
+                    .positioned(point(0.0, 0.0))
+            })
+            .collect();
+        for glyph in &glyphs {
+            cache.queue_glyph(0, glyph.clone());
+        }
+        let mut uploads = Vec::new();
+        cache
+            .cache_queued(|rect, data| uploads.push((rect, data.to_vec())))
+            .unwrap();
+        assert_eq!(uploads.len(), 3);
+
+        for glyph in &glyphs {
+            let bb = glyph.pixel_bounding_box().unwrap();
+            let (uv, _) = cache.rect_for(0, glyph).unwrap().unwrap();
+            let (rect, _) = cache.pixels_for(0, glyph).unwrap();
+            // the uv rect excludes the padding
+            assert_eq!(uv, rect.to_f32() / 128.0);
+            assert_eq!(rect.width() as i32, bb.width());
+            assert_eq!(rect.height() as i32, bb.height());
+
+            // uploaded with a transparent border 3 texels wide
+            let (uploaded, data) = uploads
+                .iter()
+                .find(|(upload, _)| upload.min == rect.min - vector(3, 3))
+                .unwrap();
+            assert_eq!(uploaded.max, rect.max + vector(3, 3));
+            let width = uploaded.width();
+            for (i, &v) in data.iter().enumerate() {
+                let (x, y) = (i as u32 % width, i as u32 / width);
+                if x < 3 || y < 3 || x >= width - 3 || y >= uploaded.height() - 3 {
+                    assert_eq!(v, 0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn subpixel() {
+        let font_data = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+        let glyph = |x, y| {
+            font.glyph('a')
+                .scaled(Scale::uniform(24.0))
+                .positioned(point(x, y))
+        };
+        let strategy = SubpixelStrategy::HorizontalThirds;
+
+        let mut cache = Cache::builder()
+            .position_tolerance(0.01)
+            .subpixel(strategy)
+            .build();
+        // the same bin, whatever the vertical position
+        for &(x, y) in &[(10.3, 20.0), (10.35, 20.4), (11.32, 30.9)] {
+            cache.queue_glyph(0, glyph(x, y));
+        }
+        let mut uploads = 0;
+        cache.cache_queued(|_, _| uploads += 1).unwrap();
+        assert_eq!(uploads, 1);
+
+        // drawn where the snapped glyph is
+        let unsnapped = glyph(10.35, 20.4);
+        let snapped = strategy.snap_glyph(unsnapped.clone());
+        assert_relative_eq!(snapped.position().x, 31.0 / 3.0);
+        assert_relative_eq!(snapped.position().y, 20.0);
+        let (uv_rect, screen_rect) = cache.rect_for(0, &unsnapped).unwrap().unwrap();
+        assert_eq!(
+            cache.rect_for(0, &snapped),
+            Ok(Some((uv_rect, screen_rect)))
+        );
+        assert_eq!(Some(screen_rect), snapped.pixel_bounding_box());
+
+        // a new bin after the strategy is changed
+        cache.set_subpixel(Some(SubpixelStrategy::Thirds));
+        cache.queue_glyph(0, glyph(10.3, 20.4));
+        cache.cache_queued(|_, _| uploads += 1).unwrap();
+        assert_eq!(uploads, 2);
+        // on a whole pixel vertically, still cached
+        cache.queue_glyph(0, glyph(10.3, 20.0));
+        cache.cache_queued(|_, _| uploads += 1).unwrap();
+        assert_eq!(uploads, 2);
+    }
+
+    #[test]
+    fn pages() {
+        let font_data = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+        let font = Font::try_from_bytes(font_data as &[u8]).unwrap();
+
+        // the page size of `return_cache_by_reordering`
+        let mut cache = Cache::builder().dimensions(36, 27).pages(2).build();
+        let mut uploads = Vec::new();
+        for text in &["ABCDEFG", "DEFGHIJK"] {
+            let glyphs: Vec<_> = font
+                .layout(text, Scale::uniform(16.0), point(0.0, 0.0))
+                .collect();
+            for glyph in &glyphs {
+                cache.queue_glyph(0, glyph.clone());
+            }
+            // a new page is used instead of reordering
+            let cached_by = cache.cache_queued_paged(|page, rect, _| uploads.push((page, rect)));
+            assert_eq!(cached_by, Ok(CachedBy::Adding));
+
+            for glyph in &glyphs {
+                let (page, (uv_rect, bb)) = cache.page_rect_for(0, glyph).unwrap().unwrap();
+                let (stacked_uv, stacked_bb) = cache.rect_for(0, glyph).unwrap().unwrap();
+                assert_eq!(bb, stacked_bb);
+                assert!(uv_rect.max.y <= 1.0);
+                assert_relative_eq!(stacked_uv.min.y, (page as f32 + uv_rect.min.y) / 2.0);
+            }
+        }
+        assert!(uploads.iter().any(|&(page, _)| page == 0));
+        assert!(uploads.iter().any(|&(page, _)| page == 1));
+        assert!(uploads.iter().all(|&(_, rect)| rect.max.y <= 27));
+
+        // unpaged uploads are of the pages stacked vertically
+        cache.clear();
+        for glyph in font.layout("ABCDEFGHIJK", Scale::uniform(16.0), point(0.0, 0.0)) {
+            cache.queue_glyph(0, glyph);
+        }
+        let mut stacked = Vec::new();
+        cache.cache_queued(|rect, _| stacked.push(rect)).unwrap();
+        assert!(stacked.iter().any(|rect| rect.min.y >= 27));
+        assert!(stacked.iter().all(|rect| rect.max.y <= 54));
+    }
+
     #[test]
     fn align_4x4() {
         // First, test align_4x4 disabled, to confirm non-4x4 alignment
@@ -0,0 +1,106 @@
+//! Stable glyph content hashes, see `Glyph::outline_hash`.
+use crate::{Glyph, OutlineBuilder, Scale, Vector};
+
+/// Bumped whenever hashed content changes, so old hashes don't match.
+const HASH_VERSION: u8 = 2;
+
+/// 64 bit FNV-1a, chosen as it's simple & stable across platforms & releases,
+/// unlike `std::hash`.
+pub(crate) struct Fnv1a(u64);
+
+impl Fnv1a {
+    pub(crate) fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    #[cfg(feature = "gpu_cache")]
+    #[inline]
+    pub(crate) fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    pub(crate) fn write_f32(&mut self, v: f32) {
+        // -0.0 draws the same as 0.0
+        let v = if v == 0.0 { 0.0 } else { v };
+        self.write(&v.to_bits().to_le_bytes());
+    }
+}
+
+impl OutlineBuilder for Fnv1a {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.write(b"M");
+        self.write_f32(x);
+        self.write_f32(y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.write(b"L");
+        self.write_f32(x);
+        self.write_f32(y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.write(b"Q");
+        for v in [x1, y1, x, y].iter() {
+            self.write_f32(*v);
+        }
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.write(b"C");
+        for v in [x1, y1, x2, y2, x, y].iter() {
+            self.write_f32(*v);
+        }
+    }
+
+    fn close(&mut self) {
+        self.write(b"Z");
+    }
+}
+
+impl Glyph<'_> {
+    /// Returns a hash of the glyph's outline at `scale` & `subpixel` offset,
+    /// the fractional part of the position it's drawn at, to key rasterized
+    /// glyphs shared between processes & machines, e.g. by distributed
+    /// renderers or disk caches.
+    ///
+    /// Unlike the glyph id, the hash covers what is drawn, so matches glyphs
+    /// with the same outline in different fonts or font versions & differs
+    /// between variable font instances. It's stable across platforms &
+    /// releases, unless the hashed content changes which also changes all
+    /// hashes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let scale = Scale::uniform(24.0);
+    /// let hash = font.glyph('a').outline_hash(scale, vector(0.25, 0.0));
+    ///
+    /// let reloaded = Font::try_from_vec(font_data.to_vec()).unwrap();
+    /// assert_eq!(reloaded.glyph('a').outline_hash(scale, vector(0.25, 0.0)), hash);
+    /// assert_ne!(font.glyph('a').outline_hash(scale, vector(0.5, 0.0)), hash);
+    /// ```
+    pub fn outline_hash(&self, scale: Scale, subpixel: Vector<f32>) -> u64 {
+        let mut hasher = Fnv1a::new();
+        hasher.write(&[HASH_VERSION, self.is_pixel_rendered() as u8]);
+        hasher.write(&self.units_per_em().to_le_bytes());
+        // the font unit to pixel factors, which depend on the ascent & descent
+        let factor = self.clone().scaled(scale).scale;
+        for v in [scale.x, scale.y, factor.x, factor.y, subpixel.x, subpixel.y].iter() {
+            hasher.write_f32(*v);
+        }
+        // font unit outlines, so hashes don't depend on scaling float error
+        self.build_unscaled_outline(&mut hasher);
+        hasher.0
+    }
+}
@@ -0,0 +1,235 @@
+//! TrueType & PostScript hinting, see `ScaledGlyph::hinted`.
+use crate::{point, Font, OutlineBuilder, Rect, ScaledGlyph};
+use skrifa::instance::Size;
+use skrifa::outline::{
+    DrawSettings, Engine, HintingInstance, HintingOptions, OutlinePen, SmoothMode, Target,
+};
+use skrifa::{FontRef, MetadataProvider, Tag};
+use std::sync::{Arc, Mutex};
+
+/// Hinting instances kept per font, for the most recently used sizes.
+const CACHED_INSTANCES: usize = 8;
+
+/// How glyph outlines are adjusted to the pixel grid before rasterization,
+/// see `ScaledGlyph::hinted`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum HintingMode {
+    /// Outlines are only scaled, keeping their exact shape.
+    #[default]
+    None,
+    /// Outlines are fitted to the pixel grid vertically only, sharpening
+    /// horizontal stems & the baseline, x height & cap height while keeping
+    /// glyph shapes & spacing, like FreeType's light hinting.
+    Light,
+    /// Outlines are fitted to the pixel grid in both directions by the font's
+    /// hinting programs, snapping vertical stems too. The sharpest, but
+    /// changing shapes & widths most, so best for glyphs positioned at whole
+    /// pixels, e.g. small UI text.
+    Full,
+}
+
+/// Hinting instances of a font by size & mode.
+///
+/// Creating an instance runs the font's setup programs, so instances are
+/// reused by glyphs of the same size.
+#[derive(Default)]
+pub(crate) struct HintingCache {
+    /// Most recently used last.
+    instances: Mutex<Vec<(u32, HintingMode, Arc<HintingInstance>)>>,
+}
+
+impl HintingCache {
+    /// Returns the instance of `font`, with axis values `variations`, for
+    /// `ppem` pixels per em & `mode`, `None` for `HintingMode::None`.
+    fn get(
+        &self,
+        font: &FontRef<'_>,
+        variations: &[([u8; 4], f32)],
+        ppem: f32,
+        mode: HintingMode,
+    ) -> Option<Arc<HintingInstance>> {
+        let key = (ppem.to_bits(), mode);
+        let mut instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(i) = instances.iter().position(|(p, m, _)| (*p, *m) == key) {
+            let entry = instances.remove(i);
+            let instance = Arc::clone(&entry.2);
+            instances.push(entry);
+            return Some(instance);
+        }
+
+        // the TrueType interpreter only hints horizontally in the backward
+        // compatible mode of aliased targets
+        let target = match mode {
+            HintingMode::None => return None,
+            HintingMode::Light => Target::Smooth {
+                mode: SmoothMode::Light,
+                symmetric_rendering: true,
+                preserve_linear_metrics: true,
+            },
+            HintingMode::Full => Target::Mono,
+        };
+        let options = HintingOptions {
+            engine: Engine::Interpreter,
+            target,
+        };
+        let location = font.axes().location(
+            variations
+                .iter()
+                .map(|&(tag, value)| (Tag::new(&tag), value)),
+        );
+        let outlines = font.outline_glyphs();
+        let instance = HintingInstance::new(&outlines, Size::new(ppem), &location, options).ok()?;
+        let instance = Arc::new(instance);
+        if instances.len() == CACHED_INSTANCES {
+            instances.remove(0);
+        }
+        instances.push((key.0, mode, Arc::clone(&instance)));
+        Some(instance)
+    }
+}
+
+/// Maps hinted outlines, in pixels y up, to `OutlineBuilder` pixels y down,
+/// scaled horizontally for non-uniform scales.
+struct Pen<'b, T: ?Sized> {
+    inner: &'b mut T,
+    x_scale: f32,
+}
+
+impl<T: OutlineBuilder + ?Sized> OutlinePen for Pen<'_, T> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.inner.move_to(x * self.x_scale, -y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.inner.line_to(x * self.x_scale, -y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let s = self.x_scale;
+        self.inner.quad_to(x1 * s, -y1, x * s, -y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let s = self.x_scale;
+        self.inner.curve_to(x1 * s, -y1, x2 * s, -y2, x * s, -y);
+    }
+
+    fn close(&mut self) {
+        self.inner.close();
+    }
+}
+
+/// Collects the bounds of an outline's points.
+struct Bounds(Option<Rect<f32>>);
+
+impl Bounds {
+    fn add(&mut self, x: f32, y: f32) {
+        let p = point(x, y);
+        self.0 = Some(match self.0 {
+            Some(r) => Rect {
+                min: point(r.min.x.min(x), r.min.y.min(y)),
+                max: point(r.max.x.max(x), r.max.y.max(y)),
+            },
+            None => Rect { min: p, max: p },
+        });
+    }
+}
+
+impl OutlineBuilder for Bounds {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.add(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.add(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.add(x1, y1);
+        self.add(x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.add(x1, y1);
+        self.add(x2, y2);
+        self.add(x, y);
+    }
+
+    fn close(&mut self) {}
+}
+
+/// Builds the hinted outline of `glyph` in pixels relative to its origin,
+/// before any transform. `None` if the glyph isn't hinted, `Some(false)` if
+/// its outline is empty or malformed.
+pub(crate) fn build_outline(
+    glyph: &ScaledGlyph<'_>,
+    builder: &mut (impl OutlineBuilder + ?Sized),
+) -> Option<bool> {
+    if glyph.hinting == HintingMode::None {
+        return None;
+    }
+    let font: &Font<'_> = glyph.font()?;
+    let font_ref = FontRef::from_index(font.data(), font.index()).ok()?;
+    // hinting is for the em size in pixels, see `Font::scale_for_pixel_height`
+    let ppem = glyph.scale.y * f32::from(font.units_per_em());
+    let instance = font
+        .hinting_cache()
+        .get(&font_ref, font.variations(), ppem, glyph.hinting)?;
+
+    let outline = match font_ref.outline_glyphs().get(glyph.id().0.into()) {
+        Some(outline) => outline,
+        None => return Some(false),
+    };
+    let mut pen = Pen {
+        inner: builder,
+        x_scale: glyph.scale.x / glyph.scale.y,
+    };
+    Some(
+        outline
+            .draw(DrawSettings::hinted(&instance, false), &mut pen)
+            .is_ok(),
+    )
+}
+
+/// The bounding box of the points of `glyph`'s hinted outline, see
+/// `build_outline`.
+pub(crate) fn bounding_box(glyph: &ScaledGlyph<'_>) -> Option<Option<Rect<f32>>> {
+    let mut bounds = Bounds(None);
+    build_outline(glyph, &mut bounds)?;
+    Some(bounds.0)
+}
+
+impl<'font> ScaledGlyph<'font> {
+    /// Returns this glyph hinted with `mode`, fitting its outline to the
+    /// pixel grid by running the font's TrueType or PostScript hinting
+    /// programs before rasterization. Hinting sharpens small text, especially
+    /// on low resolution displays, at the cost of distorting glyph shapes.
+    ///
+    /// Hinting changes the outline, so the bounding boxes & drawing, but not
+    /// metrics, like `h_metrics`, keeping layouts the same. Glyphs drawn from
+    /// embedded bitmaps are unaffected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let glyph = font
+    ///     .glyph('H')
+    ///     .scaled(Scale::uniform(12.0))
+    ///     .hinted(HintingMode::Full)
+    ///     .positioned(point(0.0, 12.0));
+    /// glyph.draw(|x, y, v| { /* crisp stems */ });
+    /// ```
+    pub fn hinted(mut self, mode: HintingMode) -> ScaledGlyph<'font> {
+        self.hinting = mode;
+        self
+    }
+
+    /// The hinting set by `hinted`, `HintingMode::None` if none.
+    #[inline]
+    pub fn hinting(&self) -> HintingMode {
+        self.hinting
+    }
+}
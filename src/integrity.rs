@@ -0,0 +1,273 @@
+//! Font file checksum & structure validation, see `Font::integrity_report`.
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Magic number the whole font checksum must sum to with the `head` table's
+/// `checksumAdjustment` included.
+const CHECKSUM_MAGIC: u32 = 0xB1B0_AFBA;
+
+/// Byte offset of `checksumAdjustment` in the `head` table.
+const HEAD_CHECKSUM_ADJUSTMENT: usize = 8;
+
+/// Tables that the OpenType spec requires every font to contain.
+const REQUIRED_TABLES: [[u8; 4]; 7] = [
+    *b"cmap", *b"head", *b"hhea", *b"hmtx", *b"maxp", *b"name", *b"post",
+];
+
+/// A table directory entry & the result of checking it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TableIntegrity {
+    /// The table tag, e.g. `*b"glyf"`.
+    pub tag: [u8; 4],
+    /// Byte offset of the table in the font file.
+    pub offset: u32,
+    /// Byte length of the table.
+    pub length: u32,
+    /// The checksum recorded in the table directory.
+    pub expected_checksum: u32,
+    /// The checksum of the table data, `None` if the table is out of bounds.
+    pub actual_checksum: Option<u32>,
+}
+
+impl TableIntegrity {
+    /// Returns `true` if the table data is in bounds & matches its checksum.
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.actual_checksum == Some(self.expected_checksum)
+    }
+}
+
+/// A problem found checking the integrity of a font file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum IntegrityIssue {
+    /// A table's data does not match the checksum in the table directory.
+    TableChecksumMismatch {
+        tag: [u8; 4],
+        expected: u32,
+        actual: u32,
+    },
+    /// A table extends past the end of the font data.
+    TableOutOfBounds {
+        tag: [u8; 4],
+        offset: u32,
+        length: u32,
+    },
+    /// A table does not start on a 4-byte boundary.
+    UnalignedTable { tag: [u8; 4], offset: u32 },
+    /// A table required by the OpenType spec is missing.
+    MissingTable { tag: [u8; 4] },
+    /// The `head` table's `checksumAdjustment` doesn't match the whole file.
+    ChecksumAdjustmentMismatch { expected: u32, actual: u32 },
+}
+
+impl fmt::Display for IntegrityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            IntegrityIssue::TableChecksumMismatch {
+                tag,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "'{}' table checksum is {:#010x}, expected {:#010x}",
+                DisplayTag(tag),
+                actual,
+                expected
+            ),
+            IntegrityIssue::TableOutOfBounds {
+                tag,
+                offset,
+                length,
+            } => write!(
+                f,
+                "'{}' table at offset {} with length {} is out of bounds",
+                DisplayTag(tag),
+                offset,
+                length
+            ),
+            IntegrityIssue::UnalignedTable { tag, offset } => write!(
+                f,
+                "'{}' table offset {} is not 4-byte aligned",
+                DisplayTag(tag),
+                offset
+            ),
+            IntegrityIssue::MissingTable { tag } => {
+                write!(f, "required '{}' table is missing", DisplayTag(tag))
+            }
+            IntegrityIssue::ChecksumAdjustmentMismatch { expected, actual } => write!(
+                f,
+                "'head' checksumAdjustment is {:#010x}, expected {:#010x}",
+                actual, expected
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IntegrityIssue {}
+
+pub(crate) struct DisplayTag(pub(crate) [u8; 4]);
+
+impl fmt::Display for DisplayTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &b in &self.0 {
+            if b.is_ascii_graphic() || b == b' ' {
+                write!(f, "{}", b as char)?;
+            } else {
+                write!(f, "\\x{:02x}", b)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The result of checking a font file's checksums & table directory, see
+/// `Font::integrity_report`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntegrityReport {
+    tables: Vec<TableIntegrity>,
+    issues: Vec<IntegrityIssue>,
+}
+
+impl IntegrityReport {
+    pub(crate) fn check(face: &owned_ttf_parser::RawFace<'_>) -> Self {
+        let data = face.data;
+        let mut tables = Vec::with_capacity(face.table_records.len() as usize);
+        let mut issues = Vec::new();
+        let mut head = None;
+
+        for record in face.table_records {
+            let tag = record.tag.to_bytes();
+            let start = record.offset as usize;
+            let table = start
+                .checked_add(record.length as usize)
+                .and_then(|end| data.get(start..end));
+
+            let actual_checksum = table.map(|table| {
+                if &tag == b"head" {
+                    head = Some(start);
+                    head_checksum(table)
+                } else {
+                    checksum(table)
+                }
+            });
+            match actual_checksum {
+                Some(actual) if actual != record.check_sum => {
+                    issues.push(IntegrityIssue::TableChecksumMismatch {
+                        tag,
+                        expected: record.check_sum,
+                        actual,
+                    })
+                }
+                None => issues.push(IntegrityIssue::TableOutOfBounds {
+                    tag,
+                    offset: record.offset,
+                    length: record.length,
+                }),
+                _ => {}
+            }
+            if record.offset % 4 != 0 {
+                issues.push(IntegrityIssue::UnalignedTable {
+                    tag,
+                    offset: record.offset,
+                });
+            }
+
+            tables.push(TableIntegrity {
+                tag,
+                offset: record.offset,
+                length: record.length,
+                expected_checksum: record.check_sum,
+                actual_checksum,
+            });
+        }
+
+        for &tag in &REQUIRED_TABLES {
+            if !tables.iter().any(|t| t.tag == tag) {
+                issues.push(IntegrityIssue::MissingTable { tag });
+            }
+        }
+
+        // The whole file checksum isn't meaningful for font collections,
+        // where the file contains multiple fonts sharing tables.
+        let is_collection = data.starts_with(b"ttcf");
+        let adjustment = head.and_then(|start| {
+            let at = start + HEAD_CHECKSUM_ADJUSTMENT;
+            data.get(at..at + 4).map(|b| (at, read_u32(b)))
+        });
+        if let (false, Some((at, actual))) = (is_collection, adjustment) {
+            let file_sum = checksum(data).wrapping_sub(actual_contribution(at, actual));
+            let expected = CHECKSUM_MAGIC.wrapping_sub(file_sum);
+            if actual != expected {
+                issues.push(IntegrityIssue::ChecksumAdjustmentMismatch { expected, actual });
+            }
+        }
+
+        Self { tables, issues }
+    }
+
+    /// Returns `true` if no issues were found.
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// The issues found, in table directory order followed by missing tables
+    /// & finally the whole file checksum.
+    #[inline]
+    pub fn issues(&self) -> &[IntegrityIssue] {
+        &self.issues
+    }
+
+    /// All tables in table directory order.
+    #[inline]
+    pub fn tables(&self) -> &[TableIntegrity] {
+        &self.tables
+    }
+
+    /// Returns the table with the `tag`, if present.
+    pub fn table(&self, tag: [u8; 4]) -> Option<&TableIntegrity> {
+        self.tables.iter().find(|t| t.tag == tag)
+    }
+}
+
+#[inline]
+fn read_u32(b: &[u8]) -> u32 {
+    u32::from_be_bytes([b[0], b[1], b[2], b[3]])
+}
+
+/// The OpenType checksum: the sum of big endian `u32`s, zero padded.
+fn checksum(data: &[u8]) -> u32 {
+    let mut chunks = data.chunks_exact(4);
+    let mut sum = chunks
+        .by_ref()
+        .fold(0u32, |sum, word| sum.wrapping_add(read_u32(word)));
+    let rest = chunks.remainder();
+    if !rest.is_empty() {
+        let mut word = [0; 4];
+        word[..rest.len()].copy_from_slice(rest);
+        sum = sum.wrapping_add(u32::from_be_bytes(word));
+    }
+    sum
+}
+
+/// The `head` table checksum is calculated with `checksumAdjustment` as zero.
+fn head_checksum(head: &[u8]) -> u32 {
+    let sum = checksum(head);
+    match head.get(HEAD_CHECKSUM_ADJUSTMENT..HEAD_CHECKSUM_ADJUSTMENT + 4) {
+        Some(b) => sum.wrapping_sub(read_u32(b)),
+        None => sum,
+    }
+}
+
+/// What `value` at byte offset `at` contributes to `checksum` of a whole file.
+/// Offsets not 4-byte aligned straddle two words.
+fn actual_contribution(at: usize, value: u32) -> u32 {
+    let shift = (at % 4) as u32 * 8;
+    if shift == 0 {
+        value
+    } else {
+        (value >> shift).wrapping_add(value << (32 - shift))
+    }
+}
@@ -0,0 +1,157 @@
+//! Contextual `kern` table state machine kerning, format 1.
+use crate::{Font, GlyphId};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use owned_ttf_parser::{apple_layout as aat, kern};
+
+/// The kerning stack depth, as defined by the `kern` spec.
+const STACK_DEPTH: usize = 8;
+
+/// Returns if `font` has horizontal state machine kerning subtables.
+pub(crate) fn has_state_machine(font: &Font<'_>) -> bool {
+    state_tables(font).next().is_some()
+}
+
+/// Returns the state machine kerning, in font units, to add to the advance of
+/// each of `glyphs`.
+pub(crate) fn state_machine_kerning(font: &Font<'_>, glyphs: &[GlyphId]) -> Vec<i32> {
+    let mut adjustments = vec![0; glyphs.len()];
+    for table in state_tables(font) {
+        apply(&table, glyphs, &mut adjustments);
+    }
+    adjustments
+}
+
+fn state_tables<'a>(font: &'a Font<'_>) -> impl Iterator<Item = aat::StateTable<'a>> + 'a {
+    font.inner()
+        .tables()
+        .kern
+        .into_iter()
+        .flat_map(|kern| kern.subtables)
+        // cross stream kerning shifts glyphs vertically, which isn't supported
+        .filter(|st| st.horizontal && !st.variable && !st.has_cross_stream)
+        .filter_map(|st| match st.format {
+            kern::Format::Format1(table) => Some(table),
+            _ => None,
+        })
+}
+
+/// Runs the state machine of `table` over `glyphs`, adding kerning values to
+/// `adjustments`.
+fn apply(table: &aat::StateTable<'_>, glyphs: &[GlyphId], adjustments: &mut [i32]) {
+    let mut stack = [0_usize; STACK_DEPTH];
+    let mut depth = 0;
+    let mut state = aat::state::START_OF_TEXT;
+    let mut idx = 0;
+    // entries may not advance, so bound the work on malformed tables
+    let mut ops = glyphs.len() * 16 + 64;
+
+    loop {
+        let class = match glyphs.get(idx) {
+            Some(&id) => table.class(id.into()).unwrap_or(aat::class::OUT_OF_BOUNDS),
+            None => aat::class::END_OF_TEXT,
+        };
+        let entry = match table.entry(state, class) {
+            Some(entry) => entry,
+            None => break,
+        };
+
+        if entry.has_push() {
+            if depth < STACK_DEPTH {
+                stack[depth] = idx;
+                depth += 1;
+            } else {
+                depth = 0;
+            }
+        }
+
+        if entry.has_offset() && depth != 0 {
+            let mut offset = entry.value_offset();
+            // a list of values applied to popped glyphs, ending with an odd
+            // value
+            loop {
+                let value = match table.kerning(offset) {
+                    Some(value) => value,
+                    None => {
+                        depth = 0;
+                        break;
+                    }
+                };
+                depth -= 1;
+                if let Some(adjustment) = adjustments.get_mut(stack[depth]) {
+                    *adjustment += i32::from(value & !1);
+                }
+                offset = offset.next();
+                if value & 1 != 0 || depth == 0 {
+                    break;
+                }
+            }
+        }
+
+        state = table.new_state(entry.new_state);
+        ops = ops.saturating_sub(1);
+        if idx >= glyphs.len() || ops == 0 {
+            break;
+        }
+        if entry.has_advance() {
+            idx += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// An AAT `kern` table with a format 1 subtable kerning glyph 1 followed by
+    /// glyph 2 by -100, unless something else is between them.
+    #[rustfmt::skip]
+    const KERN: &[u8] = &[
+        0x00, 0x01, 0x00, 0x00, // version 1.0
+        0x00, 0x00, 0x00, 0x01, // 1 subtable
+        0x00, 0x00, 0x00, 56, // length
+        0x00, // coverage, horizontal
+        0x01, // format 1
+        0x00, 0x00, // tuple index
+        // state table header
+        0x00, 6, // 6 classes, 4 & 5 are glyphs 1 & 2
+        0x00, 10, // class table offset
+        0x00, 16, // state array offset
+        0x00, 34, // entry table offset
+        0x00, 46, // values offset
+        // class table
+        0x00, 0x01, 0x00, 0x02, 4, 5,
+        // state array, 0 & 1 the initial states, 2 after glyph 1
+        0, 0, 0, 0, 1, 0,
+        0, 0, 0, 0, 1, 0,
+        0, 0, 0, 0, 1, 2,
+        // entries of new state (a state array offset) & flags
+        0x00, 16, 0x00, 0x00, // to state 0
+        0x00, 28, 0x80, 0x00, // push, to state 2
+        0x00, 16, 0x00, 46, // kern with the values at 46, to state 0
+        // values, odd ending the list
+        0xFF, 0x9D, // -100 | 1
+    ];
+
+    fn kerning(glyphs: &[u16]) -> Vec<i32> {
+        let table = kern::Table::parse(KERN).unwrap();
+        let subtable = table.subtables.into_iter().next().unwrap();
+        let state_table = match subtable.format {
+            kern::Format::Format1(table) => table,
+            _ => panic!("expected format 1"),
+        };
+        let glyphs: Vec<_> = glyphs.iter().map(|&id| GlyphId(id)).collect();
+        let mut adjustments = vec![0; glyphs.len()];
+        apply(&state_table, &glyphs, &mut adjustments);
+        adjustments
+    }
+
+    #[test]
+    fn state_machine_kerning() {
+        assert_eq!(kerning(&[1, 2]), [-100, 0]);
+        assert_eq!(kerning(&[1, 1, 2]), [0, -100, 0]);
+        assert_eq!(kerning(&[1, 3, 2]), [0, 0, 0]);
+        assert_eq!(kerning(&[2, 1, 2, 1, 2]), [0, -100, 0, -100, 0]);
+        assert_eq!(kerning(&[]), []);
+    }
+}
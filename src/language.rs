@@ -0,0 +1,184 @@
+//! Language specific glyph forms using the OpenType `locl` feature.
+use crate::{Font, GlyphId};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+use owned_ttf_parser::gsub::{SingleSubstitution, SubstitutionSubtable};
+use owned_ttf_parser::Tag;
+
+/// An OpenType language system tag, e.g. `JAN ` for Japanese. Selects
+/// language specific glyph forms, see `LayoutOptions::language`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Language(pub [u8; 4]);
+
+/// BCP 47 primary language subtags & the OpenType language system tag.
+const LANGUAGES: &[(&str, &[u8; 4])] = &[
+    ("az", b"AZE "),
+    ("bg", b"BGR "),
+    ("ca", b"CAT "),
+    ("de", b"DEU "),
+    ("el", b"ELL "),
+    ("es", b"ESP "),
+    ("fr", b"FRA "),
+    ("ja", b"JAN "),
+    ("kk", b"KAZ "),
+    ("ko", b"KOR "),
+    ("mk", b"MKD "),
+    ("mo", b"MOL "),
+    ("nl", b"NLD "),
+    ("pl", b"PLK "),
+    ("ro", b"ROM "),
+    ("ru", b"RUS "),
+    ("sr", b"SRB "),
+    ("tr", b"TRK "),
+    ("tt", b"TAT "),
+    ("uk", b"UKR "),
+    ("vi", b"VIT "),
+];
+
+impl Language {
+    pub const JAPANESE: Language = Language(*b"JAN ");
+    pub const KOREAN: Language = Language(*b"KOR ");
+    pub const CHINESE_SIMPLIFIED: Language = Language(*b"ZHS ");
+    pub const CHINESE_TRADITIONAL: Language = Language(*b"ZHT ");
+    pub const CHINESE_HONG_KONG: Language = Language(*b"ZHH ");
+
+    /// Returns the language of a BCP 47 language tag, e.g. `"ja"` or
+    /// `"zh-Hant-TW"`, if known.
+    ///
+    /// Chinese is simplified unless the tag has a `Hant` script or a `TW`,
+    /// `HK` or `MO` region.
+    pub fn from_bcp47(tag: &str) -> Option<Language> {
+        let mut subtags = tag.split(['-', '_']);
+        let primary = subtags.next()?;
+        if primary.eq_ignore_ascii_case("zh") {
+            let mut language = Language::CHINESE_SIMPLIFIED;
+            for subtag in subtags {
+                if subtag.eq_ignore_ascii_case("hans") {
+                    return Some(Language::CHINESE_SIMPLIFIED);
+                } else if subtag.eq_ignore_ascii_case("hant") || subtag.eq_ignore_ascii_case("tw") {
+                    language = Language::CHINESE_TRADITIONAL;
+                } else if subtag.eq_ignore_ascii_case("hk") || subtag.eq_ignore_ascii_case("mo") {
+                    language = Language::CHINESE_HONG_KONG;
+                }
+            }
+            return Some(language);
+        }
+        LANGUAGES
+            .iter()
+            .find(|(bcp47, _)| bcp47.eq_ignore_ascii_case(primary))
+            .map(|(_, tag)| Language(**tag))
+    }
+}
+
+impl fmt::Debug for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Language({:?})", Tag::from_bytes(&self.0))
+    }
+}
+
+/// The `locl` single substitution lookups of a language, resolved once per
+/// layout.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct LocalizedForms {
+    lookups: Vec<u16>,
+}
+
+impl LocalizedForms {
+    /// Finds the `locl` lookups of `language` under any script of the font.
+    pub(crate) fn new(font: &Font<'_>, language: Language) -> Self {
+        let mut lookups = Vec::new();
+        let gsub = match font.layout_face().and_then(|face| face.tables().gsub) {
+            Some(gsub) => gsub,
+            None => return Self { lookups },
+        };
+        let language_tag = Tag::from_bytes(&language.0);
+        let locl = Tag::from_bytes(b"locl");
+
+        for script in gsub.scripts {
+            let language_system = match script.languages.find(language_tag) {
+                Some(language_system) => language_system,
+                None => continue,
+            };
+            for feature_index in language_system.feature_indices {
+                let feature = match gsub.features.get(feature_index) {
+                    Some(feature) if feature.tag == locl => feature,
+                    _ => continue,
+                };
+                lookups.extend(feature.lookup_indices);
+            }
+        }
+        lookups.sort_unstable();
+        lookups.dedup();
+        Self { lookups }
+    }
+
+    /// Finds the lookups of the `vert` vertical alternates feature of the
+    /// default language system of every script, e.g. rotated brackets for
+    /// `Font::layout_vertical`.
+    pub(crate) fn vertical(font: &Font<'_>) -> Self {
+        let mut lookups = Vec::new();
+        let gsub = match font.layout_face().and_then(|face| face.tables().gsub) {
+            Some(gsub) => gsub,
+            None => return Self { lookups },
+        };
+        let vert = Tag::from_bytes(b"vert");
+        for script in gsub.scripts {
+            let language_system = match script.default_language {
+                Some(language_system) => language_system,
+                None => continue,
+            };
+            for feature_index in language_system.feature_indices {
+                match gsub.features.get(feature_index) {
+                    Some(feature) if feature.tag == vert => lookups.extend(feature.lookup_indices),
+                    _ => continue,
+                }
+            }
+        }
+        lookups.sort_unstable();
+        lookups.dedup();
+        Self { lookups }
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.lookups.is_empty()
+    }
+
+    /// Returns the localized form of `id`, or `id` itself if it has none.
+    pub(crate) fn apply(&self, font: &Font<'_>, id: GlyphId) -> GlyphId {
+        let gsub = match font.layout_face().and_then(|face| face.tables().gsub) {
+            Some(gsub) if !self.is_empty() => gsub,
+            _ => return id,
+        };
+        let mut glyph = owned_ttf_parser::GlyphId(id.0);
+        for &index in &self.lookups {
+            let lookup = match gsub.lookups.get(index) {
+                Some(lookup) => lookup,
+                None => continue,
+            };
+            for subtable in lookup.subtables.into_iter::<SubstitutionSubtable<'_>>() {
+                if let SubstitutionSubtable::Single(single) = subtable {
+                    if let Some(substitute) = single_substitute(&single, glyph) {
+                        glyph = substitute;
+                        break;
+                    }
+                }
+            }
+        }
+        GlyphId(glyph.0)
+    }
+}
+
+pub(crate) fn single_substitute(
+    subtable: &SingleSubstitution<'_>,
+    glyph: owned_ttf_parser::GlyphId,
+) -> Option<owned_ttf_parser::GlyphId> {
+    let coverage_index = subtable.coverage().get(glyph)?;
+    match subtable {
+        SingleSubstitution::Format1 { delta, .. } => Some(owned_ttf_parser::GlyphId(
+            (glyph.0 as i32 + *delta as i32) as u16,
+        )),
+        SingleSubstitution::Format2 { substitutes, .. } => substitutes.get(coverage_index),
+    }
+}
@@ -0,0 +1,152 @@
+//! LCD subpixel anti-aliasing, see `PositionedGlyph::draw_subpixel`.
+use crate::outliner::{OutlineRasterizer, OutlineScaler, OutlineTranslator};
+use crate::{point, vector, PositionedGlyph, Rect};
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+
+/// The light FIR filter weights of FreeType's default LCD filter, out of
+/// `256`, spreading each subpixel's coverage to its neighbours to reduce
+/// color fringes.
+const FILTER: [f32; 5] = [8.0, 77.0, 86.0, 77.0, 8.0];
+
+/// The order of the red, green & blue subpixels of a display's pixels, see
+/// `PositionedGlyph::draw_subpixel`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SubpixelLayout {
+    /// Horizontal stripes of red, green & blue left to right, most desktop
+    /// monitors.
+    Rgb,
+    /// Horizontal stripes of blue, green & red left to right.
+    Bgr,
+    /// Vertical stripes of red, green & blue top to bottom, e.g. rotated
+    /// `Rgb` monitors.
+    VRgb,
+    /// Vertical stripes of blue, green & red top to bottom.
+    VBgr,
+}
+
+impl SubpixelLayout {
+    #[inline]
+    fn is_vertical(self) -> bool {
+        matches!(self, SubpixelLayout::VRgb | SubpixelLayout::VBgr)
+    }
+
+    #[inline]
+    fn is_bgr(self) -> bool {
+        matches!(self, SubpixelLayout::Bgr | SubpixelLayout::VBgr)
+    }
+}
+
+impl PositionedGlyph<'_> {
+    /// Rasterises this glyph at 3 times the resolution along the subpixels of
+    /// `layout`, for LCD displays. For each pixel in
+    /// `subpixel_pixel_bounding_box(layout)` `o` is called with its position
+    /// relative to the box min & the coverage of its red, green & blue
+    /// subpixels, e.g. to blend each channel of the text color separately.
+    ///
+    /// Coverage is filtered across neighbouring subpixels to reduce color
+    /// fringes, which spreads it a pixel beyond `pixel_bounding_box` on the
+    /// subpixel axis. Glyphs drawn from embedded bitmaps or as pixel fonts have
+    /// no outline at subpixel resolution, so get the coverage of `draw` in
+    /// every channel.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let glyph = font.glyph('a').scaled(Scale::uniform(13.0)).positioned(point(0.0, 0.0));
+    /// let bb = glyph.subpixel_pixel_bounding_box(SubpixelLayout::Rgb).unwrap();
+    /// let mut rgb = vec![[0_u8; 3]; (bb.width() * bb.height()) as usize];
+    /// glyph.draw_subpixel(SubpixelLayout::Rgb, |x, y, v| {
+    ///     rgb[(x + y * bb.width() as u32) as usize] = v.map(|c| (c * 255.0).round() as u8);
+    /// });
+    /// ```
+    pub fn draw_subpixel<O: FnMut(u32, u32, [f32; 3])>(&self, layout: SubpixelLayout, mut o: O) {
+        let bb = match self.subpixel_pixel_bounding_box(layout) {
+            Some(bb) => bb,
+            None => return,
+        };
+        let (width, height) = (bb.width() as usize, bb.height() as usize);
+        // subpixels of a row, or column if vertical, are consecutive
+        let (lines, length) = match layout.is_vertical() {
+            true => (width, 3 * height),
+            false => (height, 3 * width),
+        };
+        let mut coverage = vec![0.0; lines * length];
+        let mut set = |x: usize, y: usize, v: f32| match layout.is_vertical() {
+            true => coverage[y + x * length] = v,
+            false => coverage[x + y * length] = v,
+        };
+
+        if self.sg.uses_bitmap() || self.unscaled().is_pixel_rendered() {
+            let (dx, dy) = match layout.is_vertical() {
+                true => (0, 1),
+                false => (1, 0),
+            };
+            self.draw(|x, y, v| {
+                let (x, y) = (x as usize + dx, y as usize + dy);
+                for s in 0..3 {
+                    match layout.is_vertical() {
+                        true => set(x, 3 * y + s, v),
+                        false => set(3 * x + s, y, v),
+                    }
+                }
+            });
+        } else {
+            let (offset, scale, sub_width, sub_height) = match layout.is_vertical() {
+                true => (point(0.0, 1.0), vector(1.0, 3.0), width, 3 * height),
+                false => (point(1.0, 0.0), vector(3.0, 1.0), 3 * width, height),
+            };
+            let mut rasterizer = OutlineRasterizer::new(sub_width, sub_height);
+            let mut scaler = OutlineScaler::new(&mut rasterizer, scale);
+            self.build_outline(&mut OutlineTranslator::new(&mut scaler, offset));
+            rasterizer
+                .rasterizer
+                .for_each_pixel_2d(|x, y, v| set(x as usize, y as usize, v));
+        }
+
+        let mut filtered = vec![0.0; lines * length];
+        let rows = coverage
+            .chunks_exact(length)
+            .zip(filtered.chunks_exact_mut(length));
+        for (line, out) in rows {
+            for (i, v) in out.iter_mut().enumerate() {
+                let taps = FILTER.iter().enumerate();
+                let sum: f32 = taps
+                    .filter_map(|(k, w)| Some(w * line.get((i + k).checked_sub(2)?)?))
+                    .sum();
+                *v = (sum / 256.0).min(1.0);
+            }
+        }
+
+        for y in 0..height {
+            for x in 0..width {
+                let start = match layout.is_vertical() {
+                    true => 3 * y + x * length,
+                    false => 3 * x + y * length,
+                };
+                let mut rgb = [filtered[start], filtered[start + 1], filtered[start + 2]];
+                if layout.is_bgr() {
+                    rgb.reverse();
+                }
+                o(x as u32, y as u32, rgb);
+            }
+        }
+    }
+
+    /// The pixel bounding box of `draw_subpixel` with the same `layout`, i.e.
+    /// `pixel_bounding_box` expanded by a pixel either side on the subpixel
+    /// axis for the filtered coverage.
+    pub fn subpixel_pixel_bounding_box(&self, layout: SubpixelLayout) -> Option<Rect<i32>> {
+        let (dx, dy) = match layout.is_vertical() {
+            true => (0, 1),
+            false => (1, 0),
+        };
+        self.bb.map(|bb| Rect {
+            min: point(bb.min.x - dx, bb.min.y - dy),
+            max: point(bb.max.x + dx, bb.max.y + dy),
+        })
+    }
+}
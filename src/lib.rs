@@ -94,22 +94,139 @@
     clippy::many_single_char_names
 )]
 #![cfg_attr(not(feature = "std"), no_std)]
+#![forbid(unsafe_code)]
 
 extern crate alloc;
 
+mod bitmap;
+mod caret;
+mod classification;
+mod collection;
+mod color;
+mod complexity;
+mod coverage;
+mod decoration;
+mod digits;
+mod effects;
+mod error;
 mod font;
+mod gdef;
 mod geometry;
+mod gpos;
+mod hash;
+mod integrity;
+mod kern;
+mod language;
+mod lcd;
+mod mask;
+mod names;
+mod once;
+mod optical;
 mod outliner;
+mod paragraph;
+mod pixel;
+mod raster;
+mod rich;
+mod ruby;
+mod run;
+mod shape;
+mod shaper;
+mod standalone;
+mod subpixel;
+mod substitution;
+mod svg_path;
+mod transform;
+mod truncate;
+mod variation;
+mod vertical;
 
 #[cfg(all(feature = "libm-math", not(feature = "std")))]
 mod nostd_float;
 
+#[cfg(feature = "kurbo")]
+mod bez_path;
+#[cfg(feature = "bidi")]
+mod bidi;
 #[cfg(feature = "gpu_cache")]
 pub mod gpu_cache;
-
-pub use crate::geometry::{point, vector, Point, Rect, Vector};
+#[cfg(feature = "hinting")]
+mod hinting;
+#[cfg(feature = "tokio")]
+mod load_async;
+#[cfg(feature = "msdf")]
+mod msdf;
+#[cfg(feature = "std")]
+mod query;
+#[cfg(feature = "shaping")]
+mod shaping;
+#[cfg(feature = "tiny-skia")]
+mod skia;
+#[cfg(feature = "std")]
+mod store;
+#[cfg(feature = "svg")]
+mod svg;
+pub mod texture;
+
+#[cfg(feature = "bidi")]
+pub use crate::bidi::BidiLayout;
+pub use crate::bitmap::{BitmapPolicy, GlyphImage, GlyphImageFormat};
+pub use crate::classification::{FontClass, Panose};
+pub use crate::collection::{CollectionFonts, FontCollection};
+pub use crate::color::ColorLayer;
+pub use crate::complexity::GlyphComplexity;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub use crate::complexity::TimedBackend;
+pub use crate::coverage::{BlockCoverage, CoverageSummary, UnicodeBlock};
+pub use crate::decoration::{Decoration, DecorationMetrics, DecorationSegment};
+pub use crate::digits::{DigitSet, DigitTile};
+pub use crate::effects::{LinearGradient, Outline, Shadow, TextEffects};
+pub use crate::error::Error;
+pub use crate::gdef::GlyphClass;
+pub use crate::geometry::{point, vector, Affine2, Cubic, Curve, Line, Point, Rect, Vector};
+#[cfg(feature = "hinting")]
+pub use crate::hinting::HintingMode;
+pub use crate::integrity::{IntegrityIssue, IntegrityReport, TableIntegrity};
+pub use crate::language::Language;
+pub use crate::lcd::SubpixelLayout;
+pub use crate::mask::CoverageMask;
+pub use crate::outliner::OutlineHook;
+pub use crate::paragraph::{
+    BreakPolicy, DropCap, Justification, LineBox, ParagraphLayout, ParagraphLines, ParagraphWords,
+    PositionedLine, PositionedLines, WordBox,
+};
+pub use crate::pixel::{PixelMode, PixelScaleWarning};
+#[cfg(feature = "std")]
+pub use crate::query::{FontFamily, FontQuery, FontQueryError, LineHeight};
+pub use crate::raster::{RasterBackend, ScanlineRasterizer};
+pub use crate::rich::{RichLayout, TextRun};
+pub use crate::ruby::RubyLayout;
+pub use crate::run::{GlyphOffset, GlyphRun};
+pub use crate::shaper::{Feature, GlyphInfo, Shaper, SimpleShaper};
+#[cfg(feature = "shaping")]
+pub use crate::shaping::RustybuzzShaper;
+pub use crate::standalone::{StandaloneGlyph, StandaloneGlyphBuilder};
+#[cfg(feature = "std")]
+pub use crate::store::{FontHandle, FontKey, FontStore, Style, Weight};
+pub use crate::subpixel::{SubpixelStrategy, SubpixelTarget};
+pub use crate::substitution::GsubShaper;
+#[cfg(feature = "svg")]
+pub use crate::svg::SvgDocument;
+pub use crate::svg_path::SvgPathOptions;
+pub use crate::transform::{TransformedFont, TransformedLayoutIter};
+pub use crate::variation::VariationAxis;
+pub use crate::vertical::{GlyphVMetrics, VerticalLayoutIter};
 pub use font::*;
-
+#[cfg(feature = "fontdb")]
+pub use fontdb;
+#[cfg(feature = "kurbo")]
+pub use kurbo;
+#[cfg(feature = "shaping")]
+pub use rustybuzz;
+#[cfg(feature = "tiny-skia")]
+pub use tiny_skia;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 use core::fmt;
 
 #[cfg(all(feature = "libm-math", not(feature = "std")))]
@@ -131,24 +248,54 @@ impl From<GlyphId> for owned_ttf_parser::GlyphId {
     }
 }
 
-/// A single glyph of a font.
+/// A single glyph of a font, or of a `StandaloneGlyph`.
 ///
 /// A `Glyph` does not have an inherent scale or position associated with it. To
 /// augment a glyph with a size, give it a scale using `scaled`. You can then
 /// position it using `positioned`.
 #[derive(Clone)]
 pub struct Glyph<'font> {
-    font: Font<'font>,
+    inner: GlyphInner<'font>,
     id: GlyphId,
 }
 
+/// Where a glyph's outline & metrics come from.
+#[derive(Clone)]
+enum GlyphInner<'font> {
+    /// A glyph of the font.
+    Proxy(Font<'font>),
+    /// A user provided outline without a font.
+    Standalone(StandaloneGlyph),
+}
+
 impl<'font> Glyph<'font> {
-    /// The font to which this glyph belongs.
-    pub fn font(&self) -> &Font<'font> {
-        &self.font
+    #[inline]
+    pub(crate) fn new(font: Font<'font>, id: GlyphId) -> Self {
+        Glyph {
+            inner: GlyphInner::Proxy(font),
+            id,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn standalone(glyph: StandaloneGlyph) -> Self {
+        Glyph {
+            inner: GlyphInner::Standalone(glyph),
+            id: GlyphId(0),
+        }
+    }
+
+    /// The font to which this glyph belongs, `None` for a `StandaloneGlyph`.
+    #[inline]
+    pub fn font(&self) -> Option<&Font<'font>> {
+        match &self.inner {
+            GlyphInner::Proxy(font) => Some(font),
+            GlyphInner::Standalone(_) => None,
+        }
     }
 
     /// The glyph identifier for this glyph.
+    #[inline]
     pub fn id(&self) -> GlyphId {
         self.id
     }
@@ -156,14 +303,97 @@ impl<'font> Glyph<'font> {
     /// Augments this glyph with scaling information, making methods that depend
     /// on the scale of the glyph available.
     pub fn scaled(self, scale: Scale) -> ScaledGlyph<'font> {
-        let scale_y = self.font.scale_for_pixel_height(scale.y);
+        let scale_y = match &self.inner {
+            GlyphInner::Proxy(font) => font.scale_for_pixel_height(scale.y),
+            GlyphInner::Standalone(glyph) => glyph.scale_for_pixel_height(scale.y),
+        };
         let scale_x = scale_y * scale.x / scale.y;
         ScaledGlyph {
             g: self,
             api_scale: scale,
             scale: vector(scale_x, scale_y),
+            transform: None,
+            #[cfg(feature = "hinting")]
+            hinting: HintingMode::None,
+        }
+    }
+
+    /// Builds the outline in font units, y up. Returns `false` when the
+    /// outline is either malformed or empty.
+    pub(crate) fn build_unscaled_outline(&self, builder: &mut dyn OutlineBuilder) -> bool {
+        match &self.inner {
+            GlyphInner::Proxy(font) => font
+                .inner()
+                .outline_glyph(self.id.into(), builder)
+                .is_some(),
+            GlyphInner::Standalone(glyph) => glyph.build_outline(builder),
+        }
+    }
+
+    /// The bounding box of the outline in font units, y up.
+    pub(crate) fn unscaled_bounding_box(&self) -> Option<Rect<f32>> {
+        match &self.inner {
+            GlyphInner::Proxy(font) => {
+                let bb = font.inner().glyph_bounding_box(self.id.into())?;
+                Some(Rect {
+                    min: point(bb.x_min.into(), bb.y_min.into()),
+                    max: point(bb.x_max.into(), bb.y_max.into()),
+                })
+            }
+            GlyphInner::Standalone(glyph) => glyph.bounds(),
+        }
+    }
+
+    /// The advance width & left side bearing in font units.
+    fn unscaled_h_metrics(&self) -> (f32, f32) {
+        match &self.inner {
+            GlyphInner::Proxy(font) => {
+                let inner = font.inner();
+                let id = self.id.into();
+                let advance = inner.glyph_hor_advance(id).unwrap();
+                let left_side_bearing = inner.glyph_hor_side_bearing(id).unwrap();
+                (advance.into(), left_side_bearing.into())
+            }
+            GlyphInner::Standalone(glyph) => {
+                let x_min = glyph.bounds().map_or(0.0, |bb| bb.min.x);
+                (glyph.advance_width(), x_min)
+            }
+        }
+    }
+
+    /// The ascent, descent & line gap in font units, see
+    /// `Font::v_metrics_unscaled`.
+    pub(crate) fn unscaled_v_metrics(&self) -> VMetrics {
+        match &self.inner {
+            GlyphInner::Proxy(font) => font.v_metrics_unscaled(),
+            GlyphInner::Standalone(glyph) => glyph.v_metrics_unscaled(),
+        }
+    }
+
+    /// See `Font::units_per_em`.
+    pub(crate) fn units_per_em(&self) -> u16 {
+        match &self.inner {
+            GlyphInner::Proxy(font) => font.units_per_em(),
+            GlyphInner::Standalone(glyph) => glyph.units_per_em(),
+        }
+    }
+
+    /// Identifies the outline of a standalone glyph, see
+    /// `StandaloneGlyph::key`, `0` for font glyphs.
+    #[cfg(feature = "gpu_cache")]
+    #[inline]
+    pub(crate) fn standalone_key(&self) -> u64 {
+        match &self.inner {
+            GlyphInner::Proxy(_) => 0,
+            GlyphInner::Standalone(glyph) => glyph.key(),
         }
     }
+
+    /// See `Font::is_pixel_rendered`, `false` for standalone glyphs.
+    #[inline]
+    pub(crate) fn is_pixel_rendered(&self) -> bool {
+        self.font().is_some_and(Font::is_pixel_rendered)
+    }
 }
 
 impl fmt::Debug for Glyph<'_> {
@@ -213,6 +443,42 @@ impl core::ops::Mul<f32> for VMetrics {
     }
 }
 
+/// The embedding & licensing permissions of a font, from the OS/2 `fsType`
+/// field. See `Font::embedding_permissions`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EmbeddingPermissions {
+    /// How a document embedding the font may use it.
+    pub usage: EmbeddingUsage,
+    /// Whether a subset of the font may be embedded, as opposed to only the
+    /// whole font.
+    pub subsetting_allowed: bool,
+    /// Whether only bitmaps of the font's glyphs may be embedded, not outlines.
+    pub bitmap_only: bool,
+}
+
+impl EmbeddingPermissions {
+    /// Returns `true` if the font may be embedded in documents in any form.
+    #[inline]
+    pub fn allows_embedding(&self) -> bool {
+        self.usage != EmbeddingUsage::Restricted
+    }
+}
+
+/// How a font may be used once embedded in a document, see
+/// `EmbeddingPermissions`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EmbeddingUsage {
+    /// The font may be embedded & permanently installed on the remote system.
+    Installable,
+    /// The font must not be embedded without permission from the legal
+    /// owner.
+    Restricted,
+    /// The font may be embedded but only used to view & print the document.
+    PreviewAndPrint,
+    /// The font may be embedded & used to view, print & edit the document.
+    Editable,
+}
+
 /// A glyph augmented with scaling information. You can query such a glyph for
 /// information that depends on the scale of the glyph.
 #[derive(Clone)]
@@ -220,45 +486,129 @@ pub struct ScaledGlyph<'font> {
     g: Glyph<'font>,
     api_scale: Scale,
     scale: Vector<f32>,
+    /// Linear transform applied after scaling, see `ScaledGlyph::transformed`.
+    transform: Option<Affine2>,
+    /// See `ScaledGlyph::hinted`.
+    #[cfg(feature = "hinting")]
+    hinting: HintingMode,
 }
 
 impl<'font> ScaledGlyph<'font> {
     /// The glyph identifier for this glyph.
+    #[inline]
     pub fn id(&self) -> GlyphId {
         self.g.id()
     }
 
-    /// The font to which this glyph belongs.
+    /// The font to which this glyph belongs, `None` for a `StandaloneGlyph`.
     #[inline]
-    pub fn font(&self) -> &Font<'font> {
+    pub fn font(&self) -> Option<&Font<'font>> {
         self.g.font()
     }
 
-    /// A reference to this glyph without the scaling
+    /// Removes the scaling from this glyph.
+    ///
+    /// Scaling the result by `scale` gives this glyph back, apart from any
+    /// `transform`.
+    #[inline]
     pub fn into_unscaled(self) -> Glyph<'font> {
         self.g
     }
 
-    /// Removes the scaling from this glyph
+    /// A reference to this glyph without the scaling.
+    #[inline]
     pub fn unscaled(&self) -> &Glyph<'font> {
         &self.g
     }
 
     /// Builds the outline of the glyph with the builder specified. Returns
     /// `false` when the outline is either malformed or empty.
-    pub fn build_outline(&self, builder: &mut impl OutlineBuilder) -> bool {
-        let mut outliner =
-            crate::outliner::OutlineScaler::new(builder, vector(self.scale.x, -self.scale.y));
+    ///
+    /// Each contour is emitted as a `move_to`, then `line_to`, `quad_to` &
+    /// `curve_to` segments, then `close`, in pixels y down relative to the
+    /// glyph origin, including any `transformed` transform & hinting. E.g. to
+    /// fill or tessellate glyphs with another renderer instead of drawing
+    /// coverage.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// /// Collects an SVG path.
+    /// struct Path(String);
+    ///
+    /// impl OutlineBuilder for Path {
+    ///     fn move_to(&mut self, x: f32, y: f32) {
+    ///         self.0 += &format!("M{} {}", x, y);
+    ///     }
+    ///     fn line_to(&mut self, x: f32, y: f32) {
+    ///         self.0 += &format!("L{} {}", x, y);
+    ///     }
+    ///     fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+    ///         self.0 += &format!("Q{} {} {} {}", x1, y1, x, y);
+    ///     }
+    ///     fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+    ///         self.0 += &format!("C{} {} {} {} {} {}", x1, y1, x2, y2, x, y);
+    ///     }
+    ///     fn close(&mut self) {
+    ///         self.0 += "Z";
+    ///     }
+    /// }
+    ///
+    /// let mut path = Path(String::new());
+    /// assert!(font.glyph('a').scaled(Scale::uniform(24.0)).build_outline(&mut path));
+    /// assert!(path.0.starts_with('M') && path.0.ends_with('Z'));
+    /// ```
+    pub fn build_outline(&self, builder: &mut (impl OutlineBuilder + ?Sized)) -> bool {
+        match self.transform {
+            Some(transform) => {
+                let mut transformer = crate::outliner::OutlineTransformer::new(builder, transform);
+                self.build_untransformed_outline(&mut transformer)
+            }
+            None => self.build_untransformed_outline(builder),
+        }
+    }
+
+    fn build_untransformed_outline(&self, builder: &mut (impl OutlineBuilder + ?Sized)) -> bool {
+        #[cfg(feature = "hinting")]
+        {
+            if let Some(built) = crate::hinting::build_outline(self, builder) {
+                return built;
+            }
+        }
+        let scale = vector(self.scale.x, -self.scale.y);
+        let mut outliner = crate::outliner::OutlineScaler::new(builder, scale);
+        self.g.build_unscaled_outline(&mut outliner)
+    }
+
+    /// Applies the linear part of `transform`, e.g. a rotation or skew about
+    /// the glyph origin, to this glyph after any previous transform. The
+    /// translation is ignored, see `Font::with_transform` to transform whole
+    /// layouts.
+    ///
+    /// Transformed glyphs are always drawn from their outlines. Metrics, like
+    /// `h_metrics`, are not transformed.
+    pub fn transformed(mut self, transform: Affine2) -> ScaledGlyph<'font> {
+        let transform = self.transform().then(transform).linear();
+        self.transform = Some(transform).filter(|t| !t.is_identity());
+        self
+    }
 
-        self.font()
-            .inner()
-            .outline_glyph(self.id().into(), &mut outliner)
-            .is_some()
+    /// The transform applied by `transformed`, `Affine2::IDENTITY` if none.
+    #[inline]
+    pub fn transform(&self) -> Affine2 {
+        self.transform.unwrap_or_default()
     }
 
     /// Augments this glyph with positioning information, making methods that
     /// depend on the position of the glyph available.
+    ///
+    /// Glyphs of pixel fonts, see `Font::set_pixel_mode`, are positioned at
+    /// the nearest whole pixel.
     pub fn positioned(self, p: Point<f32>) -> PositionedGlyph<'font> {
+        let p = self.snap(p);
         let bb = self.pixel_bounds_at(p);
         PositionedGlyph {
             sg: self,
@@ -267,6 +617,8 @@ impl<'font> ScaledGlyph<'font> {
         }
     }
 
+    /// The scale given to `Glyph::scaled`.
+    #[inline]
     pub fn scale(&self) -> Scale {
         self.api_scale
     }
@@ -274,68 +626,116 @@ impl<'font> ScaledGlyph<'font> {
     /// Retrieves the "horizontal metrics" of this glyph. See `HMetrics` for
     /// more detail.
     pub fn h_metrics(&self) -> HMetrics {
-        let inner = self.font().inner();
-        let id = self.id().into();
-
-        let advance = inner.glyph_hor_advance(id).unwrap();
-        let left_side_bearing = inner.glyph_hor_side_bearing(id).unwrap();
+        let (advance, left_side_bearing) = self.g.unscaled_h_metrics();
 
-        HMetrics {
-            advance_width: advance as f32 * self.scale.x,
-            left_side_bearing: left_side_bearing as f32 * self.scale.x,
+        let h_metrics = HMetrics {
+            advance_width: advance * self.scale.x,
+            left_side_bearing: left_side_bearing * self.scale.x,
+        };
+        if self.g.is_pixel_rendered() {
+            return HMetrics {
+                advance_width: h_metrics.advance_width.round(),
+                left_side_bearing: h_metrics.left_side_bearing.round(),
+            };
         }
+        h_metrics
     }
 
     /// The bounding box of the shape of this glyph, not to be confused with
     /// `pixel_bounding_box`, the conservative pixel-boundary bounding box. The
-    /// coordinates are relative to the glyph's origin.
+    /// coordinates are relative to the glyph's origin. For `transformed`
+    /// glyphs this bounds the transformed box, so may be larger than the shape.
     pub fn exact_bounding_box(&self) -> Option<Rect<f32>> {
-        let owned_ttf_parser::Rect {
-            x_min,
-            y_min,
-            x_max,
-            y_max,
-        } = self.font().inner().glyph_bounding_box(self.id().into())?;
+        let bb = self.untransformed_bounding_box()?;
+        match self.transform {
+            Some(transform) => Some(transformed_bounds(bb, transform)),
+            None => Some(bb),
+        }
+    }
+
+    fn untransformed_bounding_box(&self) -> Option<Rect<f32>> {
+        #[cfg(feature = "hinting")]
+        {
+            if let Some(bb) = crate::hinting::bounding_box(self) {
+                return bb;
+            }
+        }
+        let Rect { min, max } = self.g.unscaled_bounding_box()?;
 
         Some(Rect {
-            min: point(x_min as f32 * self.scale.x, -y_max as f32 * self.scale.y),
-            max: point(x_max as f32 * self.scale.x, -y_min as f32 * self.scale.y),
+            min: point(min.x * self.scale.x, -max.y * self.scale.y),
+            max: point(max.x * self.scale.x, -min.y * self.scale.y),
         })
     }
 
-    fn glyph_bitmap_box_subpixel(
-        &self,
-        font: &Font<'font>,
-        shift_x: f32,
-        shift_y: f32,
-    ) -> Option<Rect<i32>> {
-        let owned_ttf_parser::Rect {
-            x_min,
-            y_min,
-            x_max,
-            y_max,
-        } = font.inner().glyph_bounding_box(self.id().into())?;
+    fn glyph_bitmap_box_subpixel(&self, shift_x: f32, shift_y: f32) -> Option<Rect<i32>> {
+        if self.transform.is_some() || self.is_hinted() {
+            let bb = self.exact_bounding_box()?;
+            return Some((bb + vector(shift_x, shift_y)).round_out());
+        }
+
+        let Rect { min, max } = self.g.unscaled_bounding_box()?;
 
         Some(Rect {
             min: point(
-                (x_min as f32 * self.scale.x + shift_x).floor() as i32,
-                (-y_max as f32 * self.scale.y + shift_y).floor() as i32,
+                (min.x * self.scale.x + shift_x).floor() as i32,
+                (-max.y * self.scale.y + shift_y).floor() as i32,
             ),
             max: point(
-                (x_max as f32 * self.scale.x + shift_x).ceil() as i32,
-                (-y_min as f32 * self.scale.y + shift_y).ceil() as i32,
+                (max.x * self.scale.x + shift_x).ceil() as i32,
+                (-min.y * self.scale.y + shift_y).ceil() as i32,
             ),
         })
     }
 
+    /// Returns if this glyph is drawn from an embedded bitmap, see
+    /// `Font::set_bitmap_policy`.
+    pub fn uses_bitmap(&self) -> bool {
+        self.strike().is_some()
+    }
+
+    /// Snaps `p` to the nearest whole pixel for pixel fonts.
+    #[inline]
+    fn snap(&self, p: Point<f32>) -> Point<f32> {
+        if self.g.is_pixel_rendered() {
+            return point(p.x.round(), p.y.round());
+        }
+        p
+    }
+
+    /// Returns if the outline is hinted, see `hinted`.
+    #[inline]
+    fn is_hinted(&self) -> bool {
+        #[cfg(feature = "hinting")]
+        {
+            self.hinting != HintingMode::None
+        }
+        #[cfg(not(feature = "hinting"))]
+        {
+            false
+        }
+    }
+
+    #[inline]
+    fn strike(&self) -> Option<owned_ttf_parser::RasterGlyphImage<'_>> {
+        if self.transform.is_some() {
+            return None;
+        }
+        crate::bitmap::strike(self.font()?, self.id(), self.api_scale)
+    }
+
     #[inline]
     fn pixel_bounds_at(&self, p: Point<f32>) -> Option<Rect<i32>> {
+        if let Some(image) = self.strike() {
+            return crate::bitmap::pixel_bounds(&image, p);
+        }
+
         // Use subpixel fraction in floor/ceil rounding to eliminate rounding error
         // from identical subpixel positions
         let (x_trunc, x_fract) = (p.x.trunc() as i32, p.x.fract());
         let (y_trunc, y_fract) = (p.y.trunc() as i32, p.y.fract());
 
-        let Rect { min, max } = self.glyph_bitmap_box_subpixel(self.font(), x_fract, y_fract)?;
+        let Rect { min, max } = self.glyph_bitmap_box_subpixel(x_fract, y_fract)?;
         Some(Rect {
             min: point(x_trunc + min.x, y_trunc + min.y),
             max: point(x_trunc + max.x, y_trunc + max.y),
@@ -343,6 +743,27 @@ impl<'font> ScaledGlyph<'font> {
     }
 }
 
+/// The bounds of `bb` transformed by the linear part of `transform`.
+fn transformed_bounds(bb: Rect<f32>, transform: Affine2) -> Rect<f32> {
+    let corners = [
+        bb.min,
+        point(bb.max.x, bb.min.y),
+        point(bb.min.x, bb.max.y),
+        bb.max,
+    ]
+    .map(|p| transform.linear().transform_point(p));
+    corners.iter().skip(1).fold(
+        Rect {
+            min: corners[0],
+            max: corners[0],
+        },
+        |r, p| Rect {
+            min: point(r.min.x.min(p.x), r.min.y.min(p.y)),
+            max: point(r.max.x.max(p.x), r.max.y.max(p.y)),
+        },
+    )
+}
+
 impl fmt::Debug for ScaledGlyph<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ScaledGlyph")
@@ -364,26 +785,59 @@ pub struct PositionedGlyph<'font> {
 
 impl<'font> PositionedGlyph<'font> {
     /// The glyph identifier for this glyph.
+    #[inline]
     pub fn id(&self) -> GlyphId {
         self.sg.id()
     }
 
-    /// The font to which this glyph belongs.
+    /// The font to which this glyph belongs, `None` for a `StandaloneGlyph`.
     #[inline]
-    pub fn font(&self) -> &Font<'font> {
+    pub fn font(&self) -> Option<&Font<'font>> {
         self.sg.font()
     }
 
-    /// A reference to this glyph without positioning
+    /// A reference to this glyph without positioning.
+    #[inline]
     pub fn unpositioned(&self) -> &ScaledGlyph<'font> {
         &self.sg
     }
 
-    /// Removes the positioning from this glyph
+    /// Removes the positioning from this glyph.
+    ///
+    /// Positioning the result at `position` gives this glyph back.
+    #[inline]
     pub fn into_unpositioned(self) -> ScaledGlyph<'font> {
         self.sg
     }
 
+    /// A reference to this glyph without positioning or scaling.
+    #[inline]
+    pub fn unscaled(&self) -> &Glyph<'font> {
+        &self.sg.g
+    }
+
+    /// Removes the positioning & scaling from this glyph, e.g. to key a cache
+    /// by glyph independently of size.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let glyph = font.glyph('a').scaled(Scale::uniform(24.0)).positioned(point(1.5, 20.0));
+    /// let (scale, position) = (glyph.scale(), glyph.position());
+    ///
+    /// let unscaled = glyph.clone().into_unscaled();
+    /// assert_eq!(unscaled.id(), glyph.id());
+    /// let again = unscaled.scaled(scale).positioned(position);
+    /// assert_eq!(again.pixel_bounding_box(), glyph.pixel_bounding_box());
+    /// ```
+    #[inline]
+    pub fn into_unscaled(self) -> Glyph<'font> {
+        self.sg.g
+    }
+
     /// The conservative pixel-boundary bounding box for this glyph. This is the
     /// smallest rectangle aligned to pixel boundaries that encloses the shape
     /// of this glyph at this position. Note that the origin of the glyph, at
@@ -392,17 +846,48 @@ impl<'font> PositionedGlyph<'font> {
         self.bb
     }
 
+    /// The pixel rect `draw`, `draw_with_hook` & `draw_with_backend` iterate,
+    /// calling their output with every `(x, y)` in
+    /// `0..width() x 0..height()` exactly once, relative to `min`. `None` if
+    /// nothing is drawn.
+    ///
+    /// The rect is the glyph's bounding box at its position, with `min`
+    /// rounded down & `max` rounded up to whole pixels. Bitmap glyphs, see
+    /// `Font::set_bitmap_policy`, use the bitmap size at the position rounded
+    /// to the nearest pixel.
+    ///
+    /// This is always the same as `pixel_bounding_box`, named for atlas code
+    /// relying on the guarantee.
+    #[inline]
+    pub fn raster_bounds(&self) -> Option<Rect<i32>> {
+        self.bb
+    }
+
+    /// The scale given to `Glyph::scaled`, see `ScaledGlyph::scale`.
+    #[inline]
     pub fn scale(&self) -> Scale {
         self.sg.api_scale
     }
 
+    /// The transform of the scaled glyph, see `ScaledGlyph::transform`.
+    #[inline]
+    pub fn transform(&self) -> Affine2 {
+        self.sg.transform()
+    }
+
+    /// The position of the glyph origin in pixels, as given to `positioned`
+    /// or `set_position`, snapped to whole pixels for pixel fonts.
+    #[inline]
     pub fn position(&self) -> Point<f32> {
         self.position
     }
 
     /// Builds the outline of the glyph with the builder specified. Returns
     /// `false` when the outline is either malformed or empty.
-    pub fn build_outline(&self, builder: &mut impl OutlineBuilder) -> bool {
+    ///
+    /// Like `ScaledGlyph::build_outline`, but in pixels relative to the min
+    /// of `pixel_bounding_box`, as drawn by `draw`.
+    pub fn build_outline(&self, builder: &mut (impl OutlineBuilder + ?Sized)) -> bool {
         let bb = if let Some(bb) = self.bb.as_ref() {
             bb
         } else {
@@ -436,12 +921,21 @@ impl<'font> PositionedGlyph<'font> {
     ///     }
     /// }
     /// ```
+    ///
+    /// Glyphs using an embedded bitmap, see `Font::set_bitmap_policy`, are
+    /// drawn from the bitmap instead. Pixel font glyphs, see
+    /// `Font::set_pixel_mode`, are drawn without anti-aliasing, with coverage
+    /// `0.0` or `1.0`.
     pub fn draw<O: FnMut(u32, u32, f32)>(&self, o: O) {
         let bb = if let Some(bb) = self.bb.as_ref() {
             bb
         } else {
             return;
         };
+        let o = self.mono(o);
+        if let Some(image) = self.sg.strike() {
+            return crate::bitmap::draw(&image, o);
+        }
 
         let width = (bb.max.x - bb.min.x) as u32;
         let height = (bb.max.y - bb.min.y) as u32;
@@ -453,8 +947,140 @@ impl<'font> PositionedGlyph<'font> {
         outliner.rasterizer.for_each_pixel_2d(o);
     }
 
+    /// Rasterises this glyph in the same way as `draw`, but first passes the
+    /// outline through `hook` allowing it to be inspected or modified right
+    /// before rasterization. See `OutlineHook`. Glyphs drawn from embedded
+    /// bitmaps have no outline to hook, so are drawn like `draw`.
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font: Font<'static> = Font::try_from_bytes(font_data).unwrap();
+    /// # let glyph = font.glyph('a').scaled(Scale::uniform(25.0)).positioned(point(0.0, 0.0));
+    /// /// A hook that leaves the outline untouched.
+    /// struct Noop;
+    /// impl OutlineHook for Noop {}
+    ///
+    /// glyph.draw_with_hook(&mut Noop, |x, y, v| {});
+    /// ```
+    pub fn draw_with_hook<H, O>(&self, hook: &mut H, o: O)
+    where
+        H: OutlineHook + ?Sized,
+        O: FnMut(u32, u32, f32),
+    {
+        let bb = if let Some(bb) = self.bb.as_ref() {
+            bb
+        } else {
+            return;
+        };
+        let o = self.mono(o);
+        if let Some(image) = self.sg.strike() {
+            return crate::bitmap::draw(&image, o);
+        }
+
+        let width = (bb.max.x - bb.min.x) as u32;
+        let height = (bb.max.y - bb.min.y) as u32;
+
+        let mut outliner = crate::outliner::OutlineRasterizer::new(width as _, height as _);
+
+        hook.begin(self, &mut outliner);
+        self.build_outline(&mut crate::outliner::OutlineHooker::new(
+            hook,
+            &mut outliner,
+        ));
+        hook.end(self, &mut outliner);
+
+        outliner.rasterizer.for_each_pixel_2d(o);
+    }
+
+    /// Rasterises this glyph in the same way as `draw`, using `backend`
+    /// instead of the built-in rasterizer. See `RasterBackend`.
+    pub fn draw_with_backend<B, O>(&self, backend: &B, mut o: O)
+    where
+        B: RasterBackend + ?Sized,
+        O: FnMut(u32, u32, f32),
+    {
+        if self.bb.is_some() {
+            backend.rasterize(self, &mut o);
+        }
+    }
+
+    /// Rasterises this glyph expanded outwards by `radius` pixels, e.g. for
+    /// outlines or glows drawn behind the glyph. See `CoverageMask::dilate`.
+    ///
+    /// The coverage covers `dilated_pixel_bounding_box`, `o` is called with
+    /// coordinates relative to its top left, like `draw`.
+    pub fn draw_dilated<O: FnMut(u32, u32, f32)>(&self, radius: f32, o: O) {
+        if self.bb.is_some() {
+            CoverageMask::from_glyph(self)
+                .dilate(radius)
+                .for_each_pixel(o);
+        }
+    }
+
+    /// The pixel bounding box of `draw_dilated` with the same `radius`, i.e.
+    /// `pixel_bounding_box` expanded by `radius.ceil()` on every side.
+    pub fn dilated_pixel_bounding_box(&self, radius: f32) -> Option<Rect<i32>> {
+        let pad = radius.max(0.0).ceil() as i32;
+        self.bb.map(|bb| Rect {
+            min: point(bb.min.x - pad, bb.min.y - pad),
+            max: point(bb.max.x + pad, bb.max.y + pad),
+        })
+    }
+
+    /// Wraps `o` to threshold coverage for pixel fonts.
+    #[inline]
+    fn mono<O: FnMut(u32, u32, f32)>(&self, mut o: O) -> impl FnMut(u32, u32, f32) {
+        let mono = self.sg.g.is_pixel_rendered();
+        move |x, y, v| {
+            if mono {
+                o(x, y, if v >= 0.5 { 1.0 } else { 0.0 })
+            } else {
+                o(x, y, v)
+            }
+        }
+    }
+
+    /// Returns this glyph, laid out in logical pixels, in the physical pixels
+    /// of a display with a device pixel ratio of `factor`, e.g. `2.0` on most
+    /// HiDPI displays.
+    ///
+    /// Both the scale & position are multiplied by `factor`, so glyphs are
+    /// rasterized at the physical resolution, rather than drawn at the logical
+    /// resolution & upscaled, while staying where the logical layout put them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let dpr = 2.0;
+    /// // layout in logical pixels, e.g. matching the UI layout
+    /// for glyph in font.layout("HiDPI", Scale::uniform(14.0), point(10.0, 20.0)) {
+    ///     let physical = glyph.for_dpr(dpr);
+    ///     assert_eq!(physical.position(), point(glyph.position().x * dpr, 40.0));
+    ///     assert_eq!(physical.scale(), Scale::uniform(28.0));
+    ///     physical.draw(|x, y, v| { /* draw into the physical framebuffer */ });
+    /// }
+    /// ```
+    pub fn for_dpr(&self, factor: f32) -> PositionedGlyph<'font> {
+        let scale = Scale {
+            x: self.sg.api_scale.x * factor,
+            y: self.sg.api_scale.y * factor,
+        };
+        let mut sg = self.sg.g.clone().scaled(scale);
+        sg.transform = self.sg.transform;
+        #[cfg(feature = "hinting")]
+        {
+            sg.hinting = self.sg.hinting;
+        }
+        sg.positioned(point(self.position.x * factor, self.position.y * factor))
+    }
+
     /// Resets positioning information and recalculates the pixel bounding box
     pub fn set_position(&mut self, p: Point<f32>) {
+        let p = self.sg.snap(p);
         let p_diff = p - self.position;
         if p_diff.x.fract().is_near_zero() && p_diff.y.fract().is_near_zero() {
             if let Some(bb) = self.bb.as_mut() {
@@ -548,6 +1174,119 @@ where
     }
 }
 
+/// An iterator over the character to glyph mappings of a font, see
+/// `Font::char_map`.
+#[derive(Clone)]
+pub struct CharMapIter<'a> {
+    inner: core::slice::Iter<'a, (char, GlyphId)>,
+}
+
+impl Iterator for CharMapIter<'_> {
+    type Item = (char, GlyphId);
+
+    #[inline]
+    fn next(&mut self) -> Option<(char, GlyphId)> {
+        self.inner.next().copied()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ExactSizeIterator for CharMapIter<'_> {}
+
+/// Options for `Font::layout_with_options` & `Font::layout_shaped`.
+///
+/// # Example
+///
+/// ```
+/// # use rusttype::{Language, LayoutOptions};
+/// let options = LayoutOptions::default().language(Language::from_bcp47("ja"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LayoutOptions {
+    language: Option<Language>,
+    kerning: Kerning,
+    features: Vec<Feature>,
+    caps_tracking: i16,
+}
+
+/// How `Font::layout_with_options` kerns glyph pairs.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Kerning {
+    /// The font's kerning, see `Font::pair_kerning`.
+    #[default]
+    Metrics,
+    /// Kerning estimated from the glyph shapes, see `Font::optical_kerning`.
+    Optical,
+    /// `Metrics` if the font has kerning data, otherwise `Optical`.
+    Auto,
+}
+
+impl LayoutOptions {
+    /// Language of the text, selecting language specific glyph forms using the
+    /// font's OpenType `locl` feature. E.g. Chinese, Japanese & Korean text
+    /// sharing unified Han code points with different regional forms.
+    ///
+    /// Only single substitutions are applied. Default `None`, using the
+    /// font's default forms.
+    pub fn language(mut self, language: Option<Language>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Returns the `language` option.
+    pub fn get_language(&self) -> Option<Language> {
+        self.language
+    }
+
+    /// How glyph pairs are kerned. Default `Kerning::Metrics`.
+    pub fn kerning(mut self, kerning: Kerning) -> Self {
+        self.kerning = kerning;
+        self
+    }
+
+    /// Returns the `kerning` option.
+    pub fn get_kerning(&self) -> Kerning {
+        self.kerning
+    }
+
+    /// Sets the OpenType feature `tag` to `value` for `Shaper`s supporting
+    /// feature settings, e.g. `GsubShaper`, replacing any previous setting of
+    /// `tag`. Ignored by the built-in `SimpleShaper`.
+    pub fn feature(mut self, tag: [u8; 4], value: u32) -> Self {
+        self.features.retain(|f| f.tag != tag);
+        self.features.push(Feature { tag, value });
+        self
+    }
+
+    /// Returns the `feature` settings.
+    pub fn get_features(&self) -> &[Feature] {
+        &self.features
+    }
+
+    /// Extra spacing between consecutive capitals & digits, in thousandths of
+    /// an em, added to their kerning. All caps labels & acronyms are commonly
+    /// tracked out, e.g. by `50`, as capitals are designed to be set next to
+    /// lowercase. Default `0`, no extra spacing.
+    ///
+    /// ```
+    /// # use rusttype::LayoutOptions;
+    /// let options = LayoutOptions::default().caps_tracking(50);
+    /// ```
+    pub fn caps_tracking(mut self, thousandths_em: i16) -> Self {
+        self.caps_tracking = thousandths_em;
+        self
+    }
+
+    /// Returns the `caps_tracking` option.
+    pub fn get_caps_tracking(&self) -> i16 {
+        self.caps_tracking
+    }
+}
+
 #[derive(Clone)]
 pub struct LayoutIter<'a, 'font, 's> {
     font: &'a Font<'font>,
@@ -556,32 +1295,300 @@ pub struct LayoutIter<'a, 'font, 's> {
     scale: Scale,
     start: Point<f32>,
     last_glyph: Option<GlyphId>,
+    /// The glyph following marks attach to & its caret, if the font attaches
+    /// marks.
+    last_base: Option<(GlyphId, f32)>,
+    localized_forms: crate::language::LocalizedForms,
+    optical_kerning: Option<crate::optical::OpticalKerning>,
+    /// State machine kerning of each glyph, in pixels.
+    contextual_kerning: Option<Vec<f32>>,
+    glyph_index: usize,
+    /// Tracking between capitals & digits, in pixels.
+    caps_tracking: f32,
+    last_caps: bool,
+    /// Multiplier & pixel delta of word separator advances.
+    word_spacing: (f32, f32),
+}
+
+/// Returns if `c` separates words, so is stretched by
+/// `LayoutIter::word_spacing`, like CSS word separators.
+fn is_word_separator(c: char) -> bool {
+    matches!(
+        c,
+        ' ' | '\u{a0}' | '\u{1361}' | '\u{10100}' | '\u{10101}' | '\u{1039f}' | '\u{1091f}'
+    )
 }
 
 impl<'a, 'font, 's> Iterator for LayoutIter<'a, 'font, 's> {
     type Item = PositionedGlyph<'font>;
 
     fn next(&mut self) -> Option<PositionedGlyph<'font>> {
+        self.next_scaled().map(|(g, offset, advance)| {
+            let g = g.positioned(point(self.start.x + self.caret, self.start.y) + offset);
+            self.caret += advance;
+            g
+        })
+    }
+}
+
+impl<'font> LayoutIter<'_, 'font, '_> {
+    /// Returns the next glyph, its offset from the caret & its advance,
+    /// moving the caret to its start.
+    fn next_scaled(&mut self) -> Option<(ScaledGlyph<'font>, Vector<f32>, f32)> {
         self.chars.next().map(|c| {
-            let g = self.font.glyph(c).scaled(self.scale);
+            let mut g = self.font.glyph(c);
+            if !self.localized_forms.is_empty() {
+                g = self
+                    .font
+                    .glyph(self.localized_forms.apply(self.font, g.id()));
+            }
+            let g = g.scaled(self.scale);
+            if let Some((base, base_caret)) = self.last_base {
+                let attachment = self.font.attachment_offset(self.scale, base, g.id());
+                if let Some(offset) = attachment {
+                    self.glyph_index += 1;
+                    // kerned as if the mark weren't there
+                    return (g, offset + vector(base_caret - self.caret, 0.0), 0.0);
+                }
+            }
             if let Some(last) = self.last_glyph {
-                self.caret += self.font.pair_kerning(self.scale, last, g.id());
+                self.caret += match &mut self.optical_kerning {
+                    Some(optical) => {
+                        let kern = optical.kerning(self.font, last, g.id());
+                        kern * self.font.kerning_factor(self.scale)
+                    }
+                    None => match &self.contextual_kerning {
+                        Some(contextual) => {
+                            let kern = self.font.pairwise_kerning(last, g.id());
+                            let factor = self.font.kerning_factor(self.scale);
+                            factor * f32::from(kern) + contextual[self.glyph_index - 1]
+                        }
+                        None => self.font.pair_kerning(self.scale, last, g.id()),
+                    },
+                };
+            }
+            let caps = c.is_uppercase() || c.is_numeric();
+            if caps && self.last_caps {
+                self.caret += self.caps_tracking;
+            }
+            self.last_caps = caps;
+            self.glyph_index += 1;
+            let mut advance = g.h_metrics().advance_width;
+            if is_word_separator(c) {
+                let (multiplier, delta) = self.word_spacing;
+                advance = advance * multiplier + delta;
             }
-            let g = g.positioned(point(self.start.x + self.caret, self.start.y));
-            self.caret += g.sg.h_metrics().advance_width;
             self.last_glyph = Some(g.id());
-            g
+            if self.font.gpos_lookups().has_marks() {
+                self.last_base = Some((g.id(), self.caret));
+            }
+            (g, vector(0.0, 0.0), advance)
         })
     }
 }
 
+impl<'a, 'font, 's> LayoutIter<'a, 'font, 's> {
+    /// Captures the layout state before the next glyph, the remaining text,
+    /// caret & kerning state, to `resume` from, e.g. to lay out ahead to the
+    /// next break opportunity & roll back if it doesn't fit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let mut layout = font.layout("Hello World", Scale::uniform(24.0), point(0.0, 24.0));
+    /// let mut line = vec![];
+    /// let mut checkpoint = layout.checkpoint();
+    /// loop {
+    ///     // lay out the next word speculatively
+    ///     let word: Vec<_> = layout.by_ref().take_while(|g| g.id() != font.glyph(' ').id()).collect();
+    ///     if word.is_empty() || word.last().unwrap().position().x > 80.0 {
+    ///         layout.resume(checkpoint);
+    ///         break;
+    ///     }
+    ///     line.extend(word);
+    ///     checkpoint = layout.checkpoint();
+    /// }
+    /// assert_eq!(line.len(), 5);
+    /// assert_eq!(layout.checkpoint().remaining(), "World");
+    /// ```
+    pub fn checkpoint(&self) -> LayoutCheckpoint<'s> {
+        LayoutCheckpoint {
+            chars: self.chars.clone(),
+            caret: self.caret,
+            last_glyph: self.last_glyph,
+            last_base: self.last_base,
+            glyph_index: self.glyph_index,
+            last_caps: self.last_caps,
+        }
+    }
+
+    /// Restores the layout state of `checkpoint`, so the next glyph is the
+    /// one after the checkpoint, positioned & kerned as it was then.
+    ///
+    /// `checkpoint` must be from this layout, or a clone of it, as kerning of
+    /// other text is meaningless here.
+    pub fn resume(&mut self, checkpoint: LayoutCheckpoint<'s>) {
+        self.chars = checkpoint.chars;
+        self.caret = checkpoint.caret;
+        self.last_glyph = checkpoint.last_glyph;
+        self.last_base = checkpoint.last_base;
+        self.glyph_index = checkpoint.glyph_index;
+        self.last_caps = checkpoint.last_caps;
+    }
+
+    /// Returns an iterator over each glyph & the caret position after it,
+    /// relative to the layout start, like `Font::advance_widths`. Measuring
+    /// while laying out saves line breakers a separate measuring pass.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let scale = Scale::uniform(24.0);
+    /// // the glyphs of "Hello World" fitting 60px
+    /// let line: Vec<_> = font
+    ///     .layout("Hello World", scale, point(0.0, 24.0))
+    ///     .with_carets()
+    ///     .take_while(|&(_, caret)| caret <= 60.0)
+    ///     .map(|(glyph, _)| glyph)
+    ///     .collect();
+    /// assert_eq!(line.len(), 4);
+    /// ```
+    pub fn with_carets(self) -> LayoutCarets<'a, 'font, 's> {
+        LayoutCarets { layout: self }
+    }
+
+    /// Scales the advance of word separators, e.g. spaces, by `multiplier` &
+    /// adds `delta` pixels, leaving other glyphs as they are. E.g. to justify a
+    /// line by spreading its slack over its `n` spaces with a `delta` of
+    /// `slack / n`, or to fit spaces to a terminal grid's cell width, see
+    /// `Font::space_advance`. Default `(1.0, 0.0)`, the font's advance.
+    ///
+    /// The word separators are those of CSS, U+0020 space, U+00A0 no-break
+    /// space, U+1361 Ethiopic wordspace, U+10100, U+10101 Aegean word
+    /// separators, U+1039F Ugaritic word divider & U+1091F Phoenician word
+    /// separator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let scale = Scale::uniform(24.0);
+    /// let width = |layout: LayoutIter| layout.with_carets().last().unwrap().1;
+    /// let natural = width(font.layout("to be or not", scale, point(0.0, 0.0)));
+    /// // justify to 200px
+    /// let delta = (200.0 - natural) / 3.0;
+    /// let justified = font.layout("to be or not", scale, point(0.0, 0.0)).word_spacing(1.0, delta);
+    /// assert!((width(justified) - 200.0).abs() < 1e-3);
+    /// ```
+    pub fn word_spacing(mut self, multiplier: f32, delta: f32) -> Self {
+        self.word_spacing = (multiplier, delta);
+        self
+    }
+}
+
+/// The layout state of a `LayoutIter` between glyphs, see
+/// `LayoutIter::checkpoint`.
+#[derive(Clone, Debug)]
+pub struct LayoutCheckpoint<'s> {
+    chars: core::str::Chars<'s>,
+    caret: f32,
+    last_glyph: Option<GlyphId>,
+    last_base: Option<(GlyphId, f32)>,
+    glyph_index: usize,
+    last_caps: bool,
+}
+
+impl<'s> LayoutCheckpoint<'s> {
+    /// The caret position relative to the layout start, after the last glyph
+    /// & before the kerning to the next.
+    #[inline]
+    pub fn caret(&self) -> f32 {
+        self.caret
+    }
+
+    /// The text not yet laid out.
+    #[inline]
+    pub fn remaining(&self) -> &'s str {
+        self.chars.as_str()
+    }
+}
+
+/// Iterator over the caret position after each char of a string, see
+/// `Font::advance_widths`.
+#[derive(Clone)]
+pub struct AdvanceWidths<'a, 'font, 's> {
+    layout: LayoutIter<'a, 'font, 's>,
+}
+
+impl Iterator for AdvanceWidths<'_, '_, '_> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let layout = &mut self.layout;
+        layout.next_scaled().map(|(_, _, advance)| {
+            layout.caret += advance;
+            layout.caret
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.layout.chars.size_hint()
+    }
+}
+
+/// Iterator over the glyphs of a layout & the caret position after each, see
+/// `LayoutIter::with_carets`.
+#[derive(Clone)]
+pub struct LayoutCarets<'a, 'font, 's> {
+    layout: LayoutIter<'a, 'font, 's>,
+}
+
+impl<'s> LayoutCarets<'_, '_, 's> {
+    /// See `LayoutIter::checkpoint`.
+    #[inline]
+    pub fn checkpoint(&self) -> LayoutCheckpoint<'s> {
+        self.layout.checkpoint()
+    }
+
+    /// See `LayoutIter::resume`.
+    #[inline]
+    pub fn resume(&mut self, checkpoint: LayoutCheckpoint<'s>) {
+        self.layout.resume(checkpoint)
+    }
+}
+
+impl<'font> Iterator for LayoutCarets<'_, 'font, '_> {
+    type Item = (PositionedGlyph<'font>, f32);
+
+    #[inline]
+    fn next(&mut self) -> Option<(PositionedGlyph<'font>, f32)> {
+        let glyph = self.layout.next()?;
+        Some((glyph, self.layout.caret))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.layout.chars.size_hint()
+    }
+}
+
 pub(crate) trait NearZero {
     /// Returns if this number is kinda pretty much zero.
+    #[allow(clippy::wrong_self_convention)]
     fn is_near_zero(self) -> bool;
 }
 impl NearZero for f32 {
     #[inline]
     fn is_near_zero(self) -> bool {
-        self.abs() <= core::f32::EPSILON
+        self.abs() <= f32::EPSILON
     }
 }
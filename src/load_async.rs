@@ -0,0 +1,48 @@
+//! Async font loading, see `Font::try_from_reader_async`.
+use crate::Font;
+use std::io;
+use std::path::Path;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+pub(crate) fn invalid_font_data() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "invalid font data")
+}
+
+impl Font<'static> {
+    /// Reads all of `reader` asynchronously, then creates a Font from the data
+    /// on tokio's blocking thread pool.
+    ///
+    /// Returns `io::ErrorKind::InvalidData` for invalid font data.
+    pub async fn try_from_reader_async<R: AsyncRead + Unpin>(mut reader: R) -> io::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).await?;
+        tokio::task::spawn_blocking(move || Font::try_from_vec(data).ok_or_else(invalid_font_data))
+            .await
+            .map_err(io::Error::other)?
+    }
+
+    /// Reads the font file at `path`, then creates a Font from the data, both
+    /// on tokio's blocking thread pool so neither holds up the calling task,
+    /// e.g. for multi-megabyte CJK fonts.
+    ///
+    /// Returns `io::ErrorKind::InvalidData` for invalid font data.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rusttype::Font;
+    /// # async fn example() -> std::io::Result<()> {
+    /// let font = Font::try_from_path_async("fonts/NotoSansCJK.ttc").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn try_from_path_async<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref().to_owned();
+        tokio::task::spawn_blocking(move || {
+            let data = std::fs::read(path)?;
+            Font::try_from_vec(data).ok_or_else(invalid_font_data)
+        })
+        .await
+        .map_err(io::Error::other)?
+    }
+}
@@ -0,0 +1,270 @@
+//! Coverage mask image processing, e.g. for glow & shadow effects.
+#[cfg(all(feature = "libm-math", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::PositionedGlyph;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// A rectangular image of coverage values in the range `0.0..=1.0`, e.g. a
+/// rasterized glyph.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CoverageMask {
+    pub width: u32,
+    pub height: u32,
+    /// Coverage values, row major.
+    pub coverage: Vec<f32>,
+}
+
+impl CoverageMask {
+    /// Creates a fully transparent mask.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            coverage: vec![0.0; width as usize * height as usize],
+        }
+    }
+
+    /// Rasterizes `glyph` into a mask the size of its pixel bounding box.
+    /// Returns an empty mask for glyphs without one.
+    pub fn from_glyph(glyph: &PositionedGlyph<'_>) -> Self {
+        let bb = match glyph.pixel_bounding_box() {
+            Some(bb) => bb,
+            None => return Self::default(),
+        };
+        let mut mask = Self::new(bb.width() as u32, bb.height() as u32);
+        let width = mask.width;
+        glyph.draw(|x, y, v| mask.coverage[(x + y * width) as usize] = v);
+        mask
+    }
+
+    /// Returns the coverage at `(x, y)`, `0.0` outside the mask.
+    #[inline]
+    pub fn get(&self, x: i32, y: i32) -> f32 {
+        if x < 0 || y < 0 || x as u32 >= self.width || y as u32 >= self.height {
+            return 0.0;
+        }
+        self.coverage[x as usize + y as usize * self.width as usize]
+    }
+
+    /// Calls `o` with the position & coverage of every pixel.
+    pub fn for_each_pixel<O: FnMut(u32, u32, f32)>(&self, mut o: O) {
+        if self.width == 0 {
+            return;
+        }
+        let rows = self.coverage.chunks_exact(self.width as usize);
+        for (y, row) in rows.enumerate() {
+            for (x, &v) in row.iter().enumerate() {
+                o(x as u32, y as u32, v);
+            }
+        }
+    }
+
+    /// Returns the mask morphologically dilated by a disc of `radius` pixels,
+    /// i.e. every shape expanded outwards by `radius`. Fractional radii give
+    /// anti-aliased edges.
+    ///
+    /// The returned mask is larger by `radius.ceil()` on every side, so
+    /// pixel `(x, y)` of this mask is pixel `(x + pad, y + pad)` of the result.
+    pub fn dilate(&self, radius: f32) -> Self {
+        let radius = radius.max(0.0);
+        let pad = radius.ceil() as i32;
+        let mut out = Self::new(self.width + 2 * pad as u32, self.height + 2 * pad as u32);
+
+        // disc kernel of (dx, dy, weight) where weight anti-aliases the edge
+        let mut kernel = Vec::new();
+        for dy in -pad..=pad {
+            for dx in -pad..=pad {
+                let distance = ((dx * dx + dy * dy) as f32).sqrt();
+                let weight = (radius + 1.0 - distance).min(1.0);
+                if weight > 0.0 {
+                    kernel.push((dx, dy, weight));
+                }
+            }
+        }
+
+        let out_width = out.width as i32;
+        for y in 0..out.height as i32 {
+            for x in 0..out_width {
+                // (x, y) in output is (x - pad, y - pad) in self
+                let (sx, sy) = (x - pad, y - pad);
+                let v = kernel
+                    .iter()
+                    .map(|&(dx, dy, weight)| self.get(sx + dx, sy + dy) * weight)
+                    .fold(0.0_f32, f32::max);
+                out.coverage[(x + y * out_width) as usize] = v;
+            }
+        }
+        out
+    }
+
+    /// Returns the mask scaled to `width` x `height`, e.g. for thumbnails or
+    /// previews of rasterized text.
+    ///
+    /// Uses a tent filter, bilinear when enlarging & widened to cover all
+    /// source pixels when shrinking, so downscaled text doesn't alias.
+    pub fn resize(&self, width: u32, height: u32) -> Self {
+        if self.width == 0 || self.height == 0 {
+            return Self::new(width, height);
+        }
+        let columns = resize_weights(self.width, width);
+        let rows = resize_weights(self.height, height);
+        let src_width = self.width as usize;
+
+        // horizontal pass
+        let mut horizontal = vec![0.0; width as usize * self.height as usize];
+        for (src_row, out_row) in self
+            .coverage
+            .chunks_exact(src_width)
+            .zip(horizontal.chunks_exact_mut(width as usize))
+        {
+            for (out, (start, weights)) in out_row.iter_mut().zip(&columns) {
+                *out = weights
+                    .iter()
+                    .zip(&src_row[*start..])
+                    .map(|(w, v)| w * v)
+                    .sum();
+            }
+        }
+
+        // vertical pass
+        let mut out = Self::new(width, height);
+        let width = width as usize;
+        for (out_row, (start, weights)) in out.coverage.chunks_exact_mut(width).zip(&rows) {
+            for (k, w) in weights.iter().enumerate() {
+                let src_row = &horizontal[(start + k) * width..(start + k + 1) * width];
+                for (out, v) in out_row.iter_mut().zip(src_row) {
+                    *out += w * v;
+                }
+            }
+        }
+        out
+    }
+
+    /// Returns the mask scaled, preserving its aspect ratio, to the largest
+    /// size that fits within `max_width` x `max_height`. See `resize`.
+    pub fn resize_to_fit(&self, max_width: u32, max_height: u32) -> Self {
+        if self.width == 0 || self.height == 0 {
+            return self.clone();
+        }
+        let scale = f32::min(
+            max_width as f32 / self.width as f32,
+            max_height as f32 / self.height as f32,
+        );
+        let width = (self.width as f32 * scale).round().max(1.0) as u32;
+        let height = (self.height as f32 * scale).round().max(1.0) as u32;
+        self.resize(width.min(max_width), height.min(max_height))
+    }
+
+    /// Returns the padding `gaussian_blur` adds to every side of the mask
+    /// for `sigma`, `ceil(3 * sigma)`.
+    #[cfg(feature = "blur")]
+    pub fn blur_padding(sigma: f32) -> u32 {
+        (3.0 * sigma.max(0.0)).ceil() as u32
+    }
+
+    /// Returns the mask blurred by a gaussian with standard deviation `sigma`
+    /// pixels, e.g. for soft shadows.
+    ///
+    /// The returned mask is larger by `blur_padding(sigma)` on every side, so
+    /// no coverage is clipped. Pixel `(x, y)` of this mask is pixel
+    /// `(x + pad, y + pad)` of the result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let glyph = font.glyph('a').scaled(Scale::uniform(24.0)).positioned(point(0.0, 0.0));
+    /// let shadow = CoverageMask::from_glyph(&glyph).gaussian_blur(2.0);
+    ///
+    /// let pad = CoverageMask::blur_padding(2.0) as i32;
+    /// let bb = glyph.pixel_bounding_box().unwrap();
+    /// let shadow_offset = point(bb.min.x - pad, bb.min.y - pad);
+    /// # let _ = shadow_offset;
+    /// ```
+    #[cfg(feature = "blur")]
+    pub fn gaussian_blur(&self, sigma: f32) -> Self {
+        let pad = Self::blur_padding(sigma) as usize;
+        if pad == 0 {
+            return self.clone();
+        }
+
+        let kernel: Vec<f32> = {
+            let two_sigma_sq = 2.0 * sigma * sigma;
+            let weights: Vec<f32> = (0..=2 * pad)
+                .map(|k| {
+                    let d = k as f32 - pad as f32;
+                    (-d * d / two_sigma_sq).exp()
+                })
+                .collect();
+            let sum: f32 = weights.iter().sum();
+            weights.into_iter().map(|w| w / sum).collect()
+        };
+
+        let (width, height) = (self.width as usize, self.height as usize);
+        let out_width = width + 2 * pad;
+        let out_height = height + 2 * pad;
+
+        // horizontal pass, scattering each source pixel over its neighbours
+        let mut horizontal = vec![0.0; out_width * height];
+        for y in 0..height {
+            let row = &mut horizontal[y * out_width..(y + 1) * out_width];
+            for (x, &v) in self.coverage[y * width..(y + 1) * width].iter().enumerate() {
+                if v != 0.0 {
+                    for (out, w) in row[x..x + kernel.len()].iter_mut().zip(&kernel) {
+                        *out += v * w;
+                    }
+                }
+            }
+        }
+
+        // vertical pass
+        let mut coverage = vec![0.0; out_width * out_height];
+        for y in 0..height {
+            let row = &horizontal[y * out_width..(y + 1) * out_width];
+            for (k, w) in kernel.iter().enumerate() {
+                let out_row = &mut coverage[(y + k) * out_width..(y + k + 1) * out_width];
+                for (out, v) in out_row.iter_mut().zip(row) {
+                    *out += v * w;
+                }
+            }
+        }
+        for v in &mut coverage {
+            *v = v.min(1.0);
+        }
+
+        Self {
+            width: out_width as u32,
+            height: out_height as u32,
+            coverage,
+        }
+    }
+}
+
+/// Tent filter weights for resizing `src` pixels to `dst`, per destination
+/// pixel the first contributing source pixel & normalised weights.
+fn resize_weights(src: u32, dst: u32) -> Vec<(usize, Vec<f32>)> {
+    let ratio = src as f32 / dst as f32;
+    let radius = ratio.max(1.0);
+    (0..dst)
+        .map(|d| {
+            let center = (d as f32 + 0.5) * ratio - 0.5;
+            let first = (center - radius).ceil().max(0.0) as usize;
+            let last = ((center + radius).floor() as usize).min(src as usize - 1);
+            let mut weights: Vec<f32> = (first..=last)
+                .map(|s| (1.0 - (s as f32 - center).abs() / radius).max(0.0))
+                .collect();
+            let sum: f32 = weights.iter().sum();
+            if sum > 0.0 {
+                weights.iter_mut().for_each(|w| *w /= sum);
+            } else {
+                // only possible on edges, use the nearest pixel
+                weights.iter_mut().for_each(|w| *w = 0.0);
+                weights[0] = 1.0;
+            }
+            (first, weights)
+        })
+        .collect()
+}
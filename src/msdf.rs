@@ -0,0 +1,353 @@
+//! Multi-channel signed distance fields, see `PositionedGlyph::draw_msdf`.
+#[cfg(all(feature = "libm-math", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{
+    point, vector, CoverageMask, Cubic, Curve, Line, OutlineBuilder, Point, PositionedGlyph, Rect,
+    Vector,
+};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// Contour joints turning by more than this, the sine of ~20°, are corners
+/// kept sharp by using different channels either side.
+const CORNER_SIN: f32 = 0.34;
+
+/// Channel bits of an edge color.
+const RED: u8 = 1;
+const GREEN: u8 = 2;
+const BLUE: u8 = 4;
+const WHITE: u8 = RED | GREEN | BLUE;
+/// Colors of consecutive edges between corners, any 2 share 1 channel.
+const COLORS: [u8; 3] = [GREEN | BLUE, RED | BLUE, RED | GREEN];
+
+#[derive(Copy, Clone, Debug)]
+enum Segment {
+    Line(Line),
+    Curve(Curve),
+    Cubic(Cubic),
+}
+
+impl Segment {
+    fn start(&self) -> Point<f32> {
+        match self {
+            Segment::Line(l) => l.p[0],
+            Segment::Curve(c) => c.p[0],
+            Segment::Cubic(c) => c.p[0],
+        }
+    }
+
+    fn nearest_point(&self, p: Point<f32>) -> (f32, Point<f32>) {
+        match self {
+            Segment::Line(l) => l.nearest_point(p),
+            Segment::Curve(c) => c.nearest_point(p),
+            Segment::Cubic(c) => c.nearest_point(p),
+        }
+    }
+
+    /// The unit direction at `t`, falling back to the chord where the
+    /// derivative vanishes, e.g. at a control point on an end.
+    fn direction(&self, t: f32) -> Vector<f32> {
+        let d = match self {
+            Segment::Line(l) => l.p[1] - l.p[0],
+            Segment::Curve(c) => {
+                let [p0, p1, p2] = c.p;
+                (p1 - p0) * (2.0 * (1.0 - t)) + (p2 - p1) * (2.0 * t)
+            }
+            Segment::Cubic(c) => {
+                let [p0, p1, p2, p3] = c.p;
+                (p1 - p0) * (3.0 * (1.0 - t) * (1.0 - t))
+                    + (p2 - p1) * (6.0 * (1.0 - t) * t)
+                    + (p3 - p2) * (3.0 * t * t)
+            }
+        };
+        let d = if length(d) > 1e-6 { d } else { self.chord() };
+        d / length(d).max(1e-6)
+    }
+
+    fn chord(&self) -> Vector<f32> {
+        match self {
+            Segment::Line(l) => l.p[1] - l.p[0],
+            Segment::Curve(c) => c.p[2] - c.p[0],
+            Segment::Cubic(c) => c.p[3] - c.p[0],
+        }
+    }
+
+    /// Splits into thirds, for coloring contours of fewer than 3 edges.
+    fn thirds(&self) -> [Segment; 3] {
+        match self {
+            Segment::Line(l) => {
+                let (a, b) = (l.at(1.0 / 3.0), l.at(2.0 / 3.0));
+                [
+                    Segment::Line(Line { p: [l.p[0], a] }),
+                    Segment::Line(Line { p: [a, b] }),
+                    Segment::Line(Line { p: [b, l.p[1]] }),
+                ]
+            }
+            Segment::Curve(c) => {
+                let (first, rest) = c.split(1.0 / 3.0);
+                let (second, third) = rest.split(0.5);
+                [
+                    Segment::Curve(first),
+                    Segment::Curve(second),
+                    Segment::Curve(third),
+                ]
+            }
+            Segment::Cubic(c) => {
+                let (first, rest) = c.split(1.0 / 3.0);
+                let (second, third) = rest.split(0.5);
+                [
+                    Segment::Cubic(first),
+                    Segment::Cubic(second),
+                    Segment::Cubic(third),
+                ]
+            }
+        }
+    }
+
+    /// The pseudo-distance to `p`, signed positive on the right of the
+    /// direction, & the orthogonality, to pick between equally near edges.
+    ///
+    /// Beyond the ends the distance is to the tangent line, so nearby edges
+    /// meeting at a corner extend to a sharp corner between channels.
+    fn signed_distance(&self, p: Point<f32>) -> (f32, f32, f32) {
+        let (t, q) = self.nearest_point(p);
+        let d = self.direction(t);
+        let v = p - q;
+        let distance = length(v);
+        let cross = d.x * v.y - d.y * v.x;
+        let orthogonality = if distance > 0.0 {
+            (cross / distance).abs()
+        } else {
+            1.0
+        };
+        let mut pseudo = distance;
+        if t <= 0.0 || t >= 1.0 {
+            let along = d.x * v.x + d.y * v.y;
+            let beyond = if t <= 0.0 { along < 0.0 } else { along > 0.0 };
+            if beyond {
+                pseudo = cross.abs();
+            }
+        }
+        (distance, pseudo.copysign(cross), orthogonality)
+    }
+}
+
+/// Collects contours of segments.
+#[derive(Default)]
+struct ContourBuilder {
+    contours: Vec<Vec<Segment>>,
+    last: Point<f32>,
+}
+
+impl ContourBuilder {
+    fn push(&mut self, segment: Segment, end: Point<f32>) {
+        if let Some(contour) = self.contours.last_mut() {
+            contour.push(segment);
+        }
+        self.last = end;
+    }
+}
+
+impl OutlineBuilder for ContourBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.contours.push(Vec::new());
+        self.last = point(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let end = point(x, y);
+        if end != self.last {
+            self.push(
+                Segment::Line(Line {
+                    p: [self.last, end],
+                }),
+                end,
+            );
+        }
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let end = point(x, y);
+        self.push(
+            Segment::Curve(Curve {
+                p: [self.last, point(x1, y1), end],
+            }),
+            end,
+        );
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let end = point(x, y);
+        self.push(
+            Segment::Cubic(Cubic {
+                p: [self.last, point(x1, y1), point(x2, y2), end],
+            }),
+            end,
+        );
+    }
+
+    fn close(&mut self) {
+        let start = self
+            .contours
+            .last()
+            .and_then(|c| c.first())
+            .map(|s| s.start());
+        if let Some(start) = start {
+            self.line_to(start.x, start.y);
+        }
+    }
+}
+
+/// Colors the edges of `contour` so edges meeting at a corner differ in 2
+/// channels.
+fn color_edges(contour: &mut Vec<Segment>) -> Vec<u8> {
+    let corners: Vec<usize> = (0..contour.len())
+        .filter(|&i| {
+            let previous = contour[(i + contour.len() - 1) % contour.len()];
+            let (a, b) = (previous.direction(1.0), contour[i].direction(0.0));
+            let dot = a.x * b.x + a.y * b.y;
+            dot <= 0.0 || (a.x * b.y - a.y * b.x).abs() > CORNER_SIN
+        })
+        .collect();
+
+    match corners.len() {
+        0 => vec![WHITE; contour.len()],
+        // a teardrop, colored in thirds from the corner
+        1 => {
+            let mut corner = corners[0];
+            if contour.len() < 3 {
+                *contour = contour.iter().flat_map(|s| s.thirds().to_vec()).collect();
+                corner *= 3;
+            }
+            let n = contour.len();
+            let colors = [COLORS[0], WHITE, COLORS[1]];
+            (0..n)
+                .map(|i| colors[(3 * ((i + n - corner) % n) / n).min(2)])
+                .collect()
+        }
+        _ => {
+            let n = contour.len();
+            let runs = corners.len();
+            let mut edge_colors = vec![WHITE; n];
+            let mut run = 0;
+            for i in 0..n {
+                let edge = (corners[0] + i) % n;
+                if i > 0 && corners.contains(&edge) {
+                    run += 1;
+                }
+                // the last run also meets the first, so must differ from it
+                edge_colors[edge] = match run % 3 {
+                    0 if run > 0 && run == runs - 1 => COLORS[1],
+                    r => COLORS[r],
+                };
+            }
+            edge_colors
+        }
+    }
+}
+
+impl PositionedGlyph<'_> {
+    /// Renders a multi-channel signed distance field of this glyph, with
+    /// distances up to `range` pixels either side of the outline. For each
+    /// pixel in `msdf_pixel_bounding_box` `o` is called with its position
+    /// relative to the box min & red, green & blue values in `0.0..=1.0`.
+    ///
+    /// Shaders reconstruct the glyph at any magnification from the median of
+    /// the 3 channels, `0.5` on the outline & above inside. Unlike a single
+    /// channel distance field, edges meeting at a corner use different
+    /// channels, so corners stay sharp rather than rounding off.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let glyph = font.glyph('A').scaled(Scale::uniform(32.0)).positioned(point(0.0, 0.0));
+    /// let bb = glyph.msdf_pixel_bounding_box(4.0).unwrap();
+    /// let mut rgb = vec![[0_u8; 3]; (bb.width() * bb.height()) as usize];
+    /// glyph.draw_msdf(4.0, |x, y, v| {
+    ///     rgb[(x + y * bb.width() as u32) as usize] = v.map(|c| (c * 255.0).round() as u8);
+    /// });
+    /// ```
+    pub fn draw_msdf<O: FnMut(u32, u32, [f32; 3])>(&self, range: f32, mut o: O) {
+        let bb = match self.msdf_pixel_bounding_box(range) {
+            Some(bb) => bb,
+            None => return,
+        };
+        let range = range.max(f32::EPSILON);
+        let pad = self.bb.map_or(0, |glyph_bb| glyph_bb.min.x - bb.min.x);
+
+        let mut builder = ContourBuilder::default();
+        self.build_outline(&mut builder);
+        let mut contours = builder.contours;
+        contours.retain(|c| !c.is_empty());
+        let colors: Vec<Vec<u8>> = contours.iter_mut().map(color_edges).collect();
+
+        // y down, so a positive area is clockwise on screen, filled on the
+        // right of the edges
+        let area: f32 = contours
+            .iter()
+            .flatten()
+            .map(|s| {
+                let (a, b) = (s.start(), s.start() + s.chord());
+                a.x * b.y - b.x * a.y
+            })
+            .sum();
+        let orientation = if area < 0.0 { -1.0 } else { 1.0 };
+
+        // pixel centers inside the glyph, to correct channel sign errors
+        let mask = CoverageMask::from_glyph(self);
+
+        let to_value = |d: f32| (0.5 + d * orientation / (2.0 * range)).clamp(0.0, 1.0);
+        for y in 0..bb.height() {
+            for x in 0..bb.width() {
+                let p = point(x as f32 + 0.5, y as f32 + 0.5) + vector(-pad as f32, -pad as f32);
+                // per channel (distance, signed pseudo-distance, orthogonality)
+                let mut nearest = [(f32::INFINITY, 0.0, 0.0); 3];
+                let mut true_nearest = (f32::INFINITY, 0.0, 0.0);
+                let edges = contours
+                    .iter()
+                    .zip(&colors)
+                    .flat_map(|(c, k)| c.iter().zip(k));
+                for (segment, &color) in edges {
+                    let d = segment.signed_distance(p);
+                    let closer =
+                        |n: &(f32, f32, f32)| d.0 < n.0 - 1e-4 || (d.0 < n.0 + 1e-4 && d.2 > n.2);
+                    if closer(&true_nearest) {
+                        true_nearest = d;
+                    }
+                    for (channel, n) in nearest.iter_mut().enumerate() {
+                        if color & (1 << channel) != 0 && closer(n) {
+                            *n = d;
+                        }
+                    }
+                }
+
+                let mut rgb = nearest.map(|n| to_value(n.1));
+                let inside = mask.get(x - pad, y - pad) >= 0.5;
+                if (median(rgb) > 0.5) != inside {
+                    let d = true_nearest.0 * if inside { 0.5 } else { -0.5 };
+                    rgb = [(0.5 + d / range).clamp(0.0, 1.0); 3];
+                }
+                o(x as u32, y as u32, rgb);
+            }
+        }
+    }
+
+    /// The pixel bounding box of `draw_msdf` with the same `range`, i.e.
+    /// `pixel_bounding_box` expanded by `range.ceil()` on every side.
+    pub fn msdf_pixel_bounding_box(&self, range: f32) -> Option<Rect<i32>> {
+        self.dilated_pixel_bounding_box(range)
+    }
+}
+
+#[inline]
+fn length(v: Vector<f32>) -> f32 {
+    (v.x * v.x + v.y * v.y).sqrt()
+}
+
+#[inline]
+fn median([r, g, b]: [f32; 3]) -> f32 {
+    r.min(g).max(r.max(g).min(b))
+}
@@ -0,0 +1,110 @@
+//! `name` table strings, see `Font::family_name`.
+use crate::Font;
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+use owned_ttf_parser::name_id;
+use owned_ttf_parser::PlatformId;
+
+/// The chars of Mac Roman bytes `0x80` to `0xFF`, lower bytes are ASCII.
+const MAC_ROMAN: [char; 128] = [
+    'Ä', 'Å', 'Ç', 'É', 'Ñ', 'Ö', 'Ü', 'á', 'à', 'â', 'ä', 'ã', 'å', 'ç', 'é', 'è', //
+    'ê', 'ë', 'í', 'ì', 'î', 'ï', 'ñ', 'ó', 'ò', 'ô', 'ö', 'õ', 'ú', 'ù', 'û', 'ü', //
+    '†', '°', '¢', '£', '§', '•', '¶', 'ß', '®', '©', '™', '´', '¨', '≠', 'Æ', 'Ø', //
+    '∞', '±', '≤', '≥', '¥', 'µ', '∂', '∑', '∏', 'π', '∫', 'ª', 'º', 'Ω', 'æ', 'ø', //
+    '¿', '¡', '¬', '√', 'ƒ', '≈', '∆', '«', '»', '…', '\u{a0}', 'À', 'Ã', 'Õ', 'Œ', 'œ', //
+    '–', '—', '“', '”', '‘', '’', '÷', '◊', 'ÿ', 'Ÿ', '⁄', '€', '‹', '›', 'ﬁ', 'ﬂ', //
+    '‡', '·', '‚', '„', '‰', 'Â', 'Ê', 'Á', 'Ë', 'È', 'Í', 'Î', 'Ï', 'Ì', 'Ó', 'Ô', //
+    '\u{f8ff}', 'Ò', 'Ú', 'Û', 'Ù', 'ı', 'ˆ', '˜', '¯', '˘', '˙', '˚', '¸', '˝', '˛', 'ˇ', //
+];
+
+/// Windows English (United States) language id.
+const WINDOWS_ENGLISH: u16 = 0x0409;
+/// Macintosh Roman encoding & English language id.
+const MAC_ROMAN_ENCODING: u16 = 0;
+const MAC_ENGLISH: u16 = 0;
+
+/// Decodes `name`, `None` for unsupported encodings.
+fn decode(name: &owned_ttf_parser::name::Name<'_>) -> Option<String> {
+    if name.is_unicode() {
+        let units = name
+            .name
+            .chunks_exact(2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]));
+        core::char::decode_utf16(units)
+            .collect::<Result<String, _>>()
+            .ok()
+    } else if name.platform_id == PlatformId::Macintosh && name.encoding_id == MAC_ROMAN_ENCODING {
+        let chars = name.name.iter().map(|&b| match b {
+            0..=0x7F => char::from(b),
+            _ => MAC_ROMAN[usize::from(b - 0x80)],
+        });
+        Some(chars.collect())
+    } else {
+        None
+    }
+}
+
+/// Returns if `name` is in English.
+fn is_english(name: &owned_ttf_parser::name::Name<'_>) -> bool {
+    match name.platform_id {
+        PlatformId::Windows => name.language_id == WINDOWS_ENGLISH,
+        PlatformId::Macintosh => name.language_id == MAC_ENGLISH,
+        _ => false,
+    }
+}
+
+impl Font<'_> {
+    /// Returns the `name` table string of `name_id`, e.g. `4` for the full
+    /// font name, preferring English. Names in Unicode, Windows & Mac Roman
+    /// encodings are decoded, others skipped. `None` if the font has no
+    /// decodable name of `name_id`.
+    ///
+    /// See the [OpenType spec](https://learn.microsoft.com/en-us/typography/opentype/spec/name#name-ids)
+    /// for the name ids.
+    pub fn name(&self, name_id: u16) -> Option<String> {
+        let names = self.inner().names().into_iter();
+        let mut fallback = None;
+        for name in names.filter(|n| n.name_id == name_id) {
+            if let Some(s) = decode(&name) {
+                if is_english(&name) {
+                    return Some(s);
+                }
+                fallback.get_or_insert(s);
+            }
+        }
+        fallback
+    }
+
+    /// The font family name, e.g. `"Open Sans"`, to group fonts in a font
+    /// picker. The typographic family name if present, otherwise the legacy
+    /// family name, preferring English.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// let font = Font::try_from_bytes(font_data).unwrap();
+    /// assert_eq!(font.family_name().as_deref(), Some("DejaVu Sans Mono"));
+    /// assert_eq!(font.style_name().as_deref(), Some("Book"));
+    /// assert_eq!(font.postscript_name().as_deref(), Some("DejaVuSansMono"));
+    /// ```
+    pub fn family_name(&self) -> Option<String> {
+        self.name(name_id::TYPOGRAPHIC_FAMILY)
+            .or_else(|| self.name(name_id::FAMILY))
+    }
+
+    /// The style name within the family, e.g. `"Bold Italic"`. The
+    /// typographic subfamily name if present, otherwise the legacy subfamily
+    /// name, preferring English.
+    pub fn style_name(&self) -> Option<String> {
+        self.name(name_id::TYPOGRAPHIC_SUBFAMILY)
+            .or_else(|| self.name(name_id::SUBFAMILY))
+    }
+
+    /// The PostScript name, e.g. `"OpenSans-Italic"`, uniquely identifying
+    /// the font, e.g. to reference it in PDF or SVG files.
+    pub fn postscript_name(&self) -> Option<String> {
+        self.name(name_id::POST_SCRIPT_NAME)
+    }
+}
@@ -4,7 +4,11 @@ pub(crate) trait FloatExt {
     fn fract(self) -> Self;
     fn trunc(self) -> Self;
     fn round(self) -> Self;
-    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+    #[cfg(feature = "blur")]
+    fn exp(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
 }
 
 impl FloatExt for f32 {
@@ -29,7 +33,60 @@ impl FloatExt for f32 {
         libm::roundf(self)
     }
     #[inline]
-    fn abs(self) -> Self {
-        libm::fabsf(self)
+    fn sqrt(self) -> Self {
+        libm::sqrtf(self)
+    }
+    #[cfg(feature = "blur")]
+    #[inline]
+    fn exp(self) -> Self {
+        libm::expf(self)
+    }
+    #[inline]
+    fn sin(self) -> Self {
+        libm::sinf(self)
+    }
+    #[inline]
+    fn cos(self) -> Self {
+        libm::cosf(self)
+    }
+}
+
+impl FloatExt for f64 {
+    #[inline]
+    fn floor(self) -> Self {
+        libm::floor(self)
+    }
+    #[inline]
+    fn ceil(self) -> Self {
+        libm::ceil(self)
+    }
+    #[inline]
+    fn fract(self) -> Self {
+        self - self.trunc()
+    }
+    #[inline]
+    fn trunc(self) -> Self {
+        libm::trunc(self)
+    }
+    #[inline]
+    fn round(self) -> Self {
+        libm::round(self)
+    }
+    #[inline]
+    fn sqrt(self) -> Self {
+        libm::sqrt(self)
+    }
+    #[cfg(feature = "blur")]
+    #[inline]
+    fn exp(self) -> Self {
+        libm::exp(self)
+    }
+    #[inline]
+    fn sin(self) -> Self {
+        libm::sin(self)
+    }
+    #[inline]
+    fn cos(self) -> Self {
+        libm::cos(self)
     }
 }
@@ -0,0 +1,32 @@
+#[cfg(feature = "has-atomics")]
+use alloc::boxed::Box;
+
+/// A value lazily computed on first access, shareable with the data it is
+/// derived from.
+///
+/// Concurrent first accesses may compute the value more than once, with only
+/// one result being kept; so initialisers should be pure.
+#[cfg(feature = "has-atomics")]
+pub(crate) struct OnceCache<T>(once_cell::race::OnceBox<T>);
+
+#[cfg(not(feature = "has-atomics"))]
+pub(crate) struct OnceCache<T>(core::cell::OnceCell<T>);
+
+impl<T> OnceCache<T> {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self(Default::default())
+    }
+
+    #[inline]
+    pub(crate) fn get_or_init<F: FnOnce() -> T>(&self, f: F) -> &T {
+        #[cfg(feature = "has-atomics")]
+        {
+            self.0.get_or_init(|| Box::new(f()))
+        }
+        #[cfg(not(feature = "has-atomics"))]
+        {
+            self.0.get_or_init(f)
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Kerning estimated from glyph ink, see `Kerning::Optical`.
+#[cfg(all(feature = "libm-math", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{Font, GlyphId};
+use owned_ttf_parser::OutlineBuilder;
+
+/// Horizontal bands, from descender to ascender, ink extents are measured in.
+const BANDS: usize = 24;
+/// Curve segments are flattened into this many lines.
+const CURVE_STEPS: u32 = 8;
+
+/// The horizontal extents of a glyph's ink per band, in font units.
+#[derive(Clone, Debug)]
+pub(crate) struct InkProfile {
+    /// Leftmost ink per band, `f32::INFINITY` if the band has no ink.
+    left: [f32; BANDS],
+    /// Rightmost ink per band, `f32::NEG_INFINITY` if the band has no ink.
+    right: [f32; BANDS],
+    advance: f32,
+}
+
+impl InkProfile {
+    pub(crate) fn new(font: &Font<'_>, id: GlyphId) -> Self {
+        let inner = font.inner();
+        let bottom = f32::from(inner.descender());
+        let height = (f32::from(inner.ascender()) - bottom).max(1.0);
+        let mut builder = ProfileBuilder {
+            profile: InkProfile {
+                left: [f32::INFINITY; BANDS],
+                right: [f32::NEG_INFINITY; BANDS],
+                advance: inner.glyph_hor_advance(id.into()).unwrap_or(0).into(),
+            },
+            bottom,
+            band_height: height / BANDS as f32,
+            last: (0.0, 0.0),
+        };
+        inner.outline_glyph(id.into(), &mut builder);
+        builder.profile
+    }
+
+    #[inline]
+    fn has_ink(&self, band: usize) -> bool {
+        self.left[band] <= self.right[band]
+    }
+
+    /// Returns the mean & minimum gap between the ink of `self` followed by
+    /// `next`, over the bands both have ink in. `None` if there are none.
+    fn spacing(&self, next: &InkProfile) -> Option<(f32, f32)> {
+        let (mut sum, mut min, mut count) = (0.0, f32::INFINITY, 0);
+        for band in (0..BANDS).filter(|&b| self.has_ink(b) && next.has_ink(b)) {
+            // extents of neighbouring bands too, so diagonals don't collide
+            let near = band.saturating_sub(1)..=(band + 1).min(BANDS - 1);
+            let right = self.right[near.clone()]
+                .iter()
+                .fold(f32::NEG_INFINITY, |a, &b| a.max(b));
+            let left = next.left[near].iter().fold(f32::INFINITY, |a, &b| a.min(b));
+            let gap = self.advance - right + left;
+            sum += gap;
+            min = min.min(gap);
+            count += 1;
+        }
+        if count == 0 {
+            return None;
+        }
+        Some((sum / count as f32, min))
+    }
+}
+
+struct ProfileBuilder {
+    profile: InkProfile,
+    bottom: f32,
+    band_height: f32,
+    last: (f32, f32),
+}
+
+impl ProfileBuilder {
+    fn add_point(&mut self, x: f32, y: f32) {
+        let band = ((y - self.bottom) / self.band_height).max(0.0) as usize;
+        let band = band.min(BANDS - 1);
+        self.profile.left[band] = self.profile.left[band].min(x);
+        self.profile.right[band] = self.profile.right[band].max(x);
+    }
+}
+
+impl OutlineBuilder for ProfileBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.add_point(x, y);
+        self.last = (x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let (x0, y0) = self.last;
+        // at least a point every half band, so lines mark every band they cross
+        let steps = ((y - y0).abs() / (self.band_height / 2.0)).ceil().max(1.0) as u32;
+        for step in 1..=steps {
+            let t = step as f32 / steps as f32;
+            self.add_point(x0 + (x - x0) * t, y0 + (y - y0) * t);
+        }
+        self.last = (x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (x0, y0) = self.last;
+        for step in 1..=CURVE_STEPS {
+            let t = step as f32 / CURVE_STEPS as f32;
+            let mt = 1.0 - t;
+            self.line_to(
+                mt * mt * x0 + 2.0 * mt * t * x1 + t * t * x,
+                mt * mt * y0 + 2.0 * mt * t * y1 + t * t * y,
+            );
+        }
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (x0, y0) = self.last;
+        for step in 1..=CURVE_STEPS {
+            let t = step as f32 / CURVE_STEPS as f32;
+            let mt = 1.0 - t;
+            let (a, b, c, d) = (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+            self.line_to(
+                a * x0 + b * x1 + c * x2 + d * x,
+                a * y0 + b * y1 + c * y2 + d * y,
+            );
+        }
+    }
+
+    fn close(&mut self) {}
+}
+
+/// Optical kerning state of a layout, remembering the profile of the last
+/// glyph so each glyph is measured once.
+#[derive(Clone, Debug)]
+pub(crate) struct OpticalKerning {
+    /// The mean spacing of `nn` & `oo`, that all pairs are adjusted towards.
+    reference: Option<f32>,
+    max_adjustment: f32,
+    last: Option<(GlyphId, InkProfile)>,
+}
+
+impl OpticalKerning {
+    pub(crate) fn new(font: &Font<'_>) -> Self {
+        let spacings: [Option<f32>; 2] = ['n', 'o'].map(|c| {
+            let id = font.glyph(c).id();
+            if id == GlyphId(0) {
+                return None;
+            }
+            let profile = InkProfile::new(font, id);
+            profile.spacing(&profile).map(|(mean, _)| mean)
+        });
+        let reference = match spacings {
+            [Some(n), Some(o)] => Some((n + o) / 2.0),
+            [n, o] => n.or(o),
+        };
+        Self {
+            reference,
+            max_adjustment: f32::from(font.units_per_em()) / 10.0,
+            last: None,
+        }
+    }
+
+    /// Returns the estimated kerning of `first` followed by `second` in font
+    /// units.
+    pub(crate) fn kerning(&mut self, font: &Font<'_>, first: GlyphId, second: GlyphId) -> f32 {
+        let reference = match self.reference {
+            Some(reference) => reference,
+            None => return 0.0,
+        };
+        let first_profile = match self.last.take() {
+            Some((id, profile)) if id == first => profile,
+            _ => InkProfile::new(font, first),
+        };
+        let second_profile = InkProfile::new(font, second);
+        let spacing = first_profile.spacing(&second_profile);
+        self.last = Some((second, second_profile));
+
+        match spacing {
+            Some((mean, min)) => {
+                // tighten or loosen towards the reference, but never closer
+                // than a quarter of it
+                let adjustment = (reference - mean).max(reference / 4.0 - min);
+                adjustment.clamp(-self.max_adjustment, self.max_adjustment)
+            }
+            None => 0.0,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Point, Vector};
+use crate::{point, Affine2, Point, Vector};
 use ab_glyph_rasterizer::{point as ab_point, Point as AbPoint, Rasterizer};
 use owned_ttf_parser::OutlineBuilder;
 
@@ -47,6 +47,56 @@ impl<T: OutlineBuilder + ?Sized> OutlineBuilder for OutlineScaler<'_, T> {
     }
 }
 
+/// Applies the linear part of an `Affine2` to an outline.
+pub(crate) struct OutlineTransformer<'b, T: ?Sized> {
+    inner: &'b mut T,
+    transform: Affine2,
+}
+
+impl<'b, T: ?Sized> OutlineTransformer<'b, T> {
+    pub(crate) fn new(inner: &'b mut T, transform: Affine2) -> Self {
+        Self {
+            inner,
+            transform: transform.linear(),
+        }
+    }
+
+    #[inline]
+    fn map(&self, x: f32, y: f32) -> (f32, f32) {
+        let p = self.transform.transform_point(point(x, y));
+        (p.x, p.y)
+    }
+}
+
+impl<T: OutlineBuilder + ?Sized> OutlineBuilder for OutlineTransformer<'_, T> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.map(x, y);
+        self.inner.move_to(x, y)
+    }
+
+    fn line_to(&mut self, x1: f32, y1: f32) {
+        let (x1, y1) = self.map(x1, y1);
+        self.inner.line_to(x1, y1)
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32) {
+        let (x1, y1) = self.map(x1, y1);
+        let (x2, y2) = self.map(x2, y2);
+        self.inner.quad_to(x1, y1, x2, y2)
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x3: f32, y3: f32) {
+        let (x1, y1) = self.map(x1, y1);
+        let (x2, y2) = self.map(x2, y2);
+        let (x3, y3) = self.map(x3, y3);
+        self.inner.curve_to(x1, y1, x2, y2, x3, y3)
+    }
+
+    fn close(&mut self) {
+        self.inner.close()
+    }
+}
+
 pub(crate) struct OutlineTranslator<'b, T: ?Sized> {
     inner: &'b mut T,
     translation: Point<f32>,
@@ -146,3 +196,105 @@ impl OutlineBuilder for OutlineRasterizer {
         }
     }
 }
+
+/// An extension point to inspect or modify the outline of a glyph right before
+/// it is rasterized, see `PositionedGlyph::draw_with_hook`.
+///
+/// Outline events are provided in pixel-space coordinates relative to the
+/// `min` of the glyph's `pixel_bounding_box()`, the same coordinate space as
+/// the pixels passed to the `draw` callback. Each event should be forwarded,
+/// possibly modified, to `sink` which feeds the rasterizer. By default events
+/// are forwarded unchanged so implementors only need to override the events
+/// they are interested in.
+///
+/// Note that the rasterizer only covers the glyph's pixel bounding box, so
+/// geometry added or moved outside of it is not drawn correctly.
+///
+/// # Example
+///
+/// ```
+/// use rusttype::{OutlineBuilder, OutlineHook};
+///
+/// /// Slightly shifts every outline point, for a "handwriting" jitter effect.
+/// struct Jitter(f32);
+///
+/// impl OutlineHook for Jitter {
+///     fn line_to(&mut self, sink: &mut dyn OutlineBuilder, x: f32, y: f32) {
+///         self.0 = -self.0;
+///         sink.line_to(x + self.0, y);
+///     }
+/// }
+/// ```
+pub trait OutlineHook {
+    /// Called before any outline events of `glyph` are emitted.
+    #[allow(unused_variables)]
+    fn begin(&mut self, glyph: &crate::PositionedGlyph<'_>, sink: &mut dyn OutlineBuilder) {}
+
+    fn move_to(&mut self, sink: &mut dyn OutlineBuilder, x: f32, y: f32) {
+        sink.move_to(x, y)
+    }
+
+    fn line_to(&mut self, sink: &mut dyn OutlineBuilder, x1: f32, y1: f32) {
+        sink.line_to(x1, y1)
+    }
+
+    fn quad_to(&mut self, sink: &mut dyn OutlineBuilder, x1: f32, y1: f32, x2: f32, y2: f32) {
+        sink.quad_to(x1, y1, x2, y2)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn curve_to(
+        &mut self,
+        sink: &mut dyn OutlineBuilder,
+        x1: f32,
+        y1: f32,
+        x2: f32,
+        y2: f32,
+        x3: f32,
+        y3: f32,
+    ) {
+        sink.curve_to(x1, y1, x2, y2, x3, y3)
+    }
+
+    fn close(&mut self, sink: &mut dyn OutlineBuilder) {
+        sink.close()
+    }
+
+    /// Called after all outline events of `glyph` have been emitted. Additional
+    /// contours, e.g. decorations, may be appended to `sink` here.
+    #[allow(unused_variables)]
+    fn end(&mut self, glyph: &crate::PositionedGlyph<'_>, sink: &mut dyn OutlineBuilder) {}
+}
+
+pub(crate) struct OutlineHooker<'b, H: ?Sized, T> {
+    hook: &'b mut H,
+    inner: &'b mut T,
+}
+
+impl<'b, H: ?Sized, T> OutlineHooker<'b, H, T> {
+    pub(crate) fn new(hook: &'b mut H, inner: &'b mut T) -> Self {
+        Self { hook, inner }
+    }
+}
+
+impl<H: OutlineHook + ?Sized, T: OutlineBuilder> OutlineBuilder for OutlineHooker<'_, H, T> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.hook.move_to(self.inner, x, y)
+    }
+
+    fn line_to(&mut self, x1: f32, y1: f32) {
+        self.hook.line_to(self.inner, x1, y1)
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32) {
+        self.hook.quad_to(self.inner, x1, y1, x2, y2)
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x3: f32, y3: f32) {
+        self.hook.curve_to(self.inner, x1, y1, x2, y2, x3, y3)
+    }
+
+    fn close(&mut self) {
+        self.hook.close(self.inner)
+    }
+}
@@ -0,0 +1,881 @@
+//! Word wrapping paragraph layout, see `Font::layout_paragraph`.
+use crate::{point, Font, GlyphId, Point, PositionedGlyph, Rect, Scale, ScaledGlyph, VMetrics};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::ops::Range;
+
+/// A paragraph of text measured for word wrapping at a width, returned from
+/// `Font::layout_paragraph`.
+///
+/// Lines break greedily between words, at whitespace, & at each `'\n'`.
+/// Words wider than the width overflow their own line. Whitespace at line
+/// breaks isn't part of any line, so e.g. a line's `advance` never includes
+/// trailing spaces.
+///
+/// # Example
+///
+/// ```
+/// # use rusttype::*;
+/// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+/// # let font = Font::try_from_bytes(font_data).unwrap();
+/// let scale = Scale::uniform(24.0);
+/// let paragraph = font.layout_paragraph("The quick brown fox", scale, 120.0);
+///
+/// let lines: Vec<_> = paragraph.lines().map(|line| paragraph.line_text(&line)).collect();
+/// assert_eq!(lines, ["The quick", "brown fox"]);
+/// // the widest word fits, so the text can be narrower
+/// let widest_word = paragraph.words().map(|w| w.advance).fold(0.0, f32::max);
+/// assert!(widest_word < 120.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ParagraphLayout<'a, 'font, 's> {
+    font: &'a Font<'font>,
+    text: &'s str,
+    scale: Scale,
+    max_width: f32,
+    break_policy: BreakPolicy,
+    justification: Justification,
+    hanging_punctuation: bool,
+    chars: Vec<CharMetrics>,
+}
+
+/// Where a `ParagraphLayout` breaks lines, see
+/// `ParagraphLayout::with_break_policy`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BreakPolicy {
+    /// Break only at whitespace, words wider than the width overflow.
+    #[default]
+    Whitespace,
+    /// Also break words too wide for a line of their own, e.g. URLs & paths
+    /// in log viewers & chat, after `'/'`, `'-'` & `'_'` & before the
+    /// capital of a camelCase boundary. Parts between these still overflow.
+    Tokens,
+}
+
+/// How `ParagraphLayout::positioned_lines` fills wrapped lines to the
+/// paragraph width, see `ParagraphLayout::with_justification`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Justification {
+    /// Lines are left as laid out, ragged at the end.
+    #[default]
+    None,
+    /// Stretch the spaces between words.
+    Spaces,
+    /// Elongate the joins between Arabic letters with tatweel, kashida,
+    /// glyphs, the traditional way of justifying Arabic, stretching spaces
+    /// by the remainder narrower than a tatweel. Lines without joins, & fonts
+    /// without a tatweel, stretch spaces.
+    ///
+    /// `ParagraphLayout` doesn't shape or reorder text, so this is only
+    /// meaningful for pre-shaped text in visual order, e.g. from a shaper
+    /// without justification support. Joins are found before the initial &
+    /// medial forms of Arabic Presentation Forms-B letters, unshaped Arabic
+    /// letters aren't joined.
+    Kashida,
+}
+
+/// The layout of a char, on a single line starting at `0.0`.
+#[derive(Clone, Debug)]
+struct CharMetrics {
+    /// Byte offset in the text.
+    index: usize,
+    c: char,
+    id: GlyphId,
+    breaking: bool,
+    newline: bool,
+    x: f32,
+    advance: f32,
+    bounds: Option<Rect<f32>>,
+}
+
+/// A word of a `ParagraphLayout`, a run of text between whitespace.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WordBox {
+    /// Byte range of the word in the text.
+    pub range: Range<usize>,
+    /// Advance from the start of the first glyph to the end of the last.
+    pub advance: f32,
+    /// Ink bounds of the word glyphs relative to the start of the word on the
+    /// baseline, `None` without ink.
+    pub bounds: Option<Rect<f32>>,
+}
+
+/// A line of a `ParagraphLayout`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineBox {
+    /// Byte range of the line in the text, without whitespace at breaks.
+    /// Empty for blank lines, e.g. between two `'\n'`.
+    pub range: Range<usize>,
+    /// Advance from the start of the first word to the end of the last.
+    pub advance: f32,
+    /// Ink bounds of the line glyphs relative to the start of the line on the
+    /// baseline, `None` without ink.
+    pub bounds: Option<Rect<f32>>,
+    /// Advance of the punctuation hanging into the margins at the start &
+    /// end of the line, see `ParagraphLayout::with_hanging_punctuation`.
+    pub hanging: (f32, f32),
+}
+
+impl<'font> Font<'font> {
+    /// Measures `text` at `scale` for wrapping into lines at most `max_width`
+    /// pixels wide, see `ParagraphLayout`.
+    pub fn layout_paragraph<'a, 's>(
+        &'a self,
+        text: &'s str,
+        scale: Scale,
+        max_width: f32,
+    ) -> ParagraphLayout<'a, 'font, 's> {
+        let chars = text
+            .char_indices()
+            .zip(self.layout(text, scale, point(0.0, 0.0)).with_carets())
+            .map(|((index, c), (glyph, caret))| CharMetrics {
+                index,
+                c,
+                id: glyph.id(),
+                breaking: is_breaking_space(c),
+                newline: c == '\n',
+                x: glyph.position().x,
+                advance: caret - glyph.position().x,
+                bounds: glyph.unpositioned().exact_bounding_box(),
+            })
+            .collect();
+        ParagraphLayout {
+            font: self,
+            text,
+            scale,
+            max_width,
+            break_policy: BreakPolicy::default(),
+            justification: Justification::default(),
+            hanging_punctuation: false,
+            chars,
+        }
+    }
+}
+
+impl<'a, 'font, 's> ParagraphLayout<'a, 'font, 's> {
+    #[inline]
+    pub fn font(&self) -> &'a Font<'font> {
+        self.font
+    }
+
+    #[inline]
+    pub fn text(&self) -> &'s str {
+        self.text
+    }
+
+    #[inline]
+    pub fn scale(&self) -> Scale {
+        self.scale
+    }
+
+    #[inline]
+    pub fn max_width(&self) -> f32 {
+        self.max_width
+    }
+
+    /// Returns this paragraph breaking lines with `policy`. Default
+    /// `BreakPolicy::Whitespace`.
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let paragraph = font
+    ///     .layout_paragraph("see https://example.com/a/long/path", Scale::uniform(24.0), 150.0)
+    ///     .with_break_policy(BreakPolicy::Tokens);
+    /// assert!(paragraph.lines().all(|line| line.advance <= 150.0));
+    /// ```
+    pub fn with_break_policy(mut self, policy: BreakPolicy) -> Self {
+        self.break_policy = policy;
+        self
+    }
+
+    #[inline]
+    pub fn break_policy(&self) -> BreakPolicy {
+        self.break_policy
+    }
+
+    /// Returns this paragraph with `positioned_lines` justified to the
+    /// `max_width` by `justification`, except lines ending the paragraph or
+    /// at a `'\n'`. Lines are laid out as without justification, only moving
+    /// glyphs. Default `Justification::None`.
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// // "كتب الطالب درسه" shaped, in visual order
+    /// let text = "\u{feea}\u{feb3}\u{fead}\u{fea9} \u{fe90}\u{fedf}\u{fe8e}\u{fec4}\u{fedf}\u{fe8d} \
+    ///     \u{fe90}\u{fe98}\u{fedb}";
+    /// let paragraph = font
+    ///     .layout_paragraph(text, Scale::uniform(24.0), 160.0)
+    ///     .with_justification(Justification::Kashida);
+    /// let first = paragraph.positioned_lines(point(0.0, 0.0)).next().unwrap();
+    /// assert!(!first.kashidas.is_empty());
+    /// ```
+    pub fn with_justification(mut self, justification: Justification) -> Self {
+        self.justification = justification;
+        self
+    }
+
+    #[inline]
+    pub fn justification(&self) -> Justification {
+        self.justification
+    }
+
+    /// Returns this paragraph with punctuation hanging into the margins,
+    /// opening quotes at line starts & closing quotes, commas, periods &
+    /// hyphens at line ends, so the text edges look straight. Hanging
+    /// punctuation doesn't count towards the `max_width` & `positioned_lines`
+    /// places it outside, see `LineBox::hanging`. Default `false`.
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let paragraph = font
+    ///     .layout_paragraph("\"Quick, brown fox.\"", Scale::uniform(24.0), 100.0)
+    ///     .with_hanging_punctuation(true);
+    /// let first = paragraph.positioned_lines(point(0.0, 0.0)).next().unwrap();
+    /// assert_eq!(paragraph.line_text(&first.line), "\"Quick,");
+    /// // the quote starts left of the line origin
+    /// assert!(first.glyphs[0].position().x < first.origin.x);
+    /// ```
+    pub fn with_hanging_punctuation(mut self, hanging_punctuation: bool) -> Self {
+        self.hanging_punctuation = hanging_punctuation;
+        self
+    }
+
+    #[inline]
+    pub fn hanging_punctuation(&self) -> bool {
+        self.hanging_punctuation
+    }
+
+    /// Returns an iterator over the words of the paragraph in order.
+    pub fn words(&self) -> ParagraphWords<'_> {
+        ParagraphWords {
+            chars: &self.chars,
+            next: 0,
+            text_len: self.text.len(),
+        }
+    }
+
+    /// Returns an iterator over the wrapped lines of the paragraph in order.
+    /// Empty text has no lines.
+    pub fn lines(&self) -> ParagraphLines<'_> {
+        ParagraphLines {
+            chars: &self.chars,
+            words: self.words(),
+            break_policy: self.break_policy,
+            pending: None,
+            blank_lines: 0,
+            started: false,
+            max_width: self.max_width,
+            hanging_punctuation: self.hanging_punctuation,
+            indent: 0.0,
+            indented_lines: 0,
+        }
+    }
+
+    /// Returns an iterator over the wrapped lines positioned from `start`, the
+    /// top left of the paragraph, with their glyphs & metrics, e.g. to draw
+    /// the paragraph. Lines are `line_height` apart, the first baseline the
+    /// font ascent below `start`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let paragraph = font.layout_paragraph("The quick brown fox", Scale::uniform(24.0), 120.0);
+    /// for line in paragraph.positioned_lines(point(10.0, 10.0)) {
+    ///     for glyph in &line.glyphs {
+    ///         glyph.draw(|x, y, v| { /* draw at x, y offset by the pixel bounding box */ });
+    ///     }
+    /// }
+    /// ```
+    pub fn positioned_lines(&self, start: Point<f32>) -> PositionedLines<'_, 'font> {
+        PositionedLines {
+            lines: self.lines(),
+            font: self.font,
+            scale: self.scale,
+            max_width: self.max_width,
+            justification: self.justification,
+            v_metrics: self.font.v_metrics(self.scale),
+            baseline: point(start.x, start.y + self.font.v_metrics(self.scale).ascent),
+        }
+    }
+
+    /// Lays out the paragraph from `start` like `positioned_lines`, with its
+    /// first letter as a drop cap `lines` lines tall, from the cap height of
+    /// the first line to the baseline of the last. The rest of the text wraps
+    /// around the ink of the cap plus `gap` pixels. `None` for text without
+    /// words.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let paragraph = font.layout_paragraph("Once upon a time there was a quick brown fox", Scale::uniform(16.0), 150.0);
+    /// let drop_cap = paragraph.drop_cap(2, 4.0, point(0.0, 0.0)).unwrap();
+    /// assert_eq!(drop_cap.cap.id(), font.glyph('O').id());
+    /// let lines: Vec<_> = drop_cap.lines.collect();
+    /// assert_eq!(paragraph.line_text(&lines[0].line), "nce upon a time");
+    /// // beside the cap, then back at the margin
+    /// assert!(lines[1].origin.x > 15.0);
+    /// assert_eq!(lines[2].origin.x, 0.0);
+    /// ```
+    pub fn drop_cap(
+        &self,
+        lines: usize,
+        gap: f32,
+        start: Point<f32>,
+    ) -> Option<DropCap<'_, 'font>> {
+        let first = self.chars.iter().position(|c| !c.breaking)?;
+        let v_metrics = self.font.v_metrics(self.scale);
+        let cap_height = match self.font.inner().capital_height() {
+            Some(height) if height > 0 => {
+                f32::from(height) * self.font.scale_for_pixel_height(self.scale.y)
+            }
+            _ => v_metrics.ascent,
+        };
+        // the cap height of the cap on the last line's baseline
+        let lines = lines.max(1);
+        let height = (lines - 1) as f32 * self.line_height() + cap_height;
+        let ratio = height / cap_height;
+        let cap_scale = Scale {
+            x: self.scale.x * ratio,
+            y: self.scale.y * ratio,
+        };
+        let cap = self.font.glyph(self.chars[first].id).scaled(cap_scale);
+        let (left, width) = match cap.exact_bounding_box() {
+            Some(bb) => (bb.min.x, bb.width()),
+            None => (0.0, cap.h_metrics().advance_width),
+        };
+        let cap = cap.positioned(point(
+            start.x - left,
+            start.y + v_metrics.ascent + height - cap_height,
+        ));
+
+        let mut positioned = self.positioned_lines(start);
+        positioned.lines.words.next = first + 1;
+        positioned.lines.indent = width + gap;
+        positioned.lines.indented_lines = lines;
+        Some(DropCap {
+            cap,
+            lines: positioned,
+        })
+    }
+
+    /// The distance between the baselines of consecutive lines, the font's
+    /// ascent to descent plus its line gap, see `VMetrics`.
+    pub fn line_height(&self) -> f32 {
+        let v_metrics = self.font.v_metrics(self.scale);
+        v_metrics.ascent - v_metrics.descent + v_metrics.line_gap
+    }
+
+    /// Returns the `(min_content, max_content)` widths of the paragraph, for
+    /// sizing text in UI layout, e.g. flexbox.
+    ///
+    /// `min_content` is the width of the widest word, or for
+    /// `BreakPolicy::Tokens` the widest part of a word between breaks, the
+    /// narrowest the text can wrap to without overflowing. `max_content` is
+    /// the width of the widest line without wrapping, breaking only at
+    /// `'\n'`. Both are `0.0` for text without words.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let paragraph = font.layout_paragraph("Hello wide world", Scale::uniform(24.0), 0.0);
+    /// let (min_content, max_content) = paragraph.intrinsic_widths();
+    ///
+    /// let unwrapped = font.layout_paragraph("Hello wide world", Scale::uniform(24.0), max_content);
+    /// assert_eq!(unwrapped.lines().count(), 1);
+    /// let narrowest = font.layout_paragraph("Hello wide world", Scale::uniform(24.0), min_content);
+    /// assert_eq!(narrowest.lines().count(), 3);
+    /// ```
+    pub fn intrinsic_widths(&self) -> (f32, f32) {
+        let (mut min_content, mut max_content) = (0.0_f32, 0.0_f32);
+        let mut line_start_x = 0.0;
+        let mut words = self.words();
+        let mut first = true;
+        while let Some((word, newlines)) = words.next_range() {
+            let (start, end) = (&self.chars[word.start], &self.chars[word.end - 1]);
+            let end_x = end.x + end.advance;
+            if first || newlines > 0 {
+                line_start_x = start.x;
+                first = false;
+            }
+            match self.break_policy {
+                BreakPolicy::Whitespace => min_content = min_content.max(end_x - start.x),
+                BreakPolicy::Tokens => {
+                    let mut part_start = word.start;
+                    let parts = token_breaks(&self.chars, word.clone()).chain(Some(word.end));
+                    for part_end in parts {
+                        let last = &self.chars[part_end - 1];
+                        let width = last.x + last.advance - self.chars[part_start].x;
+                        min_content = min_content.max(width);
+                        part_start = part_end;
+                    }
+                }
+            }
+            max_content = max_content.max(end_x - line_start_x);
+        }
+        (min_content, max_content)
+    }
+
+    /// The text of `line`.
+    #[inline]
+    pub fn line_text(&self, line: &LineBox) -> &'s str {
+        &self.text[line.range.clone()]
+    }
+
+    /// The text of `word`.
+    #[inline]
+    pub fn word_text(&self, word: &WordBox) -> &'s str {
+        &self.text[word.range.clone()]
+    }
+}
+
+/// Whitespace that lines can break at, i.e. not no-break spaces.
+fn is_breaking_space(c: char) -> bool {
+    c.is_whitespace() && !matches!(c, '\u{a0}' | '\u{2007}' | '\u{202f}')
+}
+
+/// Punctuation hanging into the margin at the start of a line, i.e.
+/// opening quotes.
+fn hangs_at_start(c: char) -> bool {
+    matches!(
+        c,
+        '"' | '\'' | '“' | '‘' | '„' | '‚' | '«' | '»' | '‹' | '›' | '「' | '『'
+    )
+}
+
+/// Punctuation hanging into the margin at the end of a line, i.e. closing
+/// quotes, commas, periods & hyphens.
+fn hangs_at_end(c: char) -> bool {
+    matches!(
+        c,
+        '"' | '\''
+            | '”'
+            | '’'
+            | '«'
+            | '»'
+            | '‹'
+            | '›'
+            | '」'
+            | '』'
+            | '.'
+            | ','
+            | '-'
+            | '\u{2010}'
+            | '\u{2011}'
+            | '、'
+            | '。'
+            | '，'
+            | '．'
+    )
+}
+
+/// The advance of the punctuation hanging at the start & end of the line of
+/// `chars`. Lines of only punctuation don't hang.
+fn hanging(chars: &[CharMetrics]) -> (f32, f32) {
+    let lead = chars.iter().take_while(|c| hangs_at_start(c.c)).count();
+    let trail = chars[lead..]
+        .iter()
+        .rev()
+        .take_while(|c| hangs_at_end(c.c))
+        .count();
+    if lead + trail == chars.len() {
+        return (0.0, 0.0);
+    }
+    let last = &chars[chars.len() - 1];
+    let end_hang = match trail {
+        0 => 0.0,
+        _ => last.x + last.advance - chars[chars.len() - trail].x,
+    };
+    (chars[lead].x - chars[0].x, end_hang)
+}
+
+/// The char indices within the word `range` that `BreakPolicy::Tokens` can
+/// break before, in order.
+fn token_breaks(chars: &[CharMetrics], range: Range<usize>) -> impl Iterator<Item = usize> + '_ {
+    (range.start + 1..range.end).filter(move |&i| {
+        let (prev, c) = (chars[i - 1].c, chars[i].c);
+        matches!(prev, '/' | '-' | '_') || (prev.is_lowercase() && c.is_uppercase())
+    })
+}
+
+/// The box of the chars in `range`, from the first to the last.
+fn char_box(chars: &[CharMetrics], range: Range<usize>, text_len: usize) -> WordBox {
+    let first = &chars[range.start];
+    let last = &chars[range.end - 1];
+    WordBox {
+        range: first.index..chars.get(range.end).map_or(text_len, |c| c.index),
+        advance: last.x + last.advance - first.x,
+        bounds: union_bounds(&chars[range], first.x),
+    }
+}
+
+/// The union of the ink bounds of `chars`, relative to `origin_x`.
+fn union_bounds(chars: &[CharMetrics], origin_x: f32) -> Option<Rect<f32>> {
+    chars
+        .iter()
+        .filter_map(|c| {
+            let bb = c.bounds?;
+            let dx = c.x - origin_x;
+            Some(Rect {
+                min: point(bb.min.x + dx, bb.min.y),
+                max: point(bb.max.x + dx, bb.max.y),
+            })
+        })
+        .reduce(|a, b| Rect {
+            min: point(a.min.x.min(b.min.x), a.min.y.min(b.min.y)),
+            max: point(a.max.x.max(b.max.x), a.max.y.max(b.max.y)),
+        })
+}
+
+/// Iterator over the words of a paragraph, see `ParagraphLayout::words`.
+#[derive(Clone, Debug)]
+pub struct ParagraphWords<'p> {
+    chars: &'p [CharMetrics],
+    /// Char index to continue from.
+    next: usize,
+    text_len: usize,
+}
+
+impl ParagraphWords<'_> {
+    /// Returns the char range of the next word & the number of `'\n'` since
+    /// the previous word.
+    fn next_range(&mut self) -> Option<(Range<usize>, usize)> {
+        let rest = &self.chars[self.next..];
+        let skipped = rest.iter().take_while(|c| c.breaking).count();
+        let newlines = rest[..skipped].iter().filter(|c| c.newline).count();
+        let start = self.next + skipped;
+        if start == self.chars.len() {
+            self.next = start;
+            return None;
+        }
+        let len = self.chars[start..]
+            .iter()
+            .take_while(|c| !c.breaking)
+            .count();
+        self.next = start + len;
+        Some((start..start + len, newlines))
+    }
+}
+
+impl Iterator for ParagraphWords<'_> {
+    type Item = WordBox;
+
+    fn next(&mut self) -> Option<WordBox> {
+        let (range, _) = self.next_range()?;
+        Some(char_box(self.chars, range, self.text_len))
+    }
+}
+
+/// Iterator over the wrapped lines of a paragraph, see
+/// `ParagraphLayout::lines`.
+#[derive(Clone, Debug)]
+pub struct ParagraphLines<'p> {
+    chars: &'p [CharMetrics],
+    words: ParagraphWords<'p>,
+    break_policy: BreakPolicy,
+    /// The next word, or rest of a broken word, char range & the newlines before it.
+    pending: Option<(Range<usize>, usize)>,
+    /// Blank lines to yield before the pending word.
+    blank_lines: usize,
+    started: bool,
+    max_width: f32,
+    hanging_punctuation: bool,
+    /// The indent of the first `indented_lines` left, e.g. beside a drop cap.
+    indent: f32,
+    indented_lines: usize,
+}
+
+impl ParagraphLines<'_> {
+    /// The indent of the next line.
+    fn next_indent(&self) -> f32 {
+        match self.indented_lines {
+            0 => 0.0,
+            _ => self.indent,
+        }
+    }
+
+    fn next_line(&mut self, max_width: f32) -> Option<LineBox> {
+        let (first, newlines) = match self.pending.take() {
+            Some(pending) => pending,
+            None => self.words.next_range()?,
+        };
+        if !self.started {
+            self.started = true;
+            self.blank_lines = newlines;
+        } else if newlines > 1 {
+            self.blank_lines = newlines - 1;
+        }
+        if self.blank_lines > 0 {
+            self.blank_lines -= 1;
+            let index = self.chars[first.start].index;
+            self.pending = Some((first, 0));
+            return Some(LineBox {
+                range: index..index,
+                advance: 0.0,
+                bounds: None,
+                hanging: (0.0, 0.0),
+            });
+        }
+
+        let chars = self.chars;
+        let line_start = first.start;
+        let hanging_punctuation = self.hanging_punctuation;
+        let hanging = |end: usize| match hanging_punctuation {
+            true => hanging(&chars[line_start..end]),
+            false => (0.0, 0.0),
+        };
+        // the width of the line up to `end`, without hanging punctuation
+        let width = |end: usize| {
+            let last = &chars[end - 1];
+            let (start_hang, end_hang) = hanging(end);
+            last.x + last.advance - chars[line_start].x - start_hang - end_hang
+        };
+        if self.break_policy == BreakPolicy::Tokens && width(first.end) > max_width {
+            // the last break fitting, or the first one to overflow least
+            let mut breaks = token_breaks(self.chars, first.clone());
+            let mut end = breaks.next();
+            for b in breaks {
+                if width(b) > max_width {
+                    break;
+                }
+                end = Some(b);
+            }
+            if let Some(end) = end {
+                self.pending = Some((end..first.end, 0));
+                let line = char_box(self.chars, first.start..end, self.words.text_len);
+                return Some(LineBox {
+                    range: line.range,
+                    advance: line.advance,
+                    bounds: line.bounds,
+                    hanging: hanging(end),
+                });
+            }
+        }
+
+        let mut end = first.end;
+        while let Some((word, newlines)) = self.words.next_range() {
+            if newlines > 0 || width(word.end) > max_width {
+                self.pending = Some((word, newlines));
+                break;
+            }
+            end = word.end;
+        }
+
+        let line = char_box(self.chars, first.start..end, self.words.text_len);
+        Some(LineBox {
+            range: line.range,
+            advance: line.advance,
+            bounds: line.bounds,
+            hanging: hanging(end),
+        })
+    }
+}
+
+impl Iterator for ParagraphLines<'_> {
+    type Item = LineBox;
+
+    fn next(&mut self) -> Option<LineBox> {
+        let line = self.next_line(self.max_width - self.next_indent())?;
+        self.indented_lines = self.indented_lines.saturating_sub(1);
+        Some(line)
+    }
+}
+
+/// A paragraph laid out with a drop cap, see `ParagraphLayout::drop_cap`.
+#[derive(Clone, Debug)]
+pub struct DropCap<'p, 'font> {
+    /// The first letter of the paragraph, enlarged to span the first lines.
+    pub cap: PositionedGlyph<'font>,
+    /// The lines of the rest of the text, those beside the cap indented past
+    /// it.
+    pub lines: PositionedLines<'p, 'font>,
+}
+
+/// A line of a `ParagraphLayout` with its glyphs, see
+/// `ParagraphLayout::positioned_lines`.
+#[derive(Clone, Debug)]
+pub struct PositionedLine<'font> {
+    pub line: LineBox,
+    /// The start of the line on its baseline.
+    pub origin: Point<f32>,
+    /// The font's vertical metrics at the paragraph scale, e.g. the line spans
+    /// `origin.y - v_metrics.ascent` to `origin.y - v_metrics.descent`.
+    pub v_metrics: VMetrics,
+    /// The glyphs of the line text, including spaces between words.
+    pub glyphs: Vec<PositionedGlyph<'font>>,
+    /// Tatweel glyphs elongating the joins of a `Justification::Kashida`
+    /// line, to draw with `glyphs`.
+    pub kashidas: Vec<PositionedGlyph<'font>>,
+}
+
+/// Iterator over the positioned lines of a paragraph, see
+/// `ParagraphLayout::positioned_lines`.
+#[derive(Clone, Debug)]
+pub struct PositionedLines<'p, 'font> {
+    lines: ParagraphLines<'p>,
+    font: &'p Font<'font>,
+    scale: Scale,
+    max_width: f32,
+    justification: Justification,
+    v_metrics: VMetrics,
+    /// The origin of the next line.
+    baseline: Point<f32>,
+}
+
+impl<'font> Iterator for PositionedLines<'_, 'font> {
+    type Item = PositionedLine<'font>;
+
+    fn next(&mut self) -> Option<PositionedLine<'font>> {
+        let indent = self.lines.next_indent();
+        let line = self.lines.next()?;
+        let chars = self.lines.chars;
+        let first = chars.partition_point(|c| c.index < line.range.start);
+        let end = chars.partition_point(|c| c.index < line.range.end);
+        // hanging punctuation starts in the margin
+        let start_x = chars.get(first).map_or(0.0, |c| c.x) + line.hanging.0;
+        // lines broken between words are filled
+        let justification = match self.lines.pending {
+            Some((_, 0)) => self.justification,
+            _ => Justification::None,
+        };
+        let slack = self.max_width - indent - (line.advance - line.hanging.0 - line.hanging.1);
+        let tatweel = self.font.glyph(TATWEEL).scaled(self.scale);
+        let fill = justify(&chars[first..end], slack, justification, &tatweel);
+
+        let mut glyphs = Vec::with_capacity(end - first);
+        let mut kashidas = Vec::new();
+        let tatweel_advance = tatweel.h_metrics().advance_width;
+        let origin = point(self.baseline.x + indent, self.baseline.y);
+        let mut shift = 0.0;
+        for (c, (tatweels, extra)) in chars[first..end].iter().zip(fill) {
+            for _ in 0..tatweels {
+                let x = origin.x + c.x - start_x + shift;
+                kashidas.push(tatweel.clone().positioned(point(x, origin.y)));
+                shift += tatweel_advance;
+            }
+            let position = point(origin.x + c.x - start_x + shift, origin.y);
+            let glyph = self.font.glyph(c.id).scaled(self.scale);
+            glyphs.push(glyph.positioned(position));
+            shift += extra;
+        }
+
+        let v = self.v_metrics;
+        self.baseline.y += v.ascent - v.descent + v.line_gap;
+        Some(PositionedLine {
+            line,
+            origin,
+            v_metrics: v,
+            glyphs,
+            kashidas,
+        })
+    }
+}
+
+/// U+0640 Arabic tatweel, elongating joins.
+const TATWEEL: char = '\u{640}';
+
+/// The number of forms of each Arabic Presentation Forms-B letter from
+/// U+FE80, in order isolated, final, initial & medial, only isolated &
+/// final for letters not joining the letter after them.
+const PRESENTATION_FORMS: [u32; 40] = [
+    1, 2, 2, 2, 2, 4, 2, 4, 2, 4, 4, 4, 4, 4, 2, 2, 2, 2, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4,
+    4, 2, 2, 4, 2, 2, 2, 2,
+];
+
+/// Whether `c` is the initial or medial form of a pre-shaped Arabic letter,
+/// joining the letter after it, to its left in visual order.
+fn joins_left(c: char) -> bool {
+    if !('\u{fe80}'..='\u{fefc}').contains(&c) {
+        return false;
+    }
+    let mut start = 0xfe80;
+    for forms in PRESENTATION_FORMS {
+        if (c as u32) < start + forms {
+            return c as u32 - start >= 2;
+        }
+        start += forms;
+    }
+    false
+}
+
+/// Whether `c` is an Arabic mark, skipped when joining.
+fn is_arabic_mark(c: char) -> bool {
+    matches!(
+        c,
+        '\u{64b}'..='\u{65f}'
+            | '\u{670}'
+            | '\u{6d6}'..='\u{6dc}'
+            | '\u{6df}'..='\u{6e4}'
+            | '\u{6e7}'
+            | '\u{6e8}'
+            | '\u{6ea}'..='\u{6ed}'
+    )
+}
+
+/// Returns the tatweels to insert before each of the `chars` of a line &
+/// the extra advance after each, filling `slack` by `justification`.
+fn justify(
+    chars: &[CharMetrics],
+    slack: f32,
+    justification: Justification,
+    tatweel: &ScaledGlyph<'_>,
+) -> Vec<(u32, f32)> {
+    let mut fill = vec![(0, 0.0); chars.len()];
+    if slack <= 0.0 || justification == Justification::None {
+        return fill;
+    }
+    let mut slack = slack;
+
+    let tatweel_advance = tatweel.h_metrics().advance_width;
+    if justification == Justification::Kashida
+        && tatweel.id() != GlyphId(0)
+        && tatweel_advance > 0.0
+    {
+        // joins between letters in visual order, where a tatweel goes
+        // before the right letter
+        let mut joins = Vec::new();
+        let mut previous = false;
+        for (i, c) in chars.iter().enumerate() {
+            if is_arabic_mark(c.c) {
+                continue;
+            }
+            if previous && joins_left(c.c) {
+                joins.push(i);
+            }
+            previous = true;
+        }
+        if !joins.is_empty() {
+            let count = (slack / tatweel_advance) as usize;
+            for (n, &i) in joins.iter().enumerate() {
+                let extra = usize::from(n < count % joins.len());
+                fill[i].0 = (count / joins.len() + extra) as u32;
+            }
+            slack -= count as f32 * tatweel_advance;
+        }
+    }
+
+    let spaces = chars.iter().filter(|c| is_breaking_space(c.c)).count();
+    if spaces > 0 {
+        let extra = slack / spaces as f32;
+        for (c, fill) in chars.iter().zip(&mut fill) {
+            if is_breaking_space(c.c) {
+                fill.1 = extra;
+            }
+        }
+    }
+    fill
+}
@@ -0,0 +1,171 @@
+//! Pixel font rendering, see `PixelMode`.
+#[cfg(all(feature = "libm-math", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{Font, GlyphId, Scale};
+use core::fmt;
+use owned_ttf_parser::OutlineBuilder;
+
+/// The finest design pixel grid, in pixels per em, detected as a pixel font.
+const MAX_GRID_PPEM: u32 = 64;
+
+/// When to render a font as a pixel font, see `Font::set_pixel_mode`.
+///
+/// Pixel fonts, e.g. for retro games, have outlines made of squares on a
+/// coarse grid designed to be drawn at whole multiples of the grid size.
+/// Rendered as a pixel font, glyphs have whole pixel metrics & positions and
+/// are drawn without anti-aliasing, fully covered or not at all.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PixelMode {
+    /// Render fonts detected as pixel fonts, i.e. with a `Font::pixel_grid`,
+    /// as pixel fonts.
+    #[default]
+    Auto,
+    /// Always render as a pixel font.
+    On,
+    /// Never render as a pixel font.
+    Off,
+}
+
+/// A scale that doesn't draw a pixel font's design pixels as whole pixels,
+/// returned by `Font::pixel_scale_warning`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PixelScaleWarning {
+    /// The scale checked.
+    pub scale: Scale,
+    /// The nearest scale drawing each design pixel as a whole number of
+    /// pixels, at least one.
+    pub nearest: Scale,
+}
+
+impl fmt::Display for PixelScaleWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "scale {}x{} isn't a multiple of the pixel font grid, nearest is {}x{}",
+            self.scale.x, self.scale.y, self.nearest.x, self.nearest.y
+        )
+    }
+}
+
+/// Detects the design pixel grid of `font` from the outlines & advances of
+/// its printable ASCII glyphs, returning the font units per design pixel.
+///
+/// Pixel font outlines only have straight lines, on a grid of at most
+/// `MAX_GRID_PPEM` pixels per em.
+pub(crate) fn detect_grid(font: &Font<'_>) -> Option<u16> {
+    let inner = font.inner();
+    let mut builder = GridBuilder {
+        gcd: 0,
+        on_grid: true,
+    };
+    let mut has_outline = false;
+    for c in '!'..='~' {
+        let id = font.glyph(c).id();
+        if id == GlyphId(0) {
+            continue;
+        }
+        if let Some(advance) = inner.glyph_hor_advance(id.into()) {
+            builder.add(f32::from(advance));
+        }
+        has_outline |= inner.outline_glyph(id.into(), &mut builder).is_some();
+        if !builder.on_grid {
+            return None;
+        }
+    }
+
+    let upem = u32::from(font.units_per_em());
+    if !has_outline || builder.gcd == 0 || builder.gcd * MAX_GRID_PPEM < upem {
+        return None;
+    }
+    Some(builder.gcd as u16)
+}
+
+/// Finds the greatest common divisor of all outline coordinates, bailing out
+/// on curves & fractional coordinates.
+struct GridBuilder {
+    gcd: u32,
+    on_grid: bool,
+}
+
+impl GridBuilder {
+    fn add(&mut self, v: f32) {
+        if v.fract() != 0.0 || v.abs() > f32::from(u16::MAX) {
+            self.on_grid = false;
+            return;
+        }
+        let (mut a, mut b) = (self.gcd, v.abs() as u32);
+        while b != 0 {
+            let r = a % b;
+            a = b;
+            b = r;
+        }
+        self.gcd = a;
+    }
+}
+
+impl OutlineBuilder for GridBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.add(x);
+        self.add(y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.add(x);
+        self.add(y);
+    }
+
+    fn quad_to(&mut self, _: f32, _: f32, _: f32, _: f32) {
+        self.on_grid = false;
+    }
+
+    fn curve_to(&mut self, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32) {
+        self.on_grid = false;
+    }
+
+    fn close(&mut self) {}
+}
+
+impl<'font> Font<'font> {
+    /// Returns a warning if `scale` doesn't draw this pixel font's design
+    /// pixels, see `pixel_grid`, as a whole number of pixels, which would
+    /// draw some design pixels wider than others. `None` for fonts without a
+    /// pixel grid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # fn check(font: &Font<'_>) {
+    /// let mut scale = Scale::uniform(17.0);
+    /// if let Some(warning) = font.pixel_scale_warning(scale) {
+    ///     eprintln!("{}", warning);
+    ///     scale = warning.nearest;
+    /// }
+    /// # let _ = scale;
+    /// # }
+    /// ```
+    pub fn pixel_scale_warning(&self, scale: Scale) -> Option<PixelScaleWarning> {
+        let grid = f32::from(self.pixel_grid()?);
+        let x = grid * self.kerning_factor(scale);
+        let y = grid * self.scale_for_pixel_height(scale.y);
+        let snapped = |pixels: f32| pixels.round().max(1.0);
+        let on_grid = |pixels: f32| (pixels - snapped(pixels)).abs() < 0.01;
+        if on_grid(x) && on_grid(y) {
+            return None;
+        }
+        let nearest = |s: f32, pixels: f32| {
+            if on_grid(pixels) {
+                s
+            } else {
+                s * snapped(pixels) / pixels
+            }
+        };
+        Some(PixelScaleWarning {
+            scale,
+            nearest: Scale {
+                x: nearest(scale.x, x),
+                y: nearest(scale.y, y),
+            },
+        })
+    }
+}
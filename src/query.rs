@@ -0,0 +1,294 @@
+//! CSS style font descriptors, see `FontQuery`.
+use crate::{FontClass, FontHandle, FontStore, Scale, Style, Weight};
+use std::{error, fmt};
+
+/// A font family of a `FontQuery`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FontFamily {
+    /// A specific family, e.g. `"Fira Sans"`.
+    Name(String),
+    /// The generic `serif` family.
+    Serif,
+    /// The generic `sans-serif` family.
+    SansSerif,
+    /// The generic `monospace` family.
+    Monospace,
+    /// The generic `cursive` family.
+    Cursive,
+    /// The generic `fantasy` family.
+    Fantasy,
+}
+
+impl FontFamily {
+    /// The `FontClass` of fonts matching this generic family, `None` for
+    /// `Name`.
+    fn class(&self) -> Option<FontClass> {
+        match self {
+            FontFamily::Name(_) => None,
+            FontFamily::Serif => Some(FontClass::Serif),
+            FontFamily::SansSerif => Some(FontClass::SansSerif),
+            FontFamily::Monospace => Some(FontClass::Monospace),
+            FontFamily::Cursive => Some(FontClass::Script),
+            FontFamily::Fantasy => Some(FontClass::Decorative),
+        }
+    }
+}
+
+/// A font description in the syntax of the CSS `font` shorthand, e.g.
+/// `"bold 14px 'Fira Sans', sans-serif"`. See `FontStore::resolve`.
+///
+/// Style, weight, size & the family list are parsed, variant & stretch
+/// keywords are accepted but ignored. Only `px` & `pt` sizes are supported &,
+/// unlike CSS, the size is optional.
+///
+/// # Example
+///
+/// ```
+/// # use rusttype::*;
+/// let query = FontQuery::parse("italic bold 14px/1.5 'Fira Sans', sans-serif").unwrap();
+/// assert_eq!(query.style, Style::Italic);
+/// assert_eq!(query.weight, Weight::BOLD);
+/// assert_eq!(query.size, Some(14.0));
+/// assert_eq!(
+///     query.families,
+///     vec![FontFamily::Name("Fira Sans".into()), FontFamily::SansSerif]
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct FontQuery {
+    /// Families in order of preference.
+    pub families: Vec<FontFamily>,
+    pub weight: Weight,
+    pub style: Style,
+    /// The font size in pixels, `pt` sizes are converted at 96 DPI.
+    pub size: Option<f32>,
+    /// The line height following the size, e.g. `1.5` of `14px/1.5`. Unitless
+    /// values are multiples of the size, other units are converted to pixels.
+    pub line_height: Option<LineHeight>,
+}
+
+/// A `FontQuery` line height.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LineHeight {
+    /// A multiple of the font size.
+    Multiple(f32),
+    /// A height in pixels.
+    Pixels(f32),
+}
+
+/// Returned from `FontQuery::parse`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FontQueryError {
+    /// No font family was given.
+    MissingFamily,
+    /// A family in the list is empty, e.g. `"Arial,,serif"`.
+    EmptyFamily,
+    /// A quoted family name has no closing quote.
+    UnterminatedQuote,
+    /// The size or line height has an unknown unit or is not a number.
+    InvalidSize,
+}
+
+impl fmt::Display for FontQueryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FontQueryError::MissingFamily => "Missing font family",
+            FontQueryError::EmptyFamily => "Empty font family",
+            FontQueryError::UnterminatedQuote => "Unterminated quote",
+            FontQueryError::InvalidSize => "Invalid font size",
+        }
+        .fmt(f)
+    }
+}
+
+impl error::Error for FontQueryError {}
+
+impl FontQuery {
+    /// Parses a CSS `font` shorthand style descriptor.
+    pub fn parse(s: &str) -> Result<Self, FontQueryError> {
+        let mut query = FontQuery {
+            families: Vec::new(),
+            weight: Weight::NORMAL,
+            style: Style::Normal,
+            size: None,
+            line_height: None,
+        };
+
+        let mut rest = s.trim_start();
+        loop {
+            let token_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let token = &rest[..token_end];
+            if token.is_empty() || token.contains([',', '\'', '"']) {
+                break;
+            }
+            let lower = token.to_ascii_lowercase();
+            match lower.as_str() {
+                "normal" | "small-caps" | "ultra-condensed" | "extra-condensed" | "condensed"
+                | "semi-condensed" | "semi-expanded" | "expanded" | "extra-expanded"
+                | "ultra-expanded" => {}
+                "italic" => query.style = Style::Italic,
+                "oblique" => query.style = Style::Oblique,
+                "bold" | "bolder" => query.weight = Weight::BOLD,
+                "lighter" => query.weight = Weight::LIGHT,
+                _ if lower.starts_with(|c: char| c.is_ascii_digit() || c == '.') => {
+                    match lower.parse::<u16>() {
+                        Ok(weight @ 1..=1000) => query.weight = Weight(weight),
+                        _ => {
+                            // the size is always last before the families
+                            let (size, line_height) = parse_size(&lower)?;
+                            query.size = Some(size);
+                            query.line_height = line_height;
+                            rest = rest[token_end..].trim_start();
+                            break;
+                        }
+                    }
+                }
+                _ => break,
+            }
+            rest = rest[token_end..].trim_start();
+        }
+
+        query.families = parse_families(rest)?;
+        Ok(query)
+    }
+
+    /// The `size` as a uniform `Scale`.
+    pub fn scale(&self) -> Option<Scale> {
+        self.size.map(Scale::uniform)
+    }
+}
+
+/// Parses `<size>[/<line-height>]`.
+fn parse_size(s: &str) -> Result<(f32, Option<LineHeight>), FontQueryError> {
+    let (size, line_height) = match s.find('/') {
+        Some(idx) => (&s[..idx], Some(&s[idx + 1..])),
+        None => (s, None),
+    };
+    let size = parse_length(size)?.ok_or(FontQueryError::InvalidSize)?;
+    let line_height = match line_height {
+        Some(lh) => Some(match parse_length(lh)? {
+            Some(px) => LineHeight::Pixels(px),
+            None => LineHeight::Multiple(lh.parse().map_err(|_| FontQueryError::InvalidSize)?),
+        }),
+        None => None,
+    };
+    Ok((size, line_height))
+}
+
+/// Parses a `px` or `pt` length into pixels, `None` if unitless.
+fn parse_length(s: &str) -> Result<Option<f32>, FontQueryError> {
+    let (number, to_px) = if let Some(n) = s.strip_suffix("px") {
+        (n, 1.0)
+    } else if let Some(n) = s.strip_suffix("pt") {
+        (n, 96.0 / 72.0)
+    } else if s.parse::<f32>().is_ok() {
+        return Ok(None);
+    } else {
+        return Err(FontQueryError::InvalidSize);
+    };
+    let number: f32 = number.parse().map_err(|_| FontQueryError::InvalidSize)?;
+    Ok(Some(number * to_px))
+}
+
+/// Parses a comma separated list of quoted or unquoted families.
+fn parse_families(s: &str) -> Result<Vec<FontFamily>, FontQueryError> {
+    let mut families = Vec::new();
+    let mut rest = s.trim();
+    if rest.is_empty() {
+        return Err(FontQueryError::MissingFamily);
+    }
+    loop {
+        let family = if let Some(quote) = rest.chars().next().filter(|c| *c == '\'' || *c == '"') {
+            let end = rest[1..]
+                .find(quote)
+                .ok_or(FontQueryError::UnterminatedQuote)?;
+            let name = &rest[1..end + 1];
+            rest = rest[end + 2..].trim_start();
+            FontFamily::Name(name.into())
+        } else {
+            let end = rest.find(',').unwrap_or(rest.len());
+            let name = rest[..end].split_whitespace().collect::<Vec<_>>().join(" ");
+            rest = &rest[end..];
+            match name.to_ascii_lowercase().as_str() {
+                "" => return Err(FontQueryError::EmptyFamily),
+                "serif" => FontFamily::Serif,
+                "sans-serif" => FontFamily::SansSerif,
+                "monospace" => FontFamily::Monospace,
+                "cursive" => FontFamily::Cursive,
+                "fantasy" => FontFamily::Fantasy,
+                _ => FontFamily::Name(name),
+            }
+        };
+        families.push(family);
+
+        match rest.strip_prefix(',') {
+            Some(r) => rest = r.trim_start(),
+            None if rest.is_empty() => break,
+            // text after a quoted family
+            None => return Err(FontQueryError::EmptyFamily),
+        }
+    }
+    Ok(families)
+}
+
+impl FontStore {
+    /// Returns the font best matching `query`, trying each family in order,
+    /// see `query`.
+    ///
+    /// Generic families, e.g. `sans-serif`, match fonts of the corresponding
+    /// `FontClass`. With the `fontdb` feature the database's generic family
+    /// is tried first.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rusttype::*;
+    /// let mut store = FontStore::new();
+    /// let query = FontQuery::parse("bold 14px 'Fira Sans', sans-serif").unwrap();
+    /// if let Some(handle) = store.resolve(&query) {
+    ///     let scale = query.scale().unwrap();
+    ///     # let _ = (handle, scale);
+    /// }
+    /// ```
+    pub fn resolve(&mut self, query: &FontQuery) -> Option<FontHandle> {
+        for family in &query.families {
+            let handle = match family {
+                FontFamily::Name(name) => self.query(name, query.weight, query.style),
+                generic => self.query_generic(generic, query.weight, query.style),
+            };
+            if handle.is_some() {
+                return handle;
+            }
+        }
+        None
+    }
+
+    fn query_generic(
+        &mut self,
+        family: &FontFamily,
+        weight: Weight,
+        style: Style,
+    ) -> Option<FontHandle> {
+        #[cfg(feature = "fontdb")]
+        {
+            let generic = match family {
+                FontFamily::Serif => fontdb::Family::Serif,
+                FontFamily::SansSerif => fontdb::Family::SansSerif,
+                FontFamily::Monospace => fontdb::Family::Monospace,
+                FontFamily::Cursive => fontdb::Family::Cursive,
+                FontFamily::Fantasy => fontdb::Family::Fantasy,
+                FontFamily::Name(_) => unreachable!(),
+            };
+            if let Some(handle) = self.query_database_family(generic, weight, style) {
+                return Some(handle);
+            }
+        }
+
+        let class = family.class()?;
+        let family = self
+            .iter()
+            .find(|(_, _, font)| font.classification() == class)
+            .map(|(_, key, _)| key.family.clone())?;
+        self.query(&family, weight, style)
+    }
+}
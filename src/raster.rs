@@ -0,0 +1,82 @@
+//! Pluggable glyph rasterization, see `RasterBackend`.
+use crate::PositionedGlyph;
+
+/// Rasterizes glyph outlines into coverage, allowing rasterizers other than
+/// the built-in `ScanlineRasterizer`, e.g. GPU compute or platform
+/// rasterizers, to be used with `PositionedGlyph::draw_with_backend` &
+/// `gpu_cache::Cache::cache_queued_with_backend`.
+///
+/// Takes `&self` so a backend can rasterize multiple glyphs concurrently, use
+/// interior mutability for any state.
+///
+/// # Example
+///
+/// ```
+/// use rusttype::{PositionedGlyph, RasterBackend};
+///
+/// /// Rasterizes glyphs as solid boxes, e.g. for layout debugging.
+/// struct Boxes;
+///
+/// impl RasterBackend for Boxes {
+///     fn rasterize(&self, glyph: &PositionedGlyph<'_>, o: &mut dyn FnMut(u32, u32, f32)) {
+///         let bb = glyph.pixel_bounding_box().unwrap();
+///         for y in 0..bb.height() as u32 {
+///             for x in 0..bb.width() as u32 {
+///                 o(x, y, 1.0);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub trait RasterBackend {
+    /// Rasterizes `glyph`, calling `o` with the position relative to the
+    /// `pixel_bounding_box` min & the coverage of pixels within it, like
+    /// `PositionedGlyph::draw`. Pixels not passed to `o` are uncovered.
+    ///
+    /// Only called for glyphs with a pixel bounding box. The outline in the
+    /// same pixel coordinates is available with
+    /// `PositionedGlyph::build_outline`.
+    fn rasterize(&self, glyph: &PositionedGlyph<'_>, o: &mut dyn FnMut(u32, u32, f32));
+
+    /// Rasterizes a user defined `variant` of `glyph`, e.g. a synthetic bold,
+    /// see `gpu_cache::Cache::queue_glyph_variant`. By default variants are
+    /// rasterized the same with `rasterize`.
+    #[allow(unused_variables)]
+    fn rasterize_variant(
+        &self,
+        glyph: &PositionedGlyph<'_>,
+        variant: u32,
+        o: &mut dyn FnMut(u32, u32, f32),
+    ) {
+        self.rasterize(glyph, o)
+    }
+}
+
+/// The built-in anti-aliasing rasterizer used by `PositionedGlyph::draw`,
+/// calculating exact coverage of every pixel.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ScanlineRasterizer;
+
+impl RasterBackend for ScanlineRasterizer {
+    #[inline]
+    fn rasterize(&self, glyph: &PositionedGlyph<'_>, o: &mut dyn FnMut(u32, u32, f32)) {
+        glyph.draw(o)
+    }
+}
+
+impl<B: RasterBackend + ?Sized> RasterBackend for &B {
+    #[inline]
+    fn rasterize(&self, glyph: &PositionedGlyph<'_>, o: &mut dyn FnMut(u32, u32, f32)) {
+        (**self).rasterize(glyph, o)
+    }
+
+    #[inline]
+    fn rasterize_variant(
+        &self,
+        glyph: &PositionedGlyph<'_>,
+        variant: u32,
+        o: &mut dyn FnMut(u32, u32, f32),
+    ) {
+        (**self).rasterize_variant(glyph, variant, o)
+    }
+}
@@ -0,0 +1,109 @@
+//! Rich text layout of runs in different fonts & sizes, see `RichLayout`.
+use crate::{point, Font, Point, PositionedGlyph, Scale, VMetrics};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// A run of text in one style, see `RichLayout::new`.
+#[derive(Clone, Debug)]
+pub struct TextRun<'a, 'font> {
+    pub text: &'a str,
+    pub font: &'a Font<'font>,
+    pub scale: Scale,
+    /// Extra space after each glyph, in pixels.
+    pub letter_spacing: f32,
+}
+
+impl<'a, 'font> TextRun<'a, 'font> {
+    /// A run of `text` in `font` at `scale` without letter spacing.
+    #[inline]
+    pub fn new(text: &'a str, font: &'a Font<'font>, scale: Scale) -> Self {
+        TextRun {
+            text,
+            font,
+            scale,
+            letter_spacing: 0.0,
+        }
+    }
+
+    /// Returns this run with `pixels` of extra space after each glyph.
+    #[inline]
+    pub fn letter_spacing(mut self, pixels: f32) -> Self {
+        self.letter_spacing = pixels;
+        self
+    }
+}
+
+/// A line of styled runs laid out one after another on a shared baseline,
+/// e.g. mixing regular & bold text, or a text font with an emoji font.
+///
+/// # Example
+///
+/// ```
+/// # use rusttype::*;
+/// # let regular: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+/// # let italic: &[u8] = include_bytes!("../dev/fonts/opensans/OpenSans-Italic.ttf");
+/// let (regular, italic) = (Font::try_from_bytes(regular).unwrap(), Font::try_from_bytes(italic).unwrap());
+/// let runs = [
+///     TextRun::new("Hello ", &regular, Scale::uniform(16.0)),
+///     TextRun::new("world", &italic, Scale::uniform(24.0)).letter_spacing(1.0),
+/// ];
+/// let layout = RichLayout::new(&runs, point(0.0, 0.0));
+/// // the first baseline to fit the largest run below a line top at y 10
+/// let layout = RichLayout::new(&runs, point(0.0, 10.0 + layout.v_metrics.ascent));
+/// assert_eq!(layout.glyphs.len(), 11);
+/// ```
+#[derive(Clone, Debug)]
+pub struct RichLayout<'font> {
+    /// Glyphs of all runs, in order, on the `start` baseline.
+    pub glyphs: Vec<PositionedGlyph<'font>>,
+    /// The range of `glyphs` of each run.
+    pub runs: Vec<Range<usize>>,
+    /// The advance of all runs, including letter spacing.
+    pub advance_width: f32,
+    /// The largest ascent, descent & line gap of the runs, for the line height.
+    /// Zero without runs.
+    pub v_metrics: VMetrics,
+}
+
+impl<'font> RichLayout<'font> {
+    /// Lays out `runs` from `start` on the baseline, each run continuing at
+    /// the advance of the previous one. Glyphs within a run are kerned,
+    /// glyphs of different runs aren't, as they may not share a font.
+    pub fn new(runs: &[TextRun<'_, 'font>], start: Point<f32>) -> Self {
+        let mut glyphs = Vec::new();
+        let mut ranges = Vec::with_capacity(runs.len());
+        let mut caret = 0.0;
+        let mut v_metrics = VMetrics {
+            ascent: 0.0,
+            descent: 0.0,
+            line_gap: 0.0,
+        };
+        for run in runs {
+            let run_start = glyphs.len();
+            let origin = point(start.x + caret, start.y);
+            let mut run_advance = 0.0;
+            let layout = run.font.layout(run.text, run.scale, origin).with_carets();
+            for (i, (mut glyph, after)) in layout.enumerate() {
+                let spacing = run.letter_spacing * i as f32;
+                let p = glyph.position();
+                glyph.set_position(point(p.x + spacing, p.y));
+                run_advance = after + spacing + run.letter_spacing;
+                glyphs.push(glyph);
+            }
+            caret += run_advance;
+            ranges.push(run_start..glyphs.len());
+
+            let metrics = run.font.v_metrics(run.scale);
+            v_metrics.ascent = v_metrics.ascent.max(metrics.ascent);
+            v_metrics.descent = v_metrics.descent.min(metrics.descent);
+            v_metrics.line_gap = v_metrics.line_gap.max(metrics.line_gap);
+        }
+        RichLayout {
+            glyphs,
+            runs: ranges,
+            advance_width: caret,
+            v_metrics,
+        }
+    }
+}
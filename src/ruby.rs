@@ -0,0 +1,100 @@
+//! Ruby annotation layout, e.g. Japanese furigana, see `Font::layout_ruby`.
+use crate::{point, Font, Point, PositionedGlyph, Scale, VMetrics};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A base run with a ruby annotation centered above it, returned from
+/// `Font::layout_ruby`.
+#[derive(Clone, Debug)]
+pub struct RubyLayout<'font> {
+    /// Glyphs of the base text on the `start` baseline.
+    pub base: Vec<PositionedGlyph<'font>>,
+    /// Glyphs of the annotation, on a baseline above the base ascent.
+    pub annotation: Vec<PositionedGlyph<'font>>,
+    /// The advance of the whole ruby, the wider of the base & annotation.
+    pub advance_width: f32,
+    /// The base line metrics with the ascent expanded to fit the annotation.
+    /// Use these for the line height of lines containing the ruby.
+    pub v_metrics: VMetrics,
+}
+
+impl<'font> Font<'font> {
+    /// Lays out `base` starting at `start` with the ruby `annotation` at
+    /// `annotation_scale`, typically half of `scale`, centered above it.
+    ///
+    /// The narrower of the two runs is centered over the wider one, which
+    /// starts at `start`. The annotation baseline sits on the base ascent,
+    /// offset by the annotation descent, so the returned `v_metrics` ascent
+    /// grows by the annotation height `ascent - descent`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let scale = Scale::uniform(32.0);
+    /// let ruby = font.layout_ruby("漢字", "かんじ", scale, Scale::uniform(16.0), point(0.0, 64.0));
+    ///
+    /// assert!(ruby.v_metrics.ascent > font.v_metrics(scale).ascent);
+    /// // continue the line after the ruby
+    /// let rest = font.layout("です", scale, point(ruby.advance_width, 64.0));
+    /// # let _ = rest;
+    /// ```
+    pub fn layout_ruby(
+        &self,
+        base: &str,
+        annotation: &str,
+        scale: Scale,
+        annotation_scale: Scale,
+        start: Point<f32>,
+    ) -> RubyLayout<'font> {
+        let base_metrics = self.v_metrics(scale);
+        let annotation_metrics = self.v_metrics(annotation_scale);
+
+        let mut base: Vec<_> = self.layout(base, scale, start).collect();
+        let annotation_baseline = start.y - base_metrics.ascent + annotation_metrics.descent;
+        let mut annotation: Vec<_> = self
+            .layout(
+                annotation,
+                annotation_scale,
+                point(start.x, annotation_baseline),
+            )
+            .collect();
+
+        let base_width = advance_width(&base, start.x);
+        let annotation_width = advance_width(&annotation, start.x);
+        let advance_width = base_width.max(annotation_width);
+        shift(&mut base, (advance_width - base_width) / 2.0);
+        shift(&mut annotation, (advance_width - annotation_width) / 2.0);
+
+        let mut v_metrics = base_metrics;
+        if !annotation.is_empty() {
+            v_metrics.ascent += annotation_metrics.ascent - annotation_metrics.descent;
+        }
+
+        RubyLayout {
+            base,
+            annotation,
+            advance_width,
+            v_metrics,
+        }
+    }
+}
+
+/// The advance of laid out `glyphs` starting at `start_x`.
+fn advance_width(glyphs: &[PositionedGlyph<'_>], start_x: f32) -> f32 {
+    glyphs
+        .last()
+        .map(|g| g.position().x + g.unpositioned().h_metrics().advance_width - start_x)
+        .unwrap_or(0.0)
+}
+
+fn shift(glyphs: &mut [PositionedGlyph<'_>], dx: f32) {
+    if dx != 0.0 {
+        for g in glyphs {
+            let p = g.position();
+            g.set_position(point(p.x + dx, p.y));
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Shaped glyphs with their text clusters, e.g. to animate glyphs
+//! individually, see `GlyphRun`.
+use crate::{vector, Affine2, Font, LayoutOptions, Point, PositionedGlyph, Scale, Shaper};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Glyphs laid out by a `Shaper` & the text they represent, returned from
+/// `Font::glyph_run`.
+#[derive(Clone, Debug)]
+pub struct GlyphRun<'font> {
+    glyphs: Vec<PositionedGlyph<'font>>,
+    clusters: Vec<usize>,
+}
+
+/// The placement of a glyph of a `GlyphRun`, see `GlyphRun::glyph_offsets`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GlyphOffset {
+    /// Index of the glyph in `GlyphRun::glyphs`, in visual order.
+    pub index: usize,
+    /// Rank of the glyph in text order, from `0`, e.g. to reveal glyphs one
+    /// after another.
+    pub order: usize,
+    /// Byte index in the text of the first char the glyph represents.
+    pub cluster: usize,
+    /// Maps pixels relative to the glyph origin to their final position,
+    /// including the glyph's `transform`. Apply animation transforms about
+    /// the origin first, e.g. `Affine2::scale(2.0, 2.0).then(transform)`.
+    pub transform: Affine2,
+}
+
+impl<'font> Font<'font> {
+    /// Lays out `s` like `layout_shaped`, also returning the cluster of
+    /// each glyph, see `GlyphRun`.
+    pub fn glyph_run<S: Shaper + ?Sized>(
+        &self,
+        shaper: &S,
+        s: &str,
+        scale: Scale,
+        start: Point<f32>,
+        options: &LayoutOptions,
+    ) -> GlyphRun<'font> {
+        let mut infos = Vec::new();
+        shaper.shape(self, s, scale, options, &mut infos);
+
+        let mut pen = start;
+        let mut glyphs = Vec::with_capacity(infos.len());
+        let mut clusters = Vec::with_capacity(infos.len());
+        for info in infos {
+            let position = pen + vector(info.offset.0, info.offset.1);
+            pen = pen + vector(info.advance.0, info.advance.1);
+            glyphs.push(self.glyph(info.id).scaled(scale).positioned(position));
+            clusters.push(info.cluster);
+        }
+        GlyphRun { glyphs, clusters }
+    }
+}
+
+impl<'font> GlyphRun<'font> {
+    /// The glyphs in visual order, as output by the shaper.
+    #[inline]
+    pub fn glyphs(&self) -> &[PositionedGlyph<'font>] {
+        &self.glyphs
+    }
+
+    /// The byte index in the text of the first char each glyph represents.
+    #[inline]
+    pub fn clusters(&self) -> &[usize] {
+        &self.clusters
+    }
+
+    #[inline]
+    pub fn into_glyphs(self) -> Vec<PositionedGlyph<'font>> {
+        self.glyphs
+    }
+
+    /// Returns the placement of each glyph, in visual order, for animating
+    /// glyphs individually, e.g. typewriter or kinetic text, by composing
+    /// animation transforms with each glyph's `transform`.
+    ///
+    /// The `order` of glyphs follows the text, by cluster, so right-to-left
+    /// runs & reordered glyphs are revealed as read. Glyphs of one cluster,
+    /// e.g. a base & its marks, are ordered as the shaper output them, so
+    /// orders are stable for the same shaper output, whatever the positions.
+    /// A ligature counts as one glyph.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let options = LayoutOptions::default();
+    /// let run = font.glyph_run(&SimpleShaper, "Hello", Scale::uniform(24.0), point(0.0, 20.0), &options);
+    /// let time = 0.25;
+    /// for offset in run.glyph_offsets() {
+    ///     // each glyph grows in over 0.1s, one after another
+    ///     let t = ((time - offset.order as f32 * 0.05) / 0.1).max(0.0).min(1.0);
+    ///     let transform = Affine2::scale(t, t).then(offset.transform);
+    ///     // draw the outline of run.glyphs()[offset.index] with transform
+    /// }
+    /// ```
+    pub fn glyph_offsets(&self) -> Vec<GlyphOffset> {
+        let mut text_order: Vec<usize> = (0..self.glyphs.len()).collect();
+        text_order.sort_by_key(|&i| self.clusters[i]);
+
+        let mut offsets: Vec<GlyphOffset> = self
+            .glyphs
+            .iter()
+            .zip(&self.clusters)
+            .enumerate()
+            .map(|(index, (glyph, &cluster))| GlyphOffset {
+                index,
+                order: 0,
+                cluster,
+                transform: glyph
+                    .transform()
+                    .then(Affine2::translate(glyph.position() - Point::default())),
+            })
+            .collect();
+        for (order, index) in text_order.into_iter().enumerate() {
+            offsets[index].order = order;
+        }
+        offsets
+    }
+}
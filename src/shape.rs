@@ -0,0 +1,149 @@
+//! Anti-aliased geometric primitives, e.g. UI rounded rects, rasterized
+//! into a `CoverageMask` with the glyph rasterizer.
+#[cfg(all(feature = "libm-math", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::outliner::{OutlineRasterizer, OutlineTranslator};
+use crate::{point, vector, CoverageMask, OutlineBuilder, Point, Rect, Vector};
+
+/// Maximum distance of the flattened arcs of round shapes from the true
+/// arc, in pixels.
+const ARC_TOLERANCE: f32 = 0.05;
+
+/// Builds a quarter circle arc around `center` of `radius` from `start`
+/// radians, clockwise on screen, y down, as lines.
+///
+/// Lines are built directly as the rasterizer flattens curves coarsely,
+/// underestimating round shapes. Inner points are moved out so the lines
+/// enclose the arc's area.
+fn quarter_arc(
+    builder: &mut (impl OutlineBuilder + ?Sized),
+    center: Point<f32>,
+    radius: f32,
+    start: f32,
+) {
+    let quarter = core::f32::consts::FRAC_PI_2;
+    let at = |angle: f32, r: f32| center + vector(angle.cos(), angle.sin()) * r;
+    if radius > 0.0 {
+        // a chord of angle θ is ~θ²r/8 from the arc
+        let segments = (quarter / (8.0 * ARC_TOLERANCE / radius).sqrt())
+            .ceil()
+            .max(1.0);
+        let step = quarter / segments;
+        let outer = radius * (step / step.sin()).sqrt();
+        for i in 1..segments as u32 {
+            let p = at(start + step * i as f32, outer);
+            builder.line_to(p.x, p.y);
+        }
+    }
+    let end = at(start + quarter, radius);
+    builder.line_to(end.x, end.y);
+}
+
+impl CoverageMask {
+    /// Fills the outline built by `build` within `bounds`, compositing its
+    /// coverage over the mask's.
+    pub(crate) fn fill(&mut self, bounds: Rect<f32>, build: impl FnOnce(&mut dyn OutlineBuilder)) {
+        // rasterized in its own bounds, as outlines outside the rasterizer
+        // wrap into neighbouring rows
+        let min = point(bounds.min.x.floor() as i32, bounds.min.y.floor() as i32);
+        let max = point(bounds.max.x.ceil() as i32, bounds.max.y.ceil() as i32);
+        let (width, height) = (max.x - min.x, max.y - min.y);
+        let outside = max.x <= 0 || max.y <= 0 || min.x >= self.width as i32;
+        if width <= 0 || height <= 0 || outside || min.y >= self.height as i32 {
+            return;
+        }
+
+        let mut rasterizer = OutlineRasterizer::new(width as usize, height as usize);
+        let offset = point(-min.x as f32, -min.y as f32);
+        build(&mut OutlineTranslator::new(&mut rasterizer, offset));
+        let mask_width = self.width as usize;
+        let (mask_width_i, mask_height_i) = (self.width as i32, self.height as i32);
+        let coverage = &mut self.coverage;
+        rasterizer.rasterizer.for_each_pixel_2d(|x, y, v| {
+            let (x, y) = (min.x + x as i32, min.y + y as i32);
+            if v > 0.0 && x >= 0 && y >= 0 && x < mask_width_i && y < mask_height_i {
+                let c = &mut coverage[x as usize + y as usize * mask_width];
+                *c += v.min(1.0) * (1.0 - *c);
+            }
+        });
+    }
+
+    /// Fills `rect`, in pixels, with corners rounded by `radius`, clamped to
+    /// half the smaller side, e.g. for UI buttons & panels. Composited over
+    /// the existing coverage & clipped to the mask.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// let mut mask = CoverageMask::new(64, 32);
+    /// mask.fill_rounded_rect(Rect { min: point(2.0, 2.0), max: point(62.0, 30.0) }, 6.0);
+    /// assert!(mask.get(32, 16) > 0.999);
+    /// assert_eq!(mask.get(2, 2), 0.0);
+    /// ```
+    pub fn fill_rounded_rect(&mut self, rect: Rect<f32>, radius: f32) {
+        let Rect { min, max } = rect;
+        if rect.width() <= 0.0 || rect.height() <= 0.0 {
+            return;
+        }
+        let r = radius.max(0.0).min(rect.width().min(rect.height()) / 2.0);
+        self.fill(rect, |b| {
+            let quarter = core::f32::consts::FRAC_PI_2;
+            b.move_to(min.x + r, min.y);
+            b.line_to(max.x - r, min.y);
+            quarter_arc(b, point(max.x - r, min.y + r), r, -quarter);
+            b.line_to(max.x, max.y - r);
+            quarter_arc(b, point(max.x - r, max.y - r), r, 0.0);
+            b.line_to(min.x + r, max.y);
+            quarter_arc(b, point(min.x + r, max.y - r), r, quarter);
+            b.line_to(min.x, min.y + r);
+            quarter_arc(b, point(min.x + r, min.y + r), r, 2.0 * quarter);
+            b.close();
+        });
+    }
+
+    /// Fills a circle at `center` of `radius` pixels, e.g. for radio buttons
+    /// & round line caps. Composited over the existing coverage & clipped to
+    /// the mask.
+    pub fn fill_circle(&mut self, center: Point<f32>, radius: f32) {
+        let r = vector(radius, radius);
+        self.fill_rounded_rect(
+            Rect {
+                min: center - r,
+                max: center + r,
+            },
+            radius,
+        );
+    }
+
+    /// Fills a line from `from` to `to` `width` pixels wide, with square ends
+    /// at `from` & `to`, e.g. for separators & underlines. Composited over the
+    /// existing coverage & clipped to the mask. Add `fill_circle`s of half
+    /// `width` at the ends for round caps.
+    pub fn stroke_line(&mut self, from: Point<f32>, to: Point<f32>, width: f32) {
+        let d = to - from;
+        let len = (d.x * d.x + d.y * d.y).sqrt();
+        if len <= 0.0 || width <= 0.0 {
+            return;
+        }
+        let n: Vector<f32> = vector(-d.y, d.x) * (width / 2.0 / len);
+        let corners = [from + n, to + n, to - n, from - n];
+        let bounds = corners.iter().fold(
+            Rect {
+                min: corners[0],
+                max: corners[0],
+            },
+            |r, p| Rect {
+                min: point(r.min.x.min(p.x), r.min.y.min(p.y)),
+                max: point(r.max.x.max(p.x), r.max.y.max(p.y)),
+            },
+        );
+        self.fill(bounds, |b| {
+            b.move_to(corners[0].x, corners[0].y);
+            for p in &corners[1..] {
+                b.line_to(p.x, p.y);
+            }
+            b.close();
+        });
+    }
+}
@@ -0,0 +1,129 @@
+//! Pluggable text shaping, see `Shaper`.
+use crate::{point, Font, GlyphId, LayoutOptions, Point, PositionedGlyph, Scale};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// An OpenType feature setting for a `Shaper`, e.g. `liga` off or `salt` 2.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Feature {
+    pub tag: [u8; 4],
+    /// `0` disables the feature, `1` enables it & larger values select an
+    /// alternate.
+    pub value: u32,
+}
+
+/// A glyph output by a `Shaper`.
+///
+/// Advances & offsets are in pixels in rusttype's coordinates, so y points
+/// down.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GlyphInfo {
+    pub id: GlyphId,
+    /// Byte index in the shaped text of the first char this glyph represents.
+    pub cluster: usize,
+    /// How far to move the pen after this glyph, including kerning.
+    pub advance: (f32, f32),
+    /// Offset of this glyph from the pen position, e.g. for marks.
+    pub offset: (f32, f32),
+}
+
+/// Maps text to positioned glyphs, allowing other shapers, e.g. HarfBuzz or
+/// Allsorts bindings, to be used with rusttype's layout, caching &
+/// rasterization. See `Font::layout_shaped`.
+///
+/// # Example
+///
+/// ```
+/// # use rusttype::*;
+/// /// Lays out text without kerning.
+/// struct Unkerned;
+///
+/// impl Shaper for Unkerned {
+///     fn shape(
+///         &self,
+///         font: &Font<'_>,
+///         text: &str,
+///         scale: Scale,
+///         _options: &LayoutOptions,
+///         output: &mut Vec<GlyphInfo>,
+///     ) {
+///         for (cluster, c) in text.char_indices() {
+///             let glyph = font.glyph(c).scaled(scale);
+///             output.push(GlyphInfo {
+///                 id: glyph.id(),
+///                 cluster,
+///                 advance: (glyph.h_metrics().advance_width, 0.0),
+///                 offset: (0.0, 0.0),
+///             });
+///         }
+///     }
+/// }
+///
+/// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+/// # let font = Font::try_from_bytes(font_data).unwrap();
+/// let options = LayoutOptions::default();
+/// let glyphs = font.layout_shaped(&Unkerned, "Hi", Scale::uniform(24.0), point(0.0, 20.0), &options);
+/// assert_eq!(glyphs.len(), 2);
+/// ```
+pub trait Shaper {
+    /// Appends the glyphs of `text` shaped with `font` at `scale` to `output`
+    /// in visual order. `options` may select language & font features.
+    fn shape(
+        &self,
+        font: &Font<'_>,
+        text: &str,
+        scale: Scale,
+        options: &LayoutOptions,
+        output: &mut Vec<GlyphInfo>,
+    );
+}
+
+/// The built-in shaper used by `Font::layout_with_options`, mapping each char
+/// to a glyph with `locl` forms & kerning.
+///
+/// Doesn't support complex scripts or the `LayoutOptions::feature` settings.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SimpleShaper;
+
+impl Shaper for SimpleShaper {
+    fn shape(
+        &self,
+        font: &Font<'_>,
+        text: &str,
+        scale: Scale,
+        options: &LayoutOptions,
+        output: &mut Vec<GlyphInfo>,
+    ) {
+        let layout = font.layout_with_options(text, scale, point(0.0, 0.0), options);
+        let mut glyphs = text.char_indices().zip(layout).peekable();
+        while let Some(((cluster, _), glyph)) = glyphs.next() {
+            let advance = match glyphs.peek() {
+                Some((_, next)) => next.position().x - glyph.position().x,
+                None => glyph.unpositioned().h_metrics().advance_width,
+            };
+            output.push(GlyphInfo {
+                id: glyph.id(),
+                cluster,
+                advance: (advance, 0.0),
+                offset: (0.0, 0.0),
+            });
+        }
+    }
+}
+
+impl<'font> Font<'font> {
+    /// Lays out `s` like `layout_with_options`, shaped by `shaper`.
+    ///
+    /// See `Shaper` for an example.
+    pub fn layout_shaped<S: Shaper + ?Sized>(
+        &self,
+        shaper: &S,
+        s: &str,
+        scale: Scale,
+        start: Point<f32>,
+        options: &LayoutOptions,
+    ) -> Vec<PositionedGlyph<'font>> {
+        self.glyph_run(shaper, s, scale, start, options)
+            .into_glyphs()
+    }
+}
@@ -0,0 +1,82 @@
+//! Full OpenType shaping with rustybuzz, see `RustybuzzShaper`.
+use crate::{Font, GlyphId, GlyphInfo, LayoutOptions, Scale, Shaper};
+use rustybuzz::ttf_parser::Tag;
+use rustybuzz::{BufferClusterLevel, Face, UnicodeBuffer};
+
+/// A `Shaper` using rustybuzz, a port of HarfBuzz, for full OpenType
+/// shaping: complex scripts, e.g. Arabic joining forms & Indic reordering,
+/// contextual substitutions, ligatures, kerning & mark positioning.
+///
+/// The direction, script & language of the text are guessed from its
+/// chars, unless `LayoutOptions::language` is set. `LayoutOptions::feature`
+/// settings are passed on, enabling, disabling or selecting alternates as
+/// in HarfBuzz. Right-to-left text is output in visual order, so glyphs
+/// are laid out left to right, & each glyph's cluster maps it back to the
+/// text, see `Font::glyph_run`.
+///
+/// Shaping tables are parsed for each call, for large volumes of text use
+/// rustybuzz directly with a cached `rustybuzz::ShapePlan`.
+///
+/// # Example
+///
+/// ```
+/// # use rusttype::*;
+/// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+/// # let font = Font::try_from_bytes(font_data).unwrap();
+/// let options = LayoutOptions::default();
+/// let text = "بيت";
+/// let run = font.glyph_run(&RustybuzzShaper, text, Scale::uniform(24.0), point(0.0, 20.0), &options);
+/// // right to left, in joining forms
+/// assert_eq!(run.clusters(), [4, 2, 0]);
+/// assert_ne!(run.glyphs()[2].id(), font.glyph('ب').id());
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RustybuzzShaper;
+
+impl Shaper for RustybuzzShaper {
+    fn shape(
+        &self,
+        font: &Font<'_>,
+        text: &str,
+        scale: Scale,
+        options: &LayoutOptions,
+        output: &mut Vec<GlyphInfo>,
+    ) {
+        let face = Face::from_face(font.full_face());
+        let mut buffer = UnicodeBuffer::new();
+        buffer.push_str(text);
+        // marks keep their own clusters, like the built-in shapers
+        buffer.set_cluster_level(BufferClusterLevel::MonotoneCharacters);
+        if let Some(language) = options.get_language() {
+            // the OpenType language system tag as a HarfBuzz private use subtag
+            let tag = core::str::from_utf8(&language.0).unwrap_or_default();
+            if let Ok(language) = format!("x-hbot{}", tag.trim_end()).parse() {
+                buffer.set_language(language);
+            }
+        }
+        let features: Vec<_> = options
+            .get_features()
+            .iter()
+            .map(|f| rustybuzz::Feature::new(Tag::from_bytes(&f.tag), f.value, ..))
+            .collect();
+        let glyphs = rustybuzz::shape(&face, &features, buffer);
+
+        let x_factor = font.kerning_factor(scale);
+        let y_factor = font.scale_for_pixel_height(scale.y);
+        let infos = glyphs.glyph_infos().iter();
+        for (info, position) in infos.zip(glyphs.glyph_positions()) {
+            output.push(GlyphInfo {
+                id: GlyphId(info.glyph_id as u16),
+                cluster: info.cluster as usize,
+                advance: (
+                    x_factor * position.x_advance as f32,
+                    -y_factor * position.y_advance as f32,
+                ),
+                offset: (
+                    x_factor * position.x_offset as f32,
+                    -y_factor * position.y_offset as f32,
+                ),
+            });
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Glyph outline conversions to tiny-skia `Path`s, see
+//! `PositionedGlyph::fill_pixmap`.
+use crate::outliner::OutlineTranslator;
+use crate::{OutlineBuilder, PositionedGlyph, ScaledGlyph};
+use tiny_skia::{FillRule, Paint, Path, PathBuilder, PixmapMut, Transform};
+
+/// Collects an outline into a `PathBuilder`.
+struct SkiaPathBuilder(PathBuilder);
+
+impl OutlineBuilder for SkiaPathBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.line_to(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.0.quad_to(x1, y1, x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.0.cubic_to(x1, y1, x2, y2, x, y);
+    }
+
+    fn close(&mut self) {
+        self.0.close();
+    }
+}
+
+impl ScaledGlyph<'_> {
+    /// Returns the outline of this glyph as a tiny-skia `Path` in pixels, y
+    /// down relative to the glyph origin, as given to `build_outline`. `None`
+    /// for glyphs without an outline.
+    pub fn tiny_skia_path(&self) -> Option<Path> {
+        let mut builder = SkiaPathBuilder(PathBuilder::new());
+        self.build_outline(&mut builder);
+        builder.0.finish()
+    }
+}
+
+impl PositionedGlyph<'_> {
+    /// Returns the outline of this glyph as a tiny-skia `Path` in pixels at
+    /// its position, e.g. straight from a layout. `None` for glyphs without an
+    /// outline.
+    pub fn tiny_skia_path(&self) -> Option<Path> {
+        let mut builder = SkiaPathBuilder(PathBuilder::new());
+        self.sg
+            .build_outline(&mut OutlineTranslator::new(&mut builder, self.position));
+        builder.0.finish()
+    }
+
+    /// Fills the outline of this glyph, at its position, into `pixmap` with
+    /// `paint` using the non-zero fill rule of font outlines. Glyphs without an
+    /// outline draw nothing.
+    ///
+    /// Glyphs are always filled from their outline, including those drawn from
+    /// embedded bitmaps by `draw`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// use tiny_skia::{Color, Paint, Pixmap};
+    ///
+    /// let mut pixmap = Pixmap::new(200, 40).unwrap();
+    /// let mut paint = Paint::default();
+    /// paint.set_color(Color::from_rgba8(40, 40, 200, 255));
+    /// for glyph in font.layout("Hello tiny-skia", Scale::uniform(24.0), point(4.0, 28.0)) {
+    ///     glyph.fill_pixmap(&mut pixmap.as_mut(), &paint);
+    /// }
+    /// ```
+    pub fn fill_pixmap(&self, pixmap: &mut PixmapMut<'_>, paint: &Paint<'_>) {
+        if let Some(path) = self.tiny_skia_path() {
+            pixmap.fill_path(&path, paint, FillRule::Winding, Transform::identity(), None);
+        }
+    }
+}
@@ -0,0 +1,306 @@
+//! Glyphs of user provided outlines, e.g. icons, see `StandaloneGlyph`.
+#[cfg(feature = "gpu_cache")]
+use crate::hash::Fnv1a;
+use crate::{point, Glyph, OutlineBuilder, Rect, Scale, VMetrics};
+#[cfg(not(feature = "has-atomics"))]
+use alloc::rc::Rc as Arc;
+#[cfg(feature = "has-atomics")]
+use alloc::sync::Arc;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A glyph of a user provided outline, e.g. an icon or a shape from a vector
+/// image, that isn't part of a font.
+///
+/// `glyph` returns a regular `Glyph`, so the outline is scaled, positioned,
+/// drawn & cached exactly like the glyphs of a text run, e.g. positioned at
+/// the caret of a layout & queued in a `gpu_cache::Cache`, where textures
+/// are keyed by outline, so standalone glyphs can share a font id. The
+/// outline is kept as given, so drawn without rounding & with cubics as
+/// cubics. Having no font, the glyph's `font` is `None`, & font features,
+/// like hinting & embedded bitmaps, don't apply.
+///
+/// Clones share the outline.
+///
+/// # Example
+///
+/// ```
+/// # use rusttype::*;
+/// // a square occupying the lower half of the em
+/// let mut builder = StandaloneGlyph::builder(1000).advance_width(600);
+/// builder.move_to(50.0, 0.0);
+/// builder.line_to(550.0, 0.0);
+/// builder.line_to(550.0, 500.0);
+/// builder.line_to(50.0, 500.0);
+/// builder.close();
+/// let icon = builder.build();
+///
+/// let glyph = icon.glyph().scaled(Scale::uniform(20.0));
+/// assert_eq!(glyph.h_metrics().advance_width, 12.0);
+/// let glyph = glyph.positioned(point(0.0, 20.0));
+/// assert_eq!(glyph.pixel_bounding_box().unwrap().height(), 10);
+/// glyph.draw(|x, y, v| { /* composite like any other glyph */ });
+/// ```
+#[derive(Clone, Debug)]
+pub struct StandaloneGlyph {
+    outline: Arc<Outline>,
+}
+
+/// The outline & metrics of a `StandaloneGlyph`, in font units, y up.
+#[derive(Debug)]
+struct Outline {
+    units_per_em: u16,
+    ascent: f32,
+    descent: f32,
+    advance_width: f32,
+    /// Contours, each a `MoveTo`, drawing segments & a `Close`.
+    segments: Vec<Segment>,
+    /// The bounds of all the points, including control points, `None` if
+    /// empty.
+    bounds: Option<Rect<f32>>,
+    /// A hash of the outline & the metrics scaling it, see `StandaloneGlyph::key`.
+    #[cfg(feature = "gpu_cache")]
+    key: u64,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Segment {
+    MoveTo([f32; 2]),
+    LineTo([f32; 2]),
+    QuadTo([f32; 2], [f32; 2]),
+    CurveTo([f32; 2], [f32; 2], [f32; 2]),
+    Close,
+}
+
+impl StandaloneGlyph {
+    /// Returns a builder of a glyph with outline coordinates in font units
+    /// of `units_per_em` per em, y up with the baseline at `0`. Like fonts,
+    /// `units_per_em` is clamped to `16..=16384`.
+    pub fn builder(units_per_em: u16) -> StandaloneGlyphBuilder {
+        let units_per_em = units_per_em.clamp(16, 16384);
+        StandaloneGlyphBuilder {
+            units_per_em,
+            ascent: units_per_em as i16,
+            descent: 0,
+            advance_width: units_per_em,
+            segments: Vec::new(),
+            contour: None,
+            last: [0.0; 2],
+        }
+    }
+
+    /// The glyph, with `GlyphId(0)` & no font.
+    #[inline]
+    pub fn glyph(&self) -> Glyph<'static> {
+        Glyph::standalone(self.clone())
+    }
+
+    /// The vertical metrics of the glyph at `scale`, like `Font::v_metrics`
+    /// of a font with the glyph's ascent & descent, see
+    /// `StandaloneGlyphBuilder::v_metrics`.
+    pub fn v_metrics(&self, scale: Scale) -> VMetrics {
+        let scale = self.scale_for_pixel_height(scale.y);
+        self.v_metrics_unscaled() * scale
+    }
+
+    /// The vertical metrics of the glyph in font units, the line gap `0`.
+    pub(crate) fn v_metrics_unscaled(&self) -> VMetrics {
+        VMetrics {
+            ascent: self.outline.ascent,
+            descent: self.outline.descent,
+            line_gap: 0.0,
+        }
+    }
+
+    /// See `Font::scale_for_pixel_height`.
+    pub(crate) fn scale_for_pixel_height(&self, height: f32) -> f32 {
+        height / (self.outline.ascent - self.outline.descent)
+    }
+
+    #[inline]
+    pub(crate) fn units_per_em(&self) -> u16 {
+        self.outline.units_per_em
+    }
+
+    #[inline]
+    pub(crate) fn advance_width(&self) -> f32 {
+        self.outline.advance_width
+    }
+
+    /// The bounds of the outline in font units, y up.
+    #[inline]
+    pub(crate) fn bounds(&self) -> Option<Rect<f32>> {
+        self.outline.bounds
+    }
+
+    /// Identifies what the glyph draws, so glyphs of different outlines don't
+    /// share `gpu_cache` textures.
+    #[cfg(feature = "gpu_cache")]
+    #[inline]
+    pub(crate) fn key(&self) -> u64 {
+        self.outline.key
+    }
+
+    /// Builds the outline in font units, y up, returning `false` if empty.
+    pub(crate) fn build_outline(&self, builder: &mut dyn OutlineBuilder) -> bool {
+        self.outline.build(builder)
+    }
+}
+
+impl Outline {
+    #[cfg(feature = "gpu_cache")]
+    fn hash(&self) -> u64 {
+        let mut hasher = Fnv1a::new();
+        hasher.write_f32(self.ascent);
+        hasher.write_f32(self.descent);
+        self.build(&mut hasher);
+        hasher.finish()
+    }
+
+    fn build(&self, builder: &mut dyn OutlineBuilder) -> bool {
+        for segment in &self.segments {
+            match *segment {
+                Segment::MoveTo([x, y]) => builder.move_to(x, y),
+                Segment::LineTo([x, y]) => builder.line_to(x, y),
+                Segment::QuadTo([x1, y1], [x, y]) => builder.quad_to(x1, y1, x, y),
+                Segment::CurveTo([x1, y1], [x2, y2], [x, y]) => {
+                    builder.curve_to(x1, y1, x2, y2, x, y)
+                }
+                Segment::Close => builder.close(),
+            }
+        }
+        !self.segments.is_empty()
+    }
+}
+
+/// Builds a `StandaloneGlyph` from outline segments given to its
+/// `OutlineBuilder` methods, see `StandaloneGlyph::builder`.
+///
+/// Contours are closed by `close` or the next `move_to`.
+#[derive(Clone, Debug)]
+pub struct StandaloneGlyphBuilder {
+    units_per_em: u16,
+    ascent: i16,
+    descent: i16,
+    advance_width: u16,
+    segments: Vec<Segment>,
+    /// The index of the `MoveTo` of the open contour, if any.
+    contour: Option<usize>,
+    /// The current point.
+    last: [f32; 2],
+}
+
+impl StandaloneGlyphBuilder {
+    /// Sets the ascent & descent of the glyph, in font units, default
+    /// `units_per_em` & `0`. `Scale` sizes the distance between them in
+    /// pixels, like it does for fonts, so use the values of the font of a
+    /// text run to size the glyph like its text. An `ascent` not above
+    /// `descent` leaves no height to scale, so the defaults are used instead.
+    pub fn v_metrics(mut self, ascent: i16, descent: i16) -> Self {
+        self.ascent = ascent;
+        self.descent = descent;
+        self
+    }
+
+    /// Sets the advance width in font units, default `units_per_em`.
+    pub fn advance_width(mut self, advance_width: u16) -> Self {
+        self.advance_width = advance_width;
+        self
+    }
+
+    /// Builds the glyph. An empty outline builds a glyph that draws nothing,
+    /// like a space.
+    pub fn build(mut self) -> StandaloneGlyph {
+        self.end_contour();
+        if self.ascent <= self.descent {
+            self.ascent = self.units_per_em as i16;
+            self.descent = 0;
+        }
+        let bounds = self
+            .segments
+            .iter()
+            .flat_map(|segment| match *segment {
+                Segment::MoveTo(p) | Segment::LineTo(p) => [Some(p), None, None],
+                Segment::QuadTo(p1, p) => [Some(p1), Some(p), None],
+                Segment::CurveTo(p1, p2, p) => [Some(p1), Some(p2), Some(p)],
+                Segment::Close => [None; 3],
+            })
+            .flatten()
+            .fold(None, |bounds: Option<Rect<f32>>, [x, y]| {
+                Some(bounds.map_or(
+                    Rect {
+                        min: point(x, y),
+                        max: point(x, y),
+                    },
+                    |r| Rect {
+                        min: point(r.min.x.min(x), r.min.y.min(y)),
+                        max: point(r.max.x.max(x), r.max.y.max(y)),
+                    },
+                ))
+            });
+        let outline = Outline {
+            units_per_em: self.units_per_em,
+            ascent: self.ascent.into(),
+            descent: self.descent.into(),
+            advance_width: self.advance_width.into(),
+            segments: self.segments,
+            bounds,
+            #[cfg(feature = "gpu_cache")]
+            key: 0,
+        };
+        #[cfg(feature = "gpu_cache")]
+        let outline = Outline {
+            key: outline.hash(),
+            ..outline
+        };
+        StandaloneGlyph {
+            outline: Arc::new(outline),
+        }
+    }
+
+    /// Pushes a drawing segment ending at `end`, starting a contour at the
+    /// current point if none is open.
+    fn segment(&mut self, segment: Segment, end: [f32; 2]) {
+        if self.contour.is_none() {
+            let [x, y] = self.last;
+            self.move_to(x, y);
+        }
+        self.segments.push(segment);
+        self.last = end;
+    }
+
+    /// Closes the open contour, dropping it if it has no drawing segments.
+    fn end_contour(&mut self) {
+        if let Some(start) = self.contour.take() {
+            match self.segments.len() - start {
+                1 => self.segments.truncate(start),
+                _ => self.segments.push(Segment::Close),
+            }
+        }
+    }
+}
+
+impl OutlineBuilder for StandaloneGlyphBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.end_contour();
+        self.contour = Some(self.segments.len());
+        self.segments.push(Segment::MoveTo([x, y]));
+        self.last = [x, y];
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.segment(Segment::LineTo([x, y]), [x, y]);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.segment(Segment::QuadTo([x1, y1], [x, y]), [x, y]);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.segment(Segment::CurveTo([x1, y1], [x2, y2], [x, y]), [x, y]);
+    }
+
+    fn close(&mut self) {
+        self.end_contour();
+    }
+}
@@ -0,0 +1,576 @@
+//! A registry of loaded fonts, see `FontStore`.
+use crate::Font;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::sync::Arc;
+use std::{fmt, fs, io};
+
+/// A font weight on the CSS / OpenType `100`-`900` scale.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Weight(pub u16);
+
+impl Weight {
+    pub const THIN: Weight = Weight(100);
+    pub const EXTRA_LIGHT: Weight = Weight(200);
+    pub const LIGHT: Weight = Weight(300);
+    pub const NORMAL: Weight = Weight(400);
+    pub const MEDIUM: Weight = Weight(500);
+    pub const SEMI_BOLD: Weight = Weight(600);
+    pub const BOLD: Weight = Weight(700);
+    pub const EXTRA_BOLD: Weight = Weight(800);
+    pub const BLACK: Weight = Weight(900);
+}
+
+impl Default for Weight {
+    #[inline]
+    fn default() -> Self {
+        Weight::NORMAL
+    }
+}
+
+/// The slant of a font.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Style {
+    Normal,
+    /// A cursive form designed to be slanted.
+    Italic,
+    /// A slanted form of the normal design.
+    Oblique,
+}
+
+impl Default for Style {
+    #[inline]
+    fn default() -> Self {
+        Style::Normal
+    }
+}
+
+/// Identifies a font in a `FontStore`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FontKey {
+    /// The font family name, e.g. `"Fira Sans"`.
+    pub family: String,
+    pub weight: Weight,
+    pub style: Style,
+}
+
+impl FontKey {
+    /// Returns the key described by the font's name & OS/2 tables. The family
+    /// is the typographic family name if present, otherwise the legacy family
+    /// name, preferring English. It's empty if the font has neither.
+    pub fn of(font: &Font<'_>) -> Self {
+        let face = font.inner();
+        let family = font.family_name().unwrap_or_default();
+
+        let style = match face.style() {
+            owned_ttf_parser::Style::Normal => Style::Normal,
+            owned_ttf_parser::Style::Italic => Style::Italic,
+            owned_ttf_parser::Style::Oblique => Style::Oblique,
+        };
+
+        FontKey {
+            family,
+            weight: Weight(face.weight().to_number()),
+            style,
+        }
+    }
+}
+
+impl fmt::Display for FontKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {:?}", self.family, self.weight.0, self.style)
+    }
+}
+
+/// A lightweight, `Copy`, reference to a font in a `FontStore`.
+///
+/// Handles don't keep fonts loaded. Once a font is unloaded its handles are
+/// invalid & never become valid again, even if the slot is reused.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FontHandle {
+    index: u32,
+    generation: u32,
+}
+
+#[derive(Debug)]
+struct Slot {
+    generation: u32,
+    entry: Option<Entry>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    key: FontKey,
+    font: Font<'static>,
+    /// Font collection index.
+    index: u32,
+    data_hash: u64,
+}
+
+/// A registry of loaded fonts, by family, weight & style.
+///
+/// Loading identical font data more than once under the same key returns the
+/// handle of the already loaded font.
+///
+/// # Example
+///
+/// ```
+/// # use rusttype::*;
+/// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+/// let mut store = FontStore::new();
+/// let handle = store.load(font_data.to_vec()).unwrap();
+///
+/// let key = FontKey {
+///     family: "DejaVu Sans Mono".into(),
+///     weight: Weight::NORMAL,
+///     style: Style::Normal,
+/// };
+/// assert_eq!(store.find(&key), Some(handle));
+/// let font: &Font<'static> = store.get(handle).unwrap();
+///
+/// store.unload(handle);
+/// assert!(store.get(handle).is_none());
+/// ```
+#[derive(Debug, Default)]
+pub struct FontStore {
+    slots: Vec<Slot>,
+    free: Vec<u32>,
+    by_key: HashMap<FontKey, FontHandle>,
+    by_data: HashMap<u64, Vec<FontHandle>>,
+    /// Fonts available to `query`, the system fonts unless set.
+    #[cfg(feature = "fontdb")]
+    database: Option<fontdb::Database>,
+    /// The fonts loaded from `database` faces, so queries don't copy & hash
+    /// the face data again.
+    #[cfg(feature = "fontdb")]
+    by_database: HashMap<fontdb::ID, FontHandle>,
+}
+
+impl FontStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a font, keyed by its own name & OS/2 tables, see `FontKey::of`.
+    /// Replaces & unloads any font with the same key.
+    ///
+    /// Returns `None` for invalid data.
+    pub fn load(&mut self, data: Vec<u8>) -> Option<FontHandle> {
+        self.load_index(data, 0)
+    }
+
+    /// Loads the font at `index` of a font collection, see `load`.
+    pub fn load_index(&mut self, data: Vec<u8>, index: u32) -> Option<FontHandle> {
+        self.load_shared(Arc::new(data), index, None)
+    }
+
+    /// Loads a font with an explicit `key`, rather than one read from the
+    /// font's tables. Replaces & unloads any font with the same key.
+    ///
+    /// Returns `None` for invalid data.
+    pub fn load_as(&mut self, key: FontKey, data: Vec<u8>) -> Option<FontHandle> {
+        self.load_shared(Arc::new(data), 0, Some(key))
+    }
+
+    /// Loads the font at `index` of `data`, keyed by `key` or the font's own
+    /// key if `None`.
+    fn load_shared(
+        &mut self,
+        data: Arc<Vec<u8>>,
+        index: u32,
+        key: Option<FontKey>,
+    ) -> Option<FontHandle> {
+        let data_hash = hash_data(&data, index);
+        if let Some(handle) = self.find_data(data_hash, &data, index, key.as_ref()) {
+            return Some(handle);
+        }
+        let font = Font::from_shared(data, index).ok()?;
+        let key = key.unwrap_or_else(|| FontKey::of(&font));
+        Some(self.add(key, font, index, data_hash))
+    }
+
+    /// Loads all `.ttf`, `.otf`, `.ttc` & `.otc` files in the directory, not
+    /// recursively. Every font of a font collection is loaded, sharing the
+    /// file data.
+    ///
+    /// Files are loaded in path order, each replacing any earlier font with
+    /// the same key. Invalid font files are skipped, returns the handles of the
+    /// loaded fonts.
+    pub fn load_dir<P: AsRef<Path>>(&mut self, dir: P) -> io::Result<Vec<FontHandle>> {
+        let mut handles = Vec::new();
+        for path in font_files(dir.as_ref())? {
+            self.load_file_data(fs::read(path)?, &mut handles);
+        }
+        Ok(handles)
+    }
+
+    /// Loads all font files in the directory asynchronously, reading, parsing
+    /// & hashing each on tokio's blocking thread pool, see `load_dir`.
+    #[cfg(feature = "tokio")]
+    pub async fn load_dir_async<P: AsRef<Path>>(&mut self, dir: P) -> io::Result<Vec<FontHandle>> {
+        let dir = dir.as_ref().to_owned();
+        let paths = tokio::task::spawn_blocking(move || font_files(&dir))
+            .await
+            .map_err(io::Error::other)??;
+
+        let mut handles = Vec::new();
+        for path in paths {
+            let fonts = tokio::task::spawn_blocking(move || fs::read(path).map(parse_file_data))
+                .await
+                .map_err(io::Error::other)??;
+            for parsed in fonts {
+                handles.push(self.add_parsed(parsed));
+            }
+        }
+        Ok(handles)
+    }
+
+    fn load_file_data(&mut self, data: Vec<u8>, handles: &mut Vec<FontHandle>) {
+        let count = owned_ttf_parser::fonts_in_collection(&data).unwrap_or(1);
+        let data = Arc::new(data);
+        for index in 0..count.max(1) {
+            handles.extend(self.load_shared(Arc::clone(&data), index, None));
+        }
+    }
+
+    /// Returns the font best matching `family`, `weight` & `style`.
+    ///
+    /// Fonts are matched like CSS: the family name must match, ignoring
+    /// ASCII case, then the closest style & finally the closest weight is
+    /// picked. E.g. a bold request prefers heavier weights, a normal style
+    /// request prefers oblique over italic fonts.
+    ///
+    /// With the `fontdb` feature, the `database` is also searched & the
+    /// best matching font file is loaded into the store, unless there's an
+    /// exact match already loaded. The system fonts are used unless
+    /// `set_database` has been called.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rusttype::*;
+    /// let mut store = FontStore::new();
+    /// if let Some(handle) = store.query("Noto Sans", Weight::BOLD, Style::Italic) {
+    ///     let font = store.get(handle).unwrap();
+    ///     # let _ = font;
+    /// }
+    /// ```
+    pub fn query(&mut self, family: &str, weight: Weight, style: Style) -> Option<FontHandle> {
+        let exact = FontKey {
+            family: family.into(),
+            weight,
+            style,
+        };
+        if let Some(handle) = self.find(&exact) {
+            return Some(handle);
+        }
+
+        #[cfg(feature = "fontdb")]
+        {
+            let family = fontdb::Family::Name(family);
+            if let Some(handle) = self.query_database_family(family, weight, style) {
+                return Some(handle);
+            }
+        }
+
+        self.iter()
+            .filter(|(_, key, _)| key.family.eq_ignore_ascii_case(family))
+            .min_by_key(|(_, key, _)| {
+                (
+                    style_distance(style, key.style),
+                    weight_distance(weight, key.weight),
+                )
+            })
+            .map(|(handle, ..)| handle)
+    }
+
+    /// Sets the font database searched by `query`, replacing the system fonts.
+    #[cfg(feature = "fontdb")]
+    pub fn set_database(&mut self, database: fontdb::Database) {
+        self.database = Some(database);
+        self.by_database.clear();
+    }
+
+    /// Returns the font database searched by `query`, loading the system fonts
+    /// on first use unless `set_database` has been called.
+    #[cfg(feature = "fontdb")]
+    pub fn database(&mut self) -> &mut fontdb::Database {
+        self.database.get_or_insert_with(|| {
+            let mut database = fontdb::Database::new();
+            database.load_system_fonts();
+            database
+        })
+    }
+
+    #[cfg(feature = "fontdb")]
+    pub(crate) fn query_database_family(
+        &mut self,
+        family: fontdb::Family<'_>,
+        weight: Weight,
+        style: Style,
+    ) -> Option<FontHandle> {
+        let id = self.database().query(&fontdb::Query {
+            families: &[family],
+            weight: fontdb::Weight(weight.0),
+            style: match style {
+                Style::Normal => fontdb::Style::Normal,
+                Style::Italic => fontdb::Style::Italic,
+                Style::Oblique => fontdb::Style::Oblique,
+            },
+            ..fontdb::Query::default()
+        })?;
+        if let Some(&handle) = self.by_database.get(&id) {
+            if self.contains(handle) {
+                return Some(handle);
+            }
+        }
+        let (data, index) = self
+            .database()
+            .with_face_data(id, |data, index| (data.to_vec(), index))?;
+        let handle = self.load_index(data, index)?;
+        self.by_database.insert(id, handle);
+        Some(handle)
+    }
+
+    /// Returns the font of `handle`, `None` if it has been unloaded.
+    pub fn get(&self, handle: FontHandle) -> Option<&Font<'static>> {
+        self.entry(handle).map(|e| &e.font)
+    }
+
+    /// Returns the key of `handle`, `None` if it has been unloaded.
+    pub fn key(&self, handle: FontHandle) -> Option<&FontKey> {
+        self.entry(handle).map(|e| &e.key)
+    }
+
+    /// Returns the handle of the font with exactly the `key`.
+    pub fn find(&self, key: &FontKey) -> Option<FontHandle> {
+        self.by_key.get(key).copied()
+    }
+
+    /// Returns `true` if `handle` refers to a loaded font.
+    pub fn contains(&self, handle: FontHandle) -> bool {
+        self.entry(handle).is_some()
+    }
+
+    /// Unloads the font of `handle`, returning it. Clones of the font, e.g.
+    /// held by glyphs, keep it alive until dropped.
+    pub fn unload(&mut self, handle: FontHandle) -> Option<Font<'static>> {
+        let slot = self.slots.get_mut(handle.index as usize)?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+        let entry = slot.entry.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(handle.index);
+
+        if self.by_key.get(&entry.key) == Some(&handle) {
+            self.by_key.remove(&entry.key);
+        }
+        if let Some(handles) = self.by_data.get_mut(&entry.data_hash) {
+            handles.retain(|h| *h != handle);
+            if handles.is_empty() {
+                self.by_data.remove(&entry.data_hash);
+            }
+        }
+        Some(entry.font)
+    }
+
+    /// The number of loaded fonts.
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over all loaded fonts.
+    pub fn iter(&self) -> impl Iterator<Item = (FontHandle, &FontKey, &Font<'static>)> + '_ {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let entry = slot.entry.as_ref()?;
+            let handle = FontHandle {
+                index: index as u32,
+                generation: slot.generation,
+            };
+            Some((handle, &entry.key, &entry.font))
+        })
+    }
+
+    fn entry(&self, handle: FontHandle) -> Option<&Entry> {
+        let slot = self.slots.get(handle.index as usize)?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+        slot.entry.as_ref()
+    }
+
+    /// Returns the font loaded from `data` at `index` under `key`, or its own
+    /// key if `None`.
+    fn find_data(
+        &self,
+        data_hash: u64,
+        data: &[u8],
+        index: u32,
+        key: Option<&FontKey>,
+    ) -> Option<FontHandle> {
+        self.by_data.get(&data_hash)?.iter().copied().find(|&h| {
+            self.entry(h).is_some_and(|e| {
+                e.index == index
+                    && e.font.data() == data
+                    && match key {
+                        Some(key) => e.key == *key,
+                        None => e.key == FontKey::of(&e.font),
+                    }
+            })
+        })
+    }
+
+    /// Adds a font parsed by `parse_file_data`, unless already loaded, see
+    /// `load_shared`.
+    #[cfg(feature = "tokio")]
+    fn add_parsed(&mut self, parsed: ParsedFont) -> FontHandle {
+        let ParsedFont {
+            key,
+            font,
+            index,
+            data_hash,
+        } = parsed;
+        match self.find_data(data_hash, font.data(), index, Some(&key)) {
+            Some(handle) => handle,
+            None => self.add(key, font, index, data_hash),
+        }
+    }
+
+    /// Adds a font, replacing & unloading any with the same key.
+    fn add(&mut self, key: FontKey, font: Font<'static>, index: u32, data_hash: u64) -> FontHandle {
+        if let Some(existing) = self.find(&key) {
+            self.unload(existing);
+        }
+
+        let entry = Entry {
+            key: key.clone(),
+            font,
+            index,
+            data_hash,
+        };
+        let handle = match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.entry = Some(entry);
+                FontHandle {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    entry: Some(entry),
+                });
+                FontHandle {
+                    index: self.slots.len() as u32 - 1,
+                    generation: 0,
+                }
+            }
+        };
+        self.by_key.insert(key, handle);
+        self.by_data.entry(data_hash).or_default().push(handle);
+        handle
+    }
+}
+
+/// CSS font style matching preference, lower is better.
+fn style_distance(desired: Style, candidate: Style) -> u8 {
+    use Style::*;
+    let preference: [Style; 3] = match desired {
+        Normal => [Normal, Oblique, Italic],
+        Italic => [Italic, Oblique, Normal],
+        Oblique => [Oblique, Italic, Normal],
+    };
+    preference.iter().position(|s| *s == candidate).unwrap_or(3) as u8
+}
+
+/// CSS font weight matching preference, lower is better.
+fn weight_distance(desired: Weight, candidate: Weight) -> (u8, u16) {
+    let (desired, candidate) = (desired.0, candidate.0);
+    let lighter = (1, desired.saturating_sub(candidate));
+    let heavier = (1, candidate.saturating_sub(desired));
+    if candidate == desired {
+        (0, 0)
+    } else if (400..=500).contains(&desired) {
+        // desired up to 500, then lighter, then heavier
+        if candidate > desired && candidate <= 500 {
+            (1, candidate - desired)
+        } else if candidate < desired {
+            (2, desired - candidate)
+        } else {
+            (3, candidate - desired)
+        }
+    } else if desired < 400 {
+        if candidate < desired {
+            lighter
+        } else {
+            (2, candidate - desired)
+        }
+    } else if candidate > desired {
+        heavier
+    } else {
+        (2, desired - candidate)
+    }
+}
+
+fn hash_data(data: &[u8], index: u32) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    index.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A font of a file, keyed by its own key, ready to add to a store.
+#[cfg(feature = "tokio")]
+struct ParsedFont {
+    key: FontKey,
+    font: Font<'static>,
+    index: u32,
+    data_hash: u64,
+}
+
+/// Parses & hashes every font of file `data`, which needs no store so can
+/// run off the async task, see `FontStore::load_dir_async`.
+#[cfg(feature = "tokio")]
+fn parse_file_data(data: Vec<u8>) -> Vec<ParsedFont> {
+    let count = owned_ttf_parser::fonts_in_collection(&data).unwrap_or(1);
+    let data = Arc::new(data);
+    (0..count.max(1))
+        .filter_map(|index| {
+            let data_hash = hash_data(&data, index);
+            let font = Font::from_shared(Arc::clone(&data), index).ok()?;
+            Some(ParsedFont {
+                key: FontKey::of(&font),
+                font,
+                index,
+                data_hash,
+            })
+        })
+        .collect()
+}
+
+fn font_files(dir: &Path) -> io::Result<Vec<std::path::PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_font = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase())
+            .is_some_and(|e| matches!(e.as_str(), "ttf" | "otf" | "ttc" | "otc"));
+        if is_font && path.is_file() {
+            paths.push(path);
+        }
+    }
+    // deterministic load order
+    paths.sort();
+    Ok(paths)
+}
@@ -0,0 +1,123 @@
+//! Subpixel positioning strategies, binning glyph positions so layout,
+//! rasterization & `gpu_cache` keys agree, see `SubpixelStrategy`.
+#[cfg(all(feature = "libm-math", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{point, Point, PositionedGlyph, Vector};
+
+/// How finely glyph positions are binned within a pixel, see
+/// `SubpixelStrategy::select`.
+///
+/// Glyphs snapped with `snap_glyph` are rasterized at one of a few subpixel
+/// offsets, so each glyph has at most `bins` distinct rasterizations. Set
+/// the same strategy with `gpu_cache::CacheBuilder::subpixel` so cache keys
+/// & screen rects use the same bins.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SubpixelStrategy {
+    /// Whole pixel positions, one rasterization per glyph.
+    WholePixels,
+    /// Thirds of a pixel horizontally & whole pixels vertically, precise
+    /// horizontal spacing with crisp baselines.
+    HorizontalThirds,
+    /// Thirds of a pixel on both axes, for smooth motion in any direction.
+    Thirds,
+}
+
+/// What text is rendered for, to pick a `SubpixelStrategy`, see
+/// `SubpixelStrategy::select`. The default is static text.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct SubpixelTarget {
+    /// Whether glyphs move freely, e.g. animated or kinetic text.
+    pub animated: bool,
+    /// The current scroll velocity of the text, in pixels per frame.
+    pub scroll_velocity: Vector<f32>,
+}
+
+impl SubpixelStrategy {
+    /// Scroll speed, in pixels per frame, above which subpixel positions
+    /// aren't noticeable, see `select`.
+    pub const FAST_SCROLL: f32 = 4.0;
+
+    /// Picks a strategy for `target`:
+    ///
+    /// * `Thirds` for animated text & slow vertical scrolling, so motion is
+    ///   smooth on both axes.
+    /// * `WholePixels` for scrolling faster than `FAST_SCROLL`, as the detail
+    ///   is lost in motion & every frame reuses the same rasterizations.
+    /// * `HorizontalThirds` otherwise, e.g. static text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// let scrolling = SubpixelTarget {
+    ///     scroll_velocity: vector(0.0, 12.0),
+    ///     ..SubpixelTarget::default()
+    /// };
+    /// assert_eq!(SubpixelStrategy::select(scrolling), SubpixelStrategy::WholePixels);
+    /// assert_eq!(
+    ///     SubpixelStrategy::select(SubpixelTarget::default()),
+    ///     SubpixelStrategy::HorizontalThirds,
+    /// );
+    /// ```
+    pub fn select(target: SubpixelTarget) -> Self {
+        let velocity = target.scroll_velocity;
+        let speed = (velocity.x * velocity.x + velocity.y * velocity.y).sqrt();
+        if target.animated {
+            SubpixelStrategy::Thirds
+        } else if speed > Self::FAST_SCROLL {
+            SubpixelStrategy::WholePixels
+        } else if velocity.y != 0.0 {
+            SubpixelStrategy::Thirds
+        } else {
+            SubpixelStrategy::HorizontalThirds
+        }
+    }
+
+    /// The number of subpixel positions per pixel, horizontally & vertically.
+    #[inline]
+    pub fn bins(self) -> (u32, u32) {
+        match self {
+            SubpixelStrategy::WholePixels => (1, 1),
+            SubpixelStrategy::HorizontalThirds => (3, 1),
+            SubpixelStrategy::Thirds => (3, 3),
+        }
+    }
+
+    /// Rounds `p` to the nearest bin.
+    #[inline]
+    pub fn snap(self, p: Point<f32>) -> Point<f32> {
+        let (x_bins, y_bins) = self.bins();
+        let (x_bins, y_bins) = (x_bins as f32, y_bins as f32);
+        point(
+            (p.x * x_bins).round() / x_bins,
+            (p.y * y_bins).round() / y_bins,
+        )
+    }
+
+    /// Moves `glyph` to the nearest bin, e.g. to position laid out glyphs
+    /// before drawing.
+    ///
+    /// Each glyph is snapped individually, so it's at most half a bin from
+    /// its laid out position & errors don't accumulate along a line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let strategy = SubpixelStrategy::HorizontalThirds;
+    /// let glyphs: Vec<_> = font
+    ///     .layout("Hello", Scale::uniform(24.0), point(0.3, 20.4))
+    ///     .map(|g| strategy.snap_glyph(g))
+    ///     .collect();
+    /// assert_eq!(glyphs[0].position(), point(1.0 / 3.0, 20.0));
+    /// ```
+    pub fn snap_glyph<'font>(self, mut glyph: PositionedGlyph<'font>) -> PositionedGlyph<'font> {
+        let position = self.snap(glyph.position());
+        if position != glyph.position() {
+            glyph.set_position(position);
+        }
+        glyph
+    }
+}
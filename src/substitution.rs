@@ -0,0 +1,268 @@
+//! OpenType glyph substitution, e.g. ligatures, see `GsubShaper`.
+use crate::language::single_substitute;
+use crate::{Font, GlyphClass, GlyphId, GlyphInfo, LayoutOptions, Scale, Shaper};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use owned_ttf_parser::gsub::{LigatureSubstitution, SubstitutionSubtable};
+use owned_ttf_parser::opentype_layout::LookupFlags;
+use owned_ttf_parser::Tag;
+
+/// Features applied by `GsubShaper` unless disabled: glyph composition,
+/// localized forms, required, standard & contextual ligatures.
+const DEFAULT_FEATURES: [&[u8; 4]; 5] = [b"ccmp", b"locl", b"rlig", b"liga", b"clig"];
+
+/// A `Shaper` applying the font's `GSUB` substitutions before kerning, so
+/// e.g. "fi" & "ffl" become ligature glyphs.
+///
+/// Applies the `ccmp`, `locl`, `rlig`, `liga` & `clig` features by default,
+/// of the `LayoutOptions::language` or default language system. Features
+/// may be disabled with a `LayoutOptions::feature` value of `0`, & others
+/// enabled, e.g. `smcp`, with values selecting alternates from `1`.
+///
+/// Single, multiple, alternate & ligature substitutions are supported,
+/// skipping glyphs as the lookup flags require, but not contextual ones, so
+/// doesn't support complex scripts. Glyphs are kerned with
+/// `Font::pair_kerning` & marks attached with `Font::attachment_offset`.
+///
+/// # Example
+///
+/// ```
+/// # use rusttype::*;
+/// # let font_data: &[u8] = include_bytes!("../dev/fonts/opensans/OpenSans-Italic.ttf");
+/// # let font = Font::try_from_bytes(font_data).unwrap();
+/// let options = LayoutOptions::default();
+/// let glyphs = font.layout_shaped(&GsubShaper, "fit", Scale::uniform(24.0), point(0.0, 20.0), &options);
+/// // an "fi" ligature & a "t"
+/// assert_eq!(glyphs.len(), 2);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GsubShaper;
+
+impl Shaper for GsubShaper {
+    fn shape(
+        &self,
+        font: &Font<'_>,
+        text: &str,
+        scale: Scale,
+        options: &LayoutOptions,
+        output: &mut Vec<GlyphInfo>,
+    ) {
+        let mut glyphs: Vec<_> = text
+            .char_indices()
+            .map(|(cluster, c)| (font.glyph(c).id(), cluster))
+            .collect();
+        for (index, value) in lookups(font, options) {
+            apply_lookup(font, index, value, &mut glyphs);
+        }
+
+        // the pen & the glyph marks attach to at its pen position
+        let mut pen = 0.0;
+        let mut base = None;
+        for (i, &(id, cluster)) in glyphs.iter().enumerate() {
+            let attachment = base.and_then(|(base, base_pen): (GlyphId, f32)| {
+                let offset = font.attachment_offset(scale, base, id)?;
+                Some((offset.x + base_pen - pen, offset.y))
+            });
+            if let Some(offset) = attachment {
+                output.push(GlyphInfo {
+                    id,
+                    cluster,
+                    advance: (0.0, 0.0),
+                    offset,
+                });
+                continue;
+            }
+            let mut advance = font.glyph(id).scaled(scale).h_metrics().advance_width;
+            if let Some(&(next, _)) = glyphs.get(i + 1) {
+                advance += font.pair_kerning(scale, id, next);
+            }
+            output.push(GlyphInfo {
+                id,
+                cluster,
+                advance: (advance, 0.0),
+                offset: (0.0, 0.0),
+            });
+            base = Some((id, pen));
+            pen += advance;
+        }
+    }
+}
+
+/// The `GSUB` lookups of the features enabled by `options` & their values,
+/// in lookup list order, as they are applied.
+fn lookups(font: &Font<'_>, options: &LayoutOptions) -> Vec<(u16, u32)> {
+    let gsub = match font.layout_face().and_then(|face| face.tables().gsub) {
+        Some(gsub) => gsub,
+        None => return Vec::new(),
+    };
+    let value_of = |tag: Tag| {
+        let setting = options
+            .get_features()
+            .iter()
+            .find(|f| Tag::from_bytes(&f.tag) == tag);
+        match setting {
+            Some(feature) => feature.value,
+            None => DEFAULT_FEATURES
+                .iter()
+                .any(|default| Tag::from_bytes(default) == tag) as u32,
+        }
+    };
+    let language = options.get_language().map(|l| Tag::from_bytes(&l.0));
+
+    let mut lookups = Vec::new();
+    for script in gsub.scripts {
+        let language_system = language
+            .and_then(|tag| script.languages.find(tag))
+            .or(script.default_language);
+        let language_system = match language_system {
+            Some(language_system) => language_system,
+            None => continue,
+        };
+        let required = language_system.required_feature;
+        for feature_index in required.into_iter().chain(language_system.feature_indices) {
+            let feature = match gsub.features.get(feature_index) {
+                Some(feature) => feature,
+                None => continue,
+            };
+            let value = match Some(feature_index) == required {
+                true => value_of(feature.tag).max(1),
+                false => value_of(feature.tag),
+            };
+            if value > 0 {
+                lookups.extend(feature.lookup_indices.into_iter().map(|i| (i, value)));
+            }
+        }
+    }
+    lookups.sort_unstable();
+    lookups.dedup_by_key(|(index, _)| *index);
+    lookups
+}
+
+/// Applies `GSUB` lookup `index` to `glyphs` & their clusters, `value`
+/// selecting alternates.
+fn apply_lookup(font: &Font<'_>, index: u16, value: u32, glyphs: &mut Vec<(GlyphId, usize)>) {
+    let lookup = match font
+        .layout_face()
+        .and_then(|face| face.tables().gsub)
+        .and_then(|g| g.lookups.get(index))
+    {
+        Some(lookup) => lookup,
+        None => return,
+    };
+    let subtables: Vec<SubstitutionSubtable<'_>> = lookup.subtables.into_iter().collect();
+    let mut i = 0;
+    while i < glyphs.len() {
+        let glyph = owned_ttf_parser::GlyphId(glyphs[i].0 .0);
+        if is_ignored(font, lookup.flags, glyphs[i].0) {
+            i += 1;
+            continue;
+        }
+        let mut next = i + 1;
+        for subtable in &subtables {
+            let applied = match subtable {
+                SubstitutionSubtable::Single(single) => single_substitute(single, glyph)
+                    .map(|substitute| glyphs[i].0 = GlyphId(substitute.0))
+                    .is_some(),
+                SubstitutionSubtable::Multiple(multiple) => {
+                    let sequence = multiple
+                        .coverage
+                        .get(glyph)
+                        .and_then(|c| multiple.sequences.get(c));
+                    match sequence {
+                        Some(sequence) => {
+                            let cluster = glyphs[i].1;
+                            let substitutes = sequence.substitutes.into_iter();
+                            let replacement = substitutes.map(|id| (GlyphId(id.0), cluster));
+                            let len = glyphs.len();
+                            glyphs.splice(i..=i, replacement);
+                            // after the sequence, which may be empty
+                            next = i + 1 + glyphs.len() - len;
+                            true
+                        }
+                        None => false,
+                    }
+                }
+                SubstitutionSubtable::Alternate(alternate) => {
+                    let substitute = alternate
+                        .coverage
+                        .get(glyph)
+                        .and_then(|c| alternate.alternate_sets.get(c))
+                        .and_then(|set| set.alternates.get((value - 1) as u16));
+                    substitute
+                        .map(|substitute| glyphs[i].0 = GlyphId(substitute.0))
+                        .is_some()
+                }
+                SubstitutionSubtable::Ligature(ligatures) => {
+                    ligate(font, ligatures, lookup.flags, i, glyphs)
+                }
+                _ => false,
+            };
+            if applied {
+                break;
+            }
+        }
+        i = next;
+    }
+}
+
+/// Replaces the glyph at `i` & the following components of a ligature in
+/// `subtable` with the ligature glyph, keeping skipped glyphs, e.g. marks,
+/// after it. Returns whether a ligature matched.
+fn ligate(
+    font: &Font<'_>,
+    subtable: &LigatureSubstitution<'_>,
+    flags: LookupFlags,
+    i: usize,
+    glyphs: &mut Vec<(GlyphId, usize)>,
+) -> bool {
+    let first = owned_ttf_parser::GlyphId(glyphs[i].0 .0);
+    let set = match subtable
+        .coverage
+        .get(first)
+        .and_then(|c| subtable.ligature_sets.get(c))
+    {
+        Some(set) => set,
+        None => return false,
+    };
+    // indices of the glyphs matched by the components
+    let mut matched = Vec::new();
+    for ligature in set {
+        matched.clear();
+        let mut j = i + 1;
+        for component in ligature.components {
+            while j < glyphs.len() && is_ignored(font, flags, glyphs[j].0) {
+                j += 1;
+            }
+            match glyphs.get(j) {
+                Some(&(id, _)) if id.0 == component.0 => matched.push(j),
+                _ => break,
+            }
+            j += 1;
+        }
+        if matched.len() == usize::from(ligature.components.len()) {
+            glyphs[i].0 = GlyphId(ligature.glyph.0);
+            for &j in matched.iter().rev() {
+                glyphs.remove(j);
+            }
+            return true;
+        }
+    }
+    false
+}
+
+/// Returns whether `id` is skipped by lookups with `flags`.
+fn is_ignored(font: &Font<'_>, flags: LookupFlags, id: GlyphId) -> bool {
+    if !flags.ignore_flags() && flags.mark_attachment_type() == 0 {
+        return false;
+    }
+    match font.glyph_class(id) {
+        Some(GlyphClass::Base) => flags.ignore_base_glyphs(),
+        Some(GlyphClass::Ligature) => flags.ignore_ligatures(),
+        Some(GlyphClass::Mark) => {
+            let attachment = u16::from(flags.mark_attachment_type());
+            flags.ignore_marks()
+                || (attachment != 0 && font.mark_attachment_class(id) != attachment)
+        }
+        _ => false,
+    }
+}
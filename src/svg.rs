@@ -0,0 +1,114 @@
+//! `SVG ` table glyphs, see `PositionedGlyph::svg_document`.
+use crate::{vector, Affine2, GlyphId, PositionedGlyph, Rect};
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::Cow, format, string::String, vec::Vec};
+use core::ops::RangeInclusive;
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+/// The SVG document of a glyph, for drawing it with an SVG renderer. See
+/// `PositionedGlyph::svg_document`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgDocument<'a> {
+    /// The SVG document, decompressed if the font stores it gzipped.
+    pub data: Cow<'a, [u8]>,
+    /// The glyphs of the document, each drawn from its element with the id
+    /// `glyph<id>`, see `element_id`.
+    pub glyphs: RangeInclusive<GlyphId>,
+    /// The glyph to draw.
+    pub glyph: GlyphId,
+    /// Maps the document's user units, font units y down with the glyph origin
+    /// at `(0, 0)`, to pixels at the glyph's position, scale & transform.
+    pub transform: Affine2,
+    /// The glyph's advance width by its font's ascent to descent in pixels at
+    /// its position, the area an SVG glyph is designed for. Unlike
+    /// `pixel_bounding_box` this isn't transformed or derived from the outline,
+    /// which may differ from the SVG shape.
+    pub viewport: Rect<f32>,
+}
+
+impl SvgDocument<'_> {
+    /// The id of the element drawing `glyph`, e.g. `"glyph42"`.
+    pub fn element_id(&self) -> String {
+        format!("glyph{}", self.glyph.0)
+    }
+}
+
+impl PositionedGlyph<'_> {
+    /// Returns the SVG document of this glyph from the font's `SVG ` table,
+    /// e.g. for color emoji, `None` if the font has no SVG for it. Such glyphs
+    /// usually also have an outline, drawn by `draw`, for renderers not
+    /// supporting SVG.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let glyph = font.glyph('a').scaled(Scale::uniform(24.0)).positioned(point(0.0, 24.0));
+    /// match glyph.svg_document() {
+    ///     Some(svg) => {
+    ///         // render element `svg.element_id()` of `svg.data` with `svg.transform`
+    ///     }
+    ///     None => glyph.draw(|x, y, v| {}),
+    /// }
+    /// ```
+    pub fn svg_document(&self) -> Option<SvgDocument<'_>> {
+        let font = self.font()?;
+        let document = font.inner().glyph_svg_image(self.id().into())?;
+        let data = match document.data {
+            [0x1F, 0x8B, ..] => Cow::Owned(gunzip(document.data)?),
+            data => Cow::Borrowed(data),
+        };
+
+        let sg = &self.sg;
+        let transform = Affine2::scale(sg.scale.x, sg.scale.y)
+            .then(sg.transform())
+            .then(Affine2::translate(vector(self.position.x, self.position.y)));
+
+        let v_metrics = font.v_metrics(sg.api_scale);
+        let advance = sg.h_metrics().advance_width;
+        let viewport = Rect {
+            min: self.position + vector(0.0, -v_metrics.ascent),
+            max: self.position + vector(advance, -v_metrics.descent),
+        };
+
+        Some(SvgDocument {
+            data,
+            glyphs: document.start_glyph_id.into()..=document.end_glyph_id.into(),
+            glyph: self.id(),
+            transform,
+            viewport,
+        })
+    }
+}
+
+/// Decompresses gzip `data`, `None` if invalid.
+fn gunzip(data: &[u8]) -> Option<Vec<u8>> {
+    const FHCRC: u8 = 0x02;
+    const FEXTRA: u8 = 0x04;
+    const FNAME: u8 = 0x08;
+    const FCOMMENT: u8 = 0x10;
+
+    // id, deflate method, flags, mtime, extra flags & os
+    let flags = *data.get(3)?;
+    if data.get(2) != Some(&8) {
+        return None;
+    }
+    let mut start = 10;
+    if flags & FEXTRA != 0 {
+        let len = data.get(start..start + 2)?;
+        start += 2 + usize::from(u16::from_le_bytes([len[0], len[1]]));
+    }
+    for &flag in [FNAME, FCOMMENT].iter() {
+        if flags & flag != 0 {
+            // zero terminated
+            start += data.get(start..)?.iter().position(|&b| b == 0)? + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        start += 2;
+    }
+    miniz_oxide::inflate::decompress_to_vec(data.get(start..)?).ok()
+}
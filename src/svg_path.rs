@@ -0,0 +1,145 @@
+//! SVG path data of glyph outlines, see `PositionedGlyph::to_svg_path`.
+use crate::outliner::OutlineTranslator;
+use crate::{OutlineBuilder, PositionedGlyph};
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Options for `PositionedGlyph::to_svg_path`.
+///
+/// # Example
+///
+/// ```
+/// # use rusttype::SvgPathOptions;
+/// let options = SvgPathOptions::default().precision(1).y_up(true);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SvgPathOptions {
+    precision: u8,
+    y_up: bool,
+}
+
+impl Default for SvgPathOptions {
+    #[inline]
+    fn default() -> Self {
+        SvgPathOptions {
+            precision: 2,
+            y_up: false,
+        }
+    }
+}
+
+impl SvgPathOptions {
+    /// The maximum number of decimal places of coordinates, trailing zeros
+    /// are omitted. Default `2`, a hundredth of a pixel.
+    pub fn precision(mut self, digits: u8) -> Self {
+        self.precision = digits;
+        self
+    }
+
+    /// Returns the `precision` option.
+    pub fn get_precision(&self) -> u8 {
+        self.precision
+    }
+
+    /// Whether y coordinates increase upwards, negating them, e.g. for
+    /// documents flipped with `transform="scale(1 -1)"`. Default `false`, y
+    /// down like SVG user space & glyph positions.
+    pub fn y_up(mut self, y_up: bool) -> Self {
+        self.y_up = y_up;
+        self
+    }
+
+    /// Returns the `y_up` option.
+    pub fn get_y_up(&self) -> bool {
+        self.y_up
+    }
+}
+
+/// Writes outline events as SVG path commands.
+struct SvgPathWriter {
+    d: String,
+    options: SvgPathOptions,
+}
+
+impl SvgPathWriter {
+    fn command(&mut self, command: char, points: &[(f32, f32)]) {
+        self.d.push(command);
+        for (i, &(x, y)) in points.iter().enumerate() {
+            let y = if self.options.y_up { -y } else { y };
+            if i > 0 {
+                self.d.push(' ');
+            }
+            self.number(x);
+            self.d.push(' ');
+            self.number(y);
+        }
+    }
+
+    /// Writes `v` with at most `precision` decimal places.
+    fn number(&mut self, v: f32) {
+        let start = self.d.len();
+        let _ = write!(self.d, "{:.*}", usize::from(self.options.precision), v);
+        if self.d[start..].contains('.') {
+            let trimmed = self.d.trim_end_matches('0').trim_end_matches('.').len();
+            self.d.truncate(trimmed);
+        }
+        if &self.d[start..] == "-0" {
+            self.d.remove(start);
+        }
+    }
+}
+
+impl OutlineBuilder for SvgPathWriter {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.command('M', &[(x, y)]);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.command('L', &[(x, y)]);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.command('Q', &[(x1, y1), (x, y)]);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.command('C', &[(x1, y1), (x2, y2), (x, y)]);
+    }
+
+    fn close(&mut self) {
+        self.d.push('Z');
+    }
+}
+
+impl PositionedGlyph<'_> {
+    /// Returns the outline of this glyph as SVG path data, for the `d`
+    /// attribute of a `path` element, in pixels at its position, e.g. to
+    /// export laid out text to an SVG document. Empty for glyphs without an
+    /// outline.
+    ///
+    /// Contours are filled with the `nonzero` fill rule, SVG's default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let mut svg = String::from(r#"<svg xmlns="http://www.w3.org/2000/svg">"#);
+    /// for glyph in font.layout("Hi", Scale::uniform(24.0), point(0.0, 24.0)) {
+    ///     let d = glyph.to_svg_path(SvgPathOptions::default());
+    ///     svg += &format!(r#"<path d="{}"/>"#, d);
+    /// }
+    /// svg += "</svg>";
+    /// ```
+    pub fn to_svg_path(&self, options: SvgPathOptions) -> String {
+        let mut writer = SvgPathWriter {
+            d: String::new(),
+            options,
+        };
+        self.sg
+            .build_outline(&mut OutlineTranslator::new(&mut writer, self.position));
+        writer.d
+    }
+}
@@ -0,0 +1,329 @@
+//! Converters from rasterized coverage to GPU texture formats.
+//!
+//! Coverage, e.g. from `PositionedGlyph::draw` or a `CoverageMask`, is first
+//! converted to single channel `R8` data, the format of gpu_cache uploads,
+//! which can then be converted to other formats.
+//!
+//! With the `texture-compression` feature `R8` data can be block compressed
+//! to `BC4` (`BC4_UNORM`, `ATI1`) or `EAC R11` (`EAC_R11_UNORM`), a quarter of
+//! the size, for platforms with constrained memory or bandwidth. Compressed
+//! formats are made of 4x4 pixel blocks, so caches uploading compressed data
+//! should use `gpu_cache::CacheBuilder::align_4x4`.
+//!
+//! For minified text, e.g. in 3D world space, `mip_chain` generates mip levels
+//! of an atlas.
+#[cfg(all(feature = "libm-math", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{point, Rect};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// Converts coverage values in the range `0.0..=1.0` to `R8` bytes.
+///
+/// # Example
+///
+/// ```
+/// # use rusttype::texture;
+/// assert_eq!(texture::r8_from_coverage(&[0.0, 0.5, 1.0]), [0, 128, 255]);
+/// ```
+pub fn r8_from_coverage(coverage: &[f32]) -> Vec<u8> {
+    coverage
+        .iter()
+        .map(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8)
+        .collect()
+}
+
+/// Converts `R8` coverage to `RGBA8` white with the coverage as alpha, for
+/// APIs without single channel textures. Colour channels are premultiplied
+/// by alpha if `premultiplied`.
+pub fn rgba8_from_r8(r8: &[u8], premultiplied: bool) -> Vec<u8> {
+    let mut rgba = Vec::with_capacity(r8.len() * 4);
+    for &v in r8 {
+        let c = if premultiplied { v } else { 255 };
+        rgba.extend_from_slice(&[c, c, c, v]);
+    }
+    rgba
+}
+
+/// A downsampled level of an `R8` texture, see `mip_chain`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MipLevel {
+    pub width: u32,
+    pub height: u32,
+    /// Row major `R8` data.
+    pub data: Vec<u8>,
+}
+
+/// Generates up to `levels` mip levels, halving the size each level down to
+/// 1x1, of a `width` x `height` row major `R8` atlas containing `regions`,
+/// e.g. the rects passed to the gpu_cache `cache_queued` uploader. Level 0,
+/// the atlas itself, isn't included.
+///
+/// Each region is box filtered separately, so entries don't bleed into each
+/// other & minified text doesn't sparkle. Pixels outside every region are
+/// `0`. Averaging suits both coverage & signed distance field atlases.
+///
+/// Regions are only fully separated if their edges are multiples of
+/// `2^levels`, e.g. 2 levels with `CacheBuilder::align_4x4`, otherwise where
+/// neighbouring regions meet within a pixel of a level the later region wins.
+///
+/// # Panics
+///
+/// `r8` is shorter than `width * height`.
+pub fn mip_chain(
+    r8: &[u8],
+    width: u32,
+    height: u32,
+    regions: &[Rect<u32>],
+    levels: u32,
+) -> Vec<MipLevel> {
+    assert!(
+        r8.len() >= (width * height) as usize,
+        "r8 data smaller than dimensions"
+    );
+    let mut mips: Vec<MipLevel> = Vec::new();
+    let mut regions: Vec<Rect<u32>> = regions
+        .iter()
+        .map(|r| Rect {
+            min: point(r.min.x.min(width), r.min.y.min(height)),
+            max: point(r.max.x.min(width), r.max.y.min(height)),
+        })
+        .collect();
+    let (mut src_width, mut src_height) = (width, height);
+
+    while (mips.len() as u32) < levels && (src_width > 1 || src_height > 1) {
+        let src = mips.last().map(|m| &m.data[..]).unwrap_or(r8);
+        let level_width = src_width.div_ceil(2);
+        let level_height = src_height.div_ceil(2);
+        let mut data = vec![0; (level_width * level_height) as usize];
+
+        for region in &mut regions {
+            let level_region = Rect {
+                min: point(region.min.x / 2, region.min.y / 2),
+                max: point(region.max.x.div_ceil(2), region.max.y.div_ceil(2)),
+            };
+            for y in level_region.min.y..level_region.max.y {
+                for x in level_region.min.x..level_region.max.x {
+                    // average the source pixels within the region
+                    let (mut sum, mut count) = (0_u32, 0);
+                    for sy in (2 * y).max(region.min.y)..(2 * y + 2).min(region.max.y) {
+                        for sx in (2 * x).max(region.min.x)..(2 * x + 2).min(region.max.x) {
+                            sum += u32::from(src[(sx + sy * src_width) as usize]);
+                            count += 1;
+                        }
+                    }
+                    if let Some(mean) = (sum + count / 2).checked_div(count) {
+                        data[(x + y * level_width) as usize] = mean as u8;
+                    }
+                }
+            }
+            *region = level_region;
+        }
+
+        mips.push(MipLevel {
+            width: level_width,
+            height: level_height,
+            data,
+        });
+        src_width = level_width;
+        src_height = level_height;
+    }
+    mips
+}
+
+/// Returns the byte length of `width` x `height` pixels block compressed to
+/// `BC4` or `EAC R11`, 8 bytes per 4x4 block.
+#[cfg(feature = "texture-compression")]
+#[inline]
+pub fn compressed_len(width: u32, height: u32) -> usize {
+    blocks(width) * blocks(height) * 8
+}
+
+/// Compresses `width` x `height` row major `R8` data into `BC4` blocks, in
+/// row major block order.
+///
+/// Sizes that aren't a multiple of 4 are padded by repeating the edge pixels.
+///
+/// # Panics
+///
+/// `r8` is shorter than `width * height`.
+#[cfg(feature = "texture-compression")]
+pub fn bc4_from_r8(r8: &[u8], width: u32, height: u32) -> Vec<u8> {
+    compress(r8, width, height, bc4_block)
+}
+
+/// Compresses `width` x `height` row major `R8` data into `EAC R11` blocks,
+/// in row major block order. See `bc4_from_r8`.
+///
+/// # Panics
+///
+/// `r8` is shorter than `width * height`.
+#[cfg(feature = "texture-compression")]
+pub fn eac_r11_from_r8(r8: &[u8], width: u32, height: u32) -> Vec<u8> {
+    compress(r8, width, height, eac_r11_block)
+}
+
+#[cfg(feature = "texture-compression")]
+#[inline]
+fn blocks(pixels: u32) -> usize {
+    (pixels as usize).div_ceil(4)
+}
+
+/// Compresses each 4x4 block, given row major, with `encode`.
+#[cfg(feature = "texture-compression")]
+fn compress(r8: &[u8], width: u32, height: u32, encode: fn(&[u8; 16]) -> [u8; 8]) -> Vec<u8> {
+    let (width, height) = (width as usize, height as usize);
+    assert!(
+        r8.len() >= width * height,
+        "r8 data smaller than dimensions"
+    );
+    let mut out = Vec::with_capacity(compressed_len(width as u32, height as u32));
+    if width == 0 || height == 0 {
+        return out;
+    }
+    for block_y in 0..blocks(height as u32) {
+        for block_x in 0..blocks(width as u32) {
+            let mut block = [0; 16];
+            for (idx, v) in block.iter_mut().enumerate() {
+                let x = (block_x * 4 + idx % 4).min(width - 1);
+                let y = (block_y * 4 + idx / 4).min(height - 1);
+                *v = r8[x + y * width];
+            }
+            out.extend_from_slice(&encode(&block));
+        }
+    }
+    out
+}
+
+/// Encodes a row major block as `BC4`, trying both the 8 interpolated value
+/// mode & the 6 value mode with exact `0` & `255`, common in glyphs.
+#[cfg(feature = "texture-compression")]
+fn bc4_block(block: &[u8; 16]) -> [u8; 8] {
+    let min = *block.iter().min().unwrap();
+    let max = *block.iter().max().unwrap();
+
+    let (eight, eight_error) = bc4_encode(block, max, min);
+    // 6 value mode endpoints exclude the exact 0 & 255 values
+    let inner = block.iter().filter(|&&v| v != 0 && v != 255);
+    let inner_min = inner.clone().min().copied().unwrap_or(0);
+    let inner_max = inner.max().copied().unwrap_or(0);
+    let (six, six_error) = bc4_encode(block, inner_min, inner_max);
+    if six_error < eight_error {
+        six
+    } else {
+        eight
+    }
+}
+
+/// Encodes a `BC4` block with endpoints `red0`, `red1`, returning it & the
+/// squared error.
+#[cfg(feature = "texture-compression")]
+fn bc4_encode(block: &[u8; 16], red0: u8, red1: u8) -> ([u8; 8], u32) {
+    let (r0, r1) = (u32::from(red0), u32::from(red1));
+    let mut palette = [0_u32; 8];
+    palette[0] = r0;
+    palette[1] = r1;
+    if red0 > red1 {
+        for (i, p) in palette.iter_mut().enumerate().skip(2) {
+            let i = i as u32;
+            *p = ((8 - i) * r0 + (i - 1) * r1) / 7;
+        }
+    } else {
+        for (i, p) in palette.iter_mut().enumerate().take(6).skip(2) {
+            let i = i as u32;
+            *p = ((6 - i) * r0 + (i - 1) * r1) / 5;
+        }
+        palette[6] = 0;
+        palette[7] = 255;
+    }
+
+    let mut indices = 0_u64;
+    let mut error = 0;
+    for (pixel, &v) in block.iter().enumerate() {
+        let (index, e) = nearest(&palette, u32::from(v));
+        indices |= (index as u64) << (3 * pixel);
+        error += e;
+    }
+    let mut out = [0; 8];
+    out[0] = red0;
+    out[1] = red1;
+    out[2..].copy_from_slice(&indices.to_le_bytes()[..6]);
+    (out, error)
+}
+
+/// ETC2 / EAC modifier tables.
+#[cfg(feature = "texture-compression")]
+const EAC_MODIFIERS: [[i32; 8]; 16] = [
+    [-3, -6, -9, -15, 2, 5, 8, 14],
+    [-3, -7, -10, -13, 2, 6, 9, 12],
+    [-2, -5, -8, -13, 1, 4, 7, 12],
+    [-2, -4, -6, -13, 1, 3, 5, 12],
+    [-3, -6, -8, -12, 2, 5, 7, 11],
+    [-3, -7, -9, -11, 2, 6, 8, 10],
+    [-4, -7, -8, -11, 3, 6, 7, 10],
+    [-3, -5, -8, -11, 2, 4, 7, 10],
+    [-2, -6, -8, -10, 1, 5, 7, 9],
+    [-2, -5, -8, -10, 1, 4, 7, 9],
+    [-2, -4, -8, -10, 1, 3, 7, 9],
+    [-2, -5, -7, -10, 1, 4, 6, 9],
+    [-3, -4, -7, -10, 2, 3, 6, 9],
+    [-1, -2, -3, -10, 0, 1, 2, 9],
+    [-4, -6, -8, -9, 3, 5, 7, 8],
+    [-3, -5, -7, -9, 2, 4, 6, 8],
+];
+
+/// Encodes a row major block as `EAC R11`, searching every table & multiplier
+/// around the block's mid value.
+#[cfg(feature = "texture-compression")]
+fn eac_r11_block(block: &[u8; 16]) -> [u8; 8] {
+    // 11 bit targets
+    let targets = block.map(|v| u32::from(v) * 2047 / 255);
+    let min = *targets.iter().min().unwrap();
+    let max = *targets.iter().max().unwrap();
+
+    let mut best = (u32::MAX, 0, 0, 0, 0_u64);
+    let mid = ((min + max) / 2) as i32;
+    for base in [(mid - 4) / 8, mid / 8, (mid + 4) / 8] {
+        let base = base.clamp(0, 255);
+        for (table, modifiers) in EAC_MODIFIERS.iter().enumerate() {
+            for multiplier in 0..16 {
+                let palette = modifiers.map(|m| {
+                    let m = if multiplier == 0 {
+                        m
+                    } else {
+                        m * multiplier * 8
+                    };
+                    (base * 8 + 4 + m).clamp(0, 2047) as u32
+                });
+                let mut indices = 0_u64;
+                let mut error = 0;
+                for pixel in 0..16 {
+                    // pixels in column major order
+                    let target = targets[(pixel % 4) * 4 + pixel / 4];
+                    let (index, e) = nearest(&palette, target);
+                    indices = (indices << 3) | index as u64;
+                    error += e;
+                }
+                if error < best.0 {
+                    best = (error, base, multiplier, table, indices);
+                }
+            }
+        }
+    }
+
+    let (_, base, multiplier, table, indices) = best;
+    let bits = (base as u64) << 56 | (multiplier as u64) << 52 | (table as u64) << 48 | indices;
+    bits.to_be_bytes()
+}
+
+/// Returns the index of the nearest palette value to `v` & the squared error.
+#[cfg(feature = "texture-compression")]
+#[inline]
+fn nearest(palette: &[u32; 8], v: u32) -> (usize, u32) {
+    palette
+        .iter()
+        .map(|&p| p.abs_diff(v).pow(2))
+        .enumerate()
+        .min_by_key(|&(_, e)| e)
+        .unwrap()
+}
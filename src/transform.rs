@@ -0,0 +1,175 @@
+//! Fonts with a transform applied to all metrics, layout & drawing, see
+//! `Font::with_transform`.
+#[cfg(all(feature = "libm-math", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{
+    vector, Affine2, Font, HMetrics, IntoGlyphId, LayoutIter, LayoutOptions, Point,
+    PositionedGlyph, Scale, VMetrics,
+};
+
+/// A font with an `Affine2` transform, e.g. a zoom or rotation, applied to
+/// its metrics, layouts & glyphs, returned from `Font::with_transform`.
+///
+/// Positions passed in, like layout starts, are in the untransformed space
+/// the transform maps to pixels. Horizontal metrics are measured along the
+/// transformed baseline & vertical metrics perpendicular to it, so e.g. a
+/// rotation leaves them unchanged while a zoom scales them.
+///
+/// # Example
+///
+/// ```
+/// # use rusttype::*;
+/// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+/// # let font = Font::try_from_bytes(font_data).unwrap();
+/// // a widget zoomed 2x, at (100, 50)
+/// let zoomed = font.with_transform(
+///     Affine2::scale(2.0, 2.0).then(Affine2::translate(vector(100.0, 50.0))),
+/// );
+/// let scale = Scale::uniform(12.0);
+/// let glyphs: Vec<_> = zoomed.layout("Zoom", scale, point(0.0, 20.0)).collect();
+///
+/// assert_eq!(glyphs[0].position(), point(100.0, 90.0));
+/// assert_eq!(zoomed.v_metrics(scale).ascent, 2.0 * font.v_metrics(scale).ascent);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TransformedFont<'font> {
+    font: Font<'font>,
+    transform: Affine2,
+}
+
+impl<'font> Font<'font> {
+    /// Returns this font with `transform` applied to all metrics, layouts &
+    /// glyphs, see `TransformedFont`.
+    pub fn with_transform(&self, transform: Affine2) -> TransformedFont<'font> {
+        TransformedFont {
+            font: self.clone(),
+            transform,
+        }
+    }
+}
+
+impl<'font> TransformedFont<'font> {
+    /// The untransformed font.
+    #[inline]
+    pub fn font(&self) -> &Font<'font> {
+        &self.font
+    }
+
+    #[inline]
+    pub fn transform(&self) -> Affine2 {
+        self.transform
+    }
+
+    /// Returns this font with `transform` applied after the current transform.
+    pub fn with_transform(&self, transform: Affine2) -> TransformedFont<'font> {
+        self.font.with_transform(self.transform.then(transform))
+    }
+
+    /// How much the transform stretches lengths along the baseline.
+    fn baseline_factor(&self) -> f32 {
+        let baseline = self.transform.transform_vector(vector(1.0, 0.0));
+        (baseline.x * baseline.x + baseline.y * baseline.y).sqrt()
+    }
+
+    /// How much the transform stretches lengths perpendicular to the
+    /// baseline.
+    fn line_factor(&self) -> f32 {
+        let baseline = self.baseline_factor();
+        if baseline == 0.0 {
+            return 0.0;
+        }
+        self.transform.determinant().abs() / baseline
+    }
+
+    /// The transformed `Font::v_metrics`, measured perpendicular to the
+    /// baseline.
+    pub fn v_metrics(&self, scale: Scale) -> VMetrics {
+        self.font.v_metrics(scale) * self.line_factor()
+    }
+
+    /// The transformed horizontal metrics of a glyph, measured along the
+    /// baseline.
+    pub fn h_metrics<C: IntoGlyphId>(&self, id: C, scale: Scale) -> HMetrics {
+        let HMetrics {
+            advance_width,
+            left_side_bearing,
+        } = self.font.glyph(id).scaled(scale).h_metrics();
+        let factor = self.baseline_factor();
+        HMetrics {
+            advance_width: advance_width * factor,
+            left_side_bearing: left_side_bearing * factor,
+        }
+    }
+
+    /// The transformed `Font::pair_kerning`, measured along the baseline.
+    pub fn pair_kerning<A, B>(&self, scale: Scale, first: A, second: B) -> f32
+    where
+        A: IntoGlyphId,
+        B: IntoGlyphId,
+    {
+        self.font.pair_kerning(scale, first, second) * self.baseline_factor()
+    }
+
+    /// Returns a glyph at `scale`, transformed & positioned at the
+    /// transformed `position`.
+    pub fn glyph<C: IntoGlyphId>(
+        &self,
+        id: C,
+        scale: Scale,
+        position: Point<f32>,
+    ) -> PositionedGlyph<'font> {
+        self.font
+            .glyph(id)
+            .scaled(scale)
+            .transformed(self.transform)
+            .positioned(self.transform.transform_point(position))
+    }
+
+    /// Lays out `s` like `Font::layout` from `start` in the untransformed
+    /// space, transforming each glyph.
+    pub fn layout<'a, 's>(
+        &'a self,
+        s: &'s str,
+        scale: Scale,
+        start: Point<f32>,
+    ) -> TransformedLayoutIter<'a, 'font, 's> {
+        TransformedLayoutIter {
+            inner: self.font.layout(s, scale, start),
+            transform: self.transform,
+        }
+    }
+
+    /// Lays out `s` like `Font::layout_with_options`, transforming each glyph.
+    pub fn layout_with_options<'a, 's>(
+        &'a self,
+        s: &'s str,
+        scale: Scale,
+        start: Point<f32>,
+        options: &LayoutOptions,
+    ) -> TransformedLayoutIter<'a, 'font, 's> {
+        TransformedLayoutIter {
+            inner: self.font.layout_with_options(s, scale, start, options),
+            transform: self.transform,
+        }
+    }
+}
+
+/// Iterator over transformed positioned glyphs, see `TransformedFont::layout`.
+#[derive(Clone)]
+pub struct TransformedLayoutIter<'a, 'font, 's> {
+    inner: LayoutIter<'a, 'font, 's>,
+    transform: Affine2,
+}
+
+impl<'font> Iterator for TransformedLayoutIter<'_, 'font, '_> {
+    type Item = PositionedGlyph<'font>;
+
+    fn next(&mut self) -> Option<PositionedGlyph<'font>> {
+        self.inner.next().map(|g| {
+            let position = self.transform.transform_point(g.position());
+            g.into_unpositioned()
+                .transformed(self.transform)
+                .positioned(position)
+        })
+    }
+}
@@ -0,0 +1,78 @@
+//! Fitting text to a width by abbreviating it, see `Font::truncate_middle`.
+use crate::{Font, GlyphId, Scale};
+use alloc::borrow::Cow;
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+impl<'font> Font<'font> {
+    /// Returns `s` abbreviated in the middle with an ellipsis to fit
+    /// `max_width` pixels at `scale`, e.g. `"verylongfilen…me.txt"`, for file
+    /// names & tab titles where both the start & end matter. Returns `s`
+    /// unchanged if it fits already.
+    ///
+    /// Widths are measured as laid out by `layout`, so include the kerning
+    /// across the ellipsis, which can make an abbreviation narrower or wider
+    /// than its parts. The start keeps one more char than the end if they
+    /// can't be balanced & whitespace next to the ellipsis is removed. Uses
+    /// `"..."` for fonts without `'…'` and returns an empty string if not even
+    /// the ellipsis fits.
+    ///
+    /// Text is cut between chars, so may split grapheme clusters, e.g. a
+    /// letter & a combining accent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// // a monospace font 10px per char
+    /// let scale = font.scale_for_pixels_per_em(10.0 * 2048.0 / 1233.0);
+    /// let title = font.truncate_middle("verylongfilename.txt", scale, 140.0);
+    /// assert_eq!(title, "verylon…me.txt");
+    /// ```
+    pub fn truncate_middle<'s>(&self, s: &'s str, scale: Scale, max_width: f32) -> Cow<'s, str> {
+        let width = |s: &str| self.advance_widths(s, scale).last().unwrap_or(0.0);
+        if width(s) <= max_width {
+            return Cow::Borrowed(s);
+        }
+
+        let ellipsis = if self.glyph('…').id() == GlyphId(0) {
+            "..."
+        } else {
+            "…"
+        };
+        // byte offsets of each char & the end
+        let bounds: Vec<usize> = s
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(Some(s.len()))
+            .collect();
+        let chars = bounds.len() - 1;
+        let abbreviated = |kept: usize| {
+            let head = s[..bounds[kept.div_ceil(2)]].trim_end();
+            let tail = s[bounds[chars - kept / 2]..].trim_start();
+            let mut out = String::with_capacity(head.len() + ellipsis.len() + tail.len());
+            out.push_str(head);
+            out.push_str(ellipsis);
+            out.push_str(tail);
+            out
+        };
+
+        if width(&abbreviated(0)) > max_width {
+            return Cow::Borrowed("");
+        }
+        // binary search the most chars kept that fit, as widths grow with
+        // the chars kept
+        let (mut lo, mut hi) = (0, chars.saturating_sub(1));
+        while lo < hi {
+            let mid = (lo + hi).div_ceil(2);
+            if width(&abbreviated(mid)) <= max_width {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        Cow::Owned(abbreviated(lo))
+    }
+}
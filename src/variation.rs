@@ -0,0 +1,108 @@
+//! Variable font axes & instances, see `Font::with_variations`.
+use crate::Font;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A variation axis of a variable font, from the `fvar` table, see
+/// `Font::axes`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct VariationAxis {
+    /// The axis tag, e.g. `*b"wght"` for weight or `*b"wdth"` for width.
+    pub tag: [u8; 4],
+    pub min: f32,
+    /// The value of the default instance, used for axes not set by
+    /// `Font::with_variations`.
+    pub default: f32,
+    pub max: f32,
+    /// The `name` table id of the axis name, e.g. "Weight".
+    pub name_id: u16,
+    /// Whether the axis is meant for programmatic use rather than shown in
+    /// user interfaces.
+    pub hidden: bool,
+}
+
+impl VariationAxis {
+    /// Returns `value` clamped to the axis range.
+    #[inline]
+    pub fn clamp(&self, value: f32) -> f32 {
+        value.max(self.min).min(self.max)
+    }
+}
+
+impl<'font> Font<'font> {
+    /// Returns if this is a variable font with at least one variation axis.
+    pub fn is_variable(&self) -> bool {
+        self.inner().is_variable()
+    }
+
+    /// The variation axes of a variable font, empty for other fonts.
+    pub fn axes(&self) -> Vec<VariationAxis> {
+        self.inner()
+            .variation_axes()
+            .into_iter()
+            .map(|axis| VariationAxis {
+                tag: axis.tag.to_bytes(),
+                min: axis.min_value,
+                default: axis.def_value,
+                max: axis.max_value,
+                name_id: axis.name_id,
+                hidden: axis.hidden,
+            })
+            .collect()
+    }
+
+    /// Returns an instance of this variable font with the axes of `variations`
+    /// set to their values, e.g. `(*b"wght", 700.0)` for a bold, so glyph
+    /// outlines & metrics, and layouts, glyphs & caches using the instance
+    /// reflect it. `GPOS` kerning follows the instance, the `kern` table
+    /// doesn't vary.
+    ///
+    /// Values are clamped to the axis range. Axes not given keep their value
+    /// in this font, the default unless set by a previous call. Axes the font
+    /// doesn't have are ignored. If no axis value changes, e.g. for other
+    /// fonts, this returns a clone of the font.
+    ///
+    /// Otherwise the instance is a separate font, e.g. needing its own
+    /// `font_id` in a `gpu_cache::Cache`, with its own `generation` & reloads,
+    /// which keep the variations. Instances share the font data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/dejavu/DejaVuSansMono.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let bold = font.with_variations(&[(*b"wght", 700.0)]);
+    /// for axis in bold.axes() {
+    ///     println!("{:?} = {}", axis.tag, bold.variation(axis.tag).unwrap());
+    /// }
+    /// ```
+    pub fn with_variations(&self, variations: &[([u8; 4], f32)]) -> Font<'font> {
+        let axes = self.axes();
+        let mut applied = self.applied_variations().to_vec();
+        for &(tag, value) in variations {
+            let axis = match axes.iter().find(|axis| axis.tag == tag) {
+                Some(axis) => axis,
+                None => continue,
+            };
+            let value = axis.clamp(value);
+            match applied.iter_mut().find(|(t, _)| *t == tag) {
+                Some(set) => set.1 = value,
+                None => applied.push((tag, value)),
+            }
+        }
+        if applied == self.applied_variations() {
+            return self.clone();
+        }
+        self.with_applied_variations(applied)
+    }
+
+    /// Returns the value of the variation axis `tag`, as set by
+    /// `with_variations` or the axis default, `None` if the font doesn't have
+    /// the axis.
+    pub fn variation(&self, tag: [u8; 4]) -> Option<f32> {
+        let axis = self.axes().into_iter().find(|axis| axis.tag == tag)?;
+        let set = self.applied_variations().iter().find(|(t, _)| *t == tag);
+        Some(set.map_or(axis.default, |&(_, value)| value))
+    }
+}
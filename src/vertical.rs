@@ -0,0 +1,163 @@
+//! Vertical text layout with the `vhea`, `vmtx` & `VORG` metrics, see
+//! `Font::layout_vertical`.
+use crate::language::LocalizedForms;
+#[cfg(all(feature = "libm-math", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{vector, Font, Point, PositionedGlyph, Scale, ScaledGlyph, Vector};
+
+/// The "vertical metrics" of a glyph, for laying out glyphs top to bottom,
+/// see `Font::layout_vertical`.
+///
+/// In vertical layout each glyph hangs from its vertical origin, centred
+/// horizontally on the line & at the top of its vertical advance.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct GlyphVMetrics {
+    /// The vertical offset, downwards, that the vertical origin of the next
+    /// glyph should be from the vertical origin of this glyph.
+    pub advance_height: f32,
+    /// The vertical offset between the vertical origin of this glyph and the
+    /// topmost edge/point of the glyph.
+    pub top_side_bearing: f32,
+    /// The offset of the glyph's origin, on its baseline, from its vertical
+    /// origin, y down, so a glyph at a vertical pen position is positioned
+    /// at the pen plus `origin`.
+    pub origin: Vector<f32>,
+}
+
+impl ScaledGlyph<'_> {
+    /// Retrieves the "vertical metrics" of this glyph. See `GlyphVMetrics`
+    /// for more detail.
+    ///
+    /// Uses the font's `vmtx` advances & side bearings, & `VORG` origins for
+    /// CFF fonts. Fonts without vertical metrics, & `StandaloneGlyph`s,
+    /// advance by the ascent to descent, with the vertical origin at the
+    /// ascent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let glyph = font.glyph('中').scaled(Scale::uniform(24.0));
+    /// let v_metrics = glyph.v_metrics();
+    /// // centred on the vertical line
+    /// assert_eq!(v_metrics.origin.x, -glyph.h_metrics().advance_width / 2.0);
+    /// assert!(v_metrics.advance_height > 0.0);
+    /// ```
+    pub fn v_metrics(&self) -> GlyphVMetrics {
+        let inner = self.font().map(Font::inner);
+        let id = self.id().into();
+        let v = self.unscaled().unscaled_v_metrics();
+
+        let advance = inner
+            .and_then(|inner| inner.glyph_ver_advance(id))
+            .map(f32::from)
+            .or_else(|| inner?.vertical_height().map(f32::from))
+            .unwrap_or(v.ascent - v.descent);
+        let y_max = self.unscaled().unscaled_bounding_box().map(|bb| bb.max.y);
+        let side_bearing = inner
+            .and_then(|inner| inner.glyph_ver_side_bearing(id))
+            .map(f32::from);
+        // the height of the vertical origin above the baseline
+        let y_origin = inner.and_then(|inner| inner.glyph_y_origin(id));
+        let origin_y = match (y_origin, side_bearing, y_max) {
+            (Some(origin), ..) => f32::from(origin),
+            (None, Some(side_bearing), Some(y_max)) => side_bearing + y_max,
+            _ => v.ascent,
+        };
+        let top_side_bearing = side_bearing.unwrap_or_else(|| origin_y - y_max.unwrap_or(origin_y));
+
+        let v_metrics = GlyphVMetrics {
+            advance_height: advance * self.scale.y,
+            top_side_bearing: top_side_bearing * self.scale.y,
+            origin: vector(
+                -self.h_metrics().advance_width / 2.0,
+                origin_y * self.scale.y,
+            ),
+        };
+        if self.unscaled().is_pixel_rendered() {
+            return GlyphVMetrics {
+                advance_height: v_metrics.advance_height.round(),
+                top_side_bearing: v_metrics.top_side_bearing.round(),
+                origin: vector(v_metrics.origin.x.round(), v_metrics.origin.y.round()),
+            };
+        }
+        v_metrics
+    }
+}
+
+impl<'font> Font<'font> {
+    /// Returns an iterator that lays out `s` top to bottom as a vertical
+    /// line, e.g. for CJK vertical typesetting, from `start`, the vertical
+    /// origin of the first glyph at the top centre of the line. Each glyph
+    /// advances by its `ScaledGlyph::v_metrics`.
+    ///
+    /// Glyphs take the font's `vert` vertical alternates, e.g. rotated
+    /// brackets & repositioned punctuation, & are otherwise upright, so
+    /// rotate Latin runs separately. Vertical kerning isn't applied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rusttype::*;
+    /// # let font_data: &[u8] = include_bytes!("../dev/fonts/wqy-microhei/WenQuanYiMicroHei.ttf");
+    /// # let font = Font::try_from_bytes(font_data).unwrap();
+    /// let glyphs: Vec<_> = font
+    ///     .layout_vertical("縦書き", Scale::uniform(24.0), point(20.0, 0.0))
+    ///     .collect();
+    /// assert!(glyphs[0].position().y < glyphs[1].position().y);
+    /// assert_eq!(glyphs[0].position().x, glyphs[1].position().x);
+    /// ```
+    pub fn layout_vertical<'a, 's>(
+        &'a self,
+        s: &'s str,
+        scale: Scale,
+        start: Point<f32>,
+    ) -> VerticalLayoutIter<'a, 'font, 's> {
+        VerticalLayoutIter {
+            font: self,
+            chars: s.chars(),
+            caret: 0.0,
+            scale,
+            start,
+            vertical_forms: LocalizedForms::vertical(self),
+        }
+    }
+}
+
+/// Iterator over the glyphs of a vertical line, see `Font::layout_vertical`.
+#[derive(Clone)]
+pub struct VerticalLayoutIter<'a, 'font, 's> {
+    font: &'a Font<'font>,
+    chars: core::str::Chars<'s>,
+    caret: f32,
+    scale: Scale,
+    start: Point<f32>,
+    vertical_forms: LocalizedForms,
+}
+
+impl VerticalLayoutIter<'_, '_, '_> {
+    /// The advance of the glyphs so far, the offset of the next glyph's
+    /// vertical origin from `start`.
+    #[inline]
+    pub fn caret(&self) -> f32 {
+        self.caret
+    }
+}
+
+impl<'font> Iterator for VerticalLayoutIter<'_, 'font, '_> {
+    type Item = PositionedGlyph<'font>;
+
+    fn next(&mut self) -> Option<PositionedGlyph<'font>> {
+        let c = self.chars.next()?;
+        let id = self
+            .vertical_forms
+            .apply(self.font, self.font.glyph(c).id());
+        let glyph = self.font.glyph(id).scaled(self.scale);
+        let v_metrics = glyph.v_metrics();
+        let position = self.start + vector(0.0, self.caret) + v_metrics.origin;
+        self.caret += v_metrics.advance_height;
+        Some(glyph.positioned(position))
+    }
+}